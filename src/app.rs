@@ -1,42 +1,226 @@
-use crate::core::env_files::{EnvFile, EnvFileList, scan_env_files};
-use crate::core::workspaces::WorkspacePackage;
-use crate::fuzzy::fuzzy_filter;
-use crate::sort::{SortableScript, sort_scripts};
+use crate::core::clipboard::{Clipboard, ClipboardMethod, SystemClipboard};
+use crate::core::directories::list_directories;
+use crate::core::env_files::{
+    create_env_file, format_exports, load_env_files, missing_example_keys, scan_env_files_with,
+};
+use crate::core::hooks::{PIPED_HOOKS, ScriptHooks};
+use crate::core::jobs::{Interrupt, JobEvent, JobId, JobRegistry, JobSpec};
+use crate::core::known_flags::flag_table_for;
+use crate::core::matrix::{MatrixProfile, matrix_profiles};
+use crate::core::notify::notify_finished;
+use crate::core::package_manager::{
+    PackageManager, detect_package_manager_in, has_unclosed_quote, shell_join, split_args,
+};
+use crate::core::project_config;
+use crate::core::queue::{ExecutionQueue, QueuedRun};
+use crate::core::required_env::missing_env;
+use crate::core::runner::{
+    ExecutionStrategy, Signal, WorkspaceTarget, format_duration, quiet_node_template,
+    use_corepack_unattended,
+};
+use crate::core::schedule::{ScheduledRun, format_countdown, local_time_now, parse_start};
+use crate::core::script_groups::{ScriptGroup, group_by_name};
+use crate::core::scripts::{HiddenScripts, find_script_line};
+use crate::core::secrets::SecretEnv;
+use crate::core::tasks::TaskRunner;
+use crate::core::watch::{FileWatch, watch_globs};
+use crate::core::workspaces::{ScanUpdate, WorkspacePackage};
+use crate::flow::{ConfigureFlow, DirPick, FlowSnapshot, NewEnvFile, PresetPick};
+use crate::fuzzy::{PrefixedQuery, fuzzy_filter};
+use crate::grouping::{ListRow, flat_rows, group_by_prefix, task_rows};
+use crate::sort::{
+    ScriptQuery, SortableScript, sort_packages, sort_scripts, sort_scripts_by, top_favorite,
+};
 use crate::store::args_history::{self, ArgsHistory};
-use crate::store::favorites;
+use crate::store::bench::{self, BenchResults};
+use crate::store::config::{Config, ConfigIssue, DuplicateJobs, StartupTab};
+use crate::store::favorites::{self, package_key};
+use crate::store::history::{self, HistoryEntry};
+use crate::store::keymap::{KeyAction, Keymap};
+use crate::store::next_runs::{self, NextRuns, suggest_next};
+use crate::store::ran_commands::{self, RanCommands};
 use crate::store::recents::{self, RecentEntry};
+use crate::store::reset::{ResetTarget, reset};
 use crate::store::script_configs::{self, ScriptConfig, ScriptConfigs};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::store::session::{self, Session};
+use crate::store::tags::{self, ScriptTags};
+use crate::store::view_prefs::{
+    self, ALL_SCRIPTS_VIEW, SCRIPTS_VIEW, ViewPrefs, ViewPrefsMap, package_view,
+};
+use crate::store::workspace_index;
+use crate::ui::symbols::Symbols;
+use crate::ui::theme::Theme;
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use indexmap::IndexMap;
 use ratatui::layout::{Constraint, Layout};
 use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant, SystemTime};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Content rows below which the script preview is hidden to leave room for the list.
+const PREVIEW_MIN_CONTENT_HEIGHT: u16 = 12;
+
+/// Two clicks on the same row within this window run the script.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// File name for env snapshots written from the confirm screen.
+const ENV_SNAPSHOT_FILE: &str = ".nr-env.sh";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Tab {
     Scripts,
     Packages,
+    History,
+    Jobs,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PackageMode {
     SelectingPackage,
-    SelectingScript { package_index: usize },
+    SelectingScript {
+        package_index: usize,
+    },
+    /// The scripts of every package in one list, labeled `package:script`.
+    AllScripts,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum AppMode {
     Normal,
+    PickPreset,
     ConfigureEnv,
+    EditEnv,
+    PreviewEnv,
     ConfigureArgs,
     ConfirmExecution,
+    PickDirectory,
+    Queue,
+    Schedule,
+    Output,
+    Tags,
+    QuickDial,
+    Settings,
+    ScriptGroups,
+    RequiredEnv,
+    DuplicateJob,
+    DangerousScript,
+}
+
+/// What the output pane shows: a job from the registry and the scroll position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputView {
+    pub job: JobId,
+    /// First visible line when not following.
+    pub scroll: usize,
+    /// Keep the view pinned to the newest output.
+    pub follow: bool,
+}
+
+/// A run held back until values are entered for the `required_env` keys it's missing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvPrompt {
+    /// Entered values are added to `spec.env`.
+    pub spec: JobSpec,
+    /// Show the output pane once it starts, rather than run it in the background.
+    pub attach: bool,
+    /// Keys still without a value; the first is being asked for.
+    pub missing: Vec<String>,
+    pub input: String,
+}
+
+/// A run held back because the same script already runs as a job, until the
+/// user picks the running job or another instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicatePrompt {
+    pub spec: JobSpec,
+    /// Show the output pane once it starts, rather than run it in the background.
+    pub attach: bool,
+    /// The job already running the script.
+    pub existing: JobId,
+}
+
+/// A run of a `dangerous_scripts` script, held back until its name is typed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DangerPrompt {
+    pub script_name: String,
+    pub run: DangerousRun,
+    pub input: String,
+}
+
+/// How the held back run starts once confirmed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DangerousRun {
+    /// As with Enter.
+    Run(QueuedRun),
+    /// In the background.
+    Background(QueuedRun),
+    /// In the background in several packages (script groups).
+    Packages(Vec<QueuedRun>),
+    /// The configure flow's run; the flow stays open until it's confirmed.
+    Configured(ConfiguredStart),
+    /// The execution queue, one at a time or all at once with `parallel`;
+    /// `confirmed` holds the dangerous script names typed so far.
+    Queue {
+        parallel: bool,
+        confirmed: Vec<String>,
+    },
+    /// In the terminal in every workspace package that defines the script.
+    AllPackages(Vec<QueuedRun>),
+}
+
+/// The confirmation screen key a configured run was started with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfiguredStart {
+    /// Enter: in the terminal or the pane, in watch mode if it's on.
+    Run,
+    /// Ctrl+B: in the background.
+    Background,
+    /// Ctrl+X: once per env profile.
+    Matrix,
+}
+
+impl DangerPrompt {
+    /// Whether the typed name is the script's.
+    pub fn confirmed(&self) -> bool {
+        self.input == self.script_name
+    }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct ExecutionConfig {
-    pub args: String,
+/// A script started in watch mode: its latest job is re-run whenever watched
+/// files change.
+pub struct ScriptWatch {
+    pub script_name: String,
+    pub cwd: PathBuf,
+    pub files: FileWatch,
+    /// Exit code and duration of the latest run, once it has finished.
+    pub last: Option<(i32, Duration)>,
+}
+
+/// What the UI shows, in a form scenario tests can compare or serialize:
+/// drive the app with [`App::feed_events`], then check [`App::snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UiSnapshot {
+    pub tab: Tab,
+    pub mode: AppMode,
+    pub show_help: bool,
+    /// Search query of the active list.
+    pub query: String,
+    /// Labels of the active list's rows; group headers read `+ test (3)`
+    /// (collapsed) or `- test (3)` (expanded), task sections `# justfile (2)`.
+    pub rows: Vec<String>,
+    /// Highlighted row, indexing `rows`.
+    pub selected: usize,
+    /// Where the list is, e.g. `["Packages", "@mono/web"]`; empty for a tab's own list.
+    pub breadcrumb: Vec<String>,
+    pub status: Option<String>,
+    /// Keys of the queued runs.
+    pub queue: Vec<String>,
+    pub flow: Option<FlowSnapshot>,
 }
 
 pub enum Action {
@@ -46,6 +230,50 @@ pub enum Action {
         cwd: PathBuf,
         env_files: Vec<PathBuf>,
         args: String,
+        /// Variables set on top of the env files.
+        env: Vec<(String, String)>,
+    },
+    /// Tear down the TUI and run the execution queue.
+    RunQueue,
+    /// Tear down the TUI and run these queued scripts at the same time.
+    RunParallel {
+        runs: Vec<QueuedRun>,
+    },
+    /// Tear down the TUI and run a script in every workspace package that defines
+    /// it, one package at a time.
+    RunAllPackages {
+        runs: Vec<QueuedRun>,
+    },
+    /// Tear down the TUI and run one script once per env profile, then print a summary.
+    RunMatrix {
+        script_name: String,
+        cwd: PathBuf,
+        args: String,
+        profiles: Vec<MatrixProfile>,
+    },
+    /// Suspend the TUI and open `path` at `line` in the user's editor.
+    OpenEditor {
+        path: PathBuf,
+        line: usize,
+    },
+    /// Tear down the TUI and print the merged env as `export` statements.
+    PrintEnv {
+        env_files: Vec<PathBuf>,
+        env: Vec<(String, String)>,
+    },
+    /// Tear down the TUI and print the resolved command instead of running it.
+    PrintCommand {
+        command: String,
+    },
+    /// Tear down the TUI and run a justfile / Makefile task in `cwd`.
+    RunTask {
+        runner: TaskRunner,
+        task: String,
+        cwd: PathBuf,
+    },
+    /// Tear down the `--picker` UI and print the chosen script's name.
+    PrintScript {
+        script_name: String,
     },
     Quit,
 }
@@ -64,6 +292,8 @@ pub struct App {
 
     // State
     pub favorites: HashSet<String>,
+    /// Favorites were toggled since they were last written to disk.
+    pub favorites_unsaved: bool,
     pub recents: Vec<RecentEntry>,
 
     // Header info
@@ -73,12 +303,25 @@ pub struct App {
 
     // Layout
     visible_height: usize,
+    /// Where the script list was last drawn, for mouse hit-testing.
+    list_area: Rect,
+    /// Row and time of the last left click, to detect double-clicks.
+    last_click: Option<(usize, Instant)>,
 
     // Scripts tab UI state
     pub query: String,
     pub selected_index: usize,
     pub scroll_offset: usize,
     pub filtered_indices: Vec<usize>,
+    /// Rows of the script list: `filtered_indices`, grouped by prefix if the view says so.
+    pub script_rows: Vec<ListRow>,
+    expanded_groups: HashSet<String>,
+    /// Scripts the config's `hide_scripts` leaves out of the lists, unless the view shows them.
+    hidden_scripts: HiddenScripts,
+    /// Env keys whose values are masked on screen (the config's `mask_env`).
+    secret_env: SecretEnv,
+    /// Sort, hidden scripts and grouping of the Scripts tab and each package's list.
+    pub view_prefs: ViewPrefsMap,
 
     // Package tab UI state
     pub pkg_query: String,
@@ -93,25 +336,106 @@ pub struct App {
     pub pkg_script_filtered_indices: Vec<usize>,
     pub pkg_script_sortable: Vec<SortableScript>,
 
+    // History tab: past runs (most recent first) and the search over them
+    pub history: Vec<HistoryEntry>,
+    pub history_query: String,
+    pub history_selected_index: usize,
+    pub history_scroll_offset: usize,
+    /// Which script usually follows which, for the suggested next run.
+    pub next_runs: NextRuns,
+    /// Command hashes scripts last ran with, for the changed badge.
+    pub ran_commands: RanCommands,
+    pub history_filtered_indices: Vec<usize>,
+
     // NEW: Configuration flow state
     pub mode: AppMode,
-    pub execution_config: ExecutionConfig,
+    /// Data of the configure flow's steps while one is open.
+    pub flow: Option<ConfigureFlow>,
     pub script_configs: ScriptConfigs,
     pub global_env_config: crate::store::global_env::GlobalEnvConfig,
     pub args_history: ArgsHistory,
     pub config_dir: PathBuf,
     pub package_manager: crate::core::package_manager::PackageManager,
-
-    // NEW: Env selection UI state
-    pub env_files_list: Option<EnvFileList>,
-    pub env_selected_index: usize,
-    pub env_scroll_offset: usize,
-    pub env_selected_files: HashSet<PathBuf>,
-
-    // NEW: Args input UI state
-    pub args_input: String,
-    pub args_cursor_pos: usize, // NEW: cursor position in args_input
-    pub args_history_index: Option<usize>,
+    pub config: Config,
+
+    // Execution queue
+    pub queue: ExecutionQueue,
+    pub queue_selected_index: usize,
+
+    // Scheduled runs (sorted by due time) and the schedule prompt
+    pub scheduled: Vec<ScheduledRun>,
+    pub schedule_input: String,
+    pub schedule_error: Option<String>,
+    pub schedule_pending: Option<QueuedRun>,
+
+    // Tags added in the TUI (package.json tags live on the scripts) and the tag prompt
+    pub script_tags: ScriptTags,
+    pub tag_input: String,
+    pub tag_pending: Option<String>,
+
+    // Runs waiting for missing required env values, asked for in order
+    pub env_prompts: Vec<EnvPrompt>,
+
+    // Runs of scripts already running as jobs, asked about in order (see `duplicate_jobs`)
+    pub duplicate_prompts: Vec<DuplicatePrompt>,
+
+    // A dangerous script's run waiting for its name to be typed (see `dangerous_scripts`)
+    pub danger_prompt: Option<DangerPrompt>,
+
+    /// The script re-run on file changes, if one was started in watch mode.
+    pub watch: Option<ScriptWatch>,
+    /// `package.json` changes that reload the scripts (`reload_on_change`).
+    manifest_watch: Option<FileWatch>,
+
+    // Quick dial: the top favorite of each package, numbered
+    pub quick_dial: Vec<QueuedRun>,
+    pub quick_dial_selected: usize,
+
+    // Script groups: names shared by several packages, and the packages
+    // picked for the opened group (None while choosing a name)
+    pub script_groups: Vec<ScriptGroup>,
+    pub script_group_selected: usize,
+    pub script_group_checked: Option<Vec<bool>>,
+    pub script_group_pkg_selected: usize,
+
+    // Settings screen: the highlighted reset target and whether it awaits y/n
+    pub settings_selected: usize,
+    pub settings_confirm: bool,
+
+    /// One-off feedback shown in the status bar until the next key press.
+    pub status_message: Option<String>,
+
+    /// Problems in the config files, shown in a banner above the list.
+    pub config_issues: Vec<ConfigIssue>,
+
+    // In-TUI execution: every script started inside the TUI, and the one shown in the pane
+    pub jobs: JobRegistry,
+    pub jobs_selected_index: usize,
+    pub output: Option<OutputView>,
+    output_height: usize,
+
+    /// Workspace scan running in the background: a fresh scan replacing the
+    /// packages from the index, or the first scan streaming them in.
+    workspace_refresh: Option<Receiver<ScanUpdate>>,
+    /// When the first scan started, while packages are still streaming in.
+    workspace_scan_started: Option<Instant>,
+    /// Pending background check for a newer nr release (see `core::update`).
+    update_check: Option<Receiver<String>>,
+    /// Newer nr release to mention in the status bar.
+    pub update_available: Option<String>,
+
+    /// Script list key bindings (defaults plus the config's `keymap`).
+    pub keymap: Keymap,
+
+    /// Colors from the config's `theme`.
+    pub theme: Theme,
+
+    /// Keybinding overlay (`?` / F1) drawn over whatever mode is active, and its scroll.
+    pub show_help: bool,
+    help_scroll: usize,
+
+    /// `--picker`: only the query and script list; Enter prints the script instead of running it.
+    pub picker: bool,
 }
 
 impl App {
@@ -130,14 +454,7 @@ impl App {
         let has_workspaces = !workspace_packages.is_empty();
 
         // Convert IndexMap to Vec<SortableScript>
-        let scripts: Vec<SortableScript> = raw_scripts
-            .iter()
-            .map(|(name, command)| SortableScript {
-                key: format!("root:{}", name),
-                name: name.clone(),
-                command: command.clone(),
-            })
-            .collect();
+        let mut scripts = to_sortable_scripts("root", &raw_scripts);
 
         // Load persisted state from project-scoped directory
         let favorites_data = favorites::load_favorites(project_dir);
@@ -147,6 +464,15 @@ impl App {
         let global_env_data =
             crate::store::global_env::load_global_env_config(project_dir).unwrap_or_default();
         let args_history_data = args_history::load_args_history(project_dir).unwrap_or_default();
+        let history_data = history::load_history(project_dir);
+        let next_runs_data = next_runs::load_next_runs(project_dir);
+        let tags_data = tags::load_tags(project_dir);
+        apply_tags(&mut scripts, &nearest_pkg, &tags_data);
+        apply_descriptions(&mut scripts, &nearest_pkg);
+        let ran_commands_data = ran_commands::load_ran_commands(project_dir);
+        apply_changed(&mut scripts, &ran_commands_data);
+        apply_bench(&mut scripts, &bench::load_bench(project_dir));
+        apply_run_times(&mut scripts, &history_data);
 
         // Initial sort/filter
         let filtered_indices = sort_scripts(&scripts, &favorites_data, &recents_data, "");
@@ -154,7 +480,7 @@ impl App {
         // Initial package filter (all packages, original order)
         let pkg_filtered_indices: Vec<usize> = (0..workspace_packages.len()).collect();
 
-        App {
+        let mut app = App {
             active_tab: Tab::Scripts,
             package_mode: PackageMode::SelectingPackage,
             has_workspaces,
@@ -165,6 +491,7 @@ impl App {
             monorepo_root: monorepo_root.clone(),
 
             favorites: favorites_data,
+            favorites_unsaved: false,
             recents: recents_data,
 
             project_name,
@@ -172,11 +499,18 @@ impl App {
             package_manager_name,
 
             visible_height: 20,
+            list_area: Rect::default(),
+            last_click: None,
 
             query: String::new(),
             selected_index: 0,
             scroll_offset: 0,
+            script_rows: flat_rows(&filtered_indices),
             filtered_indices,
+            expanded_groups: HashSet::new(),
+            hidden_scripts: HiddenScripts::default(),
+            secret_env: SecretEnv::default(),
+            view_prefs: view_prefs::load_view_prefs(project_dir),
 
             pkg_query: String::new(),
             pkg_selected_index: 0,
@@ -189,1324 +523,7196 @@ impl App {
             pkg_script_filtered_indices: Vec::new(),
             pkg_script_sortable: Vec::new(),
 
+            history_filtered_indices: (0..history_data.len()).collect(),
+            history: history_data,
+            next_runs: next_runs_data,
+            ran_commands: ran_commands_data,
+            history_query: String::new(),
+            history_selected_index: 0,
+            history_scroll_offset: 0,
+
             // NEW: Configuration flow
             mode: AppMode::Normal,
-            execution_config: ExecutionConfig::default(),
+            flow: None,
             script_configs: script_configs_data,
             global_env_config: global_env_data,
             args_history: args_history_data,
             config_dir: project_dir.to_path_buf(),
             package_manager,
+            config: Config::default(),
+
+            queue: ExecutionQueue::default(),
+            queue_selected_index: 0,
+
+            scheduled: Vec::new(),
+            schedule_input: String::new(),
+            schedule_error: None,
+            schedule_pending: None,
+
+            script_tags: tags_data,
+            tag_input: String::new(),
+            tag_pending: None,
+
+            env_prompts: Vec::new(),
+            duplicate_prompts: Vec::new(),
+            danger_prompt: None,
+            watch: None,
+            manifest_watch: None,
+
+            quick_dial: Vec::new(),
+            quick_dial_selected: 0,
+            script_groups: Vec::new(),
+            script_group_selected: 0,
+            script_group_checked: None,
+            script_group_pkg_selected: 0,
+            settings_selected: 0,
+            settings_confirm: false,
+
+            status_message: None,
+            config_issues: Vec::new(),
+
+            jobs: JobRegistry::default(),
+            jobs_selected_index: 0,
+            output: None,
+            output_height: 20,
+
+            workspace_refresh: None,
+
+            workspace_scan_started: None,
+            update_check: None,
+            update_available: None,
+
+            keymap: Keymap::default(),
+            theme: Theme::default(),
 
-            // NEW: Env selection UI state
-            env_files_list: None,
-            env_selected_index: 0,
-            env_scroll_offset: 0,
-            env_selected_files: HashSet::new(),
+            show_help: false,
+            help_scroll: 0,
 
-            // NEW: Args input UI state
-            args_input: String::new(),
-            args_cursor_pos: 0,
-            args_history_index: None,
+            picker: false,
+        };
+        app.restore_session(session::load_session(project_dir));
+        app
+    }
+
+    /// Replace the workspace packages with the result of a background rescan
+    /// once it arrives (see `store::workspace_index`).
+    pub fn with_workspace_refresh(mut self, rx: Receiver<ScanUpdate>) -> Self {
+        self.workspace_refresh = Some(rx);
+        self
+    }
+
+    /// List workspace packages as a background scan finds them, with a
+    /// spinner in the Packages tab until it is done.
+    pub fn with_workspace_scan(mut self, rx: Receiver<ScanUpdate>) -> Self {
+        self.workspace_refresh = Some(rx);
+        self.workspace_scan_started = Some(Instant::now());
+        self.has_workspaces = true;
+        self
+    }
+
+    /// Mention a newer nr release in the status bar once the background check
+    /// finds one (see `core::update`).
+    pub fn with_update_check(mut self, rx: Receiver<String>) -> Self {
+        self.update_check = Some(rx);
+        self
+    }
+
+    /// Minimal picker for scripts and tmux popups (see `Action::PrintScript`).
+    pub fn with_picker(mut self) -> Self {
+        self.picker = true;
+        self.active_tab = Tab::Scripts;
+        self
+    }
+
+    /// Config problems to point out in a banner (see `nr doctor`).
+    pub fn with_config_issues(mut self, issues: Vec<ConfigIssue>) -> Self {
+        self.config_issues = issues;
+        self
+    }
+
+    /// Apply user settings loaded from `config.json`.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.hidden_scripts = HiddenScripts::new(&config.hide_scripts);
+        self.secret_env = SecretEnv::new(config.mask_env.as_deref());
+        self.keymap = Keymap::new(&config.keymap);
+        self.theme = Theme::from(&config.theme);
+        if config.ascii {
+            self.theme.symbols = Symbols::ASCII;
+        }
+        let startup = config.startup.clone();
+        if config.reload_on_change {
+            self.manifest_watch = FileWatch::start(&self.nearest_pkg, &manifest_globs()).ok();
+        }
+        self.config = config;
+        self.load_tasks();
+        self.update_filtered();
+
+        // Startup tab (Packages only makes sense with workspaces) and initial
+        // query, over the ones restored from the last session
+        match startup.tab {
+            Some(StartupTab::Packages) if self.has_workspaces => self.active_tab = Tab::Packages,
+            Some(StartupTab::Scripts) => self.active_tab = Tab::Scripts,
+            _ => {}
+        }
+        if let Some(ref query) = startup.query {
+            match self.active_tab {
+                Tab::Scripts => {
+                    self.query = query.clone();
+                    self.update_filtered();
+                }
+                Tab::Packages => {
+                    self.pkg_query = query.clone();
+                    self.update_pkg_filtered();
+                }
+                Tab::History | Tab::Jobs => {}
+            }
+        }
+        self
+    }
+
+    /// Feeds `keys` through [`Self::handle_key`] like the event loop does,
+    /// skipping release/repeat events. Stops at the first action that would
+    /// leave the TUI and returns it, or `Continue` once every key is handled.
+    pub fn feed_events(&mut self, keys: &[KeyEvent]) -> Action {
+        for &key in keys {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let action = self.handle_key(key);
+            if !matches!(action, Action::Continue) {
+                return action;
+            }
+        }
+        Action::Continue
+    }
+
+    /// The current [`UiSnapshot`].
+    pub fn snapshot(&self) -> UiSnapshot {
+        let (rows, selected): (Vec<String>, usize) = match self.active_tab {
+            Tab::Scripts => (
+                self.script_rows
+                    .iter()
+                    .map(|row| match row {
+                        ListRow::Group {
+                            prefix,
+                            len,
+                            expanded,
+                        } => format!("{} {} ({})", if *expanded { '-' } else { '+' }, prefix, len),
+                        ListRow::Section { runner, len } => {
+                            format!("# {} ({})", runner.label(), len)
+                        }
+                        ListRow::Script { index, .. } => self.scripts[*index].name.clone(),
+                    })
+                    .collect(),
+                self.selected_index,
+            ),
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => (
+                    self.pkg_filtered_indices
+                        .iter()
+                        .map(|&i| self.workspace_packages[i].name.clone())
+                        .collect(),
+                    self.pkg_selected_index,
+                ),
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => (
+                    self.pkg_script_filtered_indices
+                        .iter()
+                        .map(|&i| self.pkg_script_sortable[i].label().to_string())
+                        .collect(),
+                    self.pkg_script_selected_index,
+                ),
+            },
+            Tab::History => (
+                self.history_filtered_indices
+                    .iter()
+                    .map(|&i| self.history[i].key.clone())
+                    .collect(),
+                self.history_selected_index,
+            ),
+            Tab::Jobs => (
+                self.jobs
+                    .iter()
+                    .map(|job| job.spec.script_name.clone())
+                    .collect(),
+                self.jobs_selected_index,
+            ),
+        };
+
+        UiSnapshot {
+            tab: self.active_tab,
+            mode: self.mode.clone(),
+            show_help: self.show_help,
+            query: self.current_query().to_string(),
+            rows,
+            selected,
+            breadcrumb: self.breadcrumb(),
+            status: self.status_message.clone(),
+            queue: self.queue.items.iter().map(|run| run.key.clone()).collect(),
+            flow: self.flow.as_ref().map(ConfigureFlow::snapshot),
         }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.status_message = None;
+
+        if self.show_help {
+            return self.handle_help_key(key);
+        }
+        if self.opens_help(key) {
+            self.show_help = true;
+            self.help_scroll = 0;
+            return Action::Continue;
+        }
+
+        // Alt+Enter runs in the other place than configured (pane vs terminal)
+        let alt_enter = key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::ALT);
+
         // Route to mode-specific handler
-        match self.mode {
+        let action = match self.mode {
             AppMode::Normal => self.handle_normal_mode(key),
+            AppMode::PickPreset => self.handle_preset_pick_mode(key),
             AppMode::ConfigureEnv => self.handle_env_mode(key),
+            AppMode::EditEnv => self.handle_env_overrides_mode(key),
+            AppMode::PreviewEnv => self.handle_env_preview_mode(key),
             AppMode::ConfigureArgs => self.handle_args_mode(key),
             AppMode::ConfirmExecution => self.handle_confirm_mode(key),
+            AppMode::PickDirectory => self.handle_dir_pick_mode(key),
+            AppMode::Queue => self.handle_queue_mode(key),
+            AppMode::Schedule => self.handle_schedule_mode(key),
+            AppMode::Output => self.handle_output_mode(key),
+            AppMode::Tags => self.handle_tags_mode(key),
+            AppMode::QuickDial => self.handle_quick_dial_mode(key),
+            AppMode::Settings => self.handle_settings_mode(key),
+            AppMode::ScriptGroups => self.handle_script_groups_mode(key),
+            AppMode::RequiredEnv => self.handle_required_env_mode(key),
+            AppMode::DuplicateJob => self.handle_duplicate_job_mode(key),
+            AppMode::DangerousScript => self.handle_dangerous_script_mode(key),
+        };
+
+        if self.config.run_in_pane != alt_enter {
+            self.run_in_pane(action)
+        } else {
+            action
         }
     }
 
-    fn handle_normal_mode(&mut self, key: KeyEvent) -> Action {
+    /// F1 anywhere; `?` where it wouldn't be typed (modes without a text
+    /// input). The script list uses its keymap, where a character key only
+    /// opens help while the search is empty.
+    fn opens_help(&self, key: KeyEvent) -> bool {
+        if self.picker {
+            return false;
+        }
+        if self.mode == AppMode::Normal && self.keymap.action_for(&key) == Some(KeyAction::Help) {
+            return !matches!(key.code, KeyCode::Char(_)) || self.current_query().is_empty();
+        }
+        match key.code {
+            KeyCode::F(1) => true,
+            KeyCode::Char('?') => match self.mode {
+                AppMode::Normal => false,
+                AppMode::ConfirmExecution => self
+                    .flow
+                    .as_ref()
+                    .is_none_or(|flow| flow.confirm.preset_name.is_none()),
+                AppMode::ConfigureEnv => self
+                    .flow
+                    .as_ref()
+                    .is_none_or(|flow| flow.env.new_file.is_none()),
+                AppMode::PickPreset
+                | AppMode::PreviewEnv
+                | AppMode::Queue
+                | AppMode::QuickDial
+                | AppMode::Settings
+                | AppMode::ScriptGroups => true,
+                AppMode::ConfigureArgs
+                | AppMode::Schedule
+                | AppMode::Output
+                | AppMode::Tags
+                | AppMode::RequiredEnv
+                | AppMode::DuplicateJob
+                | AppMode::DangerousScript
+                | AppMode::EditEnv
+                | AppMode::PickDirectory => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn handle_help_key(&mut self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Esc => self.handle_esc(),
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-            KeyCode::Enter => self.handle_enter(),
-            // Use Tab key for configure flow
-            KeyCode::Tab => {
-                self.start_configure_flow();
-                Action::Continue
-            }
             KeyCode::Up => {
-                self.move_selection(-1);
+                self.help_scroll = self.help_scroll.saturating_sub(1);
                 Action::Continue
             }
             KeyCode::Down => {
-                self.move_selection(1);
-                Action::Continue
-            }
-            KeyCode::Left => {
-                self.switch_tab(-1);
-                Action::Continue
-            }
-            KeyCode::Right => {
-                self.switch_tab(1);
-                Action::Continue
-            }
-            KeyCode::Char(' ') => {
-                self.toggle_fav();
-                Action::Continue
-            }
-            KeyCode::Char(c) => {
-                self.type_char(c);
+                let lines = crate::ui::help_overlay::help_lines(
+                    self.help_section(),
+                    &self.keymap,
+                    &self.theme,
+                )
+                .len();
+                self.help_scroll = (self.help_scroll + 1).min(lines.saturating_sub(1));
                 Action::Continue
             }
-            KeyCode::Backspace => {
-                self.delete_char();
+            KeyCode::Esc | KeyCode::Enter | KeyCode::F(1) | KeyCode::Char('?' | 'q') => {
+                self.show_help = false;
                 Action::Continue
             }
             _ => Action::Continue,
         }
     }
 
-    pub fn render(&mut self, frame: &mut Frame) {
-        let area = frame.area();
+    /// Help section for the current mode, listed first in the overlay.
+    fn help_section(&self) -> usize {
+        use crate::ui::help_overlay as help;
+        match self.mode {
+            AppMode::Normal if self.active_tab == Tab::Jobs => help::JOBS,
+            AppMode::Normal
+            | AppMode::Schedule
+            | AppMode::Tags
+            | AppMode::RequiredEnv
+            | AppMode::DuplicateJob
+            | AppMode::DangerousScript => help::NORMAL,
+            AppMode::PickPreset => help::PRESETS,
+            AppMode::ConfigureEnv | AppMode::EditEnv | AppMode::PreviewEnv => help::CONFIGURE_ENV,
+            AppMode::ConfigureArgs => help::CONFIGURE_ARGS,
+            AppMode::ConfirmExecution | AppMode::PickDirectory => help::CONFIRM,
+            AppMode::Queue => help::QUEUE,
+            AppMode::QuickDial => help::QUICK_DIAL,
+            AppMode::Settings => help::SETTINGS,
+            AppMode::ScriptGroups => help::SCRIPT_GROUPS,
+            AppMode::Output => help::OUTPUT,
+        }
+    }
 
-        // Build layout constraints depending on whether we show the tab bar
-        let chunks = if self.has_workspaces {
-            Layout::vertical([
-                Constraint::Length(1), // header bar
-                Constraint::Length(2), // tabs
-                Constraint::Length(1), // search input
-                Constraint::Min(1),    // main content
-                Constraint::Length(1), // status bar
-            ])
-            .split(area)
-        } else {
-            Layout::vertical([
-                Constraint::Length(1), // header bar
-                Constraint::Length(0), // no tabs
-                Constraint::Length(1), // search input
-                Constraint::Min(1),    // main content
-                Constraint::Length(1), // status bar
-            ])
-            .split(area)
+    /// Starts a `RunScript` action in the output pane instead of handing it to main.
+    fn run_in_pane(&mut self, action: Action) -> Action {
+        let Action::RunScript {
+            script_name,
+            cwd,
+            env_files,
+            args,
+            env,
+        } = action
+        else {
+            return action;
         };
 
-        // Track actual visible height for scroll calculations
-        self.visible_height = chunks[3].height as usize;
+        // The TUI keeps running, so persist the run now rather than on exit
+        recents::save_recents(&self.config_dir, &self.recents);
 
-        // Header bar
-        crate::ui::header_bar::render_header_bar(
-            frame,
-            chunks[0],
-            &self.project_name,
-            &self.project_path,
-            &self.package_manager_name,
+        self.spawn_job(
+            JobSpec {
+                script_name,
+                cwd,
+                env_files,
+                env,
+                args,
+            },
+            true,
         );
+        Action::Continue
+    }
 
-        // Tabs (only if workspaces exist)
-        if self.has_workspaces {
-            let tab_labels = vec!["Scripts", "Packages"];
-            let active = match self.active_tab {
-                Tab::Scripts => 0,
-                Tab::Packages => 1,
-            };
-            crate::ui::tabs::render_tabs(frame, chunks[1], &tab_labels, active);
-        }
-
-        // Search input
-        let current_query = self.current_query();
-        crate::ui::search_input::render_search_input(frame, chunks[2], current_query);
-
-        // Main content
-        match self.active_tab {
-            Tab::Scripts => {
-                crate::ui::script_list::render_script_list(
-                    frame,
-                    chunks[3],
-                    &self.scripts,
-                    &self.filtered_indices,
-                    self.selected_index,
-                    self.scroll_offset,
-                    &self.favorites,
-                );
-            }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => {
-                    crate::ui::package_list::render_package_list(
-                        frame,
-                        chunks[3],
-                        &self.workspace_packages,
-                        &self.pkg_filtered_indices,
-                        self.pkg_selected_index,
-                        self.pkg_scroll_offset,
-                    );
+    /// Starts `spec` as a job unless the same script already runs as one;
+    /// then, per the `duplicate_jobs` config, asks first or shows that job.
+    /// `attach` shows the new job in the output pane.
+    fn spawn_job(&mut self, spec: JobSpec, attach: bool) {
+        if let Some(existing) = self.jobs.running_instance(&spec) {
+            match self.config.duplicate_jobs {
+                DuplicateJobs::Allow => {}
+                DuplicateJobs::Prevent => {
+                    self.status_message = Some(format!("{} is already running", spec.script_name));
+                    self.attach(existing);
+                    return;
                 }
-                PackageMode::SelectingScript { .. } => {
-                    crate::ui::script_list::render_script_list(
-                        frame,
-                        chunks[3],
-                        &self.pkg_script_sortable,
-                        &self.pkg_script_filtered_indices,
-                        self.pkg_script_selected_index,
-                        self.pkg_script_scroll_offset,
-                        &self.favorites,
-                    );
+                DuplicateJobs::Ask => {
+                    self.duplicate_prompts.push(DuplicatePrompt {
+                        spec,
+                        attach,
+                        existing,
+                    });
+                    self.mode = AppMode::DuplicateJob;
+                    return;
                 }
-            },
+            }
         }
+        self.start_job(spec, attach);
+    }
 
-        // Status bar
-        crate::ui::status_bar::render_status_bar(frame, chunks[4]);
-
-        // NEW: Render modal overlays based on mode
-        match self.mode {
-            AppMode::ConfigureEnv => {
-                if let Some(ref env_list) = self.env_files_list {
-                    crate::ui::env_selector::render_env_selector(
-                        frame,
-                        area,
-                        env_list,
-                        self.env_selected_index,
-                        self.env_scroll_offset,
-                        &self.env_selected_files,
-                    );
-                }
-            }
-            AppMode::ConfigureArgs => {
-                crate::ui::args_input::render_args_input(
-                    frame,
-                    area,
-                    &self.args_input,
-                    self.args_cursor_pos,
-                    &self.args_history.entries,
-                    self.args_history_index,
-                );
+    /// Starts `spec` as a job, first asking for any `required_env` keys its
+    /// environment is missing.
+    fn start_job(&mut self, spec: JobSpec, attach: bool) {
+        let required = self.config.required_env_for(&spec.script_name);
+        if !required.is_empty() {
+            let mut env_vars = load_env_files(&spec.env_files).unwrap_or_default();
+            env_vars.extend(spec.env.iter().cloned());
+            let missing = missing_env(&required, &env_vars, &self.config.inherit_env);
+            if !missing.is_empty() {
+                self.env_prompts.push(EnvPrompt {
+                    spec,
+                    attach,
+                    missing,
+                    input: String::new(),
+                });
+                self.mode = AppMode::RequiredEnv;
+                return;
             }
-            AppMode::ConfirmExecution => {
-                let env_file_names: Vec<String> = if let Some(ref env_list) = self.env_files_list {
-                    env_list
-                        .all_files()
-                        .filter(|f| self.env_selected_files.contains(&f.path))
-                        .map(|f| f.display_name.clone())
-                        .collect()
-                } else {
-                    vec![]
-                };
-
-                let script_name = self.get_current_script_name();
-                let cwd = self.get_current_cwd();
+        }
 
-                crate::ui::execution_confirm::render_execution_confirm(
-                    frame,
-                    area,
-                    self.package_manager,
-                    &script_name,
-                    &env_file_names,
-                    &self.execution_config.args,
-                    &cwd,
-                );
-            }
-            AppMode::Normal => {
-                // No overlay
-            }
+        if !attach {
+            self.status_message = Some(format!(
+                "Started {} in the background (see Jobs tab)",
+                spec.script_name
+            ));
+        }
+        let pm = self.package_manager_for(&spec.cwd);
+        let (template, strategy) = self.piped_run_setup(pm, &spec.cwd);
+        let id = self.jobs.spawn(
+            spec,
+            pm,
+            template.as_deref(),
+            &strategy,
+            &self.config.inherit_env,
+        );
+        if attach {
+            self.attach(id);
         }
     }
 
-    // -- Private helpers --
+    /// Shows a job's output in the pane.
+    fn attach(&mut self, id: JobId) {
+        self.output = Some(OutputView {
+            job: id,
+            scroll: 0,
+            follow: true,
+        });
+        self.mode = AppMode::Output;
+    }
 
-    fn current_query(&self) -> &str {
-        match self.active_tab {
-            Tab::Scripts => &self.query,
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => &self.pkg_query,
-                PackageMode::SelectingScript { .. } => &self.pkg_script_query,
+    /// Starts `run` as a background job, listed in the Jobs tab.
+    fn start_background(&mut self, run: QueuedRun) {
+        self.record_run(&run);
+        recents::save_recents(&self.config_dir, &self.recents);
+
+        self.spawn_job(
+            JobSpec {
+                script_name: run.script_name,
+                cwd: run.cwd,
+                env_files: run.env_files,
+                env: run.env,
+                args: run.args,
             },
-        }
+            false,
+        );
     }
 
-    fn handle_esc(&mut self) -> Action {
-        match self.active_tab {
-            Tab::Scripts => Action::Quit,
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => Action::Quit,
-                PackageMode::SelectingScript { .. } => {
-                    // Go back to package list
-                    self.package_mode = PackageMode::SelectingPackage;
-                    self.pkg_script_query.clear();
-                    self.pkg_script_selected_index = 0;
-                    self.pkg_script_scroll_offset = 0;
-                    Action::Continue
-                }
-            },
-        }
+    /// Whether the job shown in the output pane is still running.
+    pub fn output_running(&self) -> bool {
+        self.output
+            .as_ref()
+            .and_then(|view| self.jobs.get(view.job))
+            .is_some_and(|job| job.is_running())
     }
 
-    fn handle_enter(&mut self) -> Action {
-        match self.active_tab {
-            Tab::Scripts => {
-                if let Some(&script_idx) = self.filtered_indices.get(self.selected_index) {
-                    let script = &self.scripts[script_idx];
-                    let script_name = script.name.clone();
-                    let key = script.key.clone();
-
-                    // Record execution
-                    recents::record_execution(&mut self.recents, &key);
+    /// Whether any job started inside the TUI is still running.
+    pub fn jobs_running(&self) -> bool {
+        self.jobs.running_count() > 0
+    }
 
-                    Action::RunScript {
-                        script_name,
-                        cwd: self.nearest_pkg.clone(),
-                        env_files: vec![],
-                        args: String::new(),
+    /// Collects new output and exit statuses from jobs, announcing background exits.
+    fn poll_jobs(&mut self) {
+        let attached = self.output.as_ref().map(|view| view.job);
+        for event in self.jobs.poll() {
+            if let JobEvent::Exited { id, exit_code } = event {
+                if let Some(job) = self.jobs.get(id) {
+                    let duration = job.finished.map_or(Duration::ZERO, |(_, d)| d);
+                    let spec = job.spec.clone();
+                    if let Some(ref mut watch) = self.watch {
+                        if watch.script_name == spec.script_name && watch.cwd == spec.cwd {
+                            watch.last = Some((exit_code, duration));
+                        }
                     }
-                } else {
-                    Action::Continue
+                    notify_finished(&self.config.notify, &spec.script_name, exit_code, duration);
+                    self.record_outcome(
+                        &spec.script_name,
+                        &spec.cwd,
+                        &spec.args,
+                        &spec.env_files,
+                        exit_code,
+                        duration,
+                    );
                 }
-            }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => {
-                    if let Some(&pkg_idx) = self.pkg_filtered_indices.get(self.pkg_selected_index) {
-                        // Enter package script selection mode
-                        self.enter_package_scripts(pkg_idx);
-                    }
-                    Action::Continue
+                if Some(id) == attached {
+                    continue;
                 }
-                PackageMode::SelectingScript { package_index } => {
-                    if let Some(&script_idx) = self
-                        .pkg_script_filtered_indices
-                        .get(self.pkg_script_selected_index)
-                    {
-                        let script = &self.pkg_script_sortable[script_idx];
-                        let script_name = script.name.clone();
-                        let key = script.key.clone();
-
-                        // Record execution
-                        recents::record_execution(&mut self.recents, &key);
-
-                        // cwd is the monorepo_root joined with the package's relative_path
-                        let pkg = &self.workspace_packages[package_index];
-                        let cwd = self
-                            .monorepo_root
-                            .as_ref()
-                            .map(|r| r.join(&pkg.relative_path))
-                            .unwrap_or_else(|| self.nearest_pkg.clone());
-
-                        Action::RunScript {
-                            script_name,
-                            cwd,
-                            env_files: vec![],
-                            args: String::new(),
-                        }
-                    } else {
-                        Action::Continue
-                    }
+                if let Some(job) = self.jobs.get(id) {
+                    self.status_message = Some(format!(
+                        "{} {} exited with {}",
+                        if exit_code == 0 {
+                            self.theme.symbols.success
+                        } else {
+                            self.theme.symbols.failure
+                        },
+                        job.spec.script_name,
+                        exit_code
+                    ));
                 }
-            },
+            }
         }
     }
 
-    fn enter_package_scripts(&mut self, pkg_idx: usize) {
-        let pkg = &self.workspace_packages[pkg_idx];
-        let pkg_name = &pkg.name;
-
-        // Convert package scripts to SortableScript
-        self.pkg_script_sortable = pkg
-            .scripts
-            .iter()
-            .map(|(name, command)| SortableScript {
-                key: format!("{}:{}", pkg_name, name),
-                name: name.clone(),
-                command: command.clone(),
-            })
-            .collect();
+    fn restart_job(&mut self, id: JobId) {
+        let Some(cwd) = self.jobs.get(id).map(|job| job.spec.cwd.clone()) else {
+            return;
+        };
+        let pm = self.package_manager_for(&cwd);
+        let (template, strategy) = self.piped_run_setup(pm, &cwd);
+        self.jobs.restart(
+            id,
+            pm,
+            template.as_deref(),
+            &strategy,
+            &self.config.inherit_env,
+        );
+    }
 
-        self.package_mode = PackageMode::SelectingScript {
-            package_index: pkg_idx,
+    /// Sends the next stop signal to a job and reports it in the status message.
+    fn interrupt_job(&mut self, id: JobId) {
+        let Some(name) = self.jobs.get(id).map(|j| j.spec.script_name.clone()) else {
+            return;
         };
-        self.pkg_script_query.clear();
-        self.pkg_script_selected_index = 0;
-        self.pkg_script_scroll_offset = 0;
+        self.status_message = match self.jobs.interrupt(id) {
+            Some(Ok(Interrupt::Signalled(Signal::Interrupt))) => {
+                Some(format!("Stopping {} (Ctrl+C again to force)", name))
+            }
+            Some(Ok(Interrupt::Signalled(Signal::Terminate))) => {
+                Some(format!("Terminating {} (Ctrl+C again to kill)", name))
+            }
+            Some(Ok(Interrupt::Killed)) => Some(format!("Killed {}", name)),
+            Some(Err(e)) => Some(format!("Failed to stop {}: {}", name, e)),
+            None => None,
+        };
+        if self.watched_job() == Some(id) {
+            self.watch = None;
+            if let Some(ref mut message) = self.status_message {
+                message.push_str(", no longer watching");
+            }
+        }
+    }
 
-        // Initial filter: all scripts sorted
-        self.pkg_script_filtered_indices = sort_scripts(
-            &self.pkg_script_sortable,
-            &self.favorites,
-            &self.recents,
-            "",
-        );
+    /// The job a watched script last ran in.
+    fn watched_job(&self) -> Option<JobId> {
+        let watch = self.watch.as_ref()?;
+        self.jobs
+            .iter()
+            .filter(|job| job.spec.script_name == watch.script_name && job.spec.cwd == watch.cwd)
+            .map(|job| job.id)
+            .last()
     }
 
-    fn move_selection(&mut self, delta: i32) {
-        match self.active_tab {
-            Tab::Scripts => {
-                let len = self.filtered_indices.len();
-                if len == 0 {
-                    return;
-                }
-                self.selected_index = wrap_index(self.selected_index, delta, len);
-                self.ensure_visible_scripts();
+    /// Starts `run` in the output pane and watches for file changes to run it
+    /// again. Dangerous scripts have been confirmed by then (see
+    /// [`Self::start_configured`]); their re-runs don't ask again.
+    fn start_watch(&mut self, run: QueuedRun) -> Action {
+        match FileWatch::start(&run.cwd, &self.config.watch) {
+            Ok(files) => {
+                self.watch = Some(ScriptWatch {
+                    script_name: run.script_name.clone(),
+                    cwd: run.cwd.clone(),
+                    files,
+                    last: None,
+                });
+                self.mode = AppMode::Normal;
+                let action = self.start_run(run);
+                self.run_in_pane(action)
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Can't watch {}: {:#}", run.script_name, e));
+                self.mode = AppMode::Normal;
+                Action::Continue
             }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => {
-                    let len = self.pkg_filtered_indices.len();
-                    if len == 0 {
-                        return;
-                    }
-                    self.pkg_selected_index = wrap_index(self.pkg_selected_index, delta, len);
-                    self.ensure_visible_packages();
-                }
-                PackageMode::SelectingScript { .. } => {
-                    let len = self.pkg_script_filtered_indices.len();
-                    if len == 0 {
-                        return;
-                    }
-                    self.pkg_script_selected_index =
-                        wrap_index(self.pkg_script_selected_index, delta, len);
-                    self.ensure_visible_pkg_scripts();
-                }
-            },
         }
     }
 
-    fn switch_tab(&mut self, delta: i32) {
-        if !self.has_workspaces {
+    /// Re-runs the watched script once changed files have settled. The watch
+    /// ends when its job is gone (removed, or its env prompt cancelled).
+    fn poll_watch(&mut self, now: Instant) {
+        let Some(ref mut watch) = self.watch else {
             return;
-        }
-        match (self.active_tab, delta) {
-            (Tab::Scripts, 1) => {
-                self.active_tab = Tab::Packages;
-            }
-            (Tab::Packages, -1) => {
-                // Reset package mode when switching away
-                self.package_mode = PackageMode::SelectingPackage;
-                self.pkg_script_query.clear();
-                self.active_tab = Tab::Scripts;
+        };
+        let Some(changed) = watch.files.poll(now) else {
+            return;
+        };
+        match self.watched_job() {
+            Some(id) => {
+                let name = self.watch.as_ref().map(|w| w.script_name.clone());
+                self.restart_job(id);
+                self.status_message = Some(format!(
+                    "{} changed, running {} again",
+                    changed.display(),
+                    name.unwrap_or_default()
+                ));
             }
-            _ => {}
+            None => self.watch = None,
         }
     }
 
-    fn toggle_fav(&mut self) {
-        match self.active_tab {
-            Tab::Scripts => {
-                if let Some(&script_idx) = self.filtered_indices.get(self.selected_index) {
-                    let key = self.scripts[script_idx].key.clone();
-                    favorites::toggle_favorite(&mut self.favorites, &key);
-                    self.update_filtered();
+    /// Status bar note on the watched script, e.g. `watching src/** (build ✔ 1.2s)`.
+    fn watch_label(&self, now: Instant) -> Option<String> {
+        let watch = self.watch.as_ref()?;
+        let result = match watch.last {
+            Some((code, duration)) => format!(
+                "{} {}",
+                if code == 0 {
+                    self.theme.symbols.success
+                } else {
+                    self.theme.symbols.failure
+                },
+                format_duration(duration)
+            ),
+            None => match self.watched_job().and_then(|id| self.jobs.get(id)) {
+                Some(job) => format!("running {}", format_duration(job.elapsed(now))),
+                None => "starting".to_string(),
+            },
+        };
+        Some(format!(
+            "watching {} ({} {})",
+            watch.files.label, watch.script_name, result
+        ))
+    }
+
+    fn handle_output_mode(&mut self, key: KeyEvent) -> Action {
+        let page = self.output_height.max(1);
+        let running = self.output_running();
+        let Some(ref mut view) = self.output else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        let id = view.job;
+        let line_count = self.jobs.get(id).map_or(0, |job| job.lines.len());
+        let bottom = line_count.saturating_sub(page);
+        if view.follow {
+            view.scroll = bottom;
+        }
+        view.scroll = view.scroll.min(bottom);
+
+        match key.code {
+            // Ctrl+C stops the script first; once it has exited, it quits nr as usual
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if !running {
+                    return Action::Quit;
                 }
+                self.interrupt_job(id);
+                return Action::Continue;
             }
-            Tab::Packages => {
-                if let PackageMode::SelectingScript { .. } = self.package_mode {
-                    if let Some(&script_idx) = self
-                        .pkg_script_filtered_indices
-                        .get(self.pkg_script_selected_index)
-                    {
-                        let key = self.pkg_script_sortable[script_idx].key.clone();
-                        favorites::toggle_favorite(&mut self.favorites, &key);
-                        self.update_pkg_script_filtered();
-                    }
+            KeyCode::Char('r') => {
+                self.restart_job(id);
+                self.attach(id);
+                return Action::Continue;
+            }
+            // Leaving a running script keeps it going in the Jobs tab
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.detach();
+                return Action::Continue;
+            }
+            KeyCode::Enter if !running => {
+                self.detach();
+                return Action::Continue;
+            }
+            KeyCode::Char('n') if !running => {
+                if let Some(run) = self.suggested_after_output() {
+                    let action = self.start_checked_run(run);
+                    return self.run_in_pane(action);
                 }
+                return Action::Continue;
             }
+            KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Down => view.scroll = (view.scroll + 1).min(bottom),
+            KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(page),
+            KeyCode::PageDown => view.scroll = (view.scroll + page).min(bottom),
+            KeyCode::Home => view.scroll = 0,
+            KeyCode::End => view.scroll = bottom,
+            _ => {}
         }
+        view.follow = view.scroll >= bottom;
+        Action::Continue
     }
 
-    fn type_char(&mut self, c: char) {
-        match self.active_tab {
-            Tab::Scripts => {
-                self.query.push(c);
-                self.update_filtered();
-            }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => {
-                    self.pkg_query.push(c);
-                    self.update_pkg_filtered();
-                }
-                PackageMode::SelectingScript { .. } => {
-                    self.pkg_script_query.push(c);
-                    self.update_pkg_script_filtered();
-                }
-            },
+    /// Closes the output pane; a running job carries on in the background.
+    fn detach(&mut self) {
+        if self.output_running() {
+            self.status_message = Some("Still running in the background (see Jobs tab)".into());
         }
+        self.output = None;
+        self.mode = AppMode::Normal;
     }
 
-    fn delete_char(&mut self) {
-        match self.active_tab {
-            Tab::Scripts => {
-                self.query.pop();
-                self.update_filtered();
+    fn handle_jobs_tab(&mut self, key: KeyEvent) -> Action {
+        let selected = self
+            .jobs
+            .iter()
+            .nth(self.jobs_selected_index)
+            .map(|job| job.id);
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_queue();
+                Action::Continue
             }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingPackage => {
-                    self.pkg_query.pop();
-                    self.update_pkg_filtered();
+            KeyCode::Esc => {
+                self.active_tab = Tab::Scripts;
+                Action::Continue
+            }
+            KeyCode::Left => {
+                self.switch_tab(-1);
+                Action::Continue
+            }
+            KeyCode::Right => {
+                self.switch_tab(1);
+                Action::Continue
+            }
+            KeyCode::Up => {
+                self.move_selection(-1);
+                Action::Continue
+            }
+            KeyCode::Down => {
+                self.move_selection(1);
+                Action::Continue
+            }
+            KeyCode::Enter => {
+                if let Some(id) = selected {
+                    self.attach(id);
                 }
-                PackageMode::SelectingScript { .. } => {
-                    self.pkg_script_query.pop();
-                    self.update_pkg_script_filtered();
+                Action::Continue
+            }
+            KeyCode::Char('x') => {
+                if let Some(id) = selected {
+                    self.interrupt_job(id);
                 }
-            },
+                Action::Continue
+            }
+            KeyCode::Char('r') => {
+                if let Some(id) = selected {
+                    self.restart_job(id);
+                }
+                Action::Continue
+            }
+            // Only finished jobs can be removed; stop running ones with `x` first
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(id) = selected.filter(|&id| !self.jobs.get(id).unwrap().is_running()) {
+                    self.jobs.remove(id);
+                    if self.jobs.is_empty() {
+                        self.active_tab = Tab::Scripts;
+                    }
+                    self.jobs_selected_index = self
+                        .jobs_selected_index
+                        .min(self.jobs.len().saturating_sub(1));
+                }
+                Action::Continue
+            }
+            _ => Action::Continue,
         }
     }
 
-    fn update_filtered(&mut self) {
-        self.filtered_indices =
-            sort_scripts(&self.scripts, &self.favorites, &self.recents, &self.query);
-        self.selected_index = 0;
-        self.scroll_offset = 0;
-    }
+    fn handle_normal_mode(&mut self, key: KeyEvent) -> Action {
+        if self.picker {
+            return self.handle_picker_key(key);
+        }
+        if self.active_tab == Tab::Jobs {
+            return self.handle_jobs_tab(key);
+        }
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
 
-    fn update_pkg_filtered(&mut self) {
-        self.pkg_filtered_indices =
-            fuzzy_filter(&self.workspace_packages, &self.pkg_query, |p| &p.name);
-        self.pkg_selected_index = 0;
-        self.pkg_scroll_offset = 0;
-    }
+        // Alt+Enter is whatever Enter is bound to, run in the other place (see `handle_key`)
+        let lookup = if key.code == KeyCode::Enter {
+            KeyEvent::new(KeyCode::Enter, key.modifiers - KeyModifiers::ALT)
+        } else {
+            key
+        };
+        if let Some(action) = self.keymap.action_for(&lookup) {
+            if let Some(result) = self.run_key_action(action) {
+                return result;
+            }
+        }
 
-    fn update_pkg_script_filtered(&mut self) {
-        self.pkg_script_filtered_indices = sort_scripts(
-            &self.pkg_script_sortable,
-            &self.favorites,
-            &self.recents,
-            &self.pkg_script_query,
-        );
-        self.pkg_script_selected_index = 0;
-        self.pkg_script_scroll_offset = 0;
+        match key.code {
+            KeyCode::Up => {
+                self.move_selection(-1);
+                Action::Continue
+            }
+            KeyCode::Down => {
+                self.move_selection(1);
+                Action::Continue
+            }
+            KeyCode::Left => {
+                self.switch_tab(-1);
+                Action::Continue
+            }
+            KeyCode::Right => {
+                self.switch_tab(1);
+                Action::Continue
+            }
+            KeyCode::Char(c) => {
+                self.type_char(c);
+                Action::Continue
+            }
+            KeyCode::Backspace => {
+                self.delete_char();
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
     }
 
-    fn ensure_visible_scripts(&mut self) {
-        ensure_scroll(
-            &mut self.scroll_offset,
-            self.selected_index,
-            self.visible_height,
-        );
-    }
-
-    fn ensure_visible_packages(&mut self) {
-        ensure_scroll(
-            &mut self.pkg_scroll_offset,
-            self.pkg_selected_index,
-            self.visible_height,
-        );
-    }
-
-    fn ensure_visible_pkg_scripts(&mut self) {
-        ensure_scroll(
-            &mut self.pkg_script_scroll_offset,
-            self.pkg_script_selected_index,
-            self.visible_height,
-        );
-    }
-}
-
-/// Wrap index with delta, cycling around `len`.
-fn wrap_index(current: usize, delta: i32, len: usize) -> usize {
-    if len == 0 {
-        return 0;
-    }
-    let new = current as i32 + delta;
-    if new < 0 {
-        len - 1
-    } else if new >= len as i32 {
-        0
-    } else {
-        new as usize
-    }
-}
-
-/// Adjust scroll_offset so that `selected` stays visible within the given height.
-fn ensure_scroll(scroll_offset: &mut usize, selected: usize, visible_height: usize) {
-    if selected < *scroll_offset {
-        *scroll_offset = selected;
-    }
-    let height = visible_height.max(1);
-    if selected >= *scroll_offset + height {
-        *scroll_offset = selected.saturating_sub(height - 1);
-    }
-}
-
-impl App {
-    // NEW: Configuration flow methods
-
-    fn start_configure_flow(&mut self) {
-        // Get current script key
-        let script_key = self.get_current_script_key();
-
-        // Restore script-specific args (if exists)
-        if let Some(config) = self.script_configs.get(&script_key) {
-            self.execution_config.args = config.args.clone();
-        } else {
-            self.execution_config = ExecutionConfig::default();
-        }
-
-        // Scan .env files
-        let cwd = self.get_current_cwd();
-        self.env_files_list = Some(scan_env_files(&cwd, &self.monorepo_root));
-
-        // Pre-select globally last used env files
-        self.env_selected_files = if let Some(ref env_list) = self.env_files_list {
-            env_list
-                .all_files()
-                .filter(|f| {
-                    self.global_env_config
-                        .last_env_files
-                        .contains(&f.display_name)
-                })
-                .map(|f| f.path.clone())
-                .collect()
-        } else {
-            HashSet::new()
-        };
-        self.env_selected_index = 0;
-        self.env_scroll_offset = 0;
-
-        // Enter env selection mode
-        self.mode = AppMode::ConfigureEnv;
-    }
-
-    fn get_current_script_key(&self) -> String {
-        let project_id = crate::store::project_id::project_id(&self.config_dir);
-
-        match self.active_tab {
-            Tab::Scripts => {
-                if let Some(&script_idx) = self.filtered_indices.get(self.selected_index) {
-                    let script = &self.scripts[script_idx];
-                    format!("{}:{}", project_id, script.key)
-                } else {
-                    format!("{}:unknown", project_id)
-                }
+    /// Performs a script list action from the keymap. `None` lets the key fall
+    /// through to the search input instead.
+    fn run_key_action(&mut self, action: KeyAction) -> Option<Action> {
+        let result = match action {
+            KeyAction::Run => self.handle_enter(),
+            KeyAction::QuickRun => self.quick_run(),
+            KeyAction::Configure => {
+                self.start_configure_flow();
+                Action::Continue
             }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingScript { package_index: _ } => {
-                    if let Some(&script_idx) = self
-                        .pkg_script_filtered_indices
-                        .get(self.pkg_script_selected_index)
-                    {
-                        let script = &self.pkg_script_sortable[script_idx];
-                        format!("{}:{}", project_id, script.key)
-                    } else {
-                        format!("{}:unknown", project_id)
-                    }
-                }
-                _ => format!("{}:unknown", project_id),
-            },
-        }
-    }
-
-    fn get_current_cwd(&self) -> PathBuf {
-        match self.active_tab {
-            Tab::Scripts => self.nearest_pkg.clone(),
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingScript { package_index } => {
-                    let pkg = &self.workspace_packages[package_index];
-                    self.monorepo_root
-                        .as_ref()
-                        .map(|r| r.join(&pkg.relative_path))
-                        .unwrap_or_else(|| self.nearest_pkg.clone())
-                }
-                _ => self.nearest_pkg.clone(),
-            },
-        }
-    }
-
-    fn handle_env_mode(&mut self, key: KeyEvent) -> Action {
-        match key.code {
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-            KeyCode::Esc => {
-                // Cancel configuration
-                self.mode = AppMode::Normal;
-                self.execution_config = ExecutionConfig::default();
-                self.env_files_list = None;
+            // Search terms in the History tab are separated by spaces
+            KeyAction::ToggleFavorite if self.active_tab == Tab::History => return None,
+            KeyAction::ToggleFavorite => {
+                self.toggle_fav();
                 Action::Continue
             }
-            KeyCode::Enter => {
-                // Proceed to args input
-                self.mode = AppMode::ConfigureArgs;
-                self.args_input = self.execution_config.args.clone();
-                self.args_history_index = None;
+            KeyAction::Enqueue => {
+                self.enqueue_selected();
                 Action::Continue
             }
-            KeyCode::Up => {
-                if let Some(ref env_list) = self.env_files_list {
-                    let total_files = env_list.package_files.len() + env_list.root_files.len();
-                    if total_files > 0 && self.env_selected_index > 0 {
-                        self.env_selected_index -= 1;
-                    }
-                }
+            KeyAction::OpenQueue => {
+                self.open_queue();
                 Action::Continue
             }
-            KeyCode::Down => {
-                if let Some(ref env_list) = self.env_files_list {
-                    let total_files = env_list.package_files.len() + env_list.root_files.len();
-                    if self.env_selected_index + 1 < total_files {
-                        self.env_selected_index += 1;
-                    }
+            KeyAction::Schedule => {
+                if let Some(run) = self.selected_run() {
+                    self.start_schedule(run);
                 }
                 Action::Continue
             }
-            KeyCode::Char(' ') => {
-                // Toggle selection
-                if let Some(ref env_list) = self.env_files_list {
-                    let all_files: Vec<&EnvFile> = env_list.all_files().collect();
-                    if let Some(file) = all_files.get(self.env_selected_index) {
-                        if self.env_selected_files.contains(&file.path) {
-                            self.env_selected_files.remove(&file.path);
-                        } else {
-                            self.env_selected_files.insert(file.path.clone());
-                        }
+            KeyAction::Background => {
+                if let Some(run) = self.selected_run() {
+                    if self.config.is_dangerous(&run.script_name) {
+                        self.ask_script_name(
+                            run.script_name.clone(),
+                            DangerousRun::Background(run),
+                        );
+                    } else {
+                        self.start_background(run);
                     }
                 }
                 Action::Continue
             }
-            _ => Action::Continue,
-        }
-    }
-
-    fn handle_args_mode(&mut self, key: KeyEvent) -> Action {
-        match key.code {
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-            KeyCode::Esc => {
-                // Go back to env selection
-                self.mode = AppMode::ConfigureEnv;
+            KeyAction::ShowLocation => {
+                self.status_message = self
+                    .selected_script_location()
+                    .map(|(path, line)| format!("Defined in {}:{}", path.display(), line));
                 Action::Continue
             }
-            KeyCode::Enter => {
-                // Save input and proceed to confirmation
-                self.execution_config.args = self.args_input.clone();
-                self.mode = AppMode::ConfirmExecution;
+            KeyAction::OpenEditor => match self.selected_script_location() {
+                Some((path, line)) => Action::OpenEditor { path, line },
+                None => Action::Continue,
+            },
+            KeyAction::CopyCommand => {
+                self.copy_resolved_command();
                 Action::Continue
             }
-            KeyCode::Up => {
-                // Navigate history (up = move to older/higher index)
-                if let Some(idx) = self.args_history_index {
-                    if idx == 0 {
-                        self.args_history_index = None;
-                        self.args_input = self.execution_config.args.clone();
-                    } else {
-                        let new_idx = idx - 1;
-                        self.args_input = self.args_history.entries[new_idx].clone();
-                        self.args_history_index = Some(new_idx);
-                    }
-                }
-                self.args_cursor_pos = self.args_input.len();
+            KeyAction::PrintCommand => match self.resolved_command() {
+                Some(command) => Action::PrintCommand { command },
+                None => Action::Continue,
+            },
+            KeyAction::EditTags => {
+                self.start_tagging();
                 Action::Continue
             }
-            KeyCode::Down => {
-                // Navigate history (down = move to newer/lower index)
-                let history_len = self.args_history.entries.len();
-                if history_len > 0 {
-                    let new_index = match self.args_history_index {
-                        Some(idx) if idx + 1 < history_len => Some(idx + 1),
-                        None => Some(0),
-                        _ => self.args_history_index,
-                    };
-                    if let Some(idx) = new_index {
-                        self.args_input = self.args_history.entries[idx].clone();
-                        self.args_history_index = Some(idx);
-                    }
-                }
-                self.args_cursor_pos = self.args_input.len();
+            KeyAction::QuickDial => {
+                self.open_quick_dial();
                 Action::Continue
             }
-            KeyCode::Left => {
-                // Move cursor left
-                if self.args_cursor_pos > 0 {
-                    self.args_cursor_pos -= 1;
+            KeyAction::RunSuggested => match self.suggested_next() {
+                Some(run) => self.start_checked_run(run),
+                None => {
+                    self.status_message = Some("No suggested next script yet".to_string());
+                    Action::Continue
+                }
+            },
+            KeyAction::RunLast => match self.history.first() {
+                Some(entry) => {
+                    let run = entry.to_run();
+                    self.start_checked_run(run)
                 }
+                None => {
+                    self.status_message = Some("Nothing has been run here yet".to_string());
+                    Action::Continue
+                }
+            },
+            KeyAction::ToggleGrouping => {
+                if self.current_view() != SCRIPTS_VIEW {
+                    self.status_message =
+                        Some("Grouping by prefix is available in the Scripts tab".to_string());
+                    return Some(Action::Continue);
+                }
+                let group = !self.group_scripts();
+                self.update_view_prefs(|prefs| prefs.group = Some(group));
+                self.update_filtered();
+                self.status_message = Some(
+                    if group {
+                        "Scripts grouped by prefix (Enter expands a group)"
+                    } else {
+                        "Scripts ungrouped"
+                    }
+                    .to_string(),
+                );
                 Action::Continue
             }
-            KeyCode::Right => {
-                // Move cursor right
-                if self.args_cursor_pos < self.args_input.len() {
-                    self.args_cursor_pos += 1;
-                }
+            KeyAction::ToggleHidden => {
+                let prefs = self.update_view_prefs(|prefs| prefs.show_hidden = !prefs.show_hidden);
+                self.refresh_current_view();
+                let scripts = match self.package_mode {
+                    PackageMode::SelectingScript { .. } | PackageMode::AllScripts
+                        if self.active_tab == Tab::Packages =>
+                    {
+                        &self.pkg_script_sortable
+                    }
+                    _ => &self.scripts,
+                };
+                let count = scripts
+                    .iter()
+                    .filter(|s| self.hidden_scripts.is_hidden(&s.name))
+                    .count();
+                let noun = if count == 1 { "script" } else { "scripts" };
+                self.status_message = Some(match (count, prefs.show_hidden) {
+                    (0, _) => "No scripts are hidden (see hide_scripts in the config)".to_string(),
+                    (_, true) => format!("Showing {} hidden {}", count, noun),
+                    (_, false) => format!("Hiding {} {}", count, noun),
+                });
                 Action::Continue
             }
-            KeyCode::Home => {
-                // Move cursor to start
-                self.args_cursor_pos = 0;
+            KeyAction::ScriptGroups => {
+                self.open_script_groups();
                 Action::Continue
             }
-            KeyCode::End => {
-                // Move cursor to end
-                self.args_cursor_pos = self.args_input.len();
+            KeyAction::RunAllPackages => self.run_all_packages(),
+            KeyAction::AllScripts => {
+                self.open_all_scripts();
                 Action::Continue
             }
-            KeyCode::Char(c) => {
-                // Insert character at cursor position
-                self.args_input.insert(self.args_cursor_pos, c);
-                self.args_cursor_pos += 1;
-                self.args_history_index = None;
+            KeyAction::CycleSort => {
+                let prefs = self.update_view_prefs(|prefs| prefs.sort = prefs.sort.next());
+                self.refresh_current_view();
+                self.status_message = Some(format!("Sorted by {}", prefs.sort.label()));
                 Action::Continue
             }
-            KeyCode::Backspace => {
-                // Delete character before cursor
-                if self.args_cursor_pos > 0 {
-                    self.args_input.remove(self.args_cursor_pos - 1);
-                    self.args_cursor_pos -= 1;
-                    self.args_history_index = None;
-                }
+            KeyAction::Reload => {
+                self.reload();
                 Action::Continue
             }
-            KeyCode::Delete => {
-                // Delete character at cursor
-                if self.args_cursor_pos < self.args_input.len() {
-                    self.args_input.remove(self.args_cursor_pos);
-                    self.args_history_index = None;
-                }
+            KeyAction::Settings => {
+                self.settings_selected = 0;
+                self.settings_confirm = false;
+                self.mode = AppMode::Settings;
                 Action::Continue
             }
-            _ => Action::Continue,
+            // Opened in `handle_key` unless the key is being typed into the search
+            KeyAction::Help => return None,
+            KeyAction::Quit => self.handle_esc(),
+        };
+        Some(result)
+    }
+
+    /// Mouse on a script list: click selects a row, double-click runs it, and
+    /// the wheel moves the selection without wrapping around.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, now: Instant) -> Action {
+        if self.mode != AppMode::Normal {
+            return Action::Continue;
+        }
+        let (selected, scroll, len) = match (self.active_tab, &self.package_mode) {
+            (Tab::Scripts, _) => (
+                self.selected_index,
+                self.scroll_offset,
+                self.script_rows.len(),
+            ),
+            (Tab::Packages, PackageMode::SelectingScript { .. } | PackageMode::AllScripts) => (
+                self.pkg_script_selected_index,
+                self.pkg_script_scroll_offset,
+                self.pkg_script_filtered_indices.len(),
+            ),
+            _ => return Action::Continue,
+        };
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown if selected + 1 < len => self.move_selection(1),
+            MouseEventKind::ScrollUp if selected > 0 => self.move_selection(-1),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(row) = crate::ui::script_list::row_at(
+                    self.list_area,
+                    scroll,
+                    len,
+                    mouse.column,
+                    mouse.row,
+                ) else {
+                    return Action::Continue;
+                };
+                self.move_selection(row as i32 - selected as i32);
+
+                let double = self.last_click.is_some_and(|(last_row, at)| {
+                    last_row == row && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+                if double {
+                    self.last_click = None;
+                    return self.handle_key(KeyEvent::from(KeyCode::Enter));
+                }
+                self.last_click = Some((row, now));
+            }
+            _ => {}
         }
+        Action::Continue
     }
 
-    fn handle_confirm_mode(&mut self, key: KeyEvent) -> Action {
+    /// Picker keys: navigate, type to search, Enter picks, Esc cancels.
+    fn handle_picker_key(&mut self, key: KeyEvent) -> Action {
         match key.code {
+            KeyCode::Esc => Action::Quit,
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-            KeyCode::Esc => {
-                // Go back to args input
-                self.mode = AppMode::ConfigureArgs;
-                Action::Continue
-            }
             KeyCode::Enter => {
-                // Execute with configuration
-                let script_key = self.get_current_script_key();
                 let script_name = self.get_current_script_name();
-                let cwd = self.get_current_cwd();
-
-                // Save script-specific args
-                self.script_configs.insert(
-                    script_key.clone(),
-                    ScriptConfig {
-                        args: self.execution_config.args.clone(),
-                        last_used: SystemTime::now(),
-                    },
-                );
-                let _ = script_configs::save_script_configs(&self.config_dir, &self.script_configs);
-
-                // Save globally last used env files
-                if let Some(ref env_list) = self.env_files_list {
-                    self.global_env_config.last_env_files = env_list
-                        .all_files()
-                        .filter(|f| self.env_selected_files.contains(&f.path))
-                        .map(|f| f.display_name.clone())
-                        .collect();
-                    let _ = crate::store::global_env::save_global_env_config(
-                        &self.config_dir,
-                        &self.global_env_config,
-                    );
+                if script_name.is_empty() {
+                    Action::Continue
+                } else {
+                    Action::PrintScript { script_name }
                 }
+            }
+            KeyCode::Up => {
+                self.move_selection(-1);
+                Action::Continue
+            }
+            KeyCode::Down => {
+                self.move_selection(1);
+                Action::Continue
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.type_char(c);
+                Action::Continue
+            }
+            KeyCode::Backspace => {
+                self.delete_char();
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
 
-                // Save args to history
-                if !self.execution_config.args.is_empty() {
-                    self.args_history
-                        .add_entry(self.execution_config.args.clone());
-                    let _ = args_history::save_args_history(&self.config_dir, &self.args_history);
-                }
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+
+        if self.picker {
+            let [search_area, list_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(area);
+            self.visible_height = list_area.height as usize;
+            self.list_area = list_area;
+            crate::ui::search_input::render_search_input(
+                frame,
+                search_area,
+                &self.theme,
+                &self.query,
+            );
+            crate::ui::script_list::render_script_list(
+                frame,
+                list_area,
+                &self.theme,
+                &self.scripts,
+                &self.script_rows,
+                self.selected_index,
+                self.scroll_offset,
+                &self.favorites,
+                &ScriptQuery::parse(&self.query),
+            );
+            return;
+        }
+
+        // Build layout constraints depending on whether we show the tab bar
+        let tabs = self.available_tabs();
+        let chunks = if tabs.len() > 1 {
+            Layout::vertical([
+                Constraint::Length(1), // header bar
+                Constraint::Length(2), // tabs
+                Constraint::Length(1), // search input
+                Constraint::Min(1),    // main content
+                Constraint::Length(1), // status bar
+            ])
+            .split(area)
+        } else {
+            Layout::vertical([
+                Constraint::Length(1), // header bar
+                Constraint::Length(0), // no tabs
+                Constraint::Length(1), // search input
+                Constraint::Min(1),    // main content
+                Constraint::Length(1), // status bar
+            ])
+            .split(area)
+        };
+
+        // Config problems above the list, unless that would leave no room for it
+        let banner_rows = u16::from(!self.config_issues.is_empty() && chunks[3].height > 2);
+        let [banner_area, content_area] =
+            Layout::vertical([Constraint::Length(banner_rows), Constraint::Min(1)])
+                .areas(chunks[3]);
+
+        // Preview of the highlighted script below the list, when there is room
+        let preview_rows = self
+            .highlighted_script()
+            .filter(|_| content_area.height >= PREVIEW_MIN_CONTENT_HEIGHT)
+            .map(|(script, scripts)| {
+                crate::ui::script_list::preview_height(script, scripts, content_area.width)
+                    .min(content_area.height / 3)
+            })
+            .unwrap_or(0);
+        let package_preview_rows = self
+            .highlighted_package()
+            .filter(|_| content_area.height >= PREVIEW_MIN_CONTENT_HEIGHT)
+            .map(|pkg| {
+                crate::ui::package_list::package_preview_height(pkg, content_area.width)
+                    .min(content_area.height / 3)
+            })
+            .unwrap_or(0);
+        let [list_area, preview_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(preview_rows.max(package_preview_rows)),
+        ])
+        .areas(content_area);
+
+        // Track actual visible height for scroll calculations
+        self.visible_height = list_area.height as usize;
+        self.list_area = list_area;
+
+        // Header bar
+        crate::ui::header_bar::render_header_bar(
+            frame,
+            chunks[0],
+            &self.theme,
+            self.config
+                .header_format
+                .as_deref()
+                .unwrap_or(crate::ui::header_bar::DEFAULT_HEADER_FORMAT),
+            &crate::ui::header_bar::HeaderInfo {
+                project_name: &self.project_name,
+                project_path: &self.project_path,
+                package_manager: &self.package_manager_name,
+                packages: self.workspace_packages.len(),
+                scripts: self
+                    .scripts
+                    .iter()
+                    .filter(|script| script.runner.is_none())
+                    .count(),
+                running_jobs: self.jobs.running_count(),
+                unsaved: self.favorites_unsaved,
+            },
+        );
+
+        // Tabs (only if workspaces or jobs exist)
+        if tabs.len() > 1 {
+            let jobs_label = match self.jobs.running_count() {
+                0 => "Jobs".to_string(),
+                n => format!("Jobs ({} running)", n),
+            };
+            let tab_labels: Vec<&str> = tabs
+                .iter()
+                .map(|tab| match tab {
+                    Tab::Scripts => "Scripts",
+                    Tab::Packages => "Packages",
+                    Tab::History => "History",
+                    Tab::Jobs => jobs_label.as_str(),
+                })
+                .collect();
+            let active = tabs.iter().position(|&t| t == self.active_tab).unwrap_or(0);
+            crate::ui::tabs::render_tabs(frame, chunks[1], &self.theme, &tab_labels, active);
+
+            // Inside a package, the row below the tabs says which one
+            let breadcrumb = self.breadcrumb();
+            if !breadcrumb.is_empty() {
+                let [_, breadcrumb_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Length(1)])
+                        .areas(chunks[1]);
+                crate::ui::breadcrumb::render_breadcrumb(
+                    frame,
+                    breadcrumb_area,
+                    &self.theme,
+                    &breadcrumb,
+                );
+            }
+        }
 
-                // Record execution in recents
-                let execution_key = script_key.split(':').skip(1).collect::<Vec<_>>().join(":");
-                recents::record_execution(&mut self.recents, &execution_key);
+        if banner_rows > 0 {
+            crate::ui::config_banner::render_config_banner(
+                frame,
+                banner_area,
+                &self.theme,
+                &self.config_issues,
+            );
+        }
+
+        // Search input
+        let current_query = self.current_query();
+        crate::ui::search_input::render_search_input(frame, chunks[2], &self.theme, current_query);
+
+        // Main content
+        let now = Instant::now();
+        match self.active_tab {
+            Tab::Scripts => {
+                crate::ui::script_list::render_script_list(
+                    frame,
+                    list_area,
+                    &self.theme,
+                    &self.scripts,
+                    &self.script_rows,
+                    self.selected_index,
+                    self.scroll_offset,
+                    &self.favorites,
+                    &ScriptQuery::parse(&self.query),
+                );
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => {
+                    let (package_names, package_paths) = self.package_queries();
+                    crate::ui::package_list::render_package_list(
+                        frame,
+                        list_area,
+                        &self.theme,
+                        &self.workspace_packages,
+                        &self.pkg_filtered_indices,
+                        self.pkg_selected_index,
+                        self.pkg_scroll_offset,
+                        &self.favorites,
+                        &package_names,
+                        &package_paths,
+                        self.workspace_scan_started
+                            .map(|started| self.theme.symbols.spinner_frame(now - started)),
+                    );
+                }
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    crate::ui::script_list::render_script_list(
+                        frame,
+                        list_area,
+                        &self.theme,
+                        &self.pkg_script_sortable,
+                        &flat_rows(&self.pkg_script_filtered_indices),
+                        self.pkg_script_selected_index,
+                        self.pkg_script_scroll_offset,
+                        &self.favorites,
+                        &ScriptQuery::parse(&self.pkg_script_query),
+                    );
+                }
+            },
+            Tab::History => {
+                crate::ui::history_list::render_history_list(
+                    frame,
+                    list_area,
+                    &self.theme,
+                    &self.history,
+                    &self.history_filtered_indices,
+                    self.history_selected_index,
+                    self.history_scroll_offset,
+                    self.monorepo_root.as_ref().unwrap_or(&self.nearest_pkg),
+                    recents::now_ms(),
+                );
+            }
+            Tab::Jobs => {
+                crate::ui::job_list::render_job_list(
+                    frame,
+                    list_area,
+                    &self.theme,
+                    &self.jobs,
+                    self.jobs_selected_index,
+                    now,
+                );
+            }
+        }
+
+        // Script preview
+        if preview_rows > 0 {
+            if let Some((script, scripts)) = self.highlighted_script() {
+                crate::ui::script_list::render_script_preview(
+                    frame,
+                    preview_area,
+                    &self.theme,
+                    script,
+                    scripts,
+                    self.package_manager.runs_pre_post_scripts(),
+                );
+            }
+        }
+        if package_preview_rows > 0 {
+            if let Some(pkg) = self.highlighted_package() {
+                crate::ui::package_list::render_package_preview(
+                    frame,
+                    preview_area,
+                    &self.theme,
+                    pkg,
+                );
+            }
+        }
+
+        // Status bar
+        crate::ui::status_bar::render_status_bar(
+            frame,
+            chunks[4],
+            &self.theme,
+            self.queue.len(),
+            self.next_scheduled_label(now).as_deref(),
+            self.suggested_next().as_ref().map(|run| run.key.as_str()),
+            self.watch_label(now).as_deref(),
+            self.update_available.as_deref(),
+            self.status_message.as_deref(),
+        );
+
+        // NEW: Render modal overlays based on mode
+        match self.mode {
+            AppMode::PickPreset => {
+                if let Some(pick) = self.flow.as_ref().and_then(|f| f.preset_pick.as_ref()) {
+                    crate::ui::preset_picker::render_preset_picker(
+                        frame,
+                        area,
+                        &self.theme,
+                        &pick.presets,
+                        pick.saved,
+                        pick.selected,
+                    );
+                }
+            }
+            AppMode::ConfigureEnv => {
+                if let Some(ref flow) = self.flow {
+                    crate::ui::env_selector::render_env_selector(
+                        frame,
+                        area,
+                        &self.theme,
+                        &flow.env.files,
+                        flow.env.selected,
+                        flow.env.scroll_offset,
+                        &flow.env.checked,
+                        flow.env.new_file.as_ref(),
+                    );
+                }
+            }
+            AppMode::EditEnv => {
+                if let Some(ref flow) = self.flow {
+                    crate::ui::env_overrides::render_env_overrides(
+                        frame,
+                        area,
+                        &self.theme,
+                        &flow.overrides,
+                        &self.secret_env,
+                    );
+                }
+            }
+            AppMode::PreviewEnv => {
+                if let Some(preview) = self
+                    .flow
+                    .as_ref()
+                    .and_then(|flow| flow.env_preview.as_ref())
+                {
+                    crate::ui::env_preview::render_env_preview(
+                        frame,
+                        area,
+                        &self.theme,
+                        preview,
+                        &self.secret_env,
+                    );
+                }
+            }
+            AppMode::ConfigureArgs => {
+                if let Some(ref flow) = self.flow {
+                    let warning = self.args_warning();
+                    let (history, own) = self
+                        .args_history
+                        .entries_for(&self.get_current_script_key());
+                    crate::ui::args_input::render_args_input(
+                        frame,
+                        area,
+                        &self.theme,
+                        &flow.args.input,
+                        flow.args.cursor,
+                        flow.args.suggestion(&history),
+                        &history,
+                        own,
+                        flow.args.history_index,
+                        warning.as_deref(),
+                    );
+                }
+            }
+            AppMode::ConfirmExecution => {
+                // Env files, then the overrides set on top of them
+                let env_file_names: Vec<String> =
+                    self.flow.as_ref().map_or_else(Vec::new, |flow| {
+                        flow.env
+                            .checked_files()
+                            .map(|f| f.display_name.clone())
+                            .chain(flow.overrides.vars.iter().map(|(key, value)| {
+                                format!("{}={}", key, self.secret_env.display(key, value))
+                            }))
+                            .collect()
+                    });
+
+                let command = self.get_current_invocation();
+                let cwd = self.run_cwd();
+                let pm = self.package_manager_for(&cwd);
+                let pm_label = if pm == self.package_manager {
+                    pm.to_string()
+                } else {
+                    format!(
+                        "{} (nested lockfile; the project uses {})",
+                        pm, self.package_manager
+                    )
+                };
+                // Hooks of the package.json, then the config's post-run hooks
+                let script_name = self.get_current_script_name();
+                let no_hooks = ScriptHooks::default();
+                let hooks = self
+                    .flow
+                    .as_ref()
+                    .map_or(&no_hooks, |flow| &flow.confirm.hooks);
+                let config_hooks = self.config.post_run_hooks(&script_name);
+                let post_run: Vec<String> = hooks
+                    .after
+                    .iter()
+                    .map(|command| format!("always: {}", command))
+                    .chain(config_hooks.iter().map(|h| h.describe()))
+                    .collect();
+                let hooks_note = hooks_note(hooks, !config_hooks.is_empty());
+                let watch = self
+                    .flow
+                    .as_ref()
+                    .filter(|flow| flow.confirm.watch)
+                    .map(|_| watch_globs(&self.config.watch).join(", "));
+                let args = self.configured_args();
+
+                crate::ui::execution_confirm::render_execution_confirm(
+                    frame,
+                    area,
+                    &self.theme,
+                    &command,
+                    &split_args(&args),
+                    has_unclosed_quote(&args),
+                    &env_file_names,
+                    self.flow
+                        .as_ref()
+                        .map_or(&[][..], |flow| &flow.confirm.missing_keys),
+                    &cwd,
+                    &pm_label,
+                    &hooks.before,
+                    &post_run,
+                    hooks_note,
+                    watch.as_deref(),
+                    self.flow
+                        .as_ref()
+                        .and_then(|flow| flow.confirm.preset_name.as_deref()),
+                );
+            }
+            AppMode::Queue => {
+                crate::ui::queue_panel::render_queue_panel(
+                    frame,
+                    area,
+                    &self.theme,
+                    &self.queue,
+                    &self.scheduled,
+                    self.queue_selected_index,
+                    now,
+                );
+            }
+            AppMode::Schedule => {
+                let key = self
+                    .schedule_pending
+                    .as_ref()
+                    .map(|r| r.key.as_str())
+                    .unwrap_or_default();
+                crate::ui::schedule_input::render_schedule_input(
+                    frame,
+                    area,
+                    &self.theme,
+                    key,
+                    &self.schedule_input,
+                    self.schedule_error.as_deref(),
+                );
+            }
+            AppMode::Output => {
+                if let Some(ref view) = self.output {
+                    if let Some(job) = self.jobs.get(view.job) {
+                        self.output_height = area
+                            .height
+                            .saturating_sub(crate::ui::output_view::OUTPUT_CHROME_HEIGHT)
+                            as usize;
+                        let suggested = self.suggested_after_output();
+                        crate::ui::output_view::render_output_view(
+                            frame,
+                            area,
+                            &self.theme,
+                            job,
+                            view,
+                            self.status_message.as_deref(),
+                            suggested.as_ref().map(|run| run.key.as_str()),
+                            now,
+                        );
+                    }
+                }
+            }
+            AppMode::Tags => {
+                crate::ui::tag_input::render_tag_input(
+                    frame,
+                    area,
+                    &self.theme,
+                    self.tag_pending.as_deref().unwrap_or_default(),
+                    &self.tag_input,
+                );
+            }
+            AppMode::PickDirectory => {
+                if let Some(pick) = self.flow.as_ref().and_then(|flow| flow.dir_pick.as_ref()) {
+                    crate::ui::dir_picker::render_dir_picker(frame, area, &self.theme, pick);
+                }
+            }
+            AppMode::RequiredEnv => {
+                if let Some(prompt) = self.env_prompts.first() {
+                    let input = match prompt.missing.first() {
+                        Some(key) => self.secret_env.display(key, &prompt.input),
+                        None => prompt.input.as_str().into(),
+                    };
+                    crate::ui::env_prompt::render_env_prompt(
+                        frame,
+                        area,
+                        &self.theme,
+                        &prompt.spec.script_name,
+                        &prompt.missing,
+                        &input,
+                    );
+                }
+            }
+            AppMode::DangerousScript => {
+                if let Some(ref prompt) = self.danger_prompt {
+                    crate::ui::danger_prompt::render_danger_prompt(
+                        frame,
+                        area,
+                        &self.theme,
+                        &prompt.script_name,
+                        &prompt.input,
+                    );
+                }
+            }
+            AppMode::DuplicateJob => {
+                let job = self
+                    .duplicate_prompts
+                    .first()
+                    .and_then(|prompt| self.jobs.get(prompt.existing));
+                if let Some(job) = job {
+                    let root = self.monorepo_root.as_ref().unwrap_or(&self.nearest_pkg);
+                    let dir = job.spec.cwd.strip_prefix(root).unwrap_or(&job.spec.cwd);
+                    crate::ui::duplicate_job::render_duplicate_job(
+                        frame,
+                        area,
+                        &self.theme,
+                        job,
+                        dir,
+                        now,
+                    );
+                }
+            }
+            AppMode::QuickDial => {
+                crate::ui::quick_dial::render_quick_dial(
+                    frame,
+                    area,
+                    &self.theme,
+                    &self.quick_dial,
+                    self.quick_dial_selected,
+                );
+            }
+            AppMode::ScriptGroups => {
+                crate::ui::script_groups::render_script_groups(
+                    frame,
+                    area,
+                    &self.theme,
+                    &self.script_groups,
+                    &self.workspace_packages,
+                    self.script_group_selected,
+                    self.script_group_checked.as_deref(),
+                    self.script_group_pkg_selected,
+                );
+            }
+            AppMode::Settings => {
+                let rows: Vec<(&str, String)> = ResetTarget::ALL
+                    .iter()
+                    .map(|&target| (target.label(), self.reset_preview(target)))
+                    .collect();
+                let confirm = self
+                    .settings_confirm
+                    .then(|| self.reset_question(ResetTarget::ALL[self.settings_selected]));
+                crate::ui::settings::render_settings(
+                    frame,
+                    area,
+                    &self.theme,
+                    &rows,
+                    self.settings_selected,
+                    confirm.as_deref(),
+                );
+            }
+            AppMode::Normal => {
+                // No overlay
+            }
+        }
+
+        if self.show_help {
+            crate::ui::help_overlay::render_help_overlay(
+                frame,
+                area,
+                &self.theme,
+                self.help_section(),
+                self.help_scroll,
+                &self.keymap,
+            );
+        }
+    }
+
+    // -- Private helpers --
+
+    /// The script under the cursor and the list it belongs to (for pre/post lookup).
+    fn highlighted_script(&self) -> Option<(&SortableScript, &[SortableScript])> {
+        match self.active_tab {
+            Tab::Scripts => self
+                .selected_script_index()
+                .map(|i| (&self.scripts[i], self.scripts.as_slice())),
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => self
+                    .pkg_script_filtered_indices
+                    .get(self.pkg_script_selected_index)
+                    .map(|&i| {
+                        (
+                            &self.pkg_script_sortable[i],
+                            self.pkg_script_sortable.as_slice(),
+                        )
+                    }),
+                PackageMode::SelectingPackage => None,
+            },
+            Tab::History | Tab::Jobs => None,
+        }
+    }
+
+    /// Steps to the list shown when it is inside the Packages tab, e.g.
+    /// `["Packages", "@mono/web"]`; empty otherwise.
+    fn breadcrumb(&self) -> Vec<String> {
+        if self.active_tab != Tab::Packages {
+            return Vec::new();
+        }
+        let step = match self.package_mode {
+            PackageMode::SelectingPackage => return Vec::new(),
+            PackageMode::SelectingScript { package_index } => {
+                self.workspace_packages[package_index].name.clone()
+            }
+            PackageMode::AllScripts => "All scripts".to_string(),
+        };
+        vec!["Packages".to_string(), step]
+    }
+
+    /// The package under the cursor while picking a package.
+    fn highlighted_package(&self) -> Option<&WorkspacePackage> {
+        match (self.active_tab, &self.package_mode) {
+            (Tab::Packages, PackageMode::SelectingPackage) => self
+                .pkg_filtered_indices
+                .get(self.pkg_selected_index)
+                .map(|&i| &self.workspace_packages[i]),
+            _ => None,
+        }
+    }
+
+    fn current_query(&self) -> &str {
+        match self.active_tab {
+            Tab::Scripts => &self.query,
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => &self.pkg_query,
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    &self.pkg_script_query
+                }
+            },
+            Tab::History => &self.history_query,
+            Tab::Jobs => "",
+        }
+    }
+
+    fn handle_esc(&mut self) -> Action {
+        match self.active_tab {
+            Tab::Scripts => Action::Quit,
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => Action::Quit,
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    // Go back to package list
+                    self.package_mode = PackageMode::SelectingPackage;
+                    self.pkg_script_query.clear();
+                    self.pkg_script_selected_index = 0;
+                    self.pkg_script_scroll_offset = 0;
+                    Action::Continue
+                }
+            },
+            Tab::History | Tab::Jobs => {
+                self.active_tab = Tab::Scripts;
+                Action::Continue
+            }
+        }
+    }
+
+    fn handle_enter(&mut self) -> Action {
+        if self.active_tab == Tab::Packages && self.package_mode == PackageMode::SelectingPackage {
+            if let Some(&pkg_idx) = self.pkg_filtered_indices.get(self.pkg_selected_index) {
+                // Enter package script selection mode
+                self.enter_package_scripts(pkg_idx);
+            }
+            return Action::Continue;
+        }
+
+        if self.active_tab == Tab::Scripts {
+            if let Some(ListRow::Group { prefix, .. }) = self.script_rows.get(self.selected_index) {
+                let prefix = prefix.clone();
+                self.toggle_group(&prefix);
+                return Action::Continue;
+            }
+        }
+
+        // Tasks run as they are, from the project root
+        if let Some((runner, task)) = self.selected_task() {
+            return Action::RunTask {
+                runner,
+                task: task.to_string(),
+                cwd: self
+                    .monorepo_root
+                    .clone()
+                    .unwrap_or_else(|| self.nearest_pkg.clone()),
+            };
+        }
+
+        // Scripts run as-is; history entries replay with their args and env files
+        match self.selected_run() {
+            Some(run) => self.start_checked_run(run),
+            None => Action::Continue,
+        }
+    }
+
+    fn enter_package_scripts(&mut self, pkg_idx: usize) {
+        let pkg = &self.workspace_packages[pkg_idx];
+
+        // Convert package scripts to SortableScript
+        self.pkg_script_sortable = to_sortable_scripts(&pkg.name, &pkg.scripts);
+        if let Some(ref root) = self.monorepo_root {
+            let dir = root.join(&pkg.relative_path);
+            apply_tags(&mut self.pkg_script_sortable, &dir, &self.script_tags);
+            apply_descriptions(&mut self.pkg_script_sortable, &dir);
+        }
+        apply_changed(&mut self.pkg_script_sortable, &self.ran_commands);
+        apply_run_times(&mut self.pkg_script_sortable, &self.history);
+
+        self.package_mode = PackageMode::SelectingScript {
+            package_index: pkg_idx,
+        };
+        self.pkg_script_query.clear();
+
+        // Initial filter: all scripts, sorted the way this package's list was left
+        self.update_pkg_script_filtered();
+    }
+
+    /// Lists the scripts of every workspace package in the Packages tab, to
+    /// find one without picking its package first.
+    fn open_all_scripts(&mut self) {
+        let Some(root) = self.monorepo_root.clone().filter(|_| self.has_workspaces) else {
+            self.status_message = Some("No workspace packages to search".to_string());
+            return;
+        };
+        let mut scripts = Vec::new();
+        for pkg in &self.workspace_packages {
+            let mut pkg_scripts = to_sortable_scripts(&pkg.name, &pkg.scripts);
+            let dir = root.join(&pkg.relative_path);
+            apply_tags(&mut pkg_scripts, &dir, &self.script_tags);
+            apply_descriptions(&mut pkg_scripts, &dir);
+            for script in &mut pkg_scripts {
+                script.package = Some(pkg.relative_path.clone());
+            }
+            scripts.extend(pkg_scripts);
+        }
+        apply_changed(&mut scripts, &self.ran_commands);
+        apply_run_times(&mut scripts, &self.history);
+        self.pkg_script_sortable = scripts;
+
+        self.active_tab = Tab::Packages;
+        self.package_mode = PackageMode::AllScripts;
+        self.pkg_script_query.clear();
+        self.update_pkg_script_filtered();
+    }
+
+    fn move_selection(&mut self, delta: i32) {
+        match self.active_tab {
+            Tab::Scripts => {
+                let len = self.script_rows.len();
+                if len == 0 {
+                    return;
+                }
+                self.selected_index = wrap_index(self.selected_index, delta, len);
+                self.ensure_visible_scripts();
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => {
+                    let len = self.pkg_filtered_indices.len();
+                    if len == 0 {
+                        return;
+                    }
+                    self.pkg_selected_index = wrap_index(self.pkg_selected_index, delta, len);
+                    self.ensure_visible_packages();
+                }
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    let len = self.pkg_script_filtered_indices.len();
+                    if len == 0 {
+                        return;
+                    }
+                    self.pkg_script_selected_index =
+                        wrap_index(self.pkg_script_selected_index, delta, len);
+                    self.ensure_visible_pkg_scripts();
+                }
+            },
+            Tab::History => {
+                let len = self.history_filtered_indices.len();
+                if len == 0 {
+                    return;
+                }
+                self.history_selected_index = wrap_index(self.history_selected_index, delta, len);
+                ensure_scroll(
+                    &mut self.history_scroll_offset,
+                    self.history_selected_index,
+                    self.visible_height,
+                );
+            }
+            Tab::Jobs => {
+                self.jobs_selected_index =
+                    wrap_index(self.jobs_selected_index, delta, self.jobs.len());
+            }
+        }
+    }
+
+    /// Tabs currently shown: Packages needs workspaces, Jobs needs at least one job.
+    fn available_tabs(&self) -> Vec<Tab> {
+        let mut tabs = vec![Tab::Scripts];
+        if self.picker {
+            return tabs;
+        }
+        if self.has_workspaces {
+            tabs.push(Tab::Packages);
+        }
+        if !self.history.is_empty() {
+            tabs.push(Tab::History);
+        }
+        if !self.jobs.is_empty() {
+            tabs.push(Tab::Jobs);
+        }
+        tabs
+    }
+
+    fn switch_tab(&mut self, delta: i32) {
+        let tabs = self.available_tabs();
+        let Some(current) = tabs.iter().position(|&t| t == self.active_tab) else {
+            return;
+        };
+        let Some(&next) = current
+            .checked_add_signed(delta as isize)
+            .and_then(|i| tabs.get(i))
+        else {
+            return;
+        };
+
+        if self.active_tab == Tab::Packages {
+            // Reset package mode when switching away
+            self.package_mode = PackageMode::SelectingPackage;
+            self.pkg_script_query.clear();
+        }
+        if next == Tab::Jobs {
+            self.jobs_selected_index = self
+                .jobs_selected_index
+                .min(self.jobs.len().saturating_sub(1));
+        }
+        self.active_tab = next;
+    }
+
+    /// Where the TUI is now, to come back to on the next launch.
+    pub fn session(&self) -> Session {
+        let (query, package, package_open) = match self.active_tab {
+            Tab::Scripts => (self.query.clone(), None, false),
+            Tab::Packages => {
+                let package = match self.package_mode {
+                    PackageMode::SelectingScript { package_index } => {
+                        self.workspace_packages.get(package_index)
+                    }
+                    PackageMode::SelectingPackage | PackageMode::AllScripts => self
+                        .pkg_filtered_indices
+                        .get(self.pkg_selected_index)
+                        .map(|&i| &self.workspace_packages[i]),
+                };
+                (
+                    self.pkg_query.clone(),
+                    package.map(|pkg| pkg.relative_path.clone()),
+                    matches!(self.package_mode, PackageMode::SelectingScript { .. }),
+                )
+            }
+            Tab::History => (self.history_query.clone(), None, false),
+            Tab::Jobs => (String::new(), None, false),
+        };
+        Session {
+            tab: Some(self.active_tab),
+            query,
+            package,
+            package_open,
+        }
+    }
+
+    /// Writes the [`Self::session`] to disk.
+    pub fn save_session(&self) {
+        session::save_session(&self.config_dir, &self.session());
+    }
+
+    /// Goes back to the tab, query and package of `session`. Jobs don't
+    /// outlive nr, so a session left in the Jobs tab starts in Scripts.
+    fn restore_session(&mut self, session: Session) {
+        match session.tab {
+            Some(Tab::Scripts) => {
+                self.query = session.query;
+                self.update_filtered();
+            }
+            Some(Tab::Packages) if self.has_workspaces => {
+                self.active_tab = Tab::Packages;
+                self.pkg_query = session.query;
+                self.update_pkg_filtered();
+                let package = session.package.and_then(|path| {
+                    self.workspace_packages
+                        .iter()
+                        .position(|pkg| pkg.relative_path == path)
+                });
+                let Some(pkg_idx) = package else {
+                    return;
+                };
+                if let Some(pos) = self.pkg_filtered_indices.iter().position(|&i| i == pkg_idx) {
+                    self.pkg_selected_index = pos;
+                    self.ensure_visible_packages();
+                    if session.package_open {
+                        self.enter_package_scripts(pkg_idx);
+                    }
+                }
+            }
+            Some(Tab::History) => {
+                self.active_tab = Tab::History;
+                self.history_query = session.query;
+                self.update_history_filtered();
+            }
+            _ => {}
+        }
+    }
+
+    /// Writes the favorites to disk, clearing the header's `{saved}` marker.
+    pub fn save_favorites(&mut self) {
+        favorites::save_favorites(&self.config_dir, &self.favorites);
+        self.favorites_unsaved = false;
+    }
+
+    fn toggle_fav(&mut self) {
+        match self.active_tab {
+            Tab::Scripts => {
+                if let Some(script_idx) = self.selected_script_index() {
+                    let key = self.scripts[script_idx].key.clone();
+                    favorites::toggle_favorite(&mut self.favorites, &key);
+                    self.favorites_unsaved = true;
+                    self.update_filtered();
+                }
+            }
+            Tab::Packages if self.package_mode == PackageMode::SelectingPackage => {
+                if let Some(pkg) = self.highlighted_package() {
+                    let key = package_key(&pkg.name);
+                    favorites::toggle_favorite(&mut self.favorites, &key);
+                    self.favorites_unsaved = true;
+                    self.update_pkg_filtered();
+                }
+            }
+            Tab::Packages => {
+                if let PackageMode::SelectingScript { .. } | PackageMode::AllScripts =
+                    self.package_mode
+                {
+                    if let Some(&script_idx) = self
+                        .pkg_script_filtered_indices
+                        .get(self.pkg_script_selected_index)
+                    {
+                        let key = self.pkg_script_sortable[script_idx].key.clone();
+                        favorites::toggle_favorite(&mut self.favorites, &key);
+                        self.favorites_unsaved = true;
+                        self.update_pkg_script_filtered();
+                    }
+                }
+            }
+            Tab::History | Tab::Jobs => {}
+        }
+    }
+
+    fn type_char(&mut self, c: char) {
+        match self.active_tab {
+            Tab::Scripts => {
+                self.query.push(c);
+                self.update_filtered();
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => {
+                    self.pkg_query.push(c);
+                    self.update_pkg_filtered();
+                }
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    self.pkg_script_query.push(c);
+                    self.update_pkg_script_filtered();
+                }
+            },
+            Tab::History => {
+                self.history_query.push(c);
+                self.update_history_filtered();
+            }
+            Tab::Jobs => {}
+        }
+    }
+
+    fn delete_char(&mut self) {
+        match self.active_tab {
+            Tab::Scripts => {
+                self.query.pop();
+                self.update_filtered();
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingPackage => {
+                    self.pkg_query.pop();
+                    self.update_pkg_filtered();
+                }
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    self.pkg_script_query.pop();
+                    self.update_pkg_script_filtered();
+                }
+            },
+            Tab::History => {
+                self.history_query.pop();
+                self.update_history_filtered();
+            }
+            Tab::Jobs => {}
+        }
+    }
+
+    /// Key of the list shown: the open package's scripts, else the Scripts tab.
+    fn current_view(&self) -> String {
+        match self.package_mode {
+            PackageMode::SelectingScript { package_index } if self.active_tab == Tab::Packages => {
+                package_view(&self.workspace_packages[package_index].relative_path)
+            }
+            PackageMode::AllScripts if self.active_tab == Tab::Packages => {
+                ALL_SCRIPTS_VIEW.to_string()
+            }
+            _ => SCRIPTS_VIEW.to_string(),
+        }
+    }
+
+    fn view_prefs(&self, view: &str) -> ViewPrefs {
+        self.view_prefs.get(view).copied().unwrap_or_default()
+    }
+
+    /// The open package's list settings (defaults outside a package).
+    fn package_prefs(&self) -> ViewPrefs {
+        match self.package_mode {
+            PackageMode::SelectingScript { package_index } => self.view_prefs(&package_view(
+                &self.workspace_packages[package_index].relative_path,
+            )),
+            PackageMode::AllScripts => self.view_prefs(ALL_SCRIPTS_VIEW),
+            PackageMode::SelectingPackage => ViewPrefs::default(),
+        }
+    }
+
+    /// Whether the Scripts tab is grouped by prefix: as last toggled, else the config.
+    fn group_scripts(&self) -> bool {
+        self.view_prefs(SCRIPTS_VIEW)
+            .group
+            .unwrap_or(self.config.group_scripts)
+    }
+
+    /// Changes and saves the settings of the list shown; returns the new settings.
+    fn update_view_prefs(&mut self, change: impl FnOnce(&mut ViewPrefs)) -> ViewPrefs {
+        let view = self.current_view();
+        let mut prefs = self.view_prefs(&view);
+        change(&mut prefs);
+        self.view_prefs.insert(view, prefs);
+        view_prefs::save_view_prefs(&self.config_dir, &self.view_prefs);
+        prefs
+    }
+
+    /// Re-sorts and re-filters the list shown after its settings changed.
+    fn refresh_current_view(&mut self) {
+        if self.current_view() == SCRIPTS_VIEW {
+            self.update_filtered();
+        } else {
+            self.update_pkg_script_filtered();
+        }
+    }
+
+    fn update_filtered(&mut self) {
+        self.filtered_indices = sort_scripts_by(
+            &self.scripts,
+            &self.favorites,
+            &self.recents,
+            &self.query,
+            self.view_prefs(SCRIPTS_VIEW).sort,
+        );
+        self.retain_visible(Tab::Scripts);
+        self.rebuild_script_rows();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Groups the script list by prefix if the Scripts tab's settings say so,
+    /// except while searching, where the list stays in relevance order, and in the picker.
+    /// Tasks follow the scripts in a section per runner (not in the picker).
+    fn rebuild_script_rows(&mut self) {
+        let (scripts, tasks): (Vec<usize>, Vec<usize>) = self
+            .filtered_indices
+            .iter()
+            .partition(|&&i| self.scripts[i].runner.is_none());
+        self.script_rows = if self.group_scripts() && self.query.is_empty() && !self.picker {
+            group_by_prefix(&self.scripts, &scripts, &self.expanded_groups)
+        } else {
+            flat_rows(&scripts)
+        };
+        if !self.picker {
+            self.script_rows.extend(task_rows(&self.scripts, &tasks));
+        }
+    }
+
+    /// Reads the scripts of `package.json` (and the tasks) again, keeping the
+    /// search and the selected script, and rescans the workspace packages in
+    /// the background.
+    pub fn reload(&mut self) {
+        let selected = self
+            .selected_script_index()
+            .map(|i| self.scripts[i].key.clone());
+
+        let raw_scripts = crate::core::scripts::load_scripts(&self.nearest_pkg);
+        self.scripts = to_sortable_scripts("root", &raw_scripts);
+        apply_tags(&mut self.scripts, &self.nearest_pkg, &self.script_tags);
+        apply_descriptions(&mut self.scripts, &self.nearest_pkg);
+        apply_changed(&mut self.scripts, &self.ran_commands);
+        apply_bench(&mut self.scripts, &bench::load_bench(&self.config_dir));
+        apply_run_times(&mut self.scripts, &self.history);
+        self.load_tasks();
+        self.update_filtered();
+        if let Some(key) = selected {
+            self.select_script_key(&key);
+        }
+
+        if let Some(ref root) = self.monorepo_root {
+            if self.workspace_refresh.is_none() {
+                self.workspace_refresh = Some(workspace_index::scan_in_background(
+                    root,
+                    &self.config_dir,
+                    &self.config.workspace_exclude,
+                    false,
+                ));
+            }
+        }
+        let count = raw_scripts.len();
+        self.status_message = Some(format!(
+            "Reloaded package.json ({} script{})",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
+    /// Selects the row of the Scripts tab showing the script with `key`, if any.
+    fn select_script_key(&mut self, key: &str) {
+        let row = self
+            .script_rows
+            .iter()
+            .position(|row| row.script().is_some_and(|i| self.scripts[i].key == key));
+        if let Some(row) = row {
+            self.selected_index = row;
+            self.ensure_visible_scripts();
+        }
+    }
+
+    /// Reloads the scripts once a change to `package.json` has settled, unless
+    /// a prompt or flow is open (the change is picked up after it closes).
+    fn poll_manifest_watch(&mut self, now: Instant) {
+        if self.mode != AppMode::Normal {
+            return;
+        }
+        let Some(ref mut watch) = self.manifest_watch else {
+            return;
+        };
+        if watch.poll(now).is_some() {
+            self.reload();
+        }
+    }
+
+    /// Replaces the tasks in the script list with those of the configured
+    /// `task_runners` in the project root.
+    fn load_tasks(&mut self) {
+        self.scripts.retain(|script| script.runner.is_none());
+        let root = self.monorepo_root.as_ref().unwrap_or(&self.nearest_pkg);
+        for &runner in &self.config.task_runners {
+            for (name, command) in runner.load_tasks(root) {
+                self.scripts.push(SortableScript {
+                    key: format!("{}:{}", runner.command_name(), name),
+                    name,
+                    command,
+                    runner: Some(runner),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    /// The justfile / Makefile task on the selected row of the Scripts tab.
+    fn selected_task(&self) -> Option<(TaskRunner, &str)> {
+        if self.active_tab != Tab::Scripts {
+            return None;
+        }
+        let script = &self.scripts[self.selected_script_index()?];
+        Some((script.runner?, script.name.as_str()))
+    }
+
+    /// The script on the selected row of the Scripts tab (`None` on a group header).
+    fn selected_script_index(&self) -> Option<usize> {
+        self.script_rows
+            .get(self.selected_index)
+            .and_then(ListRow::script)
+    }
+
+    /// Expands or collapses the `prefix` group, keeping its header selected.
+    fn toggle_group(&mut self, prefix: &str) {
+        if !self.expanded_groups.remove(prefix) {
+            self.expanded_groups.insert(prefix.to_string());
+        }
+        self.rebuild_script_rows();
+        if let Some(header) = self
+            .script_rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Group { prefix: p, .. } if p == prefix))
+        {
+            self.selected_index = header;
+        }
+        self.ensure_visible_scripts();
+    }
+
+    /// The package search split into what matches names and what matches
+    /// paths: `path:apps/` matches paths, `pkg:web` names, and so does plain
+    /// text, unless it contains `/` (e.g. `apps/`), which matches paths.
+    fn package_queries(&self) -> (String, String) {
+        let query = PrefixedQuery::parse(&self.pkg_query, &["path", "pkg"]);
+        let mut names = query.filter("pkg");
+        let mut paths = query.filter("path");
+        let text = if query.text.contains('/') {
+            &mut paths
+        } else {
+            &mut names
+        };
+        if !query.text.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&query.text);
+        }
+        (names, paths)
+    }
+
+    fn update_pkg_filtered(&mut self) {
+        let (names, paths) = self.package_queries();
+        let by_path = fuzzy_filter(&self.workspace_packages, &paths, |p| &p.relative_path);
+        self.pkg_filtered_indices = if names.is_empty() {
+            by_path
+        } else {
+            let mut by_name = fuzzy_filter(&self.workspace_packages, &names, |p| &p.name);
+            if !paths.is_empty() {
+                let by_path: HashSet<usize> = by_path.into_iter().collect();
+                by_name.retain(|i| by_path.contains(i));
+            }
+            by_name
+        };
+        // Searches list by relevance; otherwise starred and busy packages come first
+        if names.is_empty() && paths.is_empty() {
+            sort_packages(
+                &mut self.pkg_filtered_indices,
+                &self.workspace_packages,
+                &self.favorites,
+                &self.recents,
+            );
+        }
+        self.pkg_selected_index = 0;
+        self.pkg_scroll_offset = 0;
+    }
+
+    /// Fuzzy-matches the history query against each run's script key, args and
+    /// env file names, so `cov staging` finds `test --coverage` run with `.env.staging`.
+    fn update_history_filtered(&mut self) {
+        let haystacks: Vec<String> = self
+            .history
+            .iter()
+            .map(|entry| {
+                let mut text = format!("{} {}", entry.key, entry.args);
+                for path in &entry.env_files {
+                    if let Some(name) = path.file_name() {
+                        text.push(' ');
+                        text.push_str(&name.to_string_lossy());
+                    }
+                }
+                text
+            })
+            .collect();
+        self.history_filtered_indices = fuzzy_filter(&haystacks, &self.history_query, |s| s);
+        self.history_selected_index = 0;
+        self.history_scroll_offset = 0;
+    }
+
+    fn update_pkg_script_filtered(&mut self) {
+        self.pkg_script_filtered_indices = sort_scripts_by(
+            &self.pkg_script_sortable,
+            &self.favorites,
+            &self.recents,
+            &self.pkg_script_query,
+            self.package_prefs().sort,
+        );
+        self.retain_visible(Tab::Packages);
+        self.pkg_script_selected_index = 0;
+        self.pkg_script_scroll_offset = 0;
+    }
+
+    /// Drops hidden scripts from the Scripts tab's or the package's script list.
+    fn retain_visible(&mut self, tab: Tab) {
+        let prefs = match tab {
+            Tab::Packages => self.package_prefs(),
+            _ => self.view_prefs(SCRIPTS_VIEW),
+        };
+        if prefs.show_hidden {
+            return;
+        }
+        let (scripts, indices) = match tab {
+            Tab::Packages => (
+                &self.pkg_script_sortable,
+                &mut self.pkg_script_filtered_indices,
+            ),
+            _ => (&self.scripts, &mut self.filtered_indices),
+        };
+        indices.retain(|&i| !self.hidden_scripts.is_hidden(&scripts[i].name));
+    }
+
+    fn ensure_visible_scripts(&mut self) {
+        ensure_scroll(
+            &mut self.scroll_offset,
+            self.selected_index,
+            self.visible_height,
+        );
+    }
+
+    fn ensure_visible_packages(&mut self) {
+        ensure_scroll(
+            &mut self.pkg_scroll_offset,
+            self.pkg_selected_index,
+            self.visible_height,
+        );
+    }
+
+    fn ensure_visible_pkg_scripts(&mut self) {
+        ensure_scroll(
+            &mut self.pkg_script_scroll_offset,
+            self.pkg_script_selected_index,
+            self.visible_height,
+        );
+    }
+}
+
+/// Files whose changes reload the scripts with `reload_on_change`.
+/// What the confirm step says about run paths that skip some of the script's
+/// hooks: panes, background jobs and parallel runs never run the config's
+/// post-run hooks, and on Windows not the `package.json` ones either.
+fn hooks_note(hooks: &ScriptHooks, config_hooks: bool) -> Option<&'static str> {
+    if !PIPED_HOOKS && !hooks.is_empty() {
+        Some("Panes, background jobs and parallel runs skip these hooks")
+    } else if config_hooks {
+        Some("Panes, background jobs and parallel runs skip the config's post-run hooks")
+    } else {
+        None
+    }
+}
+
+fn manifest_globs() -> Vec<String> {
+    ["package.json", "deno.json", "deno.jsonc"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Convert a package's scripts into sortable entries keyed `{scope}:{name}`,
+/// flagging commands that reference sibling scripts which don't exist.
+fn to_sortable_scripts(scope: &str, raw_scripts: &IndexMap<String, String>) -> Vec<SortableScript> {
+    let mut missing = crate::core::script_health::find_missing_references(raw_scripts);
+
+    raw_scripts
+        .iter()
+        .map(|(name, command)| SortableScript {
+            key: format!("{}:{}", scope, name),
+            name: name.clone(),
+            command: command.clone(),
+            missing_refs: missing.remove(name).unwrap_or_default(),
+            tags: Vec::new(),
+            description: None,
+            changed: false,
+            bench: Vec::new(),
+            last_duration: None,
+            runner: None,
+            package: None,
+        })
+        .collect()
+}
+
+/// Sets each script's tags: those from `nr.tags` in `dir`'s `package.json`,
+/// then the ones added in the TUI.
+fn apply_tags(scripts: &mut [SortableScript], dir: &std::path::Path, stored: &ScriptTags) {
+    let manifest = crate::core::package_json::PackageJson::load(dir)
+        .map(|pkg| pkg.script_tags())
+        .unwrap_or_default();
+
+    for script in scripts {
+        script.tags = manifest.get(&script.name).cloned().unwrap_or_default();
+        for tag in stored.get(&script.key).into_iter().flatten() {
+            if !script.tags.contains(tag) {
+                script.tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// Sets each script's description from `dir`'s `package.json`.
+fn apply_descriptions(scripts: &mut [SortableScript], dir: &std::path::Path) {
+    let mut descriptions = crate::core::package_json::PackageJson::load(dir)
+        .map(|pkg| pkg.script_descriptions())
+        .unwrap_or_default();
+
+    for script in scripts {
+        script.description = descriptions.remove(&script.name);
+    }
+}
+
+/// Flags scripts whose command changed since they last ran from nr.
+fn apply_changed(scripts: &mut [SortableScript], ran: &RanCommands) {
+    for script in scripts {
+        script.changed = ran_commands::has_changed(ran, &script.key, &script.command);
+    }
+}
+
+/// Sets each script's run time from its latest finished run in the history.
+fn apply_run_times(scripts: &mut [SortableScript], history: &[HistoryEntry]) {
+    for script in scripts {
+        script.last_duration = history
+            .iter()
+            .filter(|entry| entry.key == script.key)
+            .find_map(|entry| entry.duration_ms)
+            .map(Duration::from_millis);
+    }
+}
+
+/// Sets each script's timings from the latest `nr --bench` of it.
+fn apply_bench(scripts: &mut [SortableScript], results: &BenchResults) {
+    for script in scripts {
+        script.bench = results.get(&script.key).cloned().unwrap_or_default();
+    }
+}
+
+/// Wrap index with delta, cycling around `len`.
+fn wrap_index(current: usize, delta: i32, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let new = current as i32 + delta;
+    if new < 0 {
+        len - 1
+    } else if new >= len as i32 {
+        0
+    } else {
+        new as usize
+    }
+}
+
+/// Adjust scroll_offset so that `selected` stays visible within the given height.
+fn ensure_scroll(scroll_offset: &mut usize, selected: usize, visible_height: usize) {
+    if selected < *scroll_offset {
+        *scroll_offset = selected;
+    }
+    let height = visible_height.max(1);
+    if selected >= *scroll_offset + height {
+        *scroll_offset = selected.saturating_sub(height - 1);
+    }
+}
+
+impl App {
+    // NEW: Configuration flow methods
+
+    fn start_configure_flow(&mut self) {
+        if let Some((runner, _)) = self.selected_task() {
+            self.status_message = Some(format!(
+                "{} tasks run as they are; press Enter",
+                runner.label()
+            ));
+            return;
+        }
+
+        let script_key = self.get_current_script_key();
+        let mut flow = self.last_used_flow(&self.get_current_cwd());
+
+        // A history entry starts from exactly how it was run
+        let saved = self
+            .script_configs
+            .get(&script_key)
+            .map(|config| config.presets.as_slice())
+            .unwrap_or_default();
+        let shared = self
+            .config
+            .presets
+            .get(&self.get_current_script_name())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let saved_count = saved.len();
+        let mut presets = project_config::merge_presets(saved, shared);
+        if let Some(run) = self
+            .selected_run()
+            .filter(|_| self.active_tab == Tab::History)
+        {
+            flow.confirm.args = run.args;
+            flow.env.checked = run.env_files.into_iter().collect();
+            presets.clear();
+        }
+
+        // Offer the script's presets first, otherwise enter env selection mode
+        if presets.is_empty() {
+            self.mode = AppMode::ConfigureEnv;
+        } else {
+            flow.preset_pick = Some(PresetPick {
+                presets,
+                saved: saved_count,
+                selected: 0,
+            });
+            self.mode = AppMode::PickPreset;
+        }
+        self.flow = Some(flow);
+    }
+
+    /// What the configure flow starts from: the env files found for a script
+    /// running in `cwd` with the script's configured `env_files` (or else those
+    /// last used by any script) checked, and the selected script's last
+    /// arguments and env overrides.
+    fn last_used_flow(&self, cwd: &Path) -> ConfigureFlow {
+        let config = self.script_configs.get(&self.get_current_script_key());
+        let args = config.map(|config| config.args.clone()).unwrap_or_default();
+        let env_files = scan_env_files_with(cwd, &self.monorepo_root, &self.config.env_dirs);
+        let defaults = self
+            .config
+            .env_files
+            .get(&self.get_current_script_name())
+            .unwrap_or(&self.global_env_config.last_env_files);
+        let checked = env_files
+            .all_files()
+            .filter(|f| defaults.contains(&f.display_name))
+            .map(|f| f.path.clone())
+            .collect();
+        let mut flow = ConfigureFlow::new(env_files, checked, args);
+        if let Some(config) = config {
+            flow.overrides.vars = config.env.clone().into_iter().collect();
+        }
+        flow
+    }
+
+    /// Runs the selected script with its [`Self::last_used_flow`], as
+    /// confirming the configure flow right away would. Elsewhere (packages,
+    /// history entries, tasks) it is Enter.
+    fn quick_run(&mut self) -> Action {
+        let Some(mut run) = self
+            .selected_run()
+            .filter(|_| self.active_tab != Tab::History)
+        else {
+            return self.handle_enter();
+        };
+        let flow = self.last_used_flow(&run.cwd);
+        run.env_files = flow.env.merge_order_paths();
+        run.args = flow.confirm.args;
+        run.env = flow.overrides.vars;
+        self.start_checked_run(run)
+    }
+
+    /// `script_key`'s saved configuration, created empty if there is none yet.
+    fn script_config(&mut self, script_key: &str) -> &mut ScriptConfig {
+        self.script_configs
+            .entry(script_key.to_string())
+            .or_insert_with(|| ScriptConfig {
+                args: String::new(),
+                last_used: SystemTime::now(),
+                presets: Vec::new(),
+                env: IndexMap::new(),
+            })
+    }
+
+    fn handle_preset_pick_mode(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        let script_key = self.get_current_script_key();
+        let Some(flow) = self.flow.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        let Some(pick) = flow.preset_pick.as_mut() else {
+            self.mode = AppMode::ConfigureEnv;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+                self.flow = None;
+            }
+            KeyCode::Up => pick.move_up(),
+            KeyCode::Down => pick.move_down(),
+            KeyCode::Enter => {
+                // Continue to the env step with the preset applied, to adjust or confirm
+                if let Some(preset) = pick.chosen().cloned() {
+                    flow.apply_preset(&preset);
+                }
+                flow.preset_pick = None;
+                self.mode = AppMode::ConfigureEnv;
+            }
+            KeyCode::Delete | KeyCode::Char('d') => {
+                if pick.is_shared() {
+                    self.status_message = Some(format!(
+                        "Shared presets are edited in {}",
+                        project_config::FILE_NAME
+                    ));
+                } else if let Some(name) = pick.remove_selected() {
+                    let presets = pick.saved_presets().to_vec();
+                    if pick.presets.is_empty() {
+                        flow.preset_pick = None;
+                        self.mode = AppMode::ConfigureEnv;
+                    }
+                    self.script_config(&script_key).presets = presets;
+                    let _ =
+                        script_configs::save_script_configs(&self.config_dir, &self.script_configs);
+                    self.status_message = Some(format!("Deleted preset '{}'", name));
+                }
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Keys while naming a preset on the confirm step: Enter saves the
+    /// configuration under the name, Esc leaves it unsaved.
+    fn handle_preset_name_key(&mut self, key: KeyEvent) -> Action {
+        let script_key = self.get_current_script_key();
+        let Some(flow) = self.flow.as_mut() else {
+            return Action::Continue;
+        };
+        let Some(name) = flow.confirm.preset_name.as_mut() else {
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Char(c) => name.push(c),
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Esc => flow.confirm.preset_name = None,
+            KeyCode::Enter => {
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Action::Continue;
+                }
+                let preset = flow.to_preset(name.clone());
+                flow.confirm.preset_name = None;
+                self.script_config(&script_key).save_preset(preset);
+                let _ = script_configs::save_script_configs(&self.config_dir, &self.script_configs);
+                self.status_message = Some(format!("Saved preset '{}'", name));
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn get_current_script_key(&self) -> String {
+        let project_id = crate::store::project_id::project_id(&self.config_dir);
+
+        match self.active_tab {
+            Tab::Scripts => {
+                if let Some(script_idx) = self.selected_script_index() {
+                    let script = &self.scripts[script_idx];
+                    format!("{}:{}", project_id, script.key)
+                } else {
+                    format!("{}:unknown", project_id)
+                }
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    if let Some(&script_idx) = self
+                        .pkg_script_filtered_indices
+                        .get(self.pkg_script_selected_index)
+                    {
+                        let script = &self.pkg_script_sortable[script_idx];
+                        format!("{}:{}", project_id, script.key)
+                    } else {
+                        format!("{}:unknown", project_id)
+                    }
+                }
+                _ => format!("{}:unknown", project_id),
+            },
+            Tab::History => match self.selected_history_entry() {
+                Some(entry) => format!("{}:{}", project_id, entry.key),
+                None => format!("{}:unknown", project_id),
+            },
+            Tab::Jobs => format!("{}:unknown", project_id),
+        }
+    }
+
+    fn get_current_cwd(&self) -> PathBuf {
+        match self.active_tab {
+            Tab::Scripts => self.nearest_pkg.clone(),
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingScript { package_index } => {
+                    let pkg = &self.workspace_packages[package_index];
+                    self.monorepo_root
+                        .as_ref()
+                        .map(|r| r.join(&pkg.relative_path))
+                        .unwrap_or_else(|| self.nearest_pkg.clone())
+                }
+                PackageMode::AllScripts => self
+                    .pkg_script_filtered_indices
+                    .get(self.pkg_script_selected_index)
+                    .and_then(|&i| self.pkg_script_sortable[i].package.as_ref())
+                    .zip(self.monorepo_root.as_ref())
+                    .map_or_else(|| self.nearest_pkg.clone(), |(path, r)| r.join(path)),
+                _ => self.nearest_pkg.clone(),
+            },
+            Tab::History => self
+                .selected_history_entry()
+                .map_or_else(|| self.nearest_pkg.clone(), |entry| entry.cwd.clone()),
+            Tab::Jobs => self.nearest_pkg.clone(),
+        }
+    }
+
+    fn handle_env_mode(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        if self
+            .flow
+            .as_ref()
+            .is_some_and(|flow| flow.env.new_file.is_some())
+        {
+            return self.handle_new_env_file_key(key);
+        }
+        let cwd = self.get_current_cwd();
+        let Some(flow) = self.flow.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                // Cancel configuration
+                self.mode = AppMode::Normal;
+                self.flow = None;
+            }
+            KeyCode::Enter => {
+                // Proceed to args input
+                flow.begin_args();
+                self.mode = AppMode::ConfigureArgs;
+            }
+            KeyCode::Up => flow.env.move_up(),
+            KeyCode::Down => flow.env.move_down(),
+            KeyCode::Char(' ') => flow.env.toggle_selected(),
+            KeyCode::Char('e') => self.mode = AppMode::EditEnv,
+            KeyCode::Char('n') => {
+                let example = cwd.join(".env.example");
+                let example = example.is_file().then_some(example);
+                flow.env.new_file = Some(NewEnvFile {
+                    name: ".env.".to_string(),
+                    from_example: example.is_some(),
+                    example,
+                    error: None,
+                });
+            }
+            KeyCode::Char('p') => {
+                flow.env_preview = Some(flow.env.preview(&flow.overrides.vars));
+                self.mode = AppMode::PreviewEnv;
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Naming a new env file: Enter creates it in the script's directory and
+    /// checks it, Tab toggles starting from `.env.example`, Esc cancels.
+    fn handle_new_env_file_key(&mut self, key: KeyEvent) -> Action {
+        let cwd = self.get_current_cwd();
+        let Some(ref mut flow) = self.flow else {
+            return Action::Continue;
+        };
+        let Some(ref mut new_file) = flow.env.new_file else {
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Esc => flow.env.new_file = None,
+            KeyCode::Tab => {
+                new_file.from_example = !new_file.from_example && new_file.example.is_some()
+            }
+            KeyCode::Char(c) => new_file.name.push(c),
+            KeyCode::Backspace => {
+                new_file.name.pop();
+            }
+            KeyCode::Enter => {
+                let template = new_file
+                    .example
+                    .as_deref()
+                    .filter(|_| new_file.from_example);
+                match create_env_file(&cwd, new_file.name.trim(), template) {
+                    Ok(path) => {
+                        flow.env.new_file = None;
+                        flow.env.files =
+                            scan_env_files_with(&cwd, &self.monorepo_root, &self.config.env_dirs);
+                        flow.env.select_path(&path);
+                        flow.env.checked.insert(path.clone());
+                        self.status_message = Some(format!("Created {}", path.display()));
+                    }
+                    Err(e) => new_file.error = Some(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Env overrides editor: Enter edits the highlighted `KEY=value` (or adds
+    /// one on the last row) and sets it, d deletes it, Esc goes back.
+    fn handle_env_overrides_mode(&mut self, key: KeyEvent) -> Action {
+        let Some(ref mut flow) = self.flow else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        let overrides = &mut flow.overrides;
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        if let Some(ref mut input) = overrides.input {
+            match key.code {
+                KeyCode::Esc => overrides.input = None,
+                KeyCode::Enter => {
+                    // A malformed input stays open for fixing
+                    let _ = overrides.commit_edit();
+                }
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                _ => {}
+            }
+            return Action::Continue;
+        }
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::ConfigureEnv,
+            KeyCode::Up => overrides.move_up(),
+            KeyCode::Down => overrides.move_down(),
+            KeyCode::Enter => overrides.begin_edit(),
+            KeyCode::Char('d') | KeyCode::Delete => overrides.remove_selected(),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn handle_env_preview_mode(&mut self, key: KeyEvent) -> Action {
+        let Some(preview) = self
+            .flow
+            .as_mut()
+            .and_then(|flow| flow.env_preview.as_mut())
+        else {
+            self.mode = AppMode::ConfigureEnv;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Esc | KeyCode::Char('p') | KeyCode::Char('q') => {
+                if let Some(ref mut flow) = self.flow {
+                    flow.env_preview = None;
+                }
+                self.mode = AppMode::ConfigureEnv;
+            }
+            KeyCode::Up => preview.scroll_up(),
+            KeyCode::Down => preview.scroll_down(),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn handle_args_mode(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        let (history, _) = self
+            .args_history
+            .entries_for(&self.get_current_script_key());
+        let Some(flow) = self.flow.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                // Go back to env selection
+                self.mode = AppMode::ConfigureEnv;
+            }
+            KeyCode::Enter => {
+                // Save input and proceed to confirmation
+                flow.commit_args();
+                self.enter_confirm();
+            }
+            code => {
+                let saved = flow.confirm.args.clone();
+                flow.args.handle_key(code, &history, &saved);
+            }
+        }
+        Action::Continue
+    }
+
+    fn handle_confirm_mode(&mut self, key: KeyEvent) -> Action {
+        if self
+            .flow
+            .as_ref()
+            .is_some_and(|flow| flow.confirm.preset_name.is_some())
+        {
+            return self.handle_preset_name_key(key);
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Esc => {
+                // Go back to args input
+                self.mode = AppMode::ConfigureArgs;
+                Action::Continue
+            }
+            KeyCode::Enter => self.start_configured(ConfiguredStart::Run),
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(ref mut flow) = self.flow {
+                    flow.confirm.watch = !flow.confirm.watch;
+                }
+                Action::Continue
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_dir_picker();
+                Action::Continue
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Queue the configured run instead of executing it now
+                let run = self.commit_configured_run();
+                self.queue.push(run);
+                Action::Continue
+            }
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Print the merged env for use in a plain shell
+                let env_files = self.selected_env_paths();
+                let env = self.configured_env();
+                self.mode = AppMode::Normal;
+                self.flow = None;
+                Action::PrintEnv { env_files, env }
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.write_env_snapshot();
+                Action::Continue
+            }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Schedule the configured run for later
+                let run = self.commit_configured_run();
+                self.start_schedule(run);
+                Action::Continue
+            }
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Run the configured script in the background (Jobs tab)
+                self.start_configured(ConfiguredStart::Background)
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.start_configured(ConfiguredStart::Matrix)
+            }
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.copy_resolved_command();
+                Action::Continue
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Name the configuration to save it as a preset
+                if let Some(ref mut flow) = self.flow {
+                    flow.confirm.preset_name = Some(String::new());
+                }
+                Action::Continue
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Print the command for use in a plain shell instead of running it
+                let command = shell_join(&self.get_current_invocation());
+                self.mode = AppMode::Normal;
+                self.flow = None;
+                Action::PrintCommand { command }
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    /// Opens the working directory picker over the monorepo (or project) root.
+    fn open_dir_picker(&mut self) {
+        let root = self
+            .monorepo_root
+            .clone()
+            .unwrap_or_else(|| self.nearest_pkg.clone());
+        let current = self.run_cwd();
+        let Some(ref mut flow) = self.flow else {
+            return;
+        };
+        let dirs = list_directories(&root);
+        flow.dir_pick = Some(DirPick::new(root, dirs, &current));
+        self.mode = AppMode::PickDirectory;
+    }
+
+    fn handle_dir_pick_mode(&mut self, key: KeyEvent) -> Action {
+        let default_cwd = self.get_current_cwd();
+        let Some(ref mut flow) = self.flow else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Esc => {
+                flow.dir_pick = None;
+                self.mode = AppMode::ConfirmExecution;
+            }
+            KeyCode::Enter => {
+                let chosen = flow.dir_pick.as_ref().and_then(DirPick::chosen);
+                if let Some(dir) = chosen {
+                    // Picking the script's own directory drops the override
+                    flow.confirm.cwd = (dir != default_cwd).then_some(dir);
+                    flow.dir_pick = None;
+                    // The hooks come from the package.json of the new directory
+                    self.enter_confirm();
+                }
+            }
+            code => {
+                if let Some(ref mut pick) = flow.dir_pick {
+                    pick.handle_key(code);
+                }
+            }
+        }
+        Action::Continue
+    }
+
+    /// Shows the confirm step, loading what it lists from the run's directory
+    /// once instead of on every frame.
+    fn enter_confirm(&mut self) {
+        let hooks = ScriptHooks::load(&self.run_cwd(), &self.get_current_script_name());
+        if let Some(ref mut flow) = self.flow {
+            flow.confirm.missing_keys =
+                missing_example_keys(&flow.env.merge_order_paths(), &flow.overrides.vars);
+            flow.confirm.hooks = hooks;
+        }
+        self.mode = AppMode::ConfirmExecution;
+    }
+
+    /// Where the configured run will run: the directory chosen with Ctrl+D on
+    /// the confirm step, else the script's own.
+    fn run_cwd(&self) -> PathBuf {
+        self.flow
+            .as_ref()
+            .and_then(|flow| flow.confirm.cwd.clone())
+            .unwrap_or_else(|| self.get_current_cwd())
+    }
+
+    /// Runs the configured script once per selected env profile (see
+    /// [`matrix_profiles`]); needs at least two profiles.
+    fn start_matrix(&mut self) -> Action {
+        let profiles = matrix_profiles(&self.selected_env_paths());
+        if profiles.len() < 2 {
+            self.status_message =
+                Some("Select at least two env files besides .env for a matrix run".to_string());
+            return Action::Continue;
+        }
+
+        let run = self.commit_configured_run();
+        for profile in &profiles {
+            self.record_run(&QueuedRun {
+                env_files: profile.env_files.clone(),
+                ..run.clone()
+            });
+        }
+        Action::RunMatrix {
+            script_name: run.script_name,
+            cwd: run.cwd,
+            args: run.args,
+            profiles,
+        }
+    }
+
+    /// Starts the configure flow's run as `start` says, first asking to type
+    /// the script's name if it's one of the `dangerous_scripts`.
+    fn start_configured(&mut self, start: ConfiguredStart) -> Action {
+        let script_name = self.get_current_script_name();
+        if self.config.is_dangerous(&script_name) {
+            self.ask_script_name(script_name, DangerousRun::Configured(start));
+            return Action::Continue;
+        }
+        self.run_configured(start)
+    }
+
+    /// Executes the configure flow's run as `start` says; Enter runs it in
+    /// watch mode if that's turned on.
+    fn run_configured(&mut self, start: ConfiguredStart) -> Action {
+        match start {
+            ConfiguredStart::Run => {
+                let watch = self.flow.as_ref().is_some_and(|flow| flow.confirm.watch);
+                let run = self.commit_configured_run();
+                if watch {
+                    self.start_watch(run)
+                } else {
+                    self.start_run(run)
+                }
+            }
+            ConfiguredStart::Background => {
+                let run = self.commit_configured_run();
+                self.start_background(run);
+                Action::Continue
+            }
+            ConfiguredStart::Matrix => self.start_matrix(),
+        }
+    }
+
+    /// Persists the configuration being confirmed (script args, last env files, args
+    /// history), leaves the configure flow and returns the run it describes.
+    fn commit_configured_run(&mut self) -> QueuedRun {
+        let script_key = self.get_current_script_key();
+        let script_name = self.get_current_script_name();
+        let cwd = self.run_cwd();
+
+        // Save script-specific args and env overrides, keeping its presets
+        let args = self.configured_args();
+        let env = self.configured_env();
+        let config = self.script_config(&script_key);
+        config.args = args;
+        config.env = env.iter().cloned().collect();
+        config.last_used = SystemTime::now();
+        let _ = script_configs::save_script_configs(&self.config_dir, &self.script_configs);
+
+        // Save globally last used env files
+        if let Some(ref flow) = self.flow {
+            self.global_env_config.last_env_files = flow
+                .env
+                .checked_files()
+                .map(|f| f.display_name.clone())
+                .collect();
+            let _ = crate::store::global_env::save_global_env_config(
+                &self.config_dir,
+                &self.global_env_config,
+            );
+        }
+
+        // Save args to history
+        let args = self.configured_args();
+        if !args.is_empty() {
+            self.args_history.add_entry(&script_key, args.clone());
+            let _ = args_history::save_args_history(&self.config_dir, &self.args_history);
+        }
+
+        let env_file_paths = self.selected_env_paths();
+
+        // Reset mode
+        self.mode = AppMode::Normal;
+        self.flow = None;
+
+        QueuedRun {
+            key: script_key.split(':').skip(1).collect::<Vec<_>>().join(":"),
+            script_name,
+            cwd,
+            env_files: env_file_paths,
+            args,
+            env,
+        }
+    }
+
+    /// Selected env files in merge order (root → package, so package overrides root).
+    fn selected_env_paths(&self) -> Vec<PathBuf> {
+        self.flow
+            .as_ref()
+            .map_or_else(Vec::new, |flow| flow.env.merge_order_paths())
+    }
+
+    /// Arguments the configure flow will run with; none outside it.
+    fn configured_args(&self) -> String {
+        self.flow
+            .as_ref()
+            .map(|flow| flow.confirm.args.clone())
+            .unwrap_or_default()
+    }
+
+    /// Env overrides the configure flow will run with; none outside it.
+    fn configured_env(&self) -> Vec<(String, String)> {
+        self.flow
+            .as_ref()
+            .map(|flow| flow.overrides.vars.clone())
+            .unwrap_or_default()
+    }
+
+    /// Writes the merged env of the configuration being confirmed to
+    /// `.nr-env.sh` in the script's directory, as `export` statements.
+    fn write_env_snapshot(&mut self) {
+        let path = self.get_current_cwd().join(ENV_SNAPSHOT_FILE);
+        let mut vars = load_env_files(&self.selected_env_paths()).unwrap_or_default();
+        vars.extend(self.configured_env());
+
+        self.status_message = Some(match std::fs::write(&path, format_exports(&vars)) {
+            Ok(()) => format!("Wrote {} variables to {}", vars.len(), path.display()),
+            Err(e) => format!("Failed to write {}: {}", path.display(), e),
+        });
+    }
+
+    /// The `package.json` defining the selected script and the 1-based line of its entry.
+    fn selected_script_location(&self) -> Option<(PathBuf, usize)> {
+        let script_name = self.get_current_script_name();
+        if script_name.is_empty() {
+            return None;
+        }
+        let path = self.get_current_cwd().join("package.json");
+        let content = std::fs::read_to_string(&path).ok()?;
+        let line = find_script_line(&content, &script_name).unwrap_or(1);
+        Some((path, line))
+    }
+
+    /// The selected script as a plain run with no env files or args, or the
+    /// selected history entry as it was run.
+    fn selected_run(&self) -> Option<QueuedRun> {
+        if self.active_tab == Tab::History {
+            return self.selected_history_entry().map(HistoryEntry::to_run);
+        }
+        // Only package scripts are queued, configured and run as jobs
+        if self.selected_task().is_some() {
+            return None;
+        }
+
+        let script_name = self.get_current_script_name();
+        if script_name.is_empty() {
+            return None;
+        }
+        let script_key = self.get_current_script_key();
+
+        Some(QueuedRun {
+            key: script_key.split(':').skip(1).collect::<Vec<_>>().join(":"),
+            script_name,
+            cwd: self.get_current_cwd(),
+            env_files: vec![],
+            args: String::new(),
+            env: Vec::new(),
+        })
+    }
+
+    fn selected_history_entry(&self) -> Option<&HistoryEntry> {
+        self.history_filtered_indices
+            .get(self.history_selected_index)
+            .map(|&i| &self.history[i])
+    }
+
+    /// Records `run` in recents and the run history, and the command it runs
+    /// as seen (clearing its changed badge).
+    pub fn record_run(&mut self, run: &QueuedRun) {
+        let mut command = None;
+        for script in self
+            .scripts
+            .iter_mut()
+            .chain(self.pkg_script_sortable.iter_mut())
+            .filter(|s| s.key == run.key)
+        {
+            script.changed = false;
+            command = Some(script.command.clone());
+        }
+        // History replays and quick dial runs may be from packages not listed
+        let command = command.or_else(|| {
+            crate::core::scripts::load_scripts(&run.cwd).shift_remove(&run.script_name)
+        });
+        if let Some(command) = command {
+            ran_commands::record_command(&mut self.ran_commands, &run.key, &command);
+            ran_commands::save_ran_commands(&self.config_dir, &self.ran_commands);
+        }
+
+        recents::record_execution(&mut self.recents, &run.key);
+        // Runs in a workspace package count for the package too
+        if let Some(ref root) = self.monorepo_root {
+            if let Some(pkg) = self
+                .workspace_packages
+                .iter()
+                .find(|pkg| root.join(&pkg.relative_path) == run.cwd)
+            {
+                recents::record_execution(&mut self.recents, &package_key(&pkg.name));
+            }
+        }
+        next_runs::record_transition(&mut self.next_runs, &self.history, &run.key);
+        next_runs::save_next_runs(&self.config_dir, &self.next_runs);
+        history::record_run(&mut self.history, run);
+        history::save_history(&self.config_dir, &self.history);
+        self.update_history_filtered();
+    }
+
+    /// Stores how a run ended on its History entry, and its run time on the
+    /// script for the list.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_outcome(
+        &mut self,
+        script_name: &str,
+        cwd: &std::path::Path,
+        args: &str,
+        env_files: &[PathBuf],
+        exit_code: i32,
+        duration: Duration,
+    ) {
+        history::record_outcome(
+            &mut self.history,
+            script_name,
+            cwd,
+            args,
+            env_files,
+            exit_code,
+            duration,
+        );
+        history::save_history(&self.config_dir, &self.history);
+        apply_run_times(&mut self.scripts, &self.history);
+        apply_run_times(&mut self.pkg_script_sortable, &self.history);
+    }
+
+    /// The script usually run after `key`, replayed with the args and env
+    /// files it last ran with.
+    fn suggested_after(&self, key: &str) -> Option<QueuedRun> {
+        let next = suggest_next(&self.next_runs, key)?;
+        self.history
+            .iter()
+            .find(|entry| entry.key == next)
+            .map(HistoryEntry::to_run)
+    }
+
+    /// Suggested follow-up to the most recent run.
+    pub fn suggested_next(&self) -> Option<QueuedRun> {
+        self.suggested_after(&self.history.first()?.key)
+    }
+
+    /// Suggested follow-up to the job in the output pane, once it has exited.
+    pub fn suggested_after_output(&self) -> Option<QueuedRun> {
+        let job = self.jobs.get(self.output.as_ref()?.job)?;
+        job.finished?;
+        let entry = self
+            .history
+            .iter()
+            .find(|e| e.script_name == job.spec.script_name && e.cwd == job.spec.cwd)?;
+        self.suggested_after(&entry.key)
+    }
+
+    /// Records `run` and hands it off for execution.
+    fn start_run(&mut self, run: QueuedRun) -> Action {
+        self.record_run(&run);
+        Action::RunScript {
+            script_name: run.script_name,
+            cwd: run.cwd,
+            env_files: run.env_files,
+            args: run.args,
+            env: run.env,
+        }
+    }
+
+    /// Starts `run` like [`Self::start_run`], unless it's one of the
+    /// `dangerous_scripts`: then its name has to be typed first.
+    fn start_checked_run(&mut self, run: QueuedRun) -> Action {
+        if self.config.is_dangerous(&run.script_name) {
+            self.ask_script_name(run.script_name.clone(), DangerousRun::Run(run));
+            return Action::Continue;
+        }
+        self.start_run(run)
+    }
+
+    /// Holds back `run` of a dangerous script until `script_name` is typed.
+    fn ask_script_name(&mut self, script_name: String, run: DangerousRun) {
+        self.danger_prompt = Some(DangerPrompt {
+            script_name,
+            run,
+            input: String::new(),
+        });
+        self.mode = AppMode::DangerousScript;
+    }
+
+    /// Dangerous script prompt: Enter runs once the script's name is typed,
+    /// Esc cancels (back to the confirmation screen for a configured run).
+    fn handle_dangerous_script_mode(&mut self, key: KeyEvent) -> Action {
+        let Some(prompt) = self.danger_prompt.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Esc => {
+                self.mode = match prompt.run {
+                    DangerousRun::Configured(_) => AppMode::ConfirmExecution,
+                    DangerousRun::Queue { .. } => AppMode::Queue,
+                    _ => AppMode::Normal,
+                };
+                self.status_message = Some(format!("Cancelled {}", prompt.script_name));
+                self.danger_prompt = None;
+                Action::Continue
+            }
+            KeyCode::Enter if prompt.confirmed() => {
+                let run = prompt.run.clone();
+                let script_name = prompt.script_name.clone();
+                self.danger_prompt = None;
+                self.mode = AppMode::Normal;
+                match run {
+                    DangerousRun::Run(run) => self.start_run(run),
+                    DangerousRun::Background(run) => {
+                        self.start_background(run);
+                        Action::Continue
+                    }
+                    DangerousRun::Packages(runs) => {
+                        self.start_in_packages(runs);
+                        Action::Continue
+                    }
+                    DangerousRun::Configured(start) => self.run_configured(start),
+                    DangerousRun::Queue {
+                        parallel,
+                        mut confirmed,
+                    } => {
+                        confirmed.push(script_name);
+                        self.start_queue(parallel, confirmed)
+                    }
+                    DangerousRun::AllPackages(runs) => Action::RunAllPackages { runs },
+                }
+            }
+            KeyCode::Char(c) => {
+                prompt.input.push(c);
+                Action::Continue
+            }
+            KeyCode::Backspace => {
+                prompt.input.pop();
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    /// Adds the selected script to the queue with no env files or args.
+    fn enqueue_selected(&mut self) {
+        if let Some(run) = self.selected_run() {
+            self.queue.push(run);
+        }
+    }
+
+    /// Opens the schedule prompt for `run`.
+    /// Opens the tag prompt for the highlighted script, prefilled with its TUI tags.
+    fn start_tagging(&mut self) {
+        let Some((script, _)) = self.highlighted_script() else {
+            return;
+        };
+        let key = script.key.clone();
+        self.tag_input = self
+            .script_tags
+            .get(&key)
+            .map(|t| t.join(" "))
+            .unwrap_or_default();
+        self.tag_pending = Some(key);
+        self.mode = AppMode::Tags;
+    }
+
+    fn handle_tags_mode(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Esc => {
+                self.tag_pending = None;
+                self.mode = AppMode::Normal;
+                Action::Continue
+            }
+            KeyCode::Enter => {
+                if let Some(script_key) = self.tag_pending.take() {
+                    self.script_tags
+                        .insert(script_key, tags::parse_tags(&self.tag_input));
+                    tags::save_tags(&self.config_dir, &self.script_tags);
+
+                    let dir = self.get_current_cwd();
+                    if self.active_tab == Tab::Packages {
+                        apply_tags(&mut self.pkg_script_sortable, &dir, &self.script_tags);
+                    } else {
+                        apply_tags(&mut self.scripts, &dir, &self.script_tags);
+                    }
+                }
+                self.mode = AppMode::Normal;
+                Action::Continue
+            }
+            KeyCode::Char(c) => {
+                self.tag_input.push(c);
+                Action::Continue
+            }
+            KeyCode::Backspace => {
+                self.tag_input.pop();
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    /// Required env prompt: type a value for the first missing key; Enter moves
+    /// to the next and starts the run once all are set, Esc drops the run.
+    fn handle_required_env_mode(&mut self, key: KeyEvent) -> Action {
+        let Some(prompt) = self.env_prompts.first_mut() else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Esc => {
+                let prompt = self.env_prompts.remove(0);
+                self.start_prompted_runs();
+                self.status_message = Some(format!(
+                    "Cancelled {}: {} not set",
+                    prompt.spec.script_name,
+                    prompt.missing.join(", ")
+                ));
+            }
+            KeyCode::Enter if !prompt.input.is_empty() => {
+                let name = prompt.missing.remove(0);
+                let entry = (name, std::mem::take(&mut prompt.input));
+                prompt.spec.env.push(entry.clone());
+                // Other waiting runs (e.g. the same script in other packages) take it too
+                for other in &mut self.env_prompts {
+                    if other.missing.contains(&entry.0) {
+                        other.missing.retain(|k| *k != entry.0);
+                        other.spec.env.push(entry.clone());
+                    }
+                }
+                self.start_prompted_runs();
+            }
+            KeyCode::Char(c) => prompt.input.push(c),
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Starts the prompted runs that have every value, then asks for the next
+    /// one's if any are still waiting.
+    fn start_prompted_runs(&mut self) {
+        self.mode = AppMode::Normal;
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.env_prompts)
+            .into_iter()
+            .partition(|p| p.missing.is_empty());
+        self.env_prompts = waiting;
+        for prompt in ready {
+            self.start_job(prompt.spec, prompt.attach);
+        }
+        if !self.env_prompts.is_empty() {
+            self.mode = AppMode::RequiredEnv;
+        }
+    }
+
+    fn handle_duplicate_job_mode(&mut self, key: KeyEvent) -> Action {
+        if self.duplicate_prompts.is_empty() {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Enter | KeyCode::Char('j') => {
+                // Showing a job leaves no room for the remaining questions
+                let prompt = self.duplicate_prompts.remove(0);
+                let skipped = std::mem::take(&mut self.duplicate_prompts).len();
+                self.status_message = (skipped > 0)
+                    .then(|| format!("Skipped {} more already running script(s)", skipped));
+                self.attach(prompt.existing);
+                return Action::Continue;
+            }
+            KeyCode::Char('s') => {
+                let prompt = self.duplicate_prompts.remove(0);
+                self.mode = AppMode::Normal;
+                self.start_job(prompt.spec, prompt.attach);
+            }
+            KeyCode::Esc => {
+                let prompt = self.duplicate_prompts.remove(0);
+                self.mode = AppMode::Normal;
+                self.status_message = Some(format!(
+                    "Not started: {} is already running",
+                    prompt.spec.script_name
+                ));
+            }
+            _ => return Action::Continue,
+        }
+        // Starting a run may have asked for env values or opened the pane
+        if self.mode == AppMode::Normal && !self.duplicate_prompts.is_empty() {
+            self.mode = AppMode::DuplicateJob;
+        }
+        Action::Continue
+    }
+
+    /// Opens the quick dial with the top favorite script of the current
+    /// package and of every workspace package.
+    fn open_quick_dial(&mut self) {
+        let mut runs = Vec::new();
+        let root_names = self.scripts.iter().map(|s| &s.name);
+        if let Some(name) = top_favorite("root", root_names, &self.favorites, &self.recents) {
+            runs.push(QueuedRun {
+                key: format!("root:{}", name),
+                script_name: name.clone(),
+                cwd: self.nearest_pkg.clone(),
+                env_files: vec![],
+                args: String::new(),
+                env: Vec::new(),
+            });
+        }
+        if let Some(ref root) = self.monorepo_root {
+            for pkg in &self.workspace_packages {
+                let Some(name) = top_favorite(
+                    &pkg.name,
+                    pkg.scripts.keys(),
+                    &self.favorites,
+                    &self.recents,
+                ) else {
+                    continue;
+                };
+                let cwd = root.join(&pkg.relative_path);
+                // Started from inside a package, the root entry is the same script
+                if runs.iter().any(|r| r.cwd == cwd && &r.script_name == name) {
+                    continue;
+                }
+                runs.push(QueuedRun {
+                    key: format!("{}:{}", pkg.name, name),
+                    script_name: name.clone(),
+                    cwd,
+                    env_files: vec![],
+                    args: String::new(),
+                    env: Vec::new(),
+                });
+            }
+        }
+
+        if runs.is_empty() {
+            self.status_message = Some("No favorite scripts yet".to_string());
+            return;
+        }
+        self.quick_dial = runs;
+        self.quick_dial_selected = 0;
+        self.mode = AppMode::QuickDial;
+    }
+
+    /// Enter or the entry's number (1-9) runs it.
+    fn handle_quick_dial_mode(&mut self, key: KeyEvent) -> Action {
+        let chosen = match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+                return Action::Continue;
+            }
+            KeyCode::Up => {
+                self.quick_dial_selected =
+                    wrap_index(self.quick_dial_selected, -1, self.quick_dial.len());
+                return Action::Continue;
+            }
+            KeyCode::Down => {
+                self.quick_dial_selected =
+                    wrap_index(self.quick_dial_selected, 1, self.quick_dial.len());
+                return Action::Continue;
+            }
+            KeyCode::Enter => self.quick_dial_selected,
+            KeyCode::Char(c @ '1'..='9') => c as usize - '1' as usize,
+            _ => return Action::Continue,
+        };
+
+        if chosen >= self.quick_dial.len() {
+            return Action::Continue;
+        }
+        let run = self.quick_dial.remove(chosen);
+        self.quick_dial.clear();
+        self.mode = AppMode::Normal;
+        self.start_checked_run(run)
+    }
+
+    /// Opens the list of script names shared by several workspace packages.
+    /// Runs the selected script's name in every workspace package that has it.
+    fn run_all_packages(&mut self) -> Action {
+        let Some(root) = self.monorepo_root.clone() else {
+            self.status_message = Some("Not in a monorepo".to_string());
+            return Action::Continue;
+        };
+        let script_name = self.get_current_script_name();
+        if script_name.is_empty() {
+            return Action::Continue;
+        }
+
+        let runs: Vec<QueuedRun> = self
+            .workspace_packages
+            .iter()
+            .filter(|pkg| pkg.scripts.contains_key(&script_name))
+            .map(|pkg| QueuedRun {
+                key: format!("{}:{}", pkg.name, script_name),
+                script_name: script_name.clone(),
+                cwd: root.join(&pkg.relative_path),
+                env_files: vec![],
+                args: String::new(),
+                env: Vec::new(),
+            })
+            .collect();
+        if runs.is_empty() {
+            self.status_message = Some(format!("No workspace package defines {}", script_name));
+            return Action::Continue;
+        }
+        if self.config.is_dangerous(&script_name) {
+            self.ask_script_name(script_name, DangerousRun::AllPackages(runs));
+            return Action::Continue;
+        }
+        Action::RunAllPackages { runs }
+    }
+
+    fn open_script_groups(&mut self) {
+        let groups = group_by_name(&self.workspace_packages);
+        if groups.is_empty() {
+            self.status_message = Some("No script names shared by several packages".to_string());
+            return;
+        }
+        self.script_groups = groups;
+        self.script_group_selected = 0;
+        self.script_group_checked = None;
+        self.mode = AppMode::ScriptGroups;
+    }
+
+    /// Enter opens a name's packages (all checked); there, Space toggles one,
+    /// `a` all of them, and Enter starts the script in each checked package.
+    fn handle_script_groups_mode(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        let Some(group) = self.script_groups.get(self.script_group_selected) else {
+            self.mode = AppMode::Normal;
+            return Action::Continue;
+        };
+        let Some(ref mut checked) = self.script_group_checked else {
+            match key.code {
+                KeyCode::Esc => self.mode = AppMode::Normal,
+                KeyCode::Up => {
+                    self.script_group_selected =
+                        wrap_index(self.script_group_selected, -1, self.script_groups.len());
+                }
+                KeyCode::Down => {
+                    self.script_group_selected =
+                        wrap_index(self.script_group_selected, 1, self.script_groups.len());
+                }
+                KeyCode::Enter | KeyCode::Right => {
+                    self.script_group_checked = Some(vec![true; group.packages.len()]);
+                    self.script_group_pkg_selected = 0;
+                }
+                _ => {}
+            }
+            return Action::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Left => self.script_group_checked = None,
+            KeyCode::Up => {
+                self.script_group_pkg_selected =
+                    wrap_index(self.script_group_pkg_selected, -1, checked.len());
+            }
+            KeyCode::Down => {
+                self.script_group_pkg_selected =
+                    wrap_index(self.script_group_pkg_selected, 1, checked.len());
+            }
+            KeyCode::Char(' ') => {
+                if let Some(on) = checked.get_mut(self.script_group_pkg_selected) {
+                    *on = !*on;
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = checked.iter().all(|&on| on);
+                checked.iter_mut().for_each(|on| *on = !all);
+            }
+            KeyCode::Enter => self.start_script_group(),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Starts the opened group's script in every checked package as a
+    /// background job, and shows them in the Jobs tab.
+    fn start_script_group(&mut self) {
+        let (Some(root), Some(checked)) = (self.monorepo_root.clone(), &self.script_group_checked)
+        else {
+            return;
+        };
+        let group = &self.script_groups[self.script_group_selected];
+        let runs: Vec<QueuedRun> = group
+            .packages
+            .iter()
+            .zip(checked)
+            .filter(|&(_, &on)| on)
+            .map(|(&i, _)| {
+                let pkg = &self.workspace_packages[i];
+                QueuedRun {
+                    key: format!("{}:{}", pkg.name, group.name),
+                    script_name: group.name.clone(),
+                    cwd: root.join(&pkg.relative_path),
+                    env_files: vec![],
+                    args: String::new(),
+                    env: Vec::new(),
+                }
+            })
+            .collect();
+        if runs.is_empty() {
+            self.status_message = Some("No packages selected".to_string());
+            return;
+        }
+
+        self.script_group_checked = None;
+        self.mode = AppMode::Normal;
+        if self.config.is_dangerous(&group.name) {
+            self.ask_script_name(group.name.clone(), DangerousRun::Packages(runs));
+            return;
+        }
+        self.start_in_packages(runs);
+    }
+
+    /// Starts `runs`, one script in several packages, in the background and
+    /// shows them in the Jobs tab.
+    fn start_in_packages(&mut self, runs: Vec<QueuedRun>) {
+        let Some(script_name) = runs.first().map(|run| run.script_name.clone()) else {
+            return;
+        };
+        let message = format!(
+            "Started {} in {} package{} (see Jobs tab)",
+            script_name,
+            runs.len(),
+            if runs.len() == 1 { "" } else { "s" }
+        );
+        for run in runs {
+            self.start_background(run);
+        }
+        self.status_message = Some(message);
+        self.active_tab = Tab::Jobs;
+    }
+
+    /// Up/Down pick what to clear, Enter asks to confirm, and `y` clears it.
+    fn handle_settings_mode(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        let target = ResetTarget::ALL[self.settings_selected];
+        if self.settings_confirm {
+            match key.code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                    self.settings_confirm = false;
+                    self.clear_store(target);
+                }
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => self.settings_confirm = false,
+                _ => {}
+            }
+            return Action::Continue;
+        }
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::Normal,
+            KeyCode::Up => {
+                self.settings_selected =
+                    wrap_index(self.settings_selected, -1, ResetTarget::ALL.len());
+            }
+            KeyCode::Down => {
+                self.settings_selected =
+                    wrap_index(self.settings_selected, 1, ResetTarget::ALL.len());
+            }
+            KeyCode::Enter if self.reset_count(target) == 0 => {
+                self.status_message = Some(format!("{} already empty", target.label()));
+            }
+            KeyCode::Enter => self.settings_confirm = true,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Entries `target` would remove for this project.
+    fn reset_count(&self, target: ResetTarget) -> usize {
+        match target {
+            ResetTarget::Favorites => self.favorites.len(),
+            ResetTarget::Recents => self.recents.len(),
+            ResetTarget::Configs => self.script_configs.len() + self.args_history.entries.len(),
+        }
+    }
+
+    /// What clearing `target` removes, e.g. `3 scripts, 12 arguments`.
+    fn reset_preview(&self, target: ResetTarget) -> String {
+        let count =
+            |n: usize, noun: &str| format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+        match target {
+            ResetTarget::Favorites => count(self.favorites.len(), "script"),
+            ResetTarget::Recents => count(self.recents.len(), "script"),
+            ResetTarget::Configs => format!(
+                "{}, {}",
+                count(self.script_configs.len(), "script"),
+                count(self.args_history.entries.len(), "argument")
+            ),
+        }
+    }
+
+    fn reset_question(&self, target: ResetTarget) -> String {
+        format!(
+            "Clear {} ({})? This can't be undone.",
+            target.label().to_lowercase(),
+            self.reset_preview(target)
+        )
+    }
+
+    /// Deletes `target`'s store files and forgets the loaded entries.
+    fn clear_store(&mut self, target: ResetTarget) {
+        let preview = self.reset_preview(target);
+        if let Err(e) = reset(&self.config_dir, target) {
+            self.status_message = Some(format!("Reset failed: {}", e));
+            return;
+        }
+        match target {
+            ResetTarget::Favorites => self.favorites.clear(),
+            ResetTarget::Recents => self.recents.clear(),
+            ResetTarget::Configs => {
+                self.script_configs.clear();
+                self.args_history = ArgsHistory::new();
+            }
+        }
+        self.update_filtered();
+        self.update_pkg_script_filtered();
+        self.status_message = Some(format!(
+            "Cleared {} ({})",
+            target.label().to_lowercase(),
+            preview
+        ));
+    }
+
+    /// Warning for flags in the args input that the script's tool (vite,
+    /// jest, tsc, ...) doesn't have.
+    fn args_warning(&self) -> Option<String> {
+        let (script, _) = self.highlighted_script()?;
+        let table = flag_table_for(&script.command)?;
+        let unknown = table.unknown_flags(&self.flow.as_ref()?.args.input);
+        if unknown.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Unknown {} flag: {}",
+            table.tool,
+            unknown.join(", ")
+        ))
+    }
+
+    fn start_schedule(&mut self, run: QueuedRun) {
+        self.schedule_pending = Some(run);
+        self.schedule_input.clear();
+        self.schedule_error = None;
+        self.mode = AppMode::Schedule;
+    }
+
+    fn handle_schedule_mode(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Esc => {
+                self.schedule_pending = None;
+                self.mode = AppMode::Normal;
+                Action::Continue
+            }
+            KeyCode::Enter => {
+                match parse_start(&self.schedule_input, local_time_now()) {
+                    Ok(delay) => {
+                        if let Some(run) = self.schedule_pending.take() {
+                            self.scheduled.push(ScheduledRun {
+                                run,
+                                due: Instant::now() + delay,
+                            });
+                            self.scheduled.sort_by_key(|s| s.due);
+                        }
+                        self.mode = AppMode::Normal;
+                    }
+                    Err(e) => self.schedule_error = Some(e.to_string()),
+                }
+                Action::Continue
+            }
+            KeyCode::Char(c) => {
+                self.schedule_input.push(c);
+                self.schedule_error = None;
+                Action::Continue
+            }
+            KeyCode::Backspace => {
+                self.schedule_input.pop();
+                self.schedule_error = None;
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    /// Starts the earliest scheduled run once it is due.
+    ///
+    /// Runs only fire from the script list or the queue panel so an open
+    /// configure flow or prompt is never interrupted.
+    pub fn tick(&mut self, now: Instant) -> Action {
+        self.poll_jobs();
+        self.poll_watch(now);
+        self.poll_manifest_watch(now);
+        self.poll_workspace_refresh();
+        self.poll_update_check();
+
+        if !matches!(self.mode, AppMode::Normal | AppMode::Queue) {
+            return Action::Continue;
+        }
+        if self.scheduled.first().is_none_or(|s| s.due > now) {
+            return Action::Continue;
+        }
+
+        // A dangerous script waits for its name to be typed, so it never runs unattended
+        let run = self.scheduled.remove(0).run;
+        self.mode = AppMode::Normal;
+        let action = self.start_checked_run(run);
+        if self.config.run_in_pane {
+            self.run_in_pane(action)
+        } else {
+            action
+        }
+    }
+
+    fn poll_workspace_refresh(&mut self) {
+        let Some(ref rx) = self.workspace_refresh else {
+            return;
+        };
+        let mut found = Vec::new();
+        let mut finished = None;
+        loop {
+            match rx.try_recv() {
+                Ok(ScanUpdate::Found(package)) => found.push(package),
+                Ok(ScanUpdate::Done(packages)) => {
+                    finished = Some(Some(packages));
+                    break;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    finished = Some(None);
+                    break;
+                }
+            }
+        }
+
+        if !found.is_empty() && self.workspace_scan_started.is_some() {
+            self.workspace_packages.extend(found);
+            self.update_pkg_filtered();
+        }
+        let Some(packages) = finished else {
+            return;
+        };
+        self.workspace_refresh = None;
+        self.workspace_scan_started = None;
+        // Without a result (the scan died), keep what was found
+        let packages = packages.unwrap_or_else(|| self.workspace_packages.clone());
+        self.replace_workspace_packages(packages);
+    }
+
+    fn poll_update_check(&mut self) {
+        let Some(ref rx) = self.update_check else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(version) => {
+                self.update_check = None;
+                self.update_available = Some(version);
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.update_check = None,
+        }
+    }
+
+    /// Swaps in a new package list, keeping the open package if it still exists.
+    fn replace_workspace_packages(&mut self, packages: Vec<WorkspacePackage>) {
+        if packages != self.workspace_packages {
+            let open_package = match self.package_mode {
+                PackageMode::SelectingScript { package_index } => {
+                    Some(self.workspace_packages[package_index].relative_path.clone())
+                }
+                PackageMode::SelectingPackage | PackageMode::AllScripts => None,
+            };
+            self.workspace_packages = packages;
+            self.update_pkg_filtered();
+            if self.package_mode == PackageMode::AllScripts {
+                self.refresh_package_scripts(None);
+            }
+
+            if let Some(path) = open_package {
+                match self
+                    .workspace_packages
+                    .iter()
+                    .position(|p| p.relative_path == path)
+                {
+                    Some(package_index) => self.refresh_package_scripts(Some(package_index)),
+                    None => self.package_mode = PackageMode::SelectingPackage,
+                }
+            }
+        }
+        // A first scan shows the tab before knowing whether there are packages
+        self.has_workspaces = !self.workspace_packages.is_empty();
+        if !self.has_workspaces && self.active_tab == Tab::Packages {
+            self.active_tab = Tab::Scripts;
+        }
+    }
+
+    /// Rebuilds the open package's script list (every package's with `None`)
+    /// from their new scripts, keeping the search and the selected script.
+    fn refresh_package_scripts(&mut self, package_index: Option<usize>) {
+        let selected = self
+            .pkg_script_filtered_indices
+            .get(self.pkg_script_selected_index)
+            .map(|&i| self.pkg_script_sortable[i].key.clone());
+        let query = std::mem::take(&mut self.pkg_script_query);
+        match package_index {
+            Some(package_index) => self.enter_package_scripts(package_index),
+            None => self.open_all_scripts(),
+        }
+        self.pkg_script_query = query;
+        self.update_pkg_script_filtered();
+        if let Some(row) = self
+            .pkg_script_filtered_indices
+            .iter()
+            .position(|&i| Some(&self.pkg_script_sortable[i].key) == selected.as_ref())
+        {
+            self.pkg_script_selected_index = row;
+            self.ensure_visible_pkg_scripts();
+        }
+    }
+
+    /// Status text for the next scheduled run, e.g. `root:build in 9m 59s`.
+    fn next_scheduled_label(&self, now: Instant) -> Option<String> {
+        self.scheduled.first().map(|s| {
+            format!(
+                "{} in {}",
+                s.run.key,
+                format_countdown(s.due.saturating_duration_since(now))
+            )
+        })
+    }
+
+    /// Shows the queue panel with the first item selected.
+    pub fn open_queue(&mut self) {
+        self.queue_selected_index = 0;
+        self.mode = AppMode::Queue;
+    }
+
+    fn handle_queue_mode(&mut self, key: KeyEvent) -> Action {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+                Action::Continue
+            }
+            KeyCode::Enter => {
+                if self.queue.is_empty() {
+                    Action::Continue
+                } else {
+                    self.mode = AppMode::Normal;
+                    self.start_queue(false, Vec::new())
+                }
+            }
+            KeyCode::Up if shift => {
+                self.queue_selected_index = self.queue.move_up(self.queue_selected_index);
+                Action::Continue
+            }
+            KeyCode::Down if shift => {
+                self.queue_selected_index = self.queue.move_down(self.queue_selected_index);
+                Action::Continue
+            }
+            KeyCode::Char('K') => {
+                self.queue_selected_index = self.queue.move_up(self.queue_selected_index);
+                Action::Continue
+            }
+            KeyCode::Char('J') => {
+                self.queue_selected_index = self.queue.move_down(self.queue_selected_index);
+                Action::Continue
+            }
+            KeyCode::Up => {
+                if self.queue_selected_index > 0 {
+                    self.queue_selected_index -= 1;
+                }
+                Action::Continue
+            }
+            KeyCode::Down => {
+                if self.queue_selected_index + 1 < self.queue.len() + self.scheduled.len() {
+                    self.queue_selected_index += 1;
+                }
+                Action::Continue
+            }
+            KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d') => {
+                // Scheduled runs are listed after the queue; removing one cancels it
+                let index = self.queue_selected_index;
+                if index < self.queue.len() {
+                    self.queue.remove(index);
+                } else if index - self.queue.len() < self.scheduled.len() {
+                    self.scheduled.remove(index - self.queue.len());
+                }
+                let total = self.queue.len() + self.scheduled.len();
+                if self.queue_selected_index >= total {
+                    self.queue_selected_index = total.saturating_sub(1);
+                }
+                Action::Continue
+            }
+            KeyCode::Char('p') => {
+                self.queue.toggle_pause();
+                Action::Continue
+            }
+            KeyCode::Char('c') => {
+                if self.queue.is_empty() {
+                    Action::Continue
+                } else {
+                    self.mode = AppMode::Normal;
+                    self.start_queue(true, Vec::new())
+                }
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    /// Runs the queue, one at a time or all at once with `parallel`, once the
+    /// name of each dangerous script in it is typed (`confirmed` holds those
+    /// typed so far).
+    fn start_queue(&mut self, parallel: bool, confirmed: Vec<String>) -> Action {
+        let unconfirmed = self
+            .queue
+            .items
+            .iter()
+            .map(|run| &run.script_name)
+            .find(|name| self.config.is_dangerous(name) && !confirmed.contains(name))
+            .cloned();
+        if let Some(script_name) = unconfirmed {
+            self.ask_script_name(
+                script_name,
+                DangerousRun::Queue {
+                    parallel,
+                    confirmed,
+                },
+            );
+            return Action::Continue;
+        }
+        if !parallel {
+            return Action::RunQueue;
+        }
+        self.queue_selected_index = 0;
+        Action::RunParallel {
+            runs: std::mem::take(&mut self.queue.items),
+        }
+    }
+
+    /// The command Enter would run, quoted for a shell: with the configured args in
+    /// the confirm step, otherwise the selected script or history entry as it runs.
+    fn resolved_command(&self) -> Option<String> {
+        if let Some((runner, task)) = self.selected_task() {
+            return Some(shell_join(&runner.invocation(task, &[])));
+        }
+        let script_name = self.get_current_script_name();
+        if script_name.is_empty() {
+            return None;
+        }
+
+        let argv = if self.mode == AppMode::ConfirmExecution {
+            self.get_current_invocation()
+        } else {
+            let run = self.selected_run()?;
+            self.invocation_in(&script_name, &run.args, &run.cwd)
+        };
+        Some(shell_join(&argv))
+    }
+
+    /// The command line running `script_name` with `args` in `cwd`: from the
+    /// monorepo root when [`Self::workspace_target`] applies.
+    fn invocation_in(&self, script_name: &str, args: &str, cwd: &Path) -> Vec<String> {
+        let args = split_args(args);
+        let pm = self.package_manager_for(cwd);
+        let template = self.config.run_template(pm);
+        match self.workspace_target(cwd) {
+            Some(target) => pm.workspace_invocation(&target.package, script_name, &args, template),
+            None => pm.invocation(script_name, &args, template),
+        }
+    }
+
+    /// The package manager running scripts in `cwd`: a nested lockfile or
+    /// `packageManager` field below the project root wins over the root's,
+    /// except for runs started from the root (see [`Self::workspace_target`]).
+    pub fn package_manager_for(&self, cwd: &Path) -> PackageManager {
+        if self.workspace_target(cwd).is_some() {
+            return self.package_manager;
+        }
+        let root = self.monorepo_root.as_ref().unwrap_or(&self.nearest_pkg);
+        detect_package_manager_in(cwd, root, self.package_manager)
+    }
+
+    /// The run template and [`ExecutionStrategy`] of a pane or background run
+    /// of `pm` in `cwd`: the `node_version` manager, `corepack` and
+    /// `run_through` configs as a terminal run applies them, except that
+    /// nothing can be asked inside the TUI, so Corepack is only used with
+    /// `"corepack": "always"`.
+    fn piped_run_setup(
+        &self,
+        pm: PackageManager,
+        cwd: &Path,
+    ) -> (Option<String>, ExecutionStrategy) {
+        let template = self.config.run_template(pm);
+        let template = quiet_node_template(cwd, self.config.node_version, template)
+            .or_else(|| template.map(String::from));
+        let strategy = ExecutionStrategy {
+            corepack: use_corepack_unattended(pm, cwd, self.config.corepack),
+            wrapper: self.config.run_through.clone(),
+        };
+        (template, strategy)
+    }
+
+    /// With the `run_from_root` config, where and how to run a script of the
+    /// workspace package in `cwd` from the monorepo root. `None` outside
+    /// workspace packages, which run in their own directory.
+    pub fn workspace_target(&self, cwd: &Path) -> Option<WorkspaceTarget> {
+        if !self.config.run_from_root {
+            return None;
+        }
+        let root = self.monorepo_root.as_ref()?;
+        self.workspace_packages
+            .iter()
+            .find(|pkg| root.join(&pkg.relative_path) == cwd)
+            .map(|pkg| WorkspaceTarget {
+                root: root.clone(),
+                package: pkg.name.clone(),
+            })
+    }
+
+    /// Copies [`Self::resolved_command`] to the clipboard and reports the outcome.
+    fn copy_resolved_command(&mut self) {
+        let Some(command) = self.resolved_command() else {
+            return;
+        };
+        self.status_message = Some(match SystemClipboard.copy(&command) {
+            Ok(ClipboardMethod::Command(_)) => format!("Copied: {}", command),
+            Ok(ClipboardMethod::Osc52) => format!("Copied via terminal: {}", command),
+            Err(e) => format!("Failed to copy: {}", e),
+        });
+    }
+
+    /// Full command line for the selected script with the configured args and run template.
+    fn get_current_invocation(&self) -> Vec<String> {
+        self.invocation_in(
+            &self.get_current_script_name(),
+            &self.configured_args(),
+            &self.run_cwd(),
+        )
+    }
+
+    fn get_current_script_name(&self) -> String {
+        match self.active_tab {
+            Tab::Scripts => {
+                if let Some(script_idx) = self.selected_script_index() {
+                    self.scripts[script_idx].name.clone()
+                } else {
+                    String::new()
+                }
+            }
+            Tab::Packages => match self.package_mode {
+                PackageMode::SelectingScript { .. } | PackageMode::AllScripts => {
+                    if let Some(&script_idx) = self
+                        .pkg_script_filtered_indices
+                        .get(self.pkg_script_selected_index)
+                    {
+                        self.pkg_script_sortable[script_idx].name.clone()
+                    } else {
+                        String::new()
+                    }
+                }
+                _ => String::new(),
+            },
+            Tab::History => self
+                .selected_history_entry()
+                .map(|entry| entry.script_name.clone())
+                .unwrap_or_default(),
+            Tab::Jobs => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::env_files::scan_env_files;
+    use crate::core::jobs::Job;
+    use crate::flow::{ArgsEdit, Confirm};
+    use crate::store::args_history::ArgsHistory;
+    use crate::store::script_configs::ScriptConfigs;
+
+    // Test helper to create SortableScript
+    fn script(name: &str, command: &str) -> SortableScript {
+        SortableScript {
+            key: format!("root:{}", name),
+            name: name.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // Test builder for App
+    struct TestAppBuilder {
+        scripts: Vec<SortableScript>,
+        workspace_packages: Vec<WorkspacePackage>,
+        favorites: HashSet<String>,
+        recents: Vec<RecentEntry>,
+        visible_height: usize,
+        has_workspaces: bool,
+    }
+
+    impl TestAppBuilder {
+        fn new() -> Self {
+            Self {
+                scripts: vec![],
+                workspace_packages: vec![],
+                favorites: HashSet::new(),
+                recents: vec![],
+                visible_height: 20,
+                has_workspaces: false,
+            }
+        }
+
+        fn with_scripts(mut self, scripts: Vec<SortableScript>) -> Self {
+            self.scripts = scripts;
+            self
+        }
+
+        fn with_favorite(mut self, key: &str) -> Self {
+            self.favorites.insert(key.to_string());
+            self
+        }
+
+        fn with_workspaces(mut self, packages: Vec<WorkspacePackage>) -> Self {
+            self.has_workspaces = !packages.is_empty();
+            self.workspace_packages = packages;
+            self
+        }
+
+        fn build(self) -> App {
+            let filtered_indices = sort_scripts(&self.scripts, &self.favorites, &self.recents, "");
+            let pkg_filtered_indices: Vec<usize> = (0..self.workspace_packages.len()).collect();
+
+            App {
+                active_tab: Tab::Scripts,
+                package_mode: PackageMode::SelectingPackage,
+                has_workspaces: self.has_workspaces,
+                scripts: self.scripts,
+                workspace_packages: self.workspace_packages,
+                nearest_pkg: PathBuf::from("/test/project"),
+                monorepo_root: None,
+                favorites: self.favorites,
+                favorites_unsaved: false,
+                recents: self.recents,
+                project_name: "test-project".to_string(),
+                project_path: "/test/project".to_string(),
+                package_manager_name: "npm".to_string(),
+                visible_height: self.visible_height,
+                list_area: Rect::default(),
+                last_click: None,
+                query: String::new(),
+                selected_index: 0,
+                scroll_offset: 0,
+                script_rows: flat_rows(&filtered_indices),
+                filtered_indices,
+                expanded_groups: HashSet::new(),
+                hidden_scripts: HiddenScripts::default(),
+                secret_env: SecretEnv::default(),
+                view_prefs: ViewPrefsMap::new(),
+                pkg_query: String::new(),
+                pkg_selected_index: 0,
+                pkg_scroll_offset: 0,
+                pkg_filtered_indices,
+                pkg_script_query: String::new(),
+                pkg_script_selected_index: 0,
+                pkg_script_scroll_offset: 0,
+                pkg_script_filtered_indices: Vec::new(),
+                pkg_script_sortable: Vec::new(),
+
+                // NEW: Config flow fields (test defaults)
+                mode: AppMode::Normal,
+                flow: None,
+                script_configs: ScriptConfigs::new(),
+                global_env_config: crate::store::global_env::GlobalEnvConfig::default(),
+                args_history: ArgsHistory::new(),
+                config_dir: PathBuf::from("/test/.config/nr"),
+                package_manager: crate::core::package_manager::PackageManager::Npm,
+                config: Config::default(),
+
+                // Execution queue (test defaults)
+                queue: ExecutionQueue::default(),
+                queue_selected_index: 0,
+                scheduled: Vec::new(),
+                schedule_input: String::new(),
+                schedule_error: None,
+                schedule_pending: None,
+                script_tags: ScriptTags::new(),
+                tag_input: String::new(),
+                tag_pending: None,
+                env_prompts: Vec::new(),
+                duplicate_prompts: Vec::new(),
+                danger_prompt: None,
+                watch: None,
+                manifest_watch: None,
+                quick_dial: Vec::new(),
+                quick_dial_selected: 0,
+                script_groups: Vec::new(),
+                script_group_selected: 0,
+                script_group_checked: None,
+                script_group_pkg_selected: 0,
+                settings_selected: 0,
+                settings_confirm: false,
+                status_message: None,
+                config_issues: Vec::new(),
+                jobs: JobRegistry::default(),
+                jobs_selected_index: 0,
+                output: None,
+                output_height: 20,
+                workspace_refresh: None,
+                workspace_scan_started: None,
+                update_check: None,
+                update_available: None,
+                keymap: Keymap::default(),
+                theme: Theme::default(),
+                show_help: false,
+                help_scroll: 0,
+                picker: false,
+                history: Vec::new(),
+                next_runs: NextRuns::new(),
+                ran_commands: RanCommands::new(),
+                history_query: String::new(),
+                history_selected_index: 0,
+                history_scroll_offset: 0,
+                history_filtered_indices: Vec::new(),
+            }
+        }
+    }
+
+    // --- move_selection tests ---
+
+    #[test]
+    fn test_move_selection_down_increments_index() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("test", "echo test"),
+                script("build", "echo build"),
+                script("lint", "echo lint"),
+            ])
+            .build();
+
+        assert_eq!(app.selected_index, 0);
+        app.move_selection(1);
+        assert_eq!(app.selected_index, 1);
+        app.move_selection(1);
+        assert_eq!(app.selected_index, 2);
+    }
+
+    #[test]
+    fn test_move_selection_up_decrements_index() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("test", "echo test"),
+                script("build", "echo build"),
+                script("lint", "echo lint"),
+            ])
+            .build();
+
+        app.selected_index = 2;
+        app.move_selection(-1);
+        assert_eq!(app.selected_index, 1);
+        app.move_selection(-1);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_move_selection_wraps_at_bottom() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("test", "echo test"),
+                script("build", "echo build"),
+            ])
+            .build();
+
+        app.selected_index = 1; // last item
+        app.move_selection(1); // should wrap to 0
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_move_selection_wraps_at_top() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("test", "echo test"),
+                script("build", "echo build"),
+            ])
+            .build();
+
+        assert_eq!(app.selected_index, 0);
+        app.move_selection(-1); // should wrap to last
+        assert_eq!(app.selected_index, 1);
+    }
+
+    #[test]
+    fn test_move_selection_handles_empty_list() {
+        let mut app = TestAppBuilder::new().build();
+        assert_eq!(app.selected_index, 0);
+        app.move_selection(1);
+        assert_eq!(app.selected_index, 0); // no change
+    }
+
+    // --- toggle_fav tests ---
+
+    #[test]
+    fn test_toggle_fav_adds_to_favorites() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        let key = "root:test";
+        assert!(!app.favorites.contains(key));
+
+        app.toggle_fav();
+        assert!(app.favorites.contains(key));
+        // Marked for the header's `{saved}` until written
+        assert!(app.favorites_unsaved);
+    }
+
+    #[test]
+    fn test_toggle_fav_removes_from_favorites() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .with_favorite("root:test")
+            .build();
+
+        assert!(app.favorites.contains("root:test"));
+        app.toggle_fav();
+        assert!(!app.favorites.contains("root:test"));
+    }
+
+    #[test]
+    fn test_toggle_fav_updates_filtered_indices() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("aaa", "echo aaa"), script("zzz", "echo zzz")])
+            .build();
+
+        // Initially alphabetical order: aaa, zzz
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+
+        // Toggle favorite on zzz (index 1)
+        app.selected_index = 1;
+        app.toggle_fav();
+
+        // Now favorites come first: zzz, aaa
+        assert_eq!(app.filtered_indices, vec![1, 0]);
+    }
+
+    // --- switch_tab tests ---
+
+    #[test]
+    fn test_switch_tab_changes_to_packages() {
+        let pkg = WorkspacePackage {
+            name: "pkg1".to_string(),
+            relative_path: "packages/pkg1".to_string(),
+            scripts: IndexMap::new(),
+            description: None,
+            version: None,
+            private: false,
+        };
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .with_workspaces(vec![pkg])
+            .build();
+
+        assert_eq!(app.active_tab, Tab::Scripts);
+        app.switch_tab(1);
+        assert_eq!(app.active_tab, Tab::Packages);
+    }
+
+    #[test]
+    fn test_switch_tab_changes_to_scripts() {
+        let pkg = WorkspacePackage {
+            name: "pkg1".to_string(),
+            relative_path: "packages/pkg1".to_string(),
+            scripts: IndexMap::new(),
+            description: None,
+            version: None,
+            private: false,
+        };
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .with_workspaces(vec![pkg])
+            .build();
+
+        app.active_tab = Tab::Packages;
+        app.switch_tab(-1);
+        assert_eq!(app.active_tab, Tab::Scripts);
+    }
+
+    #[test]
+    fn test_switch_tab_does_nothing_without_workspaces() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        assert_eq!(app.active_tab, Tab::Scripts);
+        app.switch_tab(1);
+        assert_eq!(app.active_tab, Tab::Scripts); // no change
+    }
+
+    // --- type_char / delete_char tests ---
+
+    #[test]
+    fn test_type_char_updates_query() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        assert_eq!(app.query, "");
+        app.type_char('t');
+        assert_eq!(app.query, "t");
+        app.type_char('e');
+        assert_eq!(app.query, "te");
+    }
+
+    #[test]
+    fn test_delete_char_removes_last_char() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        app.query = "test".to_string();
+        app.delete_char();
+        assert_eq!(app.query, "tes");
+        app.delete_char();
+        assert_eq!(app.query, "te");
+    }
+
+    #[test]
+    fn test_delete_char_on_empty_query() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        assert_eq!(app.query, "");
+        app.delete_char();
+        assert_eq!(app.query, ""); // no panic, no change
+    }
+
+    // --- update_filtered tests ---
+
+    #[test]
+    fn test_update_filtered_with_empty_query() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("build", "echo build"),
+                script("test", "echo test"),
+            ])
+            .build();
+
+        app.query = "".to_string();
+        app.update_filtered();
+
+        // Should return all scripts in alphabetical order
+        assert_eq!(app.filtered_indices.len(), 2);
+    }
+
+    #[test]
+    fn test_update_filtered_with_query_filters_correctly() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("build", "echo build"),
+                script("test", "echo test"),
+                script("lint", "echo lint"),
+            ])
+            .build();
+
+        app.query = "te".to_string();
+        app.update_filtered();
+
+        // Should only match "test"
+        assert_eq!(app.filtered_indices.len(), 1);
+        assert_eq!(app.scripts[app.filtered_indices[0]].name, "test");
+    }
+
+    #[test]
+    fn test_update_filtered_resets_selection() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("build", "echo build"),
+                script("test", "echo test"),
+            ])
+            .build();
+
+        app.selected_index = 1;
+        app.scroll_offset = 5;
+
+        app.update_filtered();
+
+        assert_eq!(app.selected_index, 0);
+        assert_eq!(app.scroll_offset, 0);
+    }
+
+    // --- handle_esc tests ---
+
+    #[test]
+    fn test_handle_esc_on_scripts_tab_quits() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        let action = app.handle_esc();
+        assert!(matches!(action, Action::Quit));
+    }
+
+    #[test]
+    fn test_handle_esc_in_package_mode_goes_back() {
+        let pkg = WorkspacePackage {
+            name: "pkg1".to_string(),
+            relative_path: "packages/pkg1".to_string(),
+            scripts: {
+                let mut map = IndexMap::new();
+                map.insert("test".to_string(), "echo test".to_string());
+                map
+            },
+            description: None,
+            version: None,
+            private: false,
+        };
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .with_workspaces(vec![pkg])
+            .build();
+
+        // Enter package script mode
+        app.active_tab = Tab::Packages;
+        app.enter_package_scripts(0);
+        assert!(matches!(
+            app.package_mode,
+            PackageMode::SelectingScript { .. }
+        ));
+
+        // Esc should go back to package list
+        let action = app.handle_esc();
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.package_mode, PackageMode::SelectingPackage);
+    }
+
+    // --- handle_enter tests ---
+
+    #[test]
+    fn test_handle_enter_returns_run_action() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "echo test")])
+            .build();
+
+        let action = app.handle_enter();
+        assert!(matches!(action, Action::RunScript { .. }));
+
+        if let Action::RunScript { script_name, .. } = action {
+            assert_eq!(script_name, "test");
+        }
+    }
+
+    #[test]
+    fn test_handle_enter_on_empty_list_returns_continue() {
+        let mut app = TestAppBuilder::new().build();
+
+        let action = app.handle_enter();
+        assert!(matches!(action, Action::Continue));
+    }
+
+    // --- ensure_scroll tests ---
+
+    #[test]
+    fn test_ensure_scroll_adjusts_when_selected_below_offset() {
+        let mut offset = 5;
+        ensure_scroll(&mut offset, 3, 10);
+        assert_eq!(offset, 3);
+    }
+
+    #[test]
+    fn test_ensure_scroll_adjusts_when_selected_above_visible() {
+        let mut offset = 0;
+        ensure_scroll(&mut offset, 15, 10);
+        assert_eq!(offset, 6); // 15 - 10 + 1
+    }
+
+    #[test]
+    fn test_ensure_scroll_no_change_when_in_view() {
+        let mut offset = 5;
+        ensure_scroll(&mut offset, 10, 10);
+        assert_eq!(offset, 5); // 10 is within [5, 15)
+    }
+
+    // --- to_sortable_scripts tests ---
+
+    #[test]
+    fn test_to_sortable_scripts_flags_missing_references() {
+        let mut raw = IndexMap::new();
+        raw.insert("build".to_string(), "tsc".to_string());
+        raw.insert(
+            "ci".to_string(),
+            "npm run lint && npm run build".to_string(),
+        );
+
+        let scripts = to_sortable_scripts("web", &raw);
+
+        assert_eq!(scripts[0].key, "web:build");
+        assert!(scripts[0].missing_refs.is_empty());
+        assert_eq!(scripts[1].missing_refs, vec!["lint"]);
+    }
+
+    // --- queue tests ---
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_ctrl_a_enqueues_selected_script() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+
+        app.handle_key(ctrl('a'));
+        app.move_selection(1);
+        app.handle_key(ctrl('a'));
+
+        let keys: Vec<&str> = app.queue.items.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["root:build", "root:test"]);
+        assert_eq!(app.queue.items[0].cwd, PathBuf::from("/test/project"));
+        assert!(app.query.is_empty());
+    }
+
+    #[test]
+    fn test_queue_panel_reorders_and_removes() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("a", "x"), script("b", "y"), script("c", "z")])
+            .build();
+        for _ in 0..3 {
+            app.handle_key(ctrl('a'));
+            app.move_selection(1);
+        }
+
+        app.handle_key(ctrl('q'));
+        assert_eq!(app.mode, AppMode::Queue);
+
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT));
+        assert_eq!(app.queue_selected_index, 1);
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::empty()));
+
+        let names: Vec<&str> = app
+            .queue
+            .items
+            .iter()
+            .map(|r| r.script_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["b", "c"]);
+        assert_eq!(app.queue_selected_index, 1);
+    }
+
+    #[test]
+    fn test_queue_enter_starts_only_when_not_empty() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        app.handle_key(ctrl('q'));
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(matches!(action, Action::Continue));
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        app.handle_key(ctrl('a'));
+        app.handle_key(ctrl('q'));
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty()));
+        assert!(app.queue.paused);
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(matches!(action, Action::RunQueue));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_queue_c_runs_everything_queued_in_parallel() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("api", "node api"), script("web", "vite")])
+            .build();
+        app.handle_key(ctrl('a'));
+        app.move_selection(1);
+        app.handle_key(ctrl('a'));
+        app.handle_key(ctrl('q'));
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()));
+        let Action::RunParallel { runs } = action else {
+            panic!("expected a parallel run");
+        };
+        let names: Vec<&str> = runs.iter().map(|r| r.script_name.as_str()).collect();
+        assert_eq!(names, vec!["api", "web"]);
+        assert!(app.queue.is_empty());
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_queue_asks_for_each_dangerous_script_before_running() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("db:reset", "prisma migrate reset"),
+                script("build", "tsc"),
+                script("deploy", "./deploy.sh"),
+            ])
+            .build();
+        app.config.dangerous_scripts = vec!["db:*".to_string(), "deploy".to_string()];
+        app.handle_key(ctrl('a'));
+        app.move_selection(1);
+        app.handle_key(ctrl('a'));
+        app.move_selection(1);
+        app.handle_key(ctrl('a'));
+        app.handle_key(ctrl('q'));
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.mode, AppMode::DangerousScript);
+        assert_eq!(app.danger_prompt.as_ref().unwrap().script_name, "db:reset");
+        for c in "db:reset".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.danger_prompt.as_ref().unwrap().script_name, "deploy");
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Queue);
+        assert_eq!(app.queue.len(), 3);
+    }
+
+    #[test]
+    fn test_parallel_queue_run_waits_for_dangerous_script_name() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("api", "node api"),
+                script("db:reset", "prisma"),
+            ])
+            .build();
+        app.config.dangerous_scripts = vec!["db:reset".to_string()];
+        app.handle_key(ctrl('a'));
+        app.move_selection(1);
+        app.handle_key(ctrl('a'));
+        app.handle_key(ctrl('q'));
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()));
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.mode, AppMode::DangerousScript);
+        assert_eq!(app.queue.len(), 2);
+
+        for c in "db:reset".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let Action::RunParallel { runs } = action else {
+            panic!("expected a parallel run");
+        };
+        assert_eq!(runs.len(), 2);
+        assert!(app.queue.is_empty());
+    }
+
+    // --- schedule tests ---
+
+    #[test]
+    fn test_ctrl_t_schedules_selected_script() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        app.handle_key(ctrl('t'));
+        assert_eq!(app.mode, AppMode::Schedule);
+        for c in "10m".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.scheduled.len(), 1);
+        assert_eq!(app.scheduled[0].run.key, "root:build");
+        assert!(app.scheduled[0].due > Instant::now() + std::time::Duration::from_secs(590));
+    }
+
+    #[test]
+    fn test_schedule_invalid_input_keeps_prompt_open() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        app.handle_key(ctrl('t'));
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.mode, AppMode::Schedule);
+        assert!(app.schedule_error.is_some());
+        assert!(app.scheduled.is_empty());
+    }
+
+    #[test]
+    fn test_tick_fires_due_run_and_cancel_removes_it() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("lint", "eslint")])
+            .build();
+        let now = Instant::now();
+        for (name, secs) in [("build", 0), ("lint", 60)] {
+            app.scheduled.push(ScheduledRun {
+                run: QueuedRun {
+                    key: format!("root:{}", name),
+                    script_name: name.to_string(),
+                    cwd: PathBuf::from("/test/project"),
+                    env_files: vec![],
+                    args: String::new(),
+                    env: Vec::new(),
+                },
+                due: now + std::time::Duration::from_secs(secs),
+            });
+        }
+
+        let action = app.tick(now);
+        assert!(
+            matches!(action, Action::RunScript { ref script_name, .. } if script_name == "build")
+        );
+        assert!(matches!(app.tick(now), Action::Continue));
+
+        // Cancel the remaining run from the queue panel
+        app.handle_key(ctrl('q'));
+        app.handle_key(KeyEvent::new(KeyCode::Delete, KeyModifiers::empty()));
+        assert!(app.scheduled.is_empty());
+    }
+
+    #[test]
+    fn test_ctrl_d_picks_the_working_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for dir in ["apps/api", "apps/web", "node_modules/vite"] {
+            std::fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        }
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.flow = Some(ConfigureFlow::new(
+            scan_env_files(temp_dir.path(), &None),
+            HashSet::new(),
+            String::new(),
+        ));
+        app.mode = AppMode::ConfirmExecution;
+
+        app.handle_key(ctrl('d'));
+        assert_eq!(app.mode, AppMode::PickDirectory);
+        for c in "web".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        let Action::RunScript { cwd, .. } = action else {
+            panic!("expected a run");
+        };
+        assert_eq!(cwd, temp_dir.path().join("apps/web"));
+    }
+
+    // --- env snapshot tests ---
+
+    #[test]
+    fn test_ctrl_w_writes_env_snapshot() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let env_path = temp_dir.path().join(".env");
+        std::fs::write(&env_path, "API_URL=http://localhost\n").unwrap();
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.flow = Some(ConfigureFlow::new(
+            scan_env_files(temp_dir.path(), &None),
+            HashSet::from([env_path]),
+            String::new(),
+        ));
+        app.mode = AppMode::ConfirmExecution;
+
+        let action = app.handle_key(ctrl('w'));
+
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        let snapshot = std::fs::read_to_string(temp_dir.path().join(ENV_SNAPSHOT_FILE)).unwrap();
+        assert_eq!(snapshot, "export API_URL='http://localhost'\n");
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_confirmation_screen_works_out_missing_example_keys_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let env_path = temp_dir.path().join(".env");
+        std::fs::write(&env_path, "API_URL=http://localhost\n").unwrap();
+        std::fs::write(temp_dir.path().join(".env.example"), "API_URL=\nPORT=\n").unwrap();
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.flow = Some(ConfigureFlow::new(
+            scan_env_files(temp_dir.path(), &None),
+            HashSet::from([env_path.clone()]),
+            String::new(),
+        ));
+        app.enter_confirm();
+        std::fs::write(&env_path, "API_URL=http://localhost\nPORT=3000\n").unwrap();
+
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        assert_eq!(
+            app.flow.as_ref().unwrap().confirm.missing_keys,
+            vec![(".env.example".to_string(), vec!["PORT".to_string()])]
+        );
+    }
+
+    #[test]
+    fn test_ctrl_x_runs_env_matrix() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in [".env", ".env.node18", ".env.node20"] {
+            std::fs::write(temp_dir.path().join(name), "A=1\n").unwrap();
+        }
+
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "vitest")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.mode = AppMode::ConfirmExecution;
+
+        // A single profile is not a matrix
+        app.flow = Some(ConfigureFlow::new(
+            scan_env_files(temp_dir.path(), &None),
+            [".env", ".env.node18"]
+                .iter()
+                .map(|name| temp_dir.path().join(name))
+                .collect(),
+            String::new(),
+        ));
+        assert!(matches!(app.handle_key(ctrl('x')), Action::Continue));
+        assert!(app.status_message.is_some());
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+
+        app.flow
+            .as_mut()
+            .unwrap()
+            .env
+            .checked
+            .insert(temp_dir.path().join(".env.node20"));
+        match app.handle_key(ctrl('x')) {
+            Action::RunMatrix {
+                script_name,
+                profiles,
+                ..
+            } => {
+                assert_eq!(script_name, "test");
+                let labels: Vec<&str> = profiles.iter().map(|p| p.label.as_str()).collect();
+                assert_eq!(labels, vec![".env.node18", ".env.node20"]);
+            }
+            _ => panic!("expected RunMatrix"),
+        }
+        assert_eq!(app.mode, AppMode::Normal);
+        // Each profile can be replayed from the History tab
+        assert_eq!(app.history.len(), 2);
+    }
+
+    #[test]
+    fn test_ctrl_e_prints_selected_env_files() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.mode = AppMode::ConfirmExecution;
+
+        let action = app.handle_key(ctrl('e'));
+
+        assert!(matches!(action, Action::PrintEnv { ref env_files, .. } if env_files.is_empty()));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_ctrl_p_prints_resolved_command() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        let action = app.handle_key(ctrl('p'));
+        assert!(
+            matches!(action, Action::PrintCommand { ref command } if command == "npm run build")
+        );
+
+        // The confirm step includes the configured args
+        app.mode = AppMode::ConfirmExecution;
+        app.flow = Some(ConfigureFlow {
+            confirm: Confirm {
+                args: "-- --watch".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let action = app.handle_key(ctrl('p'));
+        assert!(
+            matches!(action, Action::PrintCommand { ref command } if command == "npm run build -- --watch")
+        );
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_run_from_root_targets_the_package_from_the_monorepo_root() {
+        let mut app = quick_dial_app().with_config(Config {
+            run_from_root: true,
+            ..Config::default()
+        });
+        app.active_tab = Tab::Packages;
+        app.enter_package_scripts(0);
+
+        let action = app.handle_key(ctrl('p'));
+        assert!(matches!(
+            action,
+            Action::PrintCommand { ref command } if command == "npm run dev --workspace web"
+        ));
+        assert_eq!(
+            app.workspace_target(Path::new("/test/project/apps/web")),
+            Some(WorkspaceTarget {
+                root: PathBuf::from("/test/project"),
+                package: "web".to_string(),
+            })
+        );
+        // Root scripts still run in their own directory
+        assert_eq!(app.workspace_target(Path::new("/test/project")), None);
+    }
+
+    #[test]
+    fn test_justfile_recipes_follow_the_scripts_and_run_with_just() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("justfile"), "fmt:\n    cargo fmt\n").unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        let mut app = app.with_config(Config {
+            task_runners: vec![TaskRunner::Just],
+            ..Config::default()
+        });
+        assert_eq!(app.snapshot().rows, vec!["dev", "# justfile (1)", "fmt"]);
+
+        app.selected_index = 2;
+        assert!(matches!(
+            app.handle_key(ctrl('p')),
+            Action::PrintCommand { ref command } if command == "just fmt"
+        ));
+        let Action::RunTask { runner, task, cwd } = app.handle_key(KeyEvent::from(KeyCode::Enter))
+        else {
+            panic!("expected a task run");
+        };
+        assert_eq!(runner, TaskRunner::Just);
+        assert_eq!(task, "fmt");
+        assert_eq!(cwd, temp_dir.path());
+    }
+
+    #[test]
+    fn test_package_with_its_own_lockfile_runs_with_its_package_manager() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let legacy = temp_dir.path().join("apps/legacy");
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("yarn.lock"), "").unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .with_workspaces(vec![WorkspacePackage {
+                name: "legacy".to_string(),
+                relative_path: "apps/legacy".to_string(),
+                scripts: [("dev".to_string(), "webpack serve".to_string())].into(),
+                description: None,
+                version: None,
+                private: false,
+            }])
+            .build();
+        app.monorepo_root = Some(temp_dir.path().to_path_buf());
+
+        let action = app.handle_key(ctrl('p'));
+        assert!(
+            matches!(action, Action::PrintCommand { ref command } if command == "npm run build")
+        );
+
+        app.active_tab = Tab::Packages;
+        app.enter_package_scripts(0);
+        assert_eq!(app.package_manager_for(&legacy), PackageManager::Yarn);
+        let action = app.handle_key(ctrl('p'));
+        assert!(matches!(action, Action::PrintCommand { ref command } if command == "yarn dev"));
+    }
+
+    // --- tag tests ---
+
+    #[test]
+    fn test_ctrl_g_tags_script_and_hash_query_filters() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+
+        app.move_selection(1);
+        app.handle_key(ctrl('g'));
+        assert_eq!(app.mode, AppMode::Tags);
+        for c in "CI #slow".chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        app.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.script_tags["root:test"], vec!["ci", "slow"]);
+        assert_eq!(app.scripts[1].tags, vec!["ci", "slow"]);
+
+        for c in "#ci".chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert_eq!(app.filtered_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_package_json_tags_are_merged_with_tui_tags() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc"}, "nr": {"tags": {"build": ["ci"]}}}"#,
+        )
+        .unwrap();
+        let mut scripts = vec![script("build", "tsc")];
+        let mut stored = ScriptTags::new();
+        stored.insert(
+            "root:build".to_string(),
+            vec!["ci".to_string(), "release".to_string()],
+        );
+
+        apply_tags(&mut scripts, dir.path(), &stored);
+
+        assert_eq!(scripts[0].tags, vec!["ci", "release"]);
+    }
+
+    #[test]
+    fn test_changed_badge_clears_once_the_script_runs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc -p tsconfig.build.json")])
+            .build();
+        app.config_dir = dir.path().to_path_buf();
+        ran_commands::record_command(&mut app.ran_commands, "root:build", "tsc");
+        apply_changed(&mut app.scripts, &app.ran_commands);
+        assert!(app.scripts[0].changed);
+
+        app.record_run(&app.selected_run().unwrap());
+
+        assert!(!app.scripts[0].changed);
+        let saved = ran_commands::load_ran_commands(dir.path());
+        assert!(!ran_commands::has_changed(
+            &saved,
+            "root:build",
+            "tsc -p tsconfig.build.json"
+        ));
+    }
+
+    // --- help overlay tests ---
+
+    #[test]
+    fn test_question_mark_opens_help_only_with_empty_query() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('?')));
+        assert!(app.show_help);
+        // Keys go to the overlay while it is open
+        app.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        assert_eq!(app.query, "");
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(!app.show_help);
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        app.handle_key(KeyEvent::from(KeyCode::Char('?')));
+        assert!(!app.show_help);
+        assert_eq!(app.query, "b?");
+    }
+
+    #[test]
+    fn test_help_keeps_the_mode_it_was_opened_from() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+        app.mode = AppMode::ConfigureArgs;
+        app.flow = Some(ConfigureFlow::default());
+
+        // `?` is an argument character here; F1 still opens help
+        app.handle_key(KeyEvent::from(KeyCode::Char('?')));
+        assert!(!app.show_help);
+        app.handle_key(KeyEvent::from(KeyCode::F(1)));
+        assert!(app.show_help);
+        assert_eq!(app.help_section(), crate::ui::help_overlay::CONFIGURE_ARGS);
+
+        app.handle_key(KeyEvent::from(KeyCode::F(1)));
+        assert!(!app.show_help);
+        assert_eq!(app.mode, AppMode::ConfigureArgs);
+    }
+
+    // --- mouse tests ---
+
+    fn mouse(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 5,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_click_selects_row_and_double_click_runs() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+        app.list_area = Rect::new(0, 4, 40, 10);
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 5);
+        let now = Instant::now();
+
+        assert!(matches!(app.handle_mouse(click, now), Action::Continue));
+        assert_eq!(app.selected_index, 1);
+
+        // Too slow for a double-click
+        let later = now + DOUBLE_CLICK_INTERVAL * 2;
+        assert!(matches!(app.handle_mouse(click, later), Action::Continue));
+
+        let action = app.handle_mouse(click, later + Duration::from_millis(100));
+        assert!(
+            matches!(action, Action::RunScript { ref script_name, .. } if script_name == "test")
+        );
+    }
+
+    #[test]
+    fn test_click_outside_list_is_ignored() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+        app.list_area = Rect::new(0, 4, 40, 10);
+
+        app.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Left), 2),
+            Instant::now(),
+        );
+        app.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Left), 9),
+            Instant::now(),
+        );
+
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_wheel_moves_selection_without_wrapping() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+        let now = Instant::now();
+
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, 0), now);
+        assert_eq!(app.selected_index, 0);
+        app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0), now);
+        app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0), now);
+        assert_eq!(app.selected_index, 1);
+    }
+
+    // --- picker tests ---
+
+    #[test]
+    fn test_picker_enter_prints_script_instead_of_running() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build()
+            .with_picker();
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        app.handle_key(KeyEvent::from(KeyCode::Char('e')));
+        let action = app.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        assert!(matches!(action, Action::PrintScript { ref script_name } if script_name == "test"));
+        assert!(app.recents.is_empty());
+    }
+
+    #[test]
+    fn test_picker_ignores_tui_shortcuts() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build()
+            .with_picker();
+
+        app.handle_key(KeyEvent::from(KeyCode::Tab));
+        app.handle_key(ctrl('a'));
+        app.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.queue.is_empty());
+        assert_eq!(app.query, " ");
+        assert!(matches!(
+            app.handle_key(KeyEvent::from(KeyCode::Esc)),
+            Action::Quit
+        ));
+    }
+
+    // --- history tests ---
+
+    fn history_run(key: &str, args: &str, env_files: &[&str]) -> QueuedRun {
+        QueuedRun {
+            key: key.to_string(),
+            script_name: key.rsplit(':').next().unwrap().to_string(),
+            cwd: PathBuf::from("/test/project"),
+            env_files: env_files.iter().map(PathBuf::from).collect(),
+            args: args.to_string(),
+            env: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_running_a_script_records_history() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+        assert!(!app.available_tabs().contains(&Tab::History));
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.history[0].key, "root:build");
+        assert!(app.available_tabs().contains(&Tab::History));
+    }
+
+    #[test]
+    fn test_history_search_and_replay() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("test", "vitest")])
+            .build();
+        for run in [
+            history_run("root:test", "--coverage", &["/test/project/.env.ci"]),
+            history_run("root:test", "--watch", &[]),
+            history_run("root:lint", "", &[]),
+        ] {
+            history::record_run(&mut app.history, &run);
+        }
+        app.update_history_filtered();
+        app.active_tab = Tab::History;
+
+        // Spaces separate search terms instead of toggling favorites
+        for c in "cov ci".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        assert!(app.favorites.is_empty());
+        assert_eq!(app.history_filtered_indices.len(), 1);
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        match action {
+            Action::RunScript {
+                script_name,
+                env_files,
+                args,
+                ..
+            } => {
+                assert_eq!(script_name, "test");
+                assert_eq!(args, "--coverage");
+                assert_eq!(env_files, vec![PathBuf::from("/test/project/.env.ci")]);
+            }
+            _ => panic!("expected RunScript"),
+        }
+        // The replayed run moves to the top
+        assert_eq!(app.history[0].args, "--coverage");
+        assert_eq!(app.history[0].count, 2);
+    }
+
+    #[test]
+    fn test_history_tab_key_prefills_configure_flow() {
+        let mut app = TestAppBuilder::new().build();
+        history::record_run(
+            &mut app.history,
+            &history_run("root:dev", "--host", &["/test/project/.env"]),
+        );
+        app.update_history_filtered();
+        app.active_tab = Tab::History;
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()));
+
+        assert_eq!(app.mode, AppMode::ConfigureEnv);
+        let flow = app.flow.as_ref().unwrap();
+        assert_eq!(flow.confirm.args, "--host");
+        assert!(
+            flow.env
+                .checked
+                .contains(&PathBuf::from("/test/project/.env"))
+        );
+    }
+
+    // --- script location tests ---
+
+    #[test]
+    fn test_ctrl_l_and_ctrl_o_locate_script() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            "{\n  \"scripts\": {\n    \"build\": \"tsc\"\n  }\n}\n",
+        )
+        .unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+
+        app.handle_key(ctrl('l'));
+        assert!(
+            app.status_message
+                .as_deref()
+                .unwrap()
+                .ends_with("package.json:3")
+        );
+
+        let action = app.handle_key(ctrl('o'));
+        assert!(matches!(action, Action::OpenEditor { line: 3, .. }));
+    }
+
+    #[test]
+    fn test_f5_reloads_package_json_keeping_the_selection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("package.json");
+        std::fs::write(
+            &manifest,
+            r#"{"scripts": {"build": "tsc", "test": "vitest"}}"#,
+        )
+        .unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.config_dir = temp_dir.path().to_path_buf();
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+
+        std::fs::write(
+            &manifest,
+            r#"{"scripts": {"lint": "eslint .", "build": "tsc -b", "test": "vitest"}}"#,
+        )
+        .unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::F(5), KeyModifiers::empty()));
+
+        let snapshot = app.snapshot();
+        assert_eq!(snapshot.rows.len(), 3);
+        assert!(snapshot.rows[snapshot.selected].contains("test"));
+        assert_eq!(
+            snapshot.status.as_deref(),
+            Some("Reloaded package.json (3 scripts)")
+        );
+        let build = app.scripts.iter().find(|s| s.name == "build").unwrap();
+        assert_eq!(build.command, "tsc -b");
+    }
+
+    // --- output pane tests ---
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_in_pane_streams_output_until_exit() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("run.sh"),
+            "echo \"running $1\"; echo oops >&2; exit 2\n",
+        )
+        .unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.config_dir = temp_dir.path().to_path_buf();
+        app.config.run_in_pane = true;
+        app.config
+            .run_templates
+            .insert("npm".to_string(), "sh run.sh {script} {args}".to_string());
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.mode, AppMode::Output);
+
+        tick_until(&mut app, |app| !app.output_running());
+
+        let session = attached_job(&app);
+        assert_eq!(session.finished.map(|(code, _)| code), Some(2));
+        // The History tab shows how it ended
+        assert_eq!(app.history[0].exit_code, Some(2));
+        assert!(app.history[0].duration_ms.is_some());
+        // ...and the list how long it took
+        assert!(app.scripts[0].last_duration.is_some());
+        let texts: Vec<_> = session.lines.iter().map(|l| l.text.as_str()).collect();
+        assert!(texts.contains(&"running build"));
+        assert!(
+            session
+                .lines
+                .iter()
+                .any(|l| l.is_stderr && l.text == "oops")
+        );
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.output.is_none());
+    }
+
+    fn attached_job(app: &App) -> &Job {
+        app.jobs.get(app.output.as_ref().unwrap().job).unwrap()
+    }
+
+    #[cfg(unix)]
+    fn pane_app(temp_dir: &std::path::Path, script_body: &str) -> App {
+        std::fs::write(temp_dir.join("run.sh"), script_body).unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.to_path_buf();
+        app.config_dir = temp_dir.to_path_buf();
+        app.config.run_in_pane = true;
+        app.config
+            .run_templates
+            .insert("npm".to_string(), "sh run.sh {script} {args}".to_string());
+        app
+    }
+
+    #[cfg(unix)]
+    fn tick_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        while !done(app) && Instant::now() < deadline {
+            app.tick(Instant::now());
+        }
+    }
+
+    #[cfg(all(unix, feature = "watch"))]
+    #[test]
+    fn test_watch_mode_runs_again_on_change_until_stopped() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        let mut app = pane_app(temp_dir.path(), "echo run >> runs.log\n");
+        // Watch mode needs the TUI, so it uses the pane either way
+        app.config.run_in_pane = false;
+        app.mode = AppMode::ConfirmExecution;
+        app.flow = Some(ConfigureFlow::default());
+
+        app.handle_key(ctrl('l'));
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.mode, AppMode::Output);
+        tick_until(&mut app, |app| {
+            app.watch.as_ref().is_some_and(|watch| watch.last.is_some())
+        });
+        assert!(
+            app.watch_label(Instant::now())
+                .unwrap()
+                .starts_with("watching src/** (dev ")
+        );
+
+        let runs = |dir: &std::path::Path| {
+            std::fs::read_to_string(dir.join("runs.log")).map_or(0, |log| log.lines().count())
+        };
+        std::fs::write(temp_dir.path().join("src/main.ts"), "edited").unwrap();
+        tick_until(&mut app, |_| runs(temp_dir.path()) == 2);
+        assert_eq!(runs(temp_dir.path()), 2);
+
+        // Stopping the job from the Jobs tab ends the watch
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        app.active_tab = Tab::Jobs;
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty()));
+        assert!(app.watch.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_n_in_pane_runs_suggested_next_after_exit() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "echo \"running $1\"\n");
+        let lint = QueuedRun {
+            key: "root:lint".to_string(),
+            script_name: "lint".to_string(),
+            cwd: temp_dir.path().to_path_buf(),
+            env_files: vec![],
+            args: String::new(),
+            env: Vec::new(),
+        };
+        history::record_run(&mut app.history, &lint);
+        app.next_runs
+            .entry("root:dev".to_string())
+            .or_default()
+            .insert("root:lint".to_string(), 2);
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        let first = app.output.as_ref().unwrap().job;
+        tick_until(&mut app, |app| !app.output_running());
+        assert_eq!(app.suggested_after_output().unwrap().key, "root:lint");
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert_eq!(app.mode, AppMode::Output);
+        assert_ne!(app.output.as_ref().unwrap().job, first);
+        assert_eq!(attached_job(&app).spec.script_name, "lint");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ctrl_c_in_pane_stops_script_without_quitting() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(
+            temp_dir.path(),
+            "trap 'echo stopped; exit 130' INT\necho ready\nwhile true; do sleep 0.05; done\n",
+        );
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        tick_until(&mut app, |app| {
+            attached_job(app).lines.iter().any(|l| l.text == "ready")
+        });
+
+        let action = app.handle_key(ctrl('c'));
+        assert!(matches!(action, Action::Continue));
+        assert!(app.status_message.is_some());
+        tick_until(&mut app, |app| !app.output_running());
+
+        assert_eq!(app.mode, AppMode::Output);
+        let session = attached_job(&app);
+        assert_eq!(session.finished.map(|(code, _)| code), Some(130));
+        assert!(session.lines.iter().any(|l| l.text == "stopped"));
+
+        // With the script gone, Ctrl+C quits as everywhere else
+        assert!(matches!(app.handle_key(ctrl('c')), Action::Quit));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_r_restarts_script_in_pane() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "echo run >> runs.log; echo done\n");
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        tick_until(&mut app, |app| !app.output_running());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::empty()));
+        tick_until(&mut app, |app| !app.output_running());
+
+        let session = attached_job(&app);
+        assert_eq!(session.finished.map(|(code, _)| code), Some(0));
+        assert_eq!(session.lines.len(), 1);
+        let log = std::fs::read_to_string(temp_dir.path().join("runs.log")).unwrap();
+        assert_eq!(log.lines().count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pane_run_asks_for_missing_required_env() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "echo \"token=$NR_TEST_TOKEN\"\n");
+        app.config.required_env.insert(
+            "dev".to_string(),
+            vec!["NR_TEST_TOKEN".to_string(), "NR_TEST_REGION".to_string()],
+        );
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::RequiredEnv);
+        assert!(app.jobs.is_empty());
+
+        // An empty value isn't accepted
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.env_prompts[0].missing[0], "NR_TEST_TOKEN");
+
+        for c in "abc".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.env_prompts[0].missing, vec!["NR_TEST_REGION"]);
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.mode, AppMode::Output);
+        assert!(app.env_prompts.is_empty());
+        tick_until(&mut app, |app| !app.output_running());
+        assert_eq!(attached_job(&app).lines[0].text, "token=abc");
+    }
+
+    #[test]
+    fn test_esc_cancels_run_missing_required_env() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.config
+            .required_env
+            .insert("*".to_string(), vec!["NR_TEST_TOKEN".to_string()]);
+
+        app.handle_key(ctrl('b'));
+        assert_eq!(app.mode, AppMode::RequiredEnv);
+        assert!(!app.opens_help(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE)));
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.jobs.is_empty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Cancelled dev: NR_TEST_TOKEN not set")
+        );
+    }
 
-                // Build env file paths in merge order (root → package, so package overrides root)
-                let env_file_paths: Vec<PathBuf> = if let Some(ref env_list) = self.env_files_list {
-                    env_list
-                        .all_files_merge_order()
-                        .filter(|f| self.env_selected_files.contains(&f.path))
-                        .map(|f| f.path.clone())
-                        .collect()
-                } else {
-                    vec![]
-                };
+    #[test]
+    fn test_dangerous_script_runs_once_its_name_is_typed() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("db:reset", "prisma migrate reset")])
+            .build();
+        app.config.dangerous_scripts = vec!["db:*".to_string()];
 
-                // Reset mode
-                self.mode = AppMode::Normal;
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::DangerousScript);
+        assert!(!app.opens_help(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE)));
 
-                Action::RunScript {
-                    script_name,
-                    cwd,
-                    env_files: env_file_paths,
-                    args: self.execution_config.args.clone(),
-                }
-            }
-            _ => Action::Continue,
+        for c in "db:rese".chars() {
+            press(&mut app, KeyCode::Char(c));
         }
-    }
+        assert!(matches!(
+            app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::Continue
+        ));
+        assert_eq!(app.mode, AppMode::DangerousScript);
 
-    fn get_current_script_name(&self) -> String {
-        match self.active_tab {
-            Tab::Scripts => {
-                if let Some(&script_idx) = self.filtered_indices.get(self.selected_index) {
-                    self.scripts[script_idx].name.clone()
-                } else {
-                    String::new()
-                }
-            }
-            Tab::Packages => match self.package_mode {
-                PackageMode::SelectingScript { .. } => {
-                    if let Some(&script_idx) = self
-                        .pkg_script_filtered_indices
-                        .get(self.pkg_script_selected_index)
-                    {
-                        self.pkg_script_sortable[script_idx].name.clone()
-                    } else {
-                        String::new()
-                    }
-                }
-                _ => String::new(),
-            },
-        }
+        press(&mut app, KeyCode::Char('t'));
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(
+            matches!(action, Action::RunScript { ref script_name, .. } if script_name == "db:reset")
+        );
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.danger_prompt.is_none());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::store::args_history::ArgsHistory;
-    use crate::store::script_configs::ScriptConfigs;
+    #[test]
+    fn test_esc_on_dangerous_script_of_configure_flow_goes_back_to_confirmation() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("deploy:prod", "./deploy.sh")])
+            .build();
+        app.config.dangerous_scripts = vec!["deploy:prod".to_string()];
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::DangerousScript);
+        assert_eq!(
+            app.danger_prompt.as_ref().map(|p| &p.run),
+            Some(&DangerousRun::Configured(ConfiguredStart::Run))
+        );
 
-    // Test helper to create SortableScript
-    fn script(name: &str, command: &str) -> SortableScript {
-        SortableScript {
-            key: format!("root:{}", name),
-            name: name.to_string(),
-            command: command.to_string(),
-        }
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        assert!(app.flow.is_some());
+        assert_eq!(app.status_message.as_deref(), Some("Cancelled deploy:prod"));
     }
 
-    // Test builder for App
-    struct TestAppBuilder {
-        scripts: Vec<SortableScript>,
-        workspace_packages: Vec<WorkspacePackage>,
-        favorites: HashSet<String>,
-        recents: Vec<RecentEntry>,
-        visible_height: usize,
-        has_workspaces: bool,
+    #[test]
+    fn test_confirmation_screen_loads_the_hooks_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        std::fs::write(
+            &package_json,
+            r#"{"nr": {"hooks": {"dev": {"before": "docker compose up -d"}}}}"#,
+        )
+        .unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build();
+        app.nearest_pkg = temp_dir.path().to_path_buf();
+        app.config_dir = temp_dir.path().to_path_buf();
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        std::fs::remove_file(&package_json).unwrap();
+
+        let hooks = &app.flow.as_ref().unwrap().confirm.hooks;
+        assert_eq!(hooks.before, vec!["docker compose up -d"]);
+        assert_eq!(hooks_note(hooks, false).is_some(), !PIPED_HOOKS);
+        assert!(hooks_note(&ScriptHooks::default(), true).is_some());
     }
 
-    impl TestAppBuilder {
-        fn new() -> Self {
-            Self {
-                scripts: vec![],
-                workspace_packages: vec![],
-                favorites: HashSet::new(),
-                recents: vec![],
-                visible_height: 20,
-                has_workspaces: false,
-            }
-        }
+    #[cfg(unix)]
+    #[test]
+    fn test_dangerous_script_asks_before_confirmation_screen_background_run() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "true\n");
+        app.config.dangerous_scripts = vec!["dev".to_string()];
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::ConfirmExecution);
+        app.handle_key(ctrl('x'));
+        assert_eq!(
+            app.danger_prompt.as_ref().map(|p| &p.run),
+            Some(&DangerousRun::Configured(ConfiguredStart::Matrix))
+        );
+        press(&mut app, KeyCode::Esc);
 
-        fn with_scripts(mut self, scripts: Vec<SortableScript>) -> Self {
-            self.scripts = scripts;
-            self
+        app.handle_key(ctrl('b'));
+        assert_eq!(app.mode, AppMode::DangerousScript);
+        assert!(app.jobs.is_empty());
+        for c in "dev".chars() {
+            press(&mut app, KeyCode::Char(c));
         }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.flow.is_none());
+        assert_eq!(app.jobs.len(), 1);
+    }
 
-        fn with_favorite(mut self, key: &str) -> Self {
-            self.favorites.insert(key.to_string());
-            self
-        }
+    #[test]
+    fn test_due_scheduled_dangerous_script_waits_for_its_name() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("db:reset", "prisma migrate reset")])
+            .build();
+        app.config.dangerous_scripts = vec!["db:reset".to_string()];
+        let now = Instant::now();
+        app.scheduled.push(ScheduledRun {
+            run: QueuedRun {
+                key: "root:db:reset".to_string(),
+                script_name: "db:reset".to_string(),
+                cwd: PathBuf::from("/test/project"),
+                env_files: vec![],
+                args: String::new(),
+                env: Vec::new(),
+            },
+            due: now,
+        });
 
-        fn with_workspaces(mut self, packages: Vec<WorkspacePackage>) -> Self {
-            self.has_workspaces = !packages.is_empty();
-            self.workspace_packages = packages;
-            self
+        assert!(matches!(app.tick(now), Action::Continue));
+        assert!(app.scheduled.is_empty());
+        assert_eq!(app.mode, AppMode::DangerousScript);
+
+        for c in "db:reset".chars() {
+            press(&mut app, KeyCode::Char(c));
         }
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(
+            matches!(action, Action::RunScript { ref script_name, .. } if script_name == "db:reset")
+        );
+    }
 
-        fn build(self) -> App {
-            let filtered_indices = sort_scripts(&self.scripts, &self.favorites, &self.recents, "");
-            let pkg_filtered_indices: Vec<usize> = (0..self.workspace_packages.len()).collect();
+    // --- jobs tab tests ---
 
-            App {
-                active_tab: Tab::Scripts,
-                package_mode: PackageMode::SelectingPackage,
-                has_workspaces: self.has_workspaces,
-                scripts: self.scripts,
-                workspace_packages: self.workspace_packages,
-                nearest_pkg: PathBuf::from("/test/project"),
-                monorepo_root: None,
-                favorites: self.favorites,
-                recents: self.recents,
-                project_name: "test-project".to_string(),
-                project_path: "/test/project".to_string(),
-                package_manager_name: "npm".to_string(),
-                visible_height: self.visible_height,
-                query: String::new(),
-                selected_index: 0,
-                scroll_offset: 0,
-                filtered_indices,
-                pkg_query: String::new(),
-                pkg_selected_index: 0,
-                pkg_scroll_offset: 0,
-                pkg_filtered_indices,
-                pkg_script_query: String::new(),
-                pkg_script_selected_index: 0,
-                pkg_script_scroll_offset: 0,
-                pkg_script_filtered_indices: Vec::new(),
-                pkg_script_sortable: Vec::new(),
+    #[cfg(unix)]
+    #[test]
+    fn test_second_instance_of_running_job_asks_first() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "sleep 5\n");
+        app.config.run_in_pane = false;
+
+        app.handle_key(ctrl('b'));
+        app.handle_key(ctrl('b'));
+        assert_eq!(app.mode, AppMode::DuplicateJob);
+        assert_eq!(app.jobs.len(), 1);
+
+        // Esc leaves the running job alone
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.jobs.len(), 1);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Not started: dev is already running")
+        );
 
-                // NEW: Config flow fields (test defaults)
-                mode: AppMode::Normal,
-                execution_config: ExecutionConfig::default(),
-                script_configs: ScriptConfigs::new(),
-                global_env_config: crate::store::global_env::GlobalEnvConfig::default(),
-                args_history: ArgsHistory::new(),
-                config_dir: PathBuf::from("/test/.config/nr"),
-                package_manager: crate::core::package_manager::PackageManager::Npm,
+        // Enter jumps to it
+        app.handle_key(ctrl('b'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::Output);
+        assert_eq!(app.output.as_ref().map(|o| o.job), Some(0));
+        press(&mut app, KeyCode::Esc);
+
+        // `s` starts another anyway
+        app.handle_key(ctrl('b'));
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.jobs.len(), 2);
+
+        // `prevent` shows the running job without asking
+        app.config.duplicate_jobs = DuplicateJobs::Prevent;
+        app.handle_key(ctrl('b'));
+        assert_eq!(app.mode, AppMode::Output);
+        assert_eq!(app.jobs.len(), 2);
+    }
 
-                // NEW: Env selection UI state (test defaults)
-                env_files_list: None,
-                env_selected_index: 0,
-                env_scroll_offset: 0,
-                env_selected_files: HashSet::new(),
+    #[cfg(unix)]
+    #[test]
+    fn test_background_job_listed_in_jobs_tab() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = pane_app(temp_dir.path(), "echo started; sleep 0.2\n");
+        app.config.run_in_pane = false;
+
+        app.handle_key(ctrl('b'));
+        assert_eq!(app.jobs.len(), 1);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.available_tabs(),
+            vec![Tab::Scripts, Tab::History, Tab::Jobs]
+        );
 
-                // NEW: Args input UI state (test defaults)
-                args_input: String::new(),
-                args_cursor_pos: 0,
-                args_history_index: None,
-            }
-        }
-    }
+        app.active_tab = Tab::History;
+        app.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        assert_eq!(app.active_tab, Tab::Jobs);
+
+        // Running jobs can't be removed; attaching and leaving keeps them running
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::empty()));
+        assert_eq!(app.jobs.len(), 1);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::Output);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.jobs_running());
+
+        tick_until(&mut app, |app| !app.jobs_running());
+        assert!(
+            app.status_message
+                .as_deref()
+                .unwrap()
+                .contains("dev exited with 0")
+        );
+        assert_eq!(app.jobs.iter().next().unwrap().lines[0].text, "started");
 
-    // --- move_selection tests ---
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::empty()));
+        assert!(app.jobs.is_empty());
+        assert_eq!(app.active_tab, Tab::Scripts);
+    }
 
     #[test]
-    fn test_move_selection_down_increments_index() {
+    fn test_alt_enter_runs_in_terminal_when_pane_is_default() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![
-                script("test", "echo test"),
-                script("build", "echo build"),
-                script("lint", "echo lint"),
-            ])
+            .with_scripts(vec![script("build", "tsc")])
             .build();
+        app.config.run_in_pane = true;
 
-        assert_eq!(app.selected_index, 0);
-        app.move_selection(1);
-        assert_eq!(app.selected_index, 1);
-        app.move_selection(1);
-        assert_eq!(app.selected_index, 2);
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT));
+
+        assert!(
+            matches!(action, Action::RunScript { ref script_name, .. } if script_name == "build")
+        );
+        assert!(app.output.is_none());
     }
 
+    // --- workspace index tests ---
+
     #[test]
-    fn test_move_selection_up_decrements_index() {
+    fn test_workspace_refresh_keeps_open_package() {
+        let pkg = |name: &str, path: &str| WorkspacePackage {
+            name: name.to_string(),
+            relative_path: path.to_string(),
+            scripts: IndexMap::from([("dev".to_string(), "vite".to_string())]),
+            description: None,
+            version: None,
+            private: false,
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![
-                script("test", "echo test"),
-                script("build", "echo build"),
-                script("lint", "echo lint"),
+            .with_scripts(vec![script("dev", "vite")])
+            .with_workspaces(vec![
+                pkg("@mono/a", "packages/a"),
+                pkg("@mono/b", "packages/b"),
             ])
-            .build();
+            .build()
+            .with_workspace_refresh(rx);
+        app.active_tab = Tab::Packages;
+        app.enter_package_scripts(1);
+
+        tx.send(ScanUpdate::Done(vec![
+            pkg("@mono/new", "packages/0-new"),
+            pkg("@mono/a", "packages/a"),
+            pkg("@mono/b", "packages/b"),
+        ]))
+        .unwrap();
+        app.tick(Instant::now());
+
+        assert_eq!(app.workspace_packages.len(), 3);
+        assert_eq!(
+            app.package_mode,
+            PackageMode::SelectingScript { package_index: 2 }
+        );
+    }
 
-        app.selected_index = 2;
-        app.move_selection(-1);
-        assert_eq!(app.selected_index, 1);
-        app.move_selection(-1);
-        assert_eq!(app.selected_index, 0);
+    #[test]
+    fn test_first_workspace_scan_streams_packages_in() {
+        let pkg = |name: &str| WorkspacePackage {
+            name: name.to_string(),
+            relative_path: format!("packages/{}", name),
+            scripts: IndexMap::new(),
+            description: None,
+            version: None,
+            private: false,
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("dev", "vite")])
+            .build()
+            .with_workspace_scan(rx);
+        assert!(app.available_tabs().contains(&Tab::Packages));
+
+        tx.send(ScanUpdate::Found(pkg("b"))).unwrap();
+        tx.send(ScanUpdate::Found(pkg("a"))).unwrap();
+        app.tick(Instant::now());
+        assert_eq!(app.workspace_packages.len(), 2);
+        assert!(app.workspace_scan_started.is_some());
+
+        tx.send(ScanUpdate::Done(vec![pkg("a"), pkg("b")])).unwrap();
+        app.tick(Instant::now());
+        assert_eq!(app.workspace_packages[0].name, "a");
+        assert!(app.workspace_scan_started.is_none());
     }
 
     #[test]
-    fn test_move_selection_wraps_at_bottom() {
+    fn test_packages_tab_goes_away_when_the_first_scan_finds_nothing() {
+        let (tx, rx) = std::sync::mpsc::channel();
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![
-                script("test", "echo test"),
-                script("build", "echo build"),
-            ])
-            .build();
+            .with_scripts(vec![script("dev", "vite")])
+            .build()
+            .with_workspace_scan(rx);
+        app.active_tab = Tab::Packages;
 
-        app.selected_index = 1; // last item
-        app.move_selection(1); // should wrap to 0
-        assert_eq!(app.selected_index, 0);
+        tx.send(ScanUpdate::Done(Vec::new())).unwrap();
+        app.tick(Instant::now());
+
+        assert_eq!(app.active_tab, Tab::Scripts);
+        assert!(!app.available_tabs().contains(&Tab::Packages));
     }
 
+    // --- script preview tests ---
+
     #[test]
-    fn test_move_selection_wraps_at_top() {
+    fn test_preview_shows_full_command_and_pre_post_scripts() {
+        let long_command = format!("tsc -p tsconfig.build.json && {}", "x".repeat(120));
         let mut app = TestAppBuilder::new()
             .with_scripts(vec![
-                script("test", "echo test"),
-                script("build", "echo build"),
+                script("build", &long_command),
+                script("prebuild", "rimraf dist"),
+                script("postbuild", "size-limit"),
             ])
             .build();
+        app.selected_index = app
+            .filtered_indices
+            .iter()
+            .position(|&i| app.scripts[i].name == "build")
+            .unwrap();
 
-        assert_eq!(app.selected_index, 0);
-        app.move_selection(-1); // should wrap to last
-        assert_eq!(app.selected_index, 1);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("↳ before: prebuild  rimraf dist"));
+        assert!(screen.contains("↳ after: postbuild  size-limit"));
+        assert!(screen.contains(&"x".repeat(40)));
     }
 
     #[test]
-    fn test_move_selection_handles_empty_list() {
-        let mut app = TestAppBuilder::new().build();
-        assert_eq!(app.selected_index, 0);
-        app.move_selection(1);
-        assert_eq!(app.selected_index, 0); // no change
+    fn test_ascii_config_renders_without_unicode_symbols() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .with_favorite("root:build")
+            .build()
+            .with_config(Config {
+                ascii: true,
+                ..Config::default()
+            });
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains(">* build"));
+        assert!(!screen.contains('★'));
+        assert!(!screen.contains('⏎'));
     }
 
-    // --- toggle_fav tests ---
+    #[test]
+    fn test_newer_release_is_mentioned_once_the_check_reports_it() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build()
+            .with_update_check(rx);
+        app.tick(Instant::now());
+        assert_eq!(app.update_available, None);
+
+        tx.send("0.3.0".to_string()).unwrap();
+        app.tick(Instant::now());
+        assert_eq!(app.update_available.as_deref(), Some("0.3.0"));
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(140, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("v0.3.0 available: nr self-update"));
+    }
 
     #[test]
-    fn test_toggle_fav_adds_to_favorites() {
+    fn test_config_issues_show_in_a_banner() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .build();
+            .with_scripts(vec![script("build", "tsc")])
+            .build()
+            .with_config_issues(vec![
+                ConfigIssue {
+                    file: PathBuf::from("/home/me/.config/nr/config.json"),
+                    key: "run_in_pane".to_string(),
+                    message: "invalid type: string \"yes\", expected a boolean".to_string(),
+                },
+                ConfigIssue {
+                    file: PathBuf::from("/home/me/.config/nr/config.json"),
+                    key: "colour".to_string(),
+                    message: "unknown key".to_string(),
+                },
+            ]);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains(
+            "config.json run_in_pane: invalid type: string \"yes\", expected a boolean (+1 more)"
+        ));
+        assert!(screen.contains("build"));
+    }
 
-        let key = "root:test";
-        assert!(!app.favorites.contains(key));
+    // --- startup config tests ---
+
+    #[test]
+    fn test_with_config_starts_on_packages_with_path_query() {
+        let packages = ["apps/web", "apps/docs", "packages/ui"]
+            .iter()
+            .map(|path| WorkspacePackage {
+                name: format!("@mono/{}", path.rsplit('/').next().unwrap()),
+                relative_path: path.to_string(),
+                scripts: IndexMap::new(),
+                description: None,
+                version: None,
+                private: false,
+            })
+            .collect();
+        let mut config = Config::default();
+        config.startup.tab = Some(StartupTab::Packages);
+        config.startup.query = Some("apps/".to_string());
+
+        let app = TestAppBuilder::new()
+            .with_workspaces(packages)
+            .build()
+            .with_config(config);
+
+        assert_eq!(app.active_tab, Tab::Packages);
+        assert_eq!(app.pkg_query, "apps/");
+        let mut paths: Vec<&str> = app
+            .pkg_filtered_indices
+            .iter()
+            .map(|&i| app.workspace_packages[i].relative_path.as_str())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["apps/docs", "apps/web"]);
+    }
+
+    #[test]
+    fn test_package_search_prefix_filters() {
+        let packages = ["apps/web", "apps/docs", "packages/web-ui"]
+            .iter()
+            .map(|path| WorkspacePackage {
+                name: path.rsplit('/').next().unwrap().to_string(),
+                relative_path: path.to_string(),
+                scripts: IndexMap::new(),
+                description: None,
+                version: None,
+                private: false,
+            })
+            .collect();
+        let mut app = TestAppBuilder::new().with_workspaces(packages).build();
+        let names = |app: &App| -> Vec<String> {
+            let mut names: Vec<String> = app
+                .pkg_filtered_indices
+                .iter()
+                .map(|&i| app.workspace_packages[i].name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        app.pkg_query = "path:apps/ web".to_string();
+        app.update_pkg_filtered();
+        assert_eq!(names(&app), vec!["web"]);
+
+        app.pkg_query = "path:packages".to_string();
+        app.update_pkg_filtered();
+        assert_eq!(names(&app), vec!["web-ui"]);
+
+        app.pkg_query = "pkg:docs".to_string();
+        app.update_pkg_filtered();
+        assert_eq!(names(&app), vec!["docs"]);
+    }
+
+    #[test]
+    fn test_with_config_ignores_packages_tab_without_workspaces() {
+        let mut config = Config::default();
+        config.startup.tab = Some(StartupTab::Packages);
+        config.startup.query = Some("bui".to_string());
+
+        let app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .build()
+            .with_config(config);
+
+        assert_eq!(app.active_tab, Tab::Scripts);
+        assert_eq!(app.query, "bui");
+        assert_eq!(app.filtered_indices, vec![0]);
+    }
+
+    // --- keymap tests ---
 
-        app.toggle_fav();
-        assert!(app.favorites.contains(key));
+    fn remapped(json: &str) -> Keymap {
+        Keymap::new(&serde_json::from_str(json).unwrap())
     }
 
     #[test]
-    fn test_toggle_fav_removes_from_favorites() {
+    fn test_remapped_key_runs_action() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .with_favorite("root:test")
+            .with_scripts(vec![script("build", "tsc")])
             .build();
+        app.keymap = remapped(r#"{"toggle_favorite": "ctrl+f"}"#);
 
-        assert!(app.favorites.contains("root:test"));
-        app.toggle_fav();
-        assert!(!app.favorites.contains("root:test"));
+        app.handle_key(ctrl('f'));
+
+        assert!(app.favorites.contains("root:build"));
+        assert!(app.query.is_empty());
     }
 
     #[test]
-    fn test_toggle_fav_updates_filtered_indices() {
+    fn test_remapped_action_frees_default_key_for_search() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("aaa", "echo aaa"), script("zzz", "echo zzz")])
+            .with_scripts(vec![script("build", "tsc")])
             .build();
+        app.keymap = remapped(r#"{"toggle_favorite": "ctrl+f", "quit": "ctrl+d"}"#);
 
-        // Initially alphabetical order: aaa, zzz
-        assert_eq!(app.filtered_indices, vec![0, 1]);
-
-        // Toggle favorite on zzz (index 1)
-        app.selected_index = 1;
-        app.toggle_fav();
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert!(!app.favorites.contains("root:build"));
+        assert_eq!(app.query, " ");
 
-        // Now favorites come first: zzz, aaa
-        assert_eq!(app.filtered_indices, vec![1, 0]);
+        assert!(matches!(app.handle_key(ctrl('d')), Action::Quit));
     }
 
-    // --- switch_tab tests ---
+    // --- quick dial tests ---
 
-    #[test]
-    fn test_switch_tab_changes_to_packages() {
-        let pkg = WorkspacePackage {
-            name: "pkg1".to_string(),
-            relative_path: "packages/pkg1".to_string(),
-            scripts: IndexMap::new(),
+    fn quick_dial_app() -> App {
+        let pkg = |name: &str, scripts: &[&str]| WorkspacePackage {
+            name: name.to_string(),
+            relative_path: format!("apps/{}", name),
+            scripts: scripts
+                .iter()
+                .map(|s| (s.to_string(), "vite".to_string()))
+                .collect(),
+            description: None,
+            version: None,
+            private: false,
         };
-
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .with_workspaces(vec![pkg])
+            .with_scripts(vec![script("lint", "eslint"), script("test", "vitest")])
+            .with_workspaces(vec![
+                pkg("web", &["build", "dev"]),
+                pkg("docs", &["dev"]),
+                pkg("api", &["dev", "start"]),
+            ])
+            .with_favorite("root:test")
+            .with_favorite("web:dev")
+            .with_favorite("api:dev")
+            .with_favorite("api:start")
             .build();
-
-        assert_eq!(app.active_tab, Tab::Scripts);
-        app.switch_tab(1);
-        assert_eq!(app.active_tab, Tab::Packages);
+        app.monorepo_root = Some(PathBuf::from("/test/project"));
+        app
     }
 
     #[test]
-    fn test_switch_tab_changes_to_scripts() {
-        let pkg = WorkspacePackage {
-            name: "pkg1".to_string(),
-            relative_path: "packages/pkg1".to_string(),
-            scripts: IndexMap::new(),
-        };
+    fn test_quick_dial_lists_top_favorite_per_package() {
+        let mut app = quick_dial_app();
 
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .with_workspaces(vec![pkg])
-            .build();
+        app.handle_key(ctrl('d'));
 
-        app.active_tab = Tab::Packages;
-        app.switch_tab(-1);
-        assert_eq!(app.active_tab, Tab::Scripts);
+        assert_eq!(app.mode, AppMode::QuickDial);
+        let keys: Vec<&str> = app.quick_dial.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["root:test", "web:dev", "api:dev"]);
+        assert_eq!(
+            app.quick_dial[1].cwd,
+            PathBuf::from("/test/project/apps/web")
+        );
     }
 
     #[test]
-    fn test_switch_tab_does_nothing_without_workspaces() {
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .build();
-
-        assert_eq!(app.active_tab, Tab::Scripts);
-        app.switch_tab(1);
-        assert_eq!(app.active_tab, Tab::Scripts); // no change
+    fn test_quick_dial_number_runs_entry() {
+        let mut app = quick_dial_app();
+        app.handle_key(ctrl('d'));
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+
+        match action {
+            Action::RunScript {
+                script_name, cwd, ..
+            } => {
+                assert_eq!(script_name, "dev");
+                assert_eq!(cwd, PathBuf::from("/test/project/apps/web"));
+            }
+            _ => panic!("expected RunScript"),
+        }
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.recents[0].key, "web:dev");
     }
 
-    // --- type_char / delete_char tests ---
-
     #[test]
-    fn test_type_char_updates_query() {
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .build();
+    fn test_all_scripts_finds_a_package_script_without_opening_the_package() {
+        let mut app = quick_dial_app();
 
-        assert_eq!(app.query, "");
-        app.type_char('t');
-        assert_eq!(app.query, "t");
-        app.type_char('e');
-        assert_eq!(app.query, "te");
+        app.handle_key(ctrl('x'));
+        assert_eq!(app.active_tab, Tab::Packages);
+        assert_eq!(app.package_mode, PackageMode::AllScripts);
+        assert_eq!(app.snapshot().rows.len(), 5);
+        assert_eq!(app.snapshot().breadcrumb, vec!["Packages", "All scripts"]);
+
+        for c in "web dev".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(app.snapshot().rows, vec!["web:dev"]);
+
+        match app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) {
+            Action::RunScript {
+                script_name, cwd, ..
+            } => {
+                assert_eq!(script_name, "dev");
+                assert_eq!(cwd, PathBuf::from("/test/project/apps/web"));
+            }
+            _ => panic!("expected RunScript"),
+        }
     }
 
     #[test]
-    fn test_delete_char_removes_last_char() {
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .build();
+    fn test_starred_and_busy_packages_come_first() {
+        let mut app = quick_dial_app();
+        app.active_tab = Tab::Packages;
+        app.update_pkg_filtered();
+        assert_eq!(app.snapshot().rows, vec!["web", "docs", "api"]);
+
+        app.move_selection(2);
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert!(app.favorites.contains("pkg:api"));
+        assert_eq!(app.snapshot().rows, vec!["api", "web", "docs"]);
+
+        app.record_run(&QueuedRun {
+            key: "docs:dev".to_string(),
+            script_name: "dev".to_string(),
+            cwd: PathBuf::from("/test/project/apps/docs"),
+            env_files: vec![],
+            args: String::new(),
+            env: Vec::new(),
+        });
+        app.update_pkg_filtered();
+        assert_eq!(app.snapshot().rows, vec!["api", "docs", "web"]);
+
+        // Searching lists by relevance
+        app.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+        assert_eq!(app.snapshot().rows[0], "web");
+    }
 
-        app.query = "test".to_string();
-        app.delete_char();
-        assert_eq!(app.query, "tes");
-        app.delete_char();
-        assert_eq!(app.query, "te");
+    #[test]
+    fn test_session_restores_the_open_package() {
+        let mut app = quick_dial_app();
+        app.active_tab = Tab::Packages;
+        app.update_pkg_filtered();
+        app.move_selection(2);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let session = app.session();
+        assert_eq!(session.tab, Some(Tab::Packages));
+        assert_eq!(session.package.as_deref(), Some("apps/api"));
+        assert!(session.package_open);
+
+        let mut restored = quick_dial_app();
+        restored.restore_session(session);
+        assert_eq!(restored.active_tab, Tab::Packages);
+        assert_eq!(restored.breadcrumb(), vec!["Packages", "api"]);
+
+        // A session left in the Jobs tab starts in Scripts
+        let mut restored = quick_dial_app();
+        restored.restore_session(Session {
+            tab: Some(Tab::Jobs),
+            ..Session::default()
+        });
+        assert_eq!(restored.active_tab, Tab::Scripts);
+
+        let mut restored = quick_dial_app();
+        restored.restore_session(Session {
+            tab: Some(Tab::Scripts),
+            query: "lint".to_string(),
+            ..Session::default()
+        });
+        assert_eq!(restored.snapshot().rows, vec!["lint"]);
     }
 
     #[test]
-    fn test_delete_char_on_empty_query() {
+    fn test_quick_dial_without_favorites_shows_message() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
+            .with_scripts(vec![script("build", "tsc")])
             .build();
 
-        assert_eq!(app.query, "");
-        app.delete_char();
-        assert_eq!(app.query, ""); // no panic, no change
+        app.handle_key(ctrl('d'));
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No favorite scripts yet")
+        );
     }
 
-    // --- update_filtered tests ---
+    // --- prefix grouping tests ---
 
     #[test]
-    fn test_update_filtered_with_empty_query() {
+    fn test_grouped_list_expands_and_runs_member() {
         let mut app = TestAppBuilder::new()
             .with_scripts(vec![
-                script("build", "echo build"),
-                script("test", "echo test"),
+                script("build", "tsc"),
+                script("test", "vitest"),
+                script("test:e2e", "playwright test"),
+                script("test:unit", "vitest run"),
             ])
             .build();
 
-        app.query = "".to_string();
-        app.update_filtered();
+        app.handle_key(ctrl('e'));
+        assert!(app.group_scripts());
+        assert_eq!(app.script_rows.len(), 2);
+        assert!(matches!(
+            app.script_rows[1],
+            ListRow::Group { ref prefix, len: 3, expanded: false } if prefix == "test"
+        ));
 
-        // Should return all scripts in alphabetical order
-        assert_eq!(app.filtered_indices.len(), 2);
+        press(&mut app, KeyCode::Down);
+        let action = press(&mut app, KeyCode::Enter);
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(app.script_rows.len(), 5);
+        assert_eq!(app.selected_index, 1);
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        let action = press(&mut app, KeyCode::Enter);
+        assert!(matches!(
+            action,
+            Action::RunScript { ref script_name, .. } if script_name == "test:e2e"
+        ));
     }
 
     #[test]
-    fn test_update_filtered_with_query_filters_correctly() {
+    fn test_grouping_is_skipped_while_searching() {
         let mut app = TestAppBuilder::new()
             .with_scripts(vec![
-                script("build", "echo build"),
-                script("test", "echo test"),
-                script("lint", "echo lint"),
+                script("test:e2e", "playwright"),
+                script("test:unit", "vitest"),
             ])
-            .build();
+            .build()
+            .with_config(Config {
+                group_scripts: true,
+                ..Config::default()
+            });
+        assert_eq!(app.script_rows.len(), 1);
 
-        app.query = "te".to_string();
-        app.update_filtered();
+        press(&mut app, KeyCode::Char('u'));
 
-        // Should only match "test"
-        assert_eq!(app.filtered_indices.len(), 1);
-        assert_eq!(app.scripts[app.filtered_indices[0]].name, "test");
+        assert_eq!(app.script_rows, flat_rows(&app.filtered_indices));
+        assert_eq!(app.get_current_script_name(), "test:unit");
     }
 
+    // --- hidden scripts tests ---
+
     #[test]
-    fn test_update_filtered_resets_selection() {
+    fn test_ctrl_u_shows_scripts_hidden_by_config() {
         let mut app = TestAppBuilder::new()
             .with_scripts(vec![
-                script("build", "echo build"),
-                script("test", "echo test"),
+                script("build", "tsc"),
+                script("preinstall", "husky install"),
+                script("postbuild", "node copy.js"),
             ])
-            .build();
+            .build()
+            .with_config(Config {
+                hide_scripts: vec!["pre*".to_string(), "post*".to_string()],
+                ..Config::default()
+            });
+        assert_eq!(app.filtered_indices, vec![0]);
+
+        app.handle_key(ctrl('u'));
+        assert!(app.view_prefs(SCRIPTS_VIEW).show_hidden);
+        assert_eq!(app.filtered_indices.len(), 3);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Showing 2 hidden scripts")
+        );
 
-        app.selected_index = 1;
-        app.scroll_offset = 5;
+        app.handle_key(ctrl('u'));
+        assert_eq!(app.filtered_indices, vec![0]);
+        assert_eq!(app.status_message.as_deref(), Some("Hiding 2 scripts"));
+    }
 
-        app.update_filtered();
+    #[test]
+    fn test_package_view_keeps_its_own_sort_settings() {
+        let mut app = quick_dial_app();
+        app.active_tab = Tab::Packages;
+        app.enter_package_scripts(0);
+        let names = |app: &App| -> Vec<String> {
+            app.pkg_script_filtered_indices
+                .iter()
+                .map(|&i| app.pkg_script_sortable[i].name.clone())
+                .collect()
+        };
+        // The favorite comes first
+        assert_eq!(names(&app), vec!["dev", "build"]);
 
-        assert_eq!(app.selected_index, 0);
-        assert_eq!(app.scroll_offset, 0);
+        app.handle_key(ctrl('s'));
+        assert_eq!(names(&app), vec!["build", "dev"]);
+        assert_eq!(app.status_message.as_deref(), Some("Sorted by name"));
+        assert_eq!(app.view_prefs(SCRIPTS_VIEW), ViewPrefs::default());
+
+        // Grouping belongs to the Scripts tab
+        app.handle_key(ctrl('e'));
+        assert!(!app.group_scripts());
+
+        // Other packages keep their order; this one is remembered
+        app.enter_package_scripts(2);
+        assert_eq!(app.package_prefs().sort, crate::sort::SortMode::Frecency);
+        app.enter_package_scripts(0);
+        assert_eq!(names(&app), vec!["build", "dev"]);
     }
 
-    // --- handle_esc tests ---
+    // --- script groups tests ---
 
+    #[cfg(unix)]
     #[test]
-    fn test_handle_esc_on_scripts_tab_quits() {
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .build();
+    fn test_script_group_starts_script_in_checked_packages() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["web", "docs", "api"] {
+            std::fs::create_dir_all(temp_dir.path().join("apps").join(name)).unwrap();
+        }
+        let mut app = quick_dial_app();
+        app.monorepo_root = Some(temp_dir.path().to_path_buf());
+        app.config
+            .run_templates
+            .insert("npm".to_string(), "echo {script}".to_string());
+
+        app.handle_key(ctrl('k'));
+        assert_eq!(app.mode, AppMode::ScriptGroups);
+        assert_eq!(app.script_groups.len(), 1);
+        assert_eq!(app.script_groups[0].name, "dev");
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.script_group_checked, Some(vec![true, true, true]));
+        // Uncheck `docs`
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.active_tab, Tab::Jobs);
+        let cwds: Vec<PathBuf> = app.jobs.iter().map(|job| job.spec.cwd.clone()).collect();
+        assert_eq!(
+            cwds,
+            vec![
+                temp_dir.path().join("apps/web"),
+                temp_dir.path().join("apps/api")
+            ]
+        );
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Started dev in 2 packages (see Jobs tab)")
+        );
+    }
 
-        let action = app.handle_esc();
-        assert!(matches!(action, Action::Quit));
+    #[test]
+    fn test_script_groups_back_and_toggle_all() {
+        let mut app = quick_dial_app();
+
+        app.handle_key(ctrl('k'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.script_group_checked, Some(vec![false, false, false]));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.status_message.as_deref(), Some("No packages selected"));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.script_group_checked, None);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
     }
 
     #[test]
-    fn test_handle_esc_in_package_mode_goes_back() {
-        let pkg = WorkspacePackage {
-            name: "pkg1".to_string(),
-            relative_path: "packages/pkg1".to_string(),
-            scripts: {
-                let mut map = IndexMap::new();
-                map.insert("test".to_string(), "echo test".to_string());
-                map
-            },
-        };
+    fn test_ctrl_w_runs_the_script_in_every_package_defining_it() {
+        let mut app = quick_dial_app();
+        app.active_tab = Tab::Packages;
+        // web's favorite `dev` is listed first
+        app.enter_package_scripts(0);
 
-        let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
-            .with_workspaces(vec![pkg])
-            .build();
+        let action = app.handle_key(ctrl('w'));
+        let Action::RunAllPackages { runs } = action else {
+            panic!("expected a run in all packages");
+        };
+        assert!(runs.iter().all(|r| r.script_name == "dev"));
+        let keys: Vec<&str> = runs.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["web:dev", "docs:dev", "api:dev"]);
+        assert_eq!(runs[2].cwd, PathBuf::from("/test/project/apps/api"));
+    }
 
-        // Enter package script mode
+    #[test]
+    fn test_ctrl_w_asks_before_running_a_dangerous_script_in_every_package() {
+        let mut app = quick_dial_app();
+        app.config.dangerous_scripts = vec!["dev".to_string()];
         app.active_tab = Tab::Packages;
         app.enter_package_scripts(0);
-        assert!(matches!(
-            app.package_mode,
-            PackageMode::SelectingScript { .. }
-        ));
 
-        // Esc should go back to package list
-        let action = app.handle_esc();
+        let action = app.handle_key(ctrl('w'));
         assert!(matches!(action, Action::Continue));
-        assert_eq!(app.package_mode, PackageMode::SelectingPackage);
+        assert_eq!(app.mode, AppMode::DangerousScript);
+
+        for c in "dev".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let Action::RunAllPackages { runs } = action else {
+            panic!("expected a run in all packages");
+        };
+        assert_eq!(runs.len(), 3);
     }
 
-    // --- handle_enter tests ---
+    // --- suggested next tests ---
 
     #[test]
-    fn test_handle_enter_returns_run_action() {
+    fn test_ctrl_n_runs_script_that_usually_follows() {
         let mut app = TestAppBuilder::new()
-            .with_scripts(vec![script("test", "echo test")])
+            .with_scripts(vec![
+                script("build", "tsc"),
+                script("preview", "vite preview"),
+            ])
             .build();
+        for name in ["build", "preview", "build", "preview", "build"] {
+            app.record_run(&QueuedRun {
+                key: format!("root:{}", name),
+                script_name: name.to_string(),
+                cwd: PathBuf::from("/test/project"),
+                env_files: vec![],
+                args: String::new(),
+                env: Vec::new(),
+            });
+        }
 
-        let action = app.handle_enter();
-        assert!(matches!(action, Action::RunScript { .. }));
+        assert_eq!(app.suggested_next().unwrap().key, "root:preview");
+        let action = app.handle_key(ctrl('n'));
 
-        if let Action::RunScript { script_name, .. } = action {
-            assert_eq!(script_name, "test");
-        }
+        assert!(matches!(
+            action,
+            Action::RunScript { ref script_name, .. } if script_name == "preview"
+        ));
     }
 
     #[test]
-    fn test_handle_enter_on_empty_list_returns_continue() {
-        let mut app = TestAppBuilder::new().build();
+    fn test_ctrl_n_without_suggestion_shows_message() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+
+        let action = app.handle_key(ctrl('n'));
 
-        let action = app.handle_enter();
         assert!(matches!(action, Action::Continue));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No suggested next script yet")
+        );
     }
 
-    // --- ensure_scroll tests ---
+    #[test]
+    fn test_ctrl_r_reruns_last_run_with_its_args_and_env_files() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("dev", "vite")])
+            .build();
+        assert!(matches!(app.handle_key(ctrl('r')), Action::Continue));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Nothing has been run here yet")
+        );
+
+        app.record_run(&QueuedRun {
+            key: "root:dev".to_string(),
+            script_name: "dev".to_string(),
+            cwd: PathBuf::from("/test/project"),
+            env_files: vec![PathBuf::from("/test/project/.env.local")],
+            args: "--host".to_string(),
+            env: Vec::new(),
+        });
+        // Works whatever is selected or typed
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+
+        match app.handle_key(ctrl('r')) {
+            Action::RunScript {
+                script_name,
+                env_files,
+                args,
+                ..
+            } => {
+                assert_eq!(script_name, "dev");
+                assert_eq!(env_files, vec![PathBuf::from("/test/project/.env.local")]);
+                assert_eq!(args, "--host");
+            }
+            _ => panic!("expected RunScript"),
+        }
+    }
+
+    // --- settings tests ---
+
+    fn press(app: &mut App, code: KeyCode) -> Action {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
 
     #[test]
-    fn test_ensure_scroll_adjusts_when_selected_below_offset() {
-        let mut offset = 5;
-        ensure_scroll(&mut offset, 3, 10);
-        assert_eq!(offset, 3);
+    fn test_settings_clears_favorites_after_confirmation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("favorites.json"), "[]").unwrap();
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc"), script("test", "vitest")])
+            .with_favorite("root:build")
+            .with_favorite("root:test")
+            .build();
+        app.config_dir = dir.path().to_path_buf();
+
+        press(&mut app, KeyCode::F(2));
+        assert_eq!(app.mode, AppMode::Settings);
+        assert_eq!(app.reset_preview(ResetTarget::Favorites), "2 scripts");
+
+        press(&mut app, KeyCode::Enter);
+        assert!(app.settings_confirm);
+        assert_eq!(app.favorites.len(), 2);
+
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.favorites.is_empty());
+        assert!(!dir.path().join("favorites.json").exists());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Cleared favorites (2 scripts)")
+        );
+        assert_eq!(app.mode, AppMode::Settings);
     }
 
     #[test]
-    fn test_ensure_scroll_adjusts_when_selected_above_visible() {
-        let mut offset = 0;
-        ensure_scroll(&mut offset, 15, 10);
-        assert_eq!(offset, 6); // 15 - 10 + 1
+    fn test_settings_declining_keeps_data() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .with_favorite("root:build")
+            .build();
+
+        press(&mut app, KeyCode::F(2));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('n'));
+
+        assert!(!app.settings_confirm);
+        assert_eq!(app.favorites.len(), 1);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
     }
 
     #[test]
-    fn test_ensure_scroll_no_change_when_in_view() {
-        let mut offset = 5;
-        ensure_scroll(&mut offset, 10, 10);
-        assert_eq!(offset, 5); // 10 is within [5, 15)
+    fn test_settings_previews_configs_and_args_history() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![script("build", "tsc")])
+            .build();
+        app.script_configs.insert(
+            "root:build".to_string(),
+            ScriptConfig {
+                args: "--watch".to_string(),
+                last_used: SystemTime::now(),
+                presets: Vec::new(),
+                env: IndexMap::new(),
+            },
+        );
+        app.args_history.entries = vec!["--watch".to_string()];
+
+        press(&mut app, KeyCode::F(2));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(
+            app.reset_question(ResetTarget::Configs),
+            "Clear script configs & args history (1 script, 1 argument)? This can't be undone."
+        );
+        assert!(app.settings_confirm);
+    }
+
+    // --- args validation tests ---
+
+    #[test]
+    fn test_args_warning_names_unknown_flags() {
+        let mut app = TestAppBuilder::new()
+            .with_scripts(vec![
+                script("dev", "vite"),
+                script("serve", "node server.js"),
+            ])
+            .build();
+        app.mode = AppMode::ConfigureArgs;
+        app.flow = Some(ConfigureFlow::default());
+
+        app.flow.as_mut().unwrap().args = ArgsEdit::new("--port 3000 --hots");
+        assert_eq!(
+            app.args_warning().as_deref(),
+            Some("Unknown vite flag: --hots")
+        );
+
+        app.flow.as_mut().unwrap().args = ArgsEdit::new("--host");
+        assert!(app.args_warning().is_none());
+
+        // No flag table for plain node scripts
+        app.move_selection(1);
+        app.flow.as_mut().unwrap().args = ArgsEdit::new("--hots");
+        assert!(app.args_warning().is_none());
     }
 
     // --- wrap_index tests ---
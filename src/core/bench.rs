@@ -0,0 +1,197 @@
+//! `nr --bench <script>`: times a script under every installed package manager,
+//! to compare them before migrating (e.g. npm → pnpm or bun).
+
+use crate::core::inherit_env::InheritEnv;
+use crate::core::package_manager::PackageManager;
+use crate::core::runner::{format_duration, run_script_quiet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Runs after the first (cold) one; their median is the warm time.
+pub const WARM_RUNS: usize = 3;
+
+/// How long one package manager took to run a script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchTiming {
+    pub pm: PackageManager,
+    /// The first run, with caches as the package manager left them.
+    pub cold: Duration,
+    /// Median of the [`WARM_RUNS`] runs after it; `None` when the cold run failed.
+    pub warm: Option<Duration>,
+    pub exit_code: i32,
+}
+
+/// Package managers to compare: the detected one, then the others found on `PATH`.
+pub fn installed_managers(detected: PackageManager) -> Vec<PackageManager> {
+    installed_managers_with(detected, on_path)
+}
+
+fn installed_managers_with(
+    detected: PackageManager,
+    installed: impl Fn(&str) -> bool,
+) -> Vec<PackageManager> {
+    let yarn = match detected {
+        PackageManager::Yarn | PackageManager::YarnBerry => detected,
+        _ => PackageManager::Yarn,
+    };
+    let mut managers = vec![detected];
+    // Node package managers can't run Deno tasks
+    if detected == PackageManager::Deno {
+        return managers;
+    }
+    for pm in [
+        PackageManager::Bun,
+        PackageManager::Pnpm,
+        yarn,
+        PackageManager::Npm,
+    ] {
+        if pm != detected && installed(pm.command_name()) {
+            managers.push(pm);
+        }
+    }
+    managers
+}
+
+/// Whether an executable named `binary` is in one of the `PATH` directories.
+fn on_path(binary: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        if cfg!(windows) {
+            ["exe", "cmd"]
+                .iter()
+                .any(|ext| dir.join(binary).with_extension(ext).is_file())
+        } else {
+            dir.join(binary).is_file()
+        }
+    })
+}
+
+/// Times `script_name` run by `pm` with its output discarded: one cold run,
+/// then [`WARM_RUNS`] warm ones unless the cold run failed.
+pub fn bench(
+    pm: PackageManager,
+    script_name: &str,
+    cwd: &Path,
+    template: Option<&str>,
+    inherit: &InheritEnv,
+) -> BenchTiming {
+    let timed = || {
+        let started = Instant::now();
+        let exit_code = run_script_quiet(pm, script_name, cwd, template, inherit);
+        (exit_code, started.elapsed())
+    };
+
+    let (exit_code, cold) = timed();
+    let warm = (exit_code == 0).then(|| {
+        let mut runs: Vec<Duration> = (0..WARM_RUNS).map(|_| timed().1).collect();
+        runs.sort();
+        runs[runs.len() / 2]
+    });
+    BenchTiming {
+        pm,
+        cold,
+        warm,
+        exit_code,
+    }
+}
+
+/// Comparison table printed after benchmarking, fastest warm time first.
+pub fn format_comparison(script_name: &str, timings: &[BenchTiming]) -> String {
+    let mut sorted: Vec<&BenchTiming> = timings.iter().collect();
+    sorted.sort_by_key(|t| t.warm.unwrap_or(Duration::MAX));
+    let fastest = sorted.first().and_then(|t| t.warm);
+
+    let mut out = format!(
+        "\nBenchmark of {} (cold run, then median of {} warm runs):\n",
+        script_name, WARM_RUNS
+    );
+    for timing in sorted {
+        let pm = timing.pm.command_name();
+        match (timing.warm, fastest) {
+            (Some(warm), Some(fastest)) => {
+                let ratio = warm.as_secs_f64() / fastest.as_secs_f64().max(f64::EPSILON);
+                let note = if warm == fastest {
+                    "fastest".to_string()
+                } else {
+                    format!("{:.1}x slower", ratio)
+                };
+                out.push_str(&format!(
+                    "  ✔ {:<5} cold {:>8}  warm {:>8}  {}\n",
+                    pm,
+                    format_duration(timing.cold),
+                    format_duration(warm),
+                    note
+                ));
+            }
+            _ => out.push_str(&format!(
+                "  ✘ {:<5} exited with {} after {}\n",
+                pm,
+                timing.exit_code,
+                format_duration(timing.cold)
+            )),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detected_manager_comes_first_then_installed_ones() {
+        let installed = |binary: &str| binary == "npm" || binary == "bun";
+        assert_eq!(
+            installed_managers_with(PackageManager::Pnpm, installed),
+            vec![
+                PackageManager::Pnpm,
+                PackageManager::Bun,
+                PackageManager::Npm
+            ]
+        );
+
+        // Yarn keeps its detected flavor
+        let installed = |binary: &str| binary == "yarn";
+        assert_eq!(
+            installed_managers_with(PackageManager::YarnBerry, installed),
+            vec![PackageManager::YarnBerry]
+        );
+    }
+
+    #[test]
+    fn test_comparison_orders_by_warm_time() {
+        let timings = [
+            BenchTiming {
+                pm: PackageManager::Npm,
+                cold: Duration::from_millis(1600),
+                warm: Some(Duration::from_millis(1200)),
+                exit_code: 0,
+            },
+            BenchTiming {
+                pm: PackageManager::Yarn,
+                cold: Duration::from_millis(300),
+                warm: None,
+                exit_code: 1,
+            },
+            BenchTiming {
+                pm: PackageManager::Bun,
+                cold: Duration::from_millis(400),
+                warm: Some(Duration::from_millis(300)),
+                exit_code: 0,
+            },
+        ];
+
+        let out = format_comparison("build", &timings);
+        let lines: Vec<&str> = out.lines().skip(2).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  ✔ bun   cold    400ms  warm    300ms  fastest",
+                "  ✔ npm   cold     1.6s  warm     1.2s  4.0x slower",
+                "  ✘ yarn  exited with 1 after 300ms",
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+/// How [`Clipboard::copy`] delivered the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardMethod {
+    /// A platform clipboard tool (`pbcopy`, `wl-copy`, `xclip`, ...).
+    Command(&'static str),
+    /// An OSC 52 escape sequence, handled by the terminal (works over SSH and in tmux
+    /// with `set-clipboard on`).
+    Osc52,
+}
+
+/// Somewhere to copy commands to.
+pub trait Clipboard {
+    /// Copies `text`, saying how it got there.
+    fn copy(&self, text: &str) -> std::io::Result<ClipboardMethod>;
+}
+
+pub use system::SystemClipboard;
+
+/// The platform's clipboard tools, with OSC 52 as the fallback.
+#[cfg(feature = "clipboard")]
+mod system {
+    use super::{Clipboard, ClipboardMethod};
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// Clipboard helpers tried in order; the first one that runs successfully wins.
+    const CLIPBOARD_COMMANDS: &[&[&str]] = if cfg!(target_os = "macos") {
+        &[&["pbcopy"]]
+    } else if cfg!(windows) {
+        &[&["clip"]]
+    } else {
+        &[
+            &["wl-copy"],
+            &["xclip", "-selection", "clipboard"],
+            &["xsel", "--clipboard", "--input"],
+            // WSL
+            &["clip.exe"],
+        ]
+    };
+
+    /// The system clipboard.
+    pub struct SystemClipboard;
+
+    impl Clipboard for SystemClipboard {
+        /// Falls back to an OSC 52 escape written to stdout when no clipboard
+        /// tool is available; whether that lands depends on the terminal.
+        fn copy(&self, text: &str) -> std::io::Result<ClipboardMethod> {
+            for argv in CLIPBOARD_COMMANDS {
+                if pipe_to(argv, text) {
+                    return Ok(ClipboardMethod::Command(argv[0]));
+                }
+            }
+
+            let mut stdout = std::io::stdout();
+            stdout.write_all(osc52_sequence(text).as_bytes())?;
+            stdout.flush()?;
+            Ok(ClipboardMethod::Osc52)
+        }
+    }
+
+    /// Runs `argv` with `text` on stdin; false if it could not be started or failed.
+    fn pipe_to(argv: &[&str], text: &str) -> bool {
+        let Ok(mut child) = Command::new(argv[0])
+            .args(&argv[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    }
+
+    /// `ESC ] 52 ; c ; <base64> BEL`: asks the terminal to set the clipboard.
+    fn osc52_sequence(text: &str) -> String {
+        format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+    }
+
+    /// Standard base64 with padding; small enough not to warrant a dependency.
+    fn base64_encode(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_base64_encode_pads_partial_chunks() {
+            assert_eq!(base64_encode(b""), "");
+            assert_eq!(base64_encode(b"f"), "Zg==");
+            assert_eq!(base64_encode(b"fo"), "Zm8=");
+            assert_eq!(base64_encode(b"foo"), "Zm9v");
+            assert_eq!(base64_encode(b"pnpm run build"), "cG5wbSBydW4gYnVpbGQ=");
+        }
+
+        #[test]
+        fn test_osc52_sequence() {
+            assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+        }
+    }
+}
+
+/// Builds without the `clipboard` feature can't copy.
+#[cfg(not(feature = "clipboard"))]
+mod system {
+    use super::{Clipboard, ClipboardMethod};
+    use crate::core::features::disabled;
+
+    pub struct SystemClipboard;
+
+    impl Clipboard for SystemClipboard {
+        fn copy(&self, _text: &str) -> std::io::Result<ClipboardMethod> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                disabled("clipboard"),
+            ))
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Deno projects: `tasks` in `deno.json` / `deno.jsonc`, run as scripts with
+//! `deno task <name>`.
+
+use crate::core::jsonc;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+
+/// Config file names Deno reads, in the order it prefers them.
+pub const CONFIG_FILES: [&str; 2] = ["deno.json", "deno.jsonc"];
+
+/// The Deno config file in `dir`, if there is one.
+pub fn config_file(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Tasks declared in `dir`'s Deno config, in declaration order. A task is a
+/// command string, or an object with a `command` (Deno 2); tasks without a
+/// command (only `dependencies`) are left out.
+///
+/// Returns an empty map if there is no config or it can't be parsed.
+pub fn load_tasks(dir: &Path) -> IndexMap<String, String> {
+    let Some(contents) = config_file(dir).and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return IndexMap::new();
+    };
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(&jsonc::strip_jsonc(&contents))
+    else {
+        return IndexMap::new();
+    };
+    let Some(tasks) = config.get("tasks").and_then(|t| t.as_object()) else {
+        return IndexMap::new();
+    };
+    tasks
+        .iter()
+        .filter_map(|(name, task)| {
+            let command = match task {
+                serde_json::Value::String(command) => command.as_str(),
+                task => task.get("command")?.as_str()?,
+            };
+            Some((name.clone(), command.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_loads_tasks_from_deno_jsonc() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("deno.jsonc"),
+            r#"{
+  // Local development
+  "tasks": {
+    "dev": "deno run --watch main.ts", /* http://localhost:8000 */
+    "build": { "command": "deno compile main.ts", "description": "Binary" },
+    "ci": { "dependencies": ["build"] },
+  },
+}"#,
+        )
+        .unwrap();
+
+        let tasks = load_tasks(tmp.path());
+        let names: Vec<&str> = tasks.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["dev", "build"]);
+        assert_eq!(tasks["dev"], "deno run --watch main.ts");
+        assert_eq!(tasks["build"], "deno compile main.ts");
+    }
+}
@@ -0,0 +1,95 @@
+//! Directories a script can run in, for picking a working directory.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Levels below the root that are listed.
+const MAX_DEPTH: usize = 6;
+
+/// Listing stops after this many directories, so huge trees stay quick.
+const MAX_DIRS: usize = 5000;
+
+/// Directories below `root`, relative to it and shallowest first, starting
+/// with `root` itself (an empty path). Dependencies (`node_modules`) and
+/// hidden directories are left out.
+pub fn list_directories(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::new()];
+    let mut pending = VecDeque::from([(PathBuf::new(), 0)]);
+
+    while let Some((dir, depth)) = pending.pop_front() {
+        if depth >= MAX_DEPTH {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(root.join(&dir)) else {
+            continue;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                !name.starts_with('.') && name != "node_modules"
+            })
+            .map(|entry| dir.join(entry.file_name()))
+            .collect();
+        children.sort();
+
+        for child in children {
+            if dirs.len() >= MAX_DIRS {
+                return dirs;
+            }
+            dirs.push(child.clone());
+            pending.push_back((child, depth + 1));
+        }
+    }
+    dirs
+}
+
+/// How a listed directory is shown: `./` for the root, else its relative path.
+pub fn dir_label(dir: &Path) -> String {
+    if dir.as_os_str().is_empty() {
+        "./".to_string()
+    } else {
+        dir.to_string_lossy().replace('\\', "/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lists_shallow_directories_first_without_dependencies() {
+        let tmp = TempDir::new().unwrap();
+        for dir in [
+            "apps/web/src",
+            "apps/api",
+            "packages/ui",
+            "node_modules/react",
+            ".git/objects",
+        ] {
+            fs::create_dir_all(tmp.path().join(dir)).unwrap();
+        }
+        fs::write(tmp.path().join("package.json"), "{}").unwrap();
+
+        let labels: Vec<String> = list_directories(tmp.path())
+            .iter()
+            .map(|dir| dir_label(dir))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "./",
+                "apps",
+                "packages",
+                "apps/api",
+                "apps/web",
+                "packages/ui",
+                "apps/web/src"
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! `nr doctor`: checks the config files and explains what is wrong with them.
+
+use crate::store::config::ConfigIssue;
+use std::path::PathBuf;
+
+/// Report on each of `files` (global config first) with the issues found in it.
+pub fn config_report(files: &[PathBuf], issues: &[ConfigIssue]) -> String {
+    let mut out = String::from("Config files:\n");
+    for file in files {
+        let found: Vec<&ConfigIssue> = issues.iter().filter(|i| i.file == *file).collect();
+        if !file.exists() {
+            out.push_str(&format!("  - {} (not present)\n", file.display()));
+        } else if found.is_empty() {
+            out.push_str(&format!("  ✔ {}\n", file.display()));
+        } else {
+            out.push_str(&format!("  ✘ {}\n", file.display()));
+            for issue in found {
+                if issue.key.is_empty() {
+                    out.push_str(&format!("      {}\n", issue.message));
+                } else {
+                    out.push_str(&format!("      {}: {}\n", issue.key, issue.message));
+                }
+            }
+        }
+    }
+
+    match issues.len() {
+        0 => out.push_str("\nNo problems found.\n"),
+        n => out.push_str(&format!(
+            "\n{} problem{} found; nr ignores these settings until they are fixed.\n",
+            n,
+            if n == 1 { "" } else { "s" }
+        )),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::config::load_config;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_lists_issues_under_their_file() {
+        let global = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        fs::write(global.path().join("config.json"), r#"{"ascii": true}"#).unwrap();
+        fs::write(
+            project.path().join("config.json"),
+            r#"{"run_in_pane": "yes", "colour": "blue"}"#,
+        )
+        .unwrap();
+
+        let files = [
+            global.path().join("config.json"),
+            project.path().join("config.json"),
+        ];
+        let (_, issues) = load_config(&files);
+        let report = config_report(&files, &issues);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines[1], format!("  ✔ {}", files[0].display()));
+        assert_eq!(lines[2], format!("  ✘ {}", files[1].display()));
+        assert_eq!(
+            lines[3],
+            "      run_in_pane: invalid type: string \"yes\", expected a boolean"
+        );
+        assert_eq!(lines[4], "      colour: unknown key");
+        assert!(
+            report.ends_with("2 problems found; nr ignores these settings until they are fixed.\n")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Encrypted env files: dotenvx files, whose values are stored as
+//! `encrypted:...` next to a `DOTENV_PUBLIC_KEY`, and `.env.vault` files
+//! opened with `DOTENV_KEY`. nr decrypts them with the `dotenvx` CLI.
+
+use crate::core::program;
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+/// The encrypted vault of dotenv-vault, read with `DOTENV_KEY`.
+pub const VAULT_FILE: &str = ".env.vault";
+
+/// The private keys of dotenvx-encrypted files; never loaded as an env file.
+pub const KEYS_FILE: &str = ".env.keys";
+
+/// Whether the env file at `path`, holding `content`, needs decrypting: it is
+/// a `.env.vault`, declares a `DOTENV_PUBLIC_KEY` or has `encrypted:` values.
+pub fn is_encrypted(path: &Path, content: &str) -> bool {
+    if path.file_name().is_some_and(|name| name == VAULT_FILE) {
+        return true;
+    }
+    content.lines().any(|line| {
+        let line = line.trim_start();
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        line.starts_with("DOTENV_PUBLIC_KEY")
+            || line.split_once('=').is_some_and(|(_, value)| {
+                value
+                    .trim_start()
+                    .trim_start_matches(['"', '\''])
+                    .starts_with("encrypted:")
+            })
+    })
+}
+
+/// Decrypts the env file at `path` with `dotenvx get`, run from the file's
+/// directory so dotenvx finds its `.env.keys` (or `DOTENV_PRIVATE_KEY*` in
+/// nr's environment). A `.env.vault` is opened with `DOTENV_KEY`.
+/// Returns the variables in file order.
+pub fn decrypt(path: &Path) -> Result<Vec<(String, String)>> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut cmd = program::command("dotenvx");
+    cmd.arg("get");
+    if name == VAULT_FILE {
+        if std::env::var_os("DOTENV_KEY").is_none() {
+            bail!("{} needs DOTENV_KEY to be decrypted", name);
+        }
+        cmd.args(["-fv", &name]);
+    } else {
+        cmd.args(["-f", &name]);
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        cmd.current_dir(dir);
+    }
+
+    let output = match cmd.output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            bail!(
+                "{} is encrypted; install dotenvx (https://dotenvx.com) to decrypt it",
+                name
+            )
+        }
+        Err(e) => return Err(e).context("Failed to run dotenvx"),
+    };
+    if !output.status.success() {
+        bail!(
+            "dotenvx couldn't decrypt {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_output(&output.stdout).with_context(|| format!("Unexpected dotenvx output for {}", name))
+}
+
+/// The JSON object printed by `dotenvx get`, as variables in its order.
+fn parse_output(stdout: &[u8]) -> Result<Vec<(String, String)>> {
+    let vars: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(stdout)?;
+    Ok(vars
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_encrypted() {
+        let plain = Path::new(".env");
+        assert!(!is_encrypted(plain, "API_URL=http://localhost\n"));
+        assert!(is_encrypted(
+            plain,
+            "#/----[DOTENV_PUBLIC_KEY]----/\nDOTENV_PUBLIC_KEY=\"03a1\"\nHELLO=\"encrypted:BDqD\"\n"
+        ));
+        assert!(is_encrypted(plain, "export TOKEN='encrypted:BDqD'\n"));
+        assert!(is_encrypted(Path::new("config/.env.vault"), ""));
+    }
+
+    #[test]
+    fn test_parse_output_keeps_the_file_order() {
+        let vars = parse_output(br#"{"HELLO":"World","PORT":3000}"#).unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("HELLO".to_string(), "World".to_string()),
+                ("PORT".to_string(), "3000".to_string()),
+            ]
+        );
+        assert!(parse_output(b"[missing private key]").is_err());
+    }
+}
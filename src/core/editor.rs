@@ -0,0 +1,76 @@
+use std::path::Path;
+
+/// Resolves the user's editor from `$VISUAL` / `$EDITOR`, falling back to a platform default.
+pub fn resolve_editor() -> String {
+    std::env::var("VISUAL")
+        .ok()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// Builds the argv to open `path` at `line` with `editor` (which may carry its own
+/// flags, e.g. `code -w`).
+///
+/// GUI editors that take `file:line` get that form; everything else gets the
+/// `+line file` convention understood by vi, nano, emacs, micro, helix and friends.
+pub fn editor_command(editor: &str, path: &Path, line: usize) -> Vec<String> {
+    let mut argv: Vec<String> = editor.split_whitespace().map(String::from).collect();
+    let program = argv
+        .first()
+        .and_then(|p| Path::new(p).file_stem())
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let location = format!("{}:{}", path.display(), line);
+
+    match program.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            argv.push("--goto".to_string());
+            argv.push(location);
+        }
+        "subl" | "zed" | "idea" | "webstorm" => argv.push(location),
+        "notepad" => argv.push(path.display().to_string()),
+        _ => {
+            argv.push(format!("+{}", line));
+            argv.push(path.display().to_string());
+        }
+    }
+
+    argv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal_editors_use_plus_line() {
+        assert_eq!(
+            editor_command("nvim", Path::new("/p/package.json"), 12),
+            vec!["nvim", "+12", "/p/package.json"]
+        );
+    }
+
+    #[test]
+    fn test_vscode_keeps_flags_and_uses_goto() {
+        assert_eq!(
+            editor_command("code -w", Path::new("/p/package.json"), 3),
+            vec!["code", "-w", "--goto", "/p/package.json:3"]
+        );
+    }
+
+    #[test]
+    fn test_editor_given_as_full_path() {
+        assert_eq!(
+            editor_command("/usr/local/bin/subl", Path::new("/p/package.json"), 7),
+            vec!["/usr/local/bin/subl", "/p/package.json:7"]
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use crate::core::dotenvx;
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -14,6 +15,8 @@ pub struct EnvFile {
     pub path: PathBuf,
     pub display_name: String,
     pub scope: EnvScope,
+    /// Decrypted with dotenvx when loaded (see [`dotenvx::is_encrypted`]).
+    pub encrypted: bool,
 }
 
 #[derive(Debug, Default)]
@@ -36,53 +39,112 @@ impl EnvFileList {
 
 /// Scans for .env* files in both package directory and monorepo root (if different)
 pub fn scan_env_files(cwd: &Path, monorepo_root: &Option<PathBuf>) -> EnvFileList {
-    let mut list = EnvFileList::default();
-
-    // Scan package directory
-    if let Ok(entries) = fs::read_dir(cwd) {
-        for entry in entries.flatten() {
-            if let Some(name) = entry.file_name().to_str() {
-                if name.starts_with(".env") {
-                    list.package_files.push(EnvFile {
-                        path: entry.path(),
-                        display_name: name.to_string(),
-                        scope: EnvScope::Package(cwd.to_path_buf()),
-                    });
-                }
-            }
-        }
-    }
+    scan_env_files_with(cwd, monorepo_root, &[])
+}
 
-    // Sort package files alphabetically
-    list.package_files
-        .sort_by(|a, b| a.display_name.cmp(&b.display_name));
+/// [`scan_env_files`], also looking in `env_dirs` (relative to each of the
+/// two directories, e.g. `config/env`). Besides the directories themselves,
+/// files one level down are found too (`env/.env.staging`), named by their
+/// path relative to the scope directory; subdirectories that are packages
+/// (with a `package.json`) or hold `cwd` are left to their own scope.
+pub fn scan_env_files_with(
+    cwd: &Path,
+    monorepo_root: &Option<PathBuf>,
+    env_dirs: &[String],
+) -> EnvFileList {
+    let mut list = EnvFileList {
+        package_files: scan_scope(cwd, cwd, env_dirs, EnvScope::Package(cwd.to_path_buf())),
+        root_files: Vec::new(),
+    };
 
     // Scan monorepo root if it exists and is different from package dir
     if let Some(root) = monorepo_root {
         if root != cwd {
-            if let Ok(entries) = fs::read_dir(root) {
-                for entry in entries.flatten() {
-                    if let Some(name) = entry.file_name().to_str() {
-                        if name.starts_with(".env") {
-                            list.root_files.push(EnvFile {
-                                path: entry.path(),
-                                display_name: name.to_string(),
-                                scope: EnvScope::Root(root.clone()),
-                            });
-                        }
-                    }
-                }
-            }
-
-            // Sort root files alphabetically
-            list.root_files
-                .sort_by(|a, b| a.display_name.cmp(&b.display_name));
+            list.root_files = scan_scope(root, cwd, env_dirs, EnvScope::Root(root.clone()));
         }
     }
 
     list
 }
 
+/// The env files of one scope directory: its own, then those of its
+/// subdirectories and `env_dirs`, each group sorted alphabetically.
+fn scan_scope(dir: &Path, cwd: &Path, env_dirs: &[String], scope: EnvScope) -> Vec<EnvFile> {
+    let subdirs = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && name != "node_modules"
+        })
+        .map(|entry| entry.path())
+        .filter(|path| !path.join("package.json").exists() && !cwd.starts_with(path));
+    let extra = env_dirs.iter().map(|d| dir.join(d));
+    let mut dirs: Vec<PathBuf> = std::iter::once(dir.to_path_buf())
+        .chain(subdirs)
+        .chain(extra)
+        .collect();
+    dirs[1..].sort();
+    dirs.dedup();
+
+    let mut files = Vec::new();
+    for searched in dirs {
+        let Ok(entries) = fs::read_dir(&searched) else {
+            continue;
+        };
+        let mut found: Vec<EnvFile> = entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                if !name.starts_with(".env") || name == dotenvx::KEYS_FILE {
+                    return None;
+                }
+                let path = entry.path();
+                // `/`-separated on every platform, like workspace paths
+                let display_name = path
+                    .strip_prefix(dir)
+                    .ok()?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let content = fs::read_to_string(&path).unwrap_or_default();
+                Some(EnvFile {
+                    encrypted: dotenvx::is_encrypted(&path, &content),
+                    path,
+                    display_name,
+                    scope: scope.clone(),
+                })
+            })
+            .collect();
+        found.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        files.extend(found);
+    }
+    files
+}
+
+/// Creates the env file `name` in `dir`, as a copy of `template` if given
+/// (e.g. `.env.example`), and returns its path. The name must start with
+/// `.env` and the file must not exist yet.
+pub fn create_env_file(dir: &Path, name: &str, template: Option<&Path>) -> Result<PathBuf> {
+    if !name.starts_with(".env") || name.contains(['/', '\\']) {
+        anyhow::bail!("Env file names start with .env");
+    }
+    let path = dir.join(name);
+    if path.exists() {
+        anyhow::bail!("{} already exists", name);
+    }
+    let contents = match template {
+        Some(template) => fs::read_to_string(template)
+            .with_context(|| format!("Failed to read {}", template.display()))?,
+        None => String::new(),
+    };
+    fs::write(&path, contents).with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(path)
+}
+
 /// Loads and merges environment variables from multiple .env files
 /// Files are processed in order: later files override earlier ones
 /// Expected order: root files first, then package files (so package overrides root)
@@ -90,12 +152,8 @@ pub fn load_env_files(env_file_paths: &[PathBuf]) -> Result<HashMap<String, Stri
     let mut merged = HashMap::new();
 
     for path in env_file_paths {
-        match load_single_env_file(path) {
-            Ok(vars) => {
-                for (key, value) in vars {
-                    merged.insert(key, value);
-                }
-            }
+        match load_env_file(path, &merged) {
+            Ok(vars) => merged.extend(vars),
             Err(e) => {
                 eprintln!("⚠️  Failed to load {}: {}", path.display(), e);
                 // Continue with other files
@@ -106,47 +164,286 @@ pub fn load_env_files(env_file_paths: &[PathBuf]) -> Result<HashMap<String, Stri
     Ok(merged)
 }
 
-/// Loads a single .env file and returns its key-value pairs
-fn load_single_env_file(path: &Path) -> Result<HashMap<String, String>> {
+/// Templates listing the keys a project expects, by the names they commonly go by.
+pub const EXAMPLE_FILES: [&str; 2] = [".env.example", ".env.sample"];
+
+/// Keys of the `.env.example` (or `.env.sample`) next to each of
+/// `env_files` that neither the files nor `overrides` set, with the template's
+/// name, in template order. Templates without missing keys are left out.
+///
+/// Only key names are compared, so encrypted files aren't decrypted; with a
+/// `.env.vault`, whose keys can't be told, nothing is reported.
+pub fn missing_example_keys(
+    env_files: &[PathBuf],
+    overrides: &[(String, String)],
+) -> Vec<(String, Vec<String>)> {
+    let mut defined: HashSet<String> = overrides.iter().map(|(key, _)| key.clone()).collect();
+    for path in env_files {
+        match env_file_keys(path) {
+            Some(keys) => defined.extend(keys),
+            None => return Vec::new(),
+        }
+    }
+
+    let mut templates: Vec<PathBuf> = Vec::new();
+    for dir in env_files.iter().filter_map(|path| path.parent()) {
+        let template = EXAMPLE_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        if let Some(template) = template {
+            if !templates.contains(&template) {
+                templates.push(template);
+            }
+        }
+    }
+
+    templates
+        .into_iter()
+        .filter_map(|template| {
+            let missing: Vec<String> = env_file_keys(&template)?
+                .into_iter()
+                .filter(|key| !defined.contains(key))
+                .collect();
+            let name = template.file_name()?.to_string_lossy().into_owned();
+            (!missing.is_empty()).then_some((name, missing))
+        })
+        .collect()
+}
+
+/// The keys set by the env file at `path`, read without decrypting or
+/// expanding values. `None` for a `.env.vault`; unreadable files set none.
+fn env_file_keys(path: &Path) -> Option<Vec<String>> {
+    if path
+        .file_name()
+        .is_some_and(|name| name == dotenvx::VAULT_FILE)
+    {
+        return None;
+    }
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let parsed = parse_env(&content, &HashMap::new()).unwrap_or_default();
+    Some(parsed.vars.into_iter().map(|(key, _)| key).collect())
+}
+
+/// Formats variables as POSIX `export KEY='value'` lines, sorted by key,
+/// so a shell can reproduce the environment nr would pass to a script.
+pub fn format_exports(vars: &HashMap<String, String>) -> String {
+    let mut keys: Vec<&String> = vars.keys().collect();
+    keys.sort();
+
+    keys.into_iter()
+        .map(|key| format!("export {}={}\n", key, shell_quote(&vars[key])))
+        .collect()
+}
+
+/// Single-quotes `value` for POSIX shells (`'` becomes `'\''`).
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Loads a single .env file and returns its key-value pairs in file order.
+/// Encrypted files are decrypted with dotenvx.
+/// `${VAR}` references see the file's earlier keys, then `defined` (the
+/// files loaded before it), then nr's own environment.
+pub fn load_env_file(
+    path: &Path,
+    defined: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read env file: {}", path.display()))?;
+    if dotenvx::is_encrypted(path, &content) {
+        return dotenvx::decrypt(path);
+    }
 
-    let mut vars = HashMap::new();
+    let parsed = parse_env(&content, defined)?;
+    for line_num in parsed.invalid_lines {
+        eprintln!(
+            "⚠️  Invalid line {} in {}: {}",
+            line_num,
+            path.display(),
+            content.lines().nth(line_num - 1).unwrap_or_default().trim()
+        );
+    }
+    Ok(parsed.vars)
+}
 
-    for (line_num, line) in content.lines().enumerate() {
-        let trimmed = line.trim();
+/// The result of [`parse_env`].
+#[derive(Debug, Default, PartialEq)]
+struct ParsedEnv {
+    vars: Vec<(String, String)>,
+    /// 1-based numbers of the lines that aren't `KEY=value`; they are skipped.
+    invalid_lines: Vec<usize>,
+}
 
-        // Skip empty lines and comments
+/// Parses dotenv syntax:
+///
+/// - `KEY=value` lines, optionally prefixed with `export `; `#` starts a comment
+///   line, or a comment after an unquoted value when preceded by whitespace
+/// - `'single quoted'` values are taken literally
+/// - `"double quoted"` values understand `\n`, `\r`, `\t`, `\"`, `\\` and `\$`
+/// - quoted values may span several lines
+/// - unquoted and double-quoted values expand `$VAR`, `${VAR}` and
+///   `${VAR:-default}`; unset variables expand to the empty string
+fn parse_env(content: &str, defined: &HashMap<String, String>) -> Result<ParsedEnv> {
+    let mut parsed = ParsedEnv::default();
+    let mut file_vars: HashMap<String, String> = HashMap::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line_num = index + 1;
+        let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') {
             continue;
         }
+        let trimmed = trimmed
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(trimmed);
 
-        // Parse KEY=VALUE format
-        if let Some((key, value)) = trimmed.split_once('=') {
-            let key = key.trim();
-            let value = value.trim();
+        let Some((key, rest)) = trimmed.split_once('=') else {
+            parsed.invalid_lines.push(line_num);
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            parsed.invalid_lines.push(line_num);
+            continue;
+        }
 
-            // Remove quotes if present
-            let value = value
-                .strip_prefix('"')
-                .and_then(|v| v.strip_suffix('"'))
-                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
-                .unwrap_or(value);
+        let rest = rest.trim_start();
+        let lookup = |name: &str| {
+            file_vars
+                .get(name)
+                .or_else(|| defined.get(name))
+                .cloned()
+                .or_else(|| std::env::var(name).ok())
+        };
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut raw = rest[1..].to_string();
+                let end = loop {
+                    if let Some(end) = closing_quote(&raw, quote) {
+                        break end;
+                    }
+                    let Some((_, next)) = lines.next() else {
+                        anyhow::bail!(
+                            "unterminated {} quote for {} on line {}",
+                            quote,
+                            key,
+                            line_num
+                        );
+                    };
+                    raw.push('\n');
+                    raw.push_str(next);
+                };
+                if quote == '\'' {
+                    raw[..end].to_string()
+                } else {
+                    substitute(&raw[..end], true, &lookup)
+                }
+            }
+            _ => {
+                let value = match rest.find(" #").or_else(|| rest.find("\t#")) {
+                    Some(comment) => &rest[..comment],
+                    None => rest,
+                };
+                substitute(value.trim_end(), false, &lookup)
+            }
+        };
 
-            if !key.is_empty() {
-                vars.insert(key.to_string(), value.to_string());
+        file_vars.insert(key.to_string(), value.clone());
+        parsed.vars.push((key.to_string(), value));
+    }
+
+    Ok(parsed)
+}
+
+/// Byte offset of the quote closing a value that started with `quote`;
+/// backslashes escape quotes in double-quoted values.
+fn closing_quote(raw: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (offset, c) in raw.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Some(offset);
+        }
+    }
+    None
+}
+
+/// Expands `$VAR`, `${VAR}` and `${VAR:-default}` in `value`, and its escape
+/// sequences: `\$` everywhere, the others only in double-quoted values.
+fn substitute(value: &str, double_quoted: bool, lookup: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek().copied() {
+                Some('$') => {
+                    chars.next();
+                    out.push('$');
+                }
+                Some(escaped @ ('n' | 'r' | 't' | '"' | '\\')) if double_quoted => {
+                    chars.next();
+                    out.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        other => other,
+                    });
+                }
+                _ => out.push('\\'),
+            },
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let mut reference = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    reference.push(c);
+                }
+                if !closed {
+                    // Not a reference after all
+                    out.push_str("${");
+                    out.push_str(&reference);
+                    continue;
+                }
+                let (name, default) = match reference.split_once(":-") {
+                    Some((name, default)) => (name, Some(default)),
+                    None => (reference.as_str(), None),
+                };
+                let value = lookup(name).unwrap_or_default();
+                match default {
+                    Some(default) if value.is_empty() => out.push_str(default),
+                    _ => out.push_str(&value),
+                }
             }
-        } else {
-            eprintln!(
-                "⚠️  Invalid line {} in {}: {}",
-                line_num + 1,
-                path.display(),
-                trimmed
-            );
+            '$' if chars
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_') =>
+            {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                out.push_str(&lookup(&name).unwrap_or_default());
+            }
+            c => out.push(c),
         }
     }
 
-    Ok(vars)
+    out
 }
 
 #[cfg(test)]
@@ -212,6 +509,142 @@ mod tests {
         assert_eq!(list.root_files.len(), 0);
     }
 
+    #[test]
+    fn test_scan_env_files_finds_subdirectories_and_env_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for (sub, name) in [
+            ("", ".env"),
+            ("env", ".env.staging"),
+            ("config/env", ".env.test"),
+            ("config/deep/er", ".env.ignored"),
+            ("node_modules", ".env"),
+            ("ui", ".env"),
+        ] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+            fs::write(dir.join(sub).join(name), "KEY=value").unwrap();
+        }
+
+        fs::write(dir.join("ui/package.json"), "{}").unwrap();
+        let names = |list: EnvFileList| -> Vec<String> {
+            list.package_files
+                .into_iter()
+                .map(|f| f.display_name)
+                .collect()
+        };
+        assert_eq!(
+            names(scan_env_files(dir, &None)),
+            vec![".env", "env/.env.staging"]
+        );
+        assert_eq!(
+            names(scan_env_files_with(dir, &None, &["config/env".to_string()])),
+            vec![".env", "config/env/.env.test", "env/.env.staging"]
+        );
+    }
+
+    #[test]
+    fn test_create_env_file_copies_the_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join(".env.example"), "API_URL=\nDEBUG=0\n").unwrap();
+
+        let path = create_env_file(dir, ".env.local", Some(&dir.join(".env.example"))).unwrap();
+        assert_eq!(path, dir.join(".env.local"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "API_URL=\nDEBUG=0\n");
+
+        let empty = create_env_file(dir, ".env.test", None).unwrap();
+        assert_eq!(fs::read_to_string(empty).unwrap(), "");
+
+        assert_eq!(
+            create_env_file(dir, ".env.local", None)
+                .unwrap_err()
+                .to_string(),
+            ".env.local already exists"
+        );
+        assert!(create_env_file(dir, "secrets", None).is_err());
+        assert!(create_env_file(dir, ".env/../x", None).is_err());
+    }
+
+    #[test]
+    fn test_missing_example_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(
+            dir.join(".env.example"),
+            "API_URL=\nSENTRY_DSN=\nPORT=3000\n",
+        )
+        .unwrap();
+        fs::write(dir.join(".env"), "API_URL=http://localhost\n").unwrap();
+        let files = vec![dir.join(".env")];
+
+        assert_eq!(
+            missing_example_keys(&files, &[]),
+            vec![(
+                ".env.example".to_string(),
+                vec!["SENTRY_DSN".to_string(), "PORT".to_string()]
+            )]
+        );
+        let overrides = [
+            ("SENTRY_DSN".to_string(), String::new()),
+            ("PORT".to_string(), "4000".to_string()),
+        ];
+        assert!(missing_example_keys(&files, &overrides).is_empty());
+        assert!(missing_example_keys(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_encrypted_env_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join(".env.example"), "API_URL=\nTOKEN=\n").unwrap();
+        fs::write(
+            dir.join(".env.production"),
+            "DOTENV_PUBLIC_KEY_PRODUCTION=\"03a1\"\nTOKEN=\"encrypted:BDqD\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join(".env.keys"),
+            "DOTENV_PRIVATE_KEY_PRODUCTION=\"81d0\"\n",
+        )
+        .unwrap();
+
+        // The private keys aren't offered as an env file
+        let files = scan_env_files(dir, &None);
+        let names: Vec<(&str, bool)> = files
+            .all_files()
+            .map(|f| (f.display_name.as_str(), f.encrypted))
+            .collect();
+        assert_eq!(
+            names,
+            vec![(".env.example", false), (".env.production", true)]
+        );
+
+        // Key names are compared without decrypting
+        assert_eq!(
+            missing_example_keys(&[dir.join(".env.production")], &[]),
+            vec![(".env.example".to_string(), vec!["API_URL".to_string()])]
+        );
+        fs::write(
+            dir.join(".env.vault"),
+            "DOTENV_VAULT_PRODUCTION=\"aGVsbG8\"\n",
+        )
+        .unwrap();
+        assert!(missing_example_keys(&[dir.join(".env.vault")], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_missing_example_keys_reads_env_sample() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join(".env.sample"), "export TOKEN=\n").unwrap();
+        fs::write(dir.join(".env"), "DEBUG=1\n").unwrap();
+
+        assert_eq!(
+            missing_example_keys(&[dir.join(".env")], &[]),
+            vec![(".env.sample".to_string(), vec!["TOKEN".to_string()])]
+        );
+    }
+
     #[test]
     fn test_load_env_files_merges_correctly() {
         let temp_dir = TempDir::new().unwrap();
@@ -251,7 +684,10 @@ INVALID LINE
         )
         .unwrap();
 
-        let vars = load_single_env_file(&file).unwrap();
+        let vars: HashMap<String, String> = load_env_file(&file, &HashMap::new())
+            .unwrap()
+            .into_iter()
+            .collect();
 
         assert_eq!(vars.get("KEY1"), Some(&"value1".to_string()));
         assert_eq!(vars.get("KEY2"), Some(&"quoted value".to_string()));
@@ -261,6 +697,77 @@ INVALID LINE
         assert!(!vars.contains_key("INVALID"));
     }
 
+    #[test]
+    fn test_parse_env_handles_export_multiline_and_escapes() {
+        let parsed = parse_env(
+            r#"export HOST=localhost
+PRIVATE_KEY="-----BEGIN KEY-----
+abc\"def
+-----END KEY-----"
+GREETING="Hello\nWorld"  # comment
+LITERAL='no $HOST or \n here'
+MULTI='first
+second'
+URL=http://example.com/#anchor # trailing comment
+PRICE=\$5
+"#,
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.invalid_lines, Vec::<usize>::new());
+        let vars: HashMap<_, _> = parsed.vars.into_iter().collect();
+        assert_eq!(vars["HOST"], "localhost");
+        assert_eq!(
+            vars["PRIVATE_KEY"],
+            "-----BEGIN KEY-----\nabc\"def\n-----END KEY-----"
+        );
+        assert_eq!(vars["GREETING"], "Hello\nWorld");
+        assert_eq!(vars["LITERAL"], r"no $HOST or \n here");
+        assert_eq!(vars["MULTI"], "first\nsecond");
+        assert_eq!(vars["URL"], "http://example.com/#anchor");
+        assert_eq!(vars["PRICE"], "$5");
+    }
+
+    #[test]
+    fn test_parse_env_expands_variables() {
+        let defined = HashMap::from([("ROOT_HOST".to_string(), "api.example.com".to_string())]);
+        let parsed = parse_env(
+            r#"PORT=3000
+URL="https://${ROOT_HOST}:$PORT/v1"
+MODE=${NR_TEST_UNSET_VAR:-development}
+EMPTY=${NR_TEST_UNSET_VAR}
+BROKEN=${UNCLOSED
+"#,
+            &defined,
+        )
+        .unwrap();
+
+        let vars: HashMap<_, _> = parsed.vars.into_iter().collect();
+        assert_eq!(vars["URL"], "https://api.example.com:3000/v1");
+        assert_eq!(vars["MODE"], "development");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars["BROKEN"], "${UNCLOSED");
+    }
+
+    #[test]
+    fn test_parse_env_rejects_unterminated_quotes() {
+        let error = parse_env("OK=1\nBAD=\"never closed\nMORE=2\n", &HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "unterminated \" quote for BAD on line 2");
+    }
+
+    #[test]
+    fn test_load_env_files_expands_from_earlier_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join(".env");
+        let local = temp_dir.path().join(".env.local");
+        fs::write(&root, "API_HOST=localhost").unwrap();
+        fs::write(&local, "API_URL=http://${API_HOST}:8080").unwrap();
+
+        let vars = load_env_files(&[root, local]).unwrap();
+        assert_eq!(vars["API_URL"], "http://localhost:8080");
+    }
+
     #[test]
     fn test_load_env_files_continues_on_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -281,6 +788,18 @@ INVALID LINE
         assert_eq!(vars.get("KEY3"), Some(&"value3".to_string()));
     }
 
+    #[test]
+    fn test_format_exports_sorts_and_quotes() {
+        let mut vars = HashMap::new();
+        vars.insert("B".to_string(), "it's".to_string());
+        vars.insert("A".to_string(), "two words".to_string());
+
+        assert_eq!(
+            format_exports(&vars),
+            "export A='two words'\nexport B='it'\\''s'\n"
+        );
+    }
+
     #[test]
     fn test_env_file_list_all_files() {
         let list = EnvFileList {
@@ -288,11 +807,13 @@ INVALID LINE
                 path: PathBuf::from(".env"),
                 display_name: ".env".to_string(),
                 scope: EnvScope::Package(PathBuf::from(".")),
+                encrypted: false,
             }],
             root_files: vec![EnvFile {
                 path: PathBuf::from("../.env"),
                 display_name: ".env".to_string(),
                 scope: EnvScope::Root(PathBuf::from("..")),
+                encrypted: false,
             }],
         };
 
@@ -310,11 +831,13 @@ INVALID LINE
                 path: PathBuf::from(".env"),
                 display_name: ".env".to_string(),
                 scope: EnvScope::Package(PathBuf::from(".")),
+                encrypted: false,
             }],
             root_files: vec![EnvFile {
                 path: PathBuf::from("../.env"),
                 display_name: ".env".to_string(),
                 scope: EnvScope::Root(PathBuf::from("..")),
+                encrypted: false,
             }],
         };
 
@@ -0,0 +1,21 @@
+//! Exit codes of `nr` itself. Scripts' own exit codes are passed through
+//! unchanged; these sit apart from the usual ones so a wrapping script can
+//! tell why `nr` exited.
+
+/// The run succeeded, or nothing needed running.
+pub const SUCCESS: i32 = 0;
+
+/// `nr` itself failed: no project found, an invalid `--root`, no usable terminal.
+pub const ERROR: i32 = 125;
+
+/// The script asked for doesn't exist, or the project has no scripts at all.
+pub const NO_SCRIPT: i32 = 127;
+
+/// The user backed out: left a `required_env` prompt empty, or, with
+/// `--fail-on-cancel`, left the TUI or the menu without running anything.
+pub const CANCELLED: i32 = 130;
+
+/// Exit code for leaving without running anything.
+pub fn on_cancel(fail_on_cancel: bool) -> i32 {
+    if fail_on_cancel { CANCELLED } else { SUCCESS }
+}
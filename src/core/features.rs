@@ -0,0 +1,81 @@
+//! Optional integrations, each behind a Cargo feature of the same name so a
+//! minimal build can leave them out (`cargo build --no-default-features`).
+//!
+//! Each integration sits behind a capability the rest of nr calls through
+//! ([`Clipboard`], [`Notifier`], [`Releases`], [`FileWatch`]). Without its
+//! feature the implementation's code isn't compiled; a stub stands in that
+//! fails with [`disabled`] or does nothing, so callers don't need to know how
+//! nr was built.
+//!
+//! [`Clipboard`]: crate::core::clipboard::Clipboard
+//! [`Notifier`]: crate::core::notify::Notifier
+//! [`Releases`]: crate::core::update::Releases
+//! [`FileWatch`]: crate::core::watch::FileWatch
+
+/// An optional integration and whether this build includes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feature {
+    /// The Cargo feature that enables it.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+}
+
+/// Every optional integration, in the order `nr doctor` lists them.
+pub const FEATURES: [Feature; 4] = [
+    Feature {
+        name: "clipboard",
+        description: "copying commands to the clipboard",
+        enabled: cfg!(feature = "clipboard"),
+    },
+    Feature {
+        name: "desktop-notify",
+        description: "desktop notifications when long runs finish",
+        enabled: cfg!(feature = "desktop-notify"),
+    },
+    Feature {
+        name: "self-update",
+        description: "`nr self-update` and the new release check",
+        enabled: cfg!(feature = "self-update"),
+    },
+    Feature {
+        name: "watch",
+        description: "watch mode (re-running scripts when files change)",
+        enabled: cfg!(feature = "watch"),
+    },
+];
+
+/// The error for using the integration behind feature `name` in a build
+/// without it.
+pub fn disabled(name: &str) -> anyhow::Error {
+    anyhow::anyhow!("nr was built without the `{}` feature", name)
+}
+
+/// `nr doctor`'s summary of the integrations left out of this build.
+pub fn report() -> String {
+    let missing: Vec<&Feature> = FEATURES.iter().filter(|f| !f.enabled).collect();
+    if missing.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("\nBuilt without:\n");
+    for feature in missing {
+        out.push_str(&format!("  - {} ({})\n", feature.name, feature.description));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_only_missing_features() {
+        let missing = FEATURES.iter().filter(|f| !f.enabled).count();
+        let report = report();
+        assert_eq!(report.matches("\n  - ").count(), missing);
+        assert_eq!(
+            disabled("watch").to_string(),
+            "nr was built without the `watch` feature"
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use crate::core::inherit_env::InheritEnv;
+use crate::core::package_json::PackageJson;
+use crate::core::package_manager::PackageManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// When a post-run hook fires, based on the script's exit code.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HookWhen {
+    #[default]
+    Always,
+    Success,
+    Failure,
+}
+
+/// Something to do after a script exits, configured under `post_run` in `config.json`.
+///
+/// Exactly one of `run` (a shell command) or `script` (another script of the same
+/// package, run through the package manager) should be set. `{script}` and
+/// `{exit_code}` in `run` are replaced before the command is executed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct PostRunHook {
+    /// Shell command, e.g. `notify-send nr "{script} exited with {exit_code}"`.
+    pub run: Option<String>,
+    /// Script to run next, e.g. `cleanup`.
+    pub script: Option<String>,
+    pub when: HookWhen,
+    /// Only fire after these scripts; empty means every script.
+    pub scripts: Vec<String>,
+}
+
+impl PostRunHook {
+    /// Whether the hook is configured for `script_name` (ignoring the exit code).
+    pub fn matches_script(&self, script_name: &str) -> bool {
+        self.scripts.is_empty() || self.scripts.iter().any(|s| s == script_name)
+    }
+
+    /// Whether the hook fires after `script_name` exited with `exit_code`.
+    pub fn applies_to(&self, script_name: &str, exit_code: i32) -> bool {
+        let when = match self.when {
+            HookWhen::Always => true,
+            HookWhen::Success => exit_code == 0,
+            HookWhen::Failure => exit_code != 0,
+        };
+        when && self.matches_script(script_name)
+    }
+
+    /// One-line summary for the confirm screen, e.g. `on success: nr cleanup`.
+    pub fn describe(&self) -> String {
+        let when = match self.when {
+            HookWhen::Always => "always",
+            HookWhen::Success => "on success",
+            HookWhen::Failure => "on failure",
+        };
+        match (&self.run, &self.script) {
+            (Some(run), _) => format!("{}: {}", when, run),
+            (None, Some(script)) => format!("{}: nr {}", when, script),
+            (None, None) => format!("{}: (nothing to run)", when),
+        }
+    }
+}
+
+/// Shell commands run around a script, from the `nr.hooks` block of the
+/// package's `package.json` (see [`PackageJson::script_hooks`]), so they are
+/// checked in with the project, e.g.
+/// `"hooks": { "dev": { "before": "docker compose up -d" } }`.
+///
+/// [`PackageJson::script_hooks`]: crate::core::package_json::PackageJson::script_hooks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptHooks {
+    /// Run in order before the script; the first failure cancels the run.
+    pub before: Vec<String>,
+    /// Run in order after the script, whatever its exit code.
+    pub after: Vec<String>,
+}
+
+impl ScriptHooks {
+    /// The hooks of `script_name` in the `package.json` in `cwd`; none if it
+    /// can't be read.
+    pub fn load(cwd: &Path, script_name: &str) -> Self {
+        PackageJson::load(cwd)
+            .map(|pkg| pkg.script_hooks(script_name))
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+/// Whether runs whose output nr reads (panes, background jobs, parallel runs)
+/// get the [`ScriptHooks`] too. They are wrapped in a `sh` script there (see
+/// [`wrap_with_hooks`]), so on Windows only terminal runs have them.
+pub const PIPED_HOOKS: bool = cfg!(unix);
+
+/// Runs `run` (the script) between its `before` and `after` hooks, in `cwd`
+/// with the script's `env_vars` and `NR_SCRIPT` set (`NR_EXIT_CODE` too after
+/// it). A failing `before` command stops there: the script doesn't run and
+/// the command's exit code is returned. Failures of `after` commands are
+/// reported but don't change the script's exit code.
+pub fn run_with_hooks(
+    hooks: &ScriptHooks,
+    script_name: &str,
+    cwd: &Path,
+    env_vars: &HashMap<String, String>,
+    inherit: &InheritEnv,
+    run: impl FnOnce() -> i32,
+) -> i32 {
+    let hook_command = |command: &str| {
+        let mut cmd = shell_command(command);
+        inherit.apply(&mut cmd);
+        cmd.envs(env_vars)
+            .current_dir(cwd)
+            .env("NR_SCRIPT", script_name);
+        cmd
+    };
+
+    for command in &hooks.before {
+        eprintln!("▶ before {}: {}", script_name, command);
+        let code = match hook_command(command).status() {
+            Ok(status) => status.code().unwrap_or(1),
+            Err(e) => {
+                eprintln!("❌ Couldn't run the before hook of {}: {}", script_name, e);
+                1
+            }
+        };
+        if code != 0 {
+            eprintln!(
+                "❌ Before hook '{}' exited with {}; {} was not run",
+                command, code, script_name
+            );
+            return code;
+        }
+    }
+
+    let exit_code = run();
+
+    for command in &hooks.after {
+        eprintln!("▶ after {}: {}", script_name, command);
+        match hook_command(command)
+            .env("NR_EXIT_CODE", exit_code.to_string())
+            .status()
+        {
+            Ok(status) if status.success() => {}
+            Ok(status) => eprintln!(
+                "⚠️  After hook '{}' exited with {}",
+                command,
+                status.code().unwrap_or(1)
+            ),
+            Err(e) => eprintln!("⚠️  Couldn't run the after hook '{}': {}", command, e),
+        }
+    }
+    exit_code
+}
+
+/// `argv` wrapped in a `sh` script that runs `hooks` around it the way
+/// [`run_with_hooks`] does, for runs whose output nr reads. Returned as is
+/// when there are no hooks or they can't be wrapped (see [`PIPED_HOOKS`]).
+pub fn wrap_with_hooks(hooks: &ScriptHooks, script_name: &str, argv: Vec<String>) -> Vec<String> {
+    if !PIPED_HOOKS || hooks.is_empty() {
+        return argv;
+    }
+    let quote = |text: String| shell_words::quote(&text).into_owned();
+
+    let mut script = vec![format!("NR_SCRIPT={}", quote(script_name.to_string()))];
+    for command in &hooks.before {
+        script.push(format!(
+            "echo {} >&2",
+            quote(format!("▶ before {}: {}", script_name, command))
+        ));
+        script.push(format!(
+            "(export NR_SCRIPT\n{}\n) || {{ code=$?; echo {}\"$code\"{} >&2; exit $code; }}",
+            command,
+            quote(format!("❌ Before hook '{}' exited with ", command)),
+            quote(format!("; {} was not run", script_name)),
+        ));
+    }
+    script.push("\"$@\"; NR_EXIT_CODE=$?".to_string());
+    for command in &hooks.after {
+        script.push(format!(
+            "echo {} >&2",
+            quote(format!("▶ after {}: {}", script_name, command))
+        ));
+        script.push(format!(
+            "(export NR_SCRIPT NR_EXIT_CODE\n{}\n) || echo {}\"$?\" >&2",
+            command,
+            quote(format!("⚠️  After hook '{}' exited with ", command)),
+        ));
+    }
+    script.push("exit $NR_EXIT_CODE".to_string());
+
+    let mut wrapped = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        script.join("\n"),
+        "sh".to_string(),
+    ];
+    wrapped.extend(argv);
+    wrapped
+}
+
+/// Runs the hooks that apply after `script_name` exited with `exit_code`.
+///
+/// Hooks run in order in the script's `cwd` with the script's terminal. A hook's
+/// own failure is reported but never changes the script's exit code.
+pub fn run_post_hooks(
+    hooks: &[PostRunHook],
+    pm: PackageManager,
+    script_name: &str,
+    cwd: &Path,
+    exit_code: i32,
+    template: Option<&str>,
+    inherit: &InheritEnv,
+) {
+    for hook in hooks
+        .iter()
+        .filter(|h| h.applies_to(script_name, exit_code))
+    {
+        let hook_code = if let Some(ref run) = hook.run {
+            let command = expand_placeholders(run, script_name, exit_code);
+            let mut cmd = shell_command(&command);
+            inherit.apply(&mut cmd);
+            match cmd
+                .current_dir(cwd)
+                .env("NR_SCRIPT", script_name)
+                .env("NR_EXIT_CODE", exit_code.to_string())
+                .status()
+            {
+                Ok(s) => s.code().unwrap_or(1),
+                Err(e) => {
+                    eprintln!("⚠️  Failed to run post-run hook '{}': {}", command, e);
+                    continue;
+                }
+            }
+        } else if let Some(ref script) = hook.script {
+            crate::core::runner::run_script(pm, script, cwd, template, inherit)
+        } else {
+            continue;
+        };
+
+        if hook_code != 0 {
+            eprintln!(
+                "⚠️  Post-run hook ({}) exited with code {}",
+                hook.describe(),
+                hook_code
+            );
+        }
+    }
+}
+
+fn expand_placeholders(command: &str, script_name: &str, exit_code: i32) -> String {
+    command
+        .replace("{script}", script_name)
+        .replace("{exit_code}", &exit_code.to_string())
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(when: HookWhen, scripts: &[&str]) -> PostRunHook {
+        PostRunHook {
+            run: Some("true".to_string()),
+            when,
+            scripts: scripts.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_applies_to_respects_exit_code() {
+        assert!(hook(HookWhen::Always, &[]).applies_to("build", 1));
+        assert!(hook(HookWhen::Success, &[]).applies_to("build", 0));
+        assert!(!hook(HookWhen::Success, &[]).applies_to("build", 2));
+        assert!(hook(HookWhen::Failure, &[]).applies_to("build", 2));
+        assert!(!hook(HookWhen::Failure, &[]).applies_to("build", 0));
+    }
+
+    #[test]
+    fn test_applies_to_respects_script_filter() {
+        let hook = hook(HookWhen::Always, &["build", "test"]);
+        assert!(hook.applies_to("test", 0));
+        assert!(!hook.applies_to("dev", 0));
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        assert_eq!(
+            expand_placeholders("echo {script} -> {exit_code}", "build", 3),
+            "echo build -> 3"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_from_config() {
+        let hooks: Vec<PostRunHook> = serde_json::from_str(
+            r#"[{"run": "notify-send done"}, {"script": "cleanup", "when": "success"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(hooks[0].when, HookWhen::Always);
+        assert_eq!(hooks[1].describe(), "on success: nr cleanup");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_hooks_wraps_the_script() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks = ScriptHooks {
+            before: vec!["echo \"up $NR_SCRIPT $PORT\" >> log.txt".to_string()],
+            after: vec![
+                "echo \"down $NR_EXIT_CODE\" >> log.txt".to_string(),
+                "false".to_string(),
+            ],
+        };
+        let env_vars = HashMap::from([("PORT".to_string(), "3000".to_string())]);
+        let log = temp_dir.path().join("log.txt");
+
+        let code = run_with_hooks(
+            &hooks,
+            "dev",
+            temp_dir.path(),
+            &env_vars,
+            &InheritEnv::default(),
+            || {
+                std::fs::write(
+                    &log,
+                    format!("{}run\n", std::fs::read_to_string(&log).unwrap()),
+                )
+                .unwrap();
+                3
+            },
+        );
+
+        // A failing after hook doesn't change the script's exit code
+        assert_eq!(code, 3);
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "up dev 3000\nrun\ndown 3\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_before_hook_cancels_the_script() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks = ScriptHooks {
+            before: vec!["exit 7".to_string(), "touch second".to_string()],
+            after: vec!["touch after".to_string()],
+        };
+        let mut ran = false;
+
+        let code = run_with_hooks(
+            &hooks,
+            "dev",
+            temp_dir.path(),
+            &HashMap::new(),
+            &InheritEnv::default(),
+            || {
+                ran = true;
+                0
+            },
+        );
+
+        assert_eq!(code, 7);
+        assert!(!ran);
+        assert!(!temp_dir.path().join("second").exists());
+        assert!(!temp_dir.path().join("after").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wrapped_script_runs_between_its_hooks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks = ScriptHooks {
+            before: vec!["echo \"up $NR_SCRIPT\" >> log.txt".to_string()],
+            after: vec![
+                "echo \"down $NR_EXIT_CODE\" >> log.txt".to_string(),
+                "false".to_string(),
+            ],
+        };
+        let argv = [
+            "sh",
+            "-c",
+            "echo \"run $1\" >> log.txt; exit 3",
+            "sh",
+            "it's",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        let wrapped = wrap_with_hooks(&hooks, "dev", argv);
+        let status = Command::new(&wrapped[0])
+            .args(&wrapped[1..])
+            .current_dir(temp_dir.path())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("log.txt")).unwrap(),
+            "up dev\nrun it's\ndown 3\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wrapped_script_is_cancelled_by_its_before_hook() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks = ScriptHooks {
+            before: vec!["exit 7".to_string()],
+            after: vec!["touch after".to_string()],
+        };
+        let argv = vec!["touch".to_string(), "ran".to_string()];
+
+        let wrapped = wrap_with_hooks(&hooks, "dev", argv);
+        let status = Command::new(&wrapped[0])
+            .args(&wrapped[1..])
+            .current_dir(temp_dir.path())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+
+        assert_eq!(status.code(), Some(7));
+        assert!(!temp_dir.path().join("ran").exists());
+        assert!(!temp_dir.path().join("after").exists());
+    }
+
+    #[test]
+    fn test_nothing_to_wrap_without_hooks() {
+        let argv = vec!["npm".to_string(), "run".to_string(), "dev".to_string()];
+        assert_eq!(
+            wrap_with_hooks(&ScriptHooks::default(), "dev", argv.clone()),
+            argv
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_post_hooks_sets_env_and_cwd() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks = vec![PostRunHook {
+            run: Some("echo \"$NR_SCRIPT:$NR_EXIT_CODE\" > out.txt".to_string()),
+            ..Default::default()
+        }];
+
+        run_post_hooks(
+            &hooks,
+            PackageManager::Npm,
+            "build",
+            temp_dir.path(),
+            0,
+            None,
+            &InheritEnv::default(),
+        );
+
+        let out = std::fs::read_to_string(temp_dir.path().join("out.txt")).unwrap();
+        assert_eq!(out.trim(), "build:0");
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Which of nr's own environment variables scripts inherit, configured under
+/// `inherit_env` in `config.json`, e.g. `{ "deny": ["NODE_ENV", "CI", "GITHUB_*"] }`.
+///
+/// Both lists hold globs over variable names. With an `allow` list only matching
+/// variables are passed on (`PATH` always is); `deny` then removes from what's
+/// left. Variables from env files are set on top and never filtered.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct InheritEnv {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+/// Kept with any `allow` list so the package manager can still be found.
+const ALWAYS_INHERITED: &str = "PATH";
+
+impl InheritEnv {
+    /// Whether every variable is inherited, as without the config.
+    pub fn is_unfiltered(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// The names among `names` that scripts don't inherit.
+    pub fn blocked<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        if self.is_unfiltered() {
+            return Vec::new();
+        }
+        let allow = build_set(&self.allow);
+        let deny = build_set(&self.deny);
+        names
+            .into_iter()
+            .filter(|name| {
+                let allowed = self.allow.is_empty()
+                    || allow.is_match(name)
+                    || name.eq_ignore_ascii_case(ALWAYS_INHERITED);
+                !allowed || deny.is_match(name)
+            })
+            .collect()
+    }
+
+    /// Removes the variables scripts don't inherit from `cmd`'s environment.
+    /// Call before setting the script's own variables on `cmd`.
+    pub fn apply(&self, cmd: &mut Command) {
+        if self.is_unfiltered() {
+            return;
+        }
+        let names: Vec<String> = std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .collect();
+        for name in self.blocked(names.iter().map(String::as_str)) {
+            cmd.env_remove(name);
+        }
+    }
+}
+
+/// Compiles variable name globs; invalid patterns are ignored.
+fn build_set(patterns: &[String]) -> globset::GlobSet {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = globset::Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder
+        .build()
+        .unwrap_or_else(|_| globset::GlobSet::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: [&str; 5] = ["PATH", "HOME", "NODE_ENV", "CI", "GITHUB_SHA"];
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_deny_list_blocks_matching_names() {
+        let inherit = InheritEnv {
+            deny: patterns(&["NODE_ENV", "CI", "GITHUB_*"]),
+            ..Default::default()
+        };
+        assert_eq!(inherit.blocked(NAMES), vec!["NODE_ENV", "CI", "GITHUB_SHA"]);
+    }
+
+    #[test]
+    fn test_allow_list_keeps_path_and_deny_still_applies() {
+        let inherit = InheritEnv {
+            allow: patterns(&["HOME", "GITHUB_*"]),
+            deny: patterns(&["GITHUB_SHA"]),
+        };
+        assert_eq!(inherit.blocked(NAMES), vec!["NODE_ENV", "CI", "GITHUB_SHA"]);
+        assert!(InheritEnv::default().blocked(NAMES).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_removes_blocked_variables_from_command() {
+        let inherit = InheritEnv {
+            deny: patterns(&["HOME"]),
+            ..Default::default()
+        };
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo \"${HOME:-unset}\""]);
+        inherit.apply(&mut cmd);
+
+        let output = cmd.output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "unset");
+    }
+}
@@ -0,0 +1,383 @@
+use crate::core::env_files::load_env_files;
+use crate::core::inherit_env::InheritEnv;
+use crate::core::package_manager::PackageManager;
+use crate::core::runner::{ExecutionStrategy, OutputLine, PipedRun, Signal, spawn_piped};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Most lines kept per job; older lines are dropped.
+const MAX_OUTPUT_LINES: usize = 10_000;
+
+/// Identifies a job for as long as the registry lives; IDs are never reused.
+pub type JobId = usize;
+
+/// What a job runs: the same inputs as a `RunScript` action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobSpec {
+    pub script_name: String,
+    pub cwd: PathBuf,
+    /// Env files in merge order (root → package).
+    pub env_files: Vec<PathBuf>,
+    /// Variables set on top of the env files, e.g. values entered for `required_env`.
+    pub env: Vec<(String, String)>,
+    pub args: String,
+}
+
+/// A script started inside the TUI, running or exited.
+pub struct Job {
+    pub id: JobId,
+    pub spec: JobSpec,
+    pub lines: Vec<OutputLine>,
+    pub started: Instant,
+    /// Exit code and duration once the process has exited.
+    pub finished: Option<(i32, Duration)>,
+    /// Ctrl+C presses so far; each one escalates SIGINT → SIGTERM → SIGKILL.
+    pub interrupts: u8,
+    process: Option<PipedRun>,
+}
+
+impl Job {
+    pub fn is_running(&self) -> bool {
+        self.finished.is_none()
+    }
+
+    /// Time since start, frozen once the job has exited.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        match self.finished {
+            Some((_, duration)) => duration,
+            None => now.saturating_duration_since(self.started),
+        }
+    }
+
+    fn push_lines(&mut self, lines: Vec<OutputLine>) {
+        self.lines.extend(lines);
+        if self.lines.len() > MAX_OUTPUT_LINES {
+            let excess = self.lines.len() - MAX_OUTPUT_LINES;
+            self.lines.drain(..excess);
+        }
+    }
+
+    /// (Re)spawns the job's process, clearing previous output.
+    fn spawn(
+        &mut self,
+        pm: PackageManager,
+        template: Option<&str>,
+        strategy: &ExecutionStrategy,
+        inherit: &InheritEnv,
+    ) {
+        self.lines.clear();
+        self.started = Instant::now();
+        self.finished = None;
+        self.interrupts = 0;
+
+        let mut env_vars = load_env_files(&self.spec.env_files).unwrap_or_default();
+        env_vars.extend(self.spec.env.iter().cloned());
+        match spawn_piped(
+            pm,
+            &self.spec.script_name,
+            &self.spec.cwd,
+            env_vars,
+            &self.spec.args,
+            template,
+            strategy,
+            inherit,
+        ) {
+            Ok(process) => self.process = Some(process),
+            Err(e) => {
+                self.lines.push(OutputLine {
+                    text: format!("Failed to start {}: {}", self.spec.script_name, e),
+                    is_stderr: true,
+                });
+                self.finished = Some((1, Duration::ZERO));
+            }
+        }
+    }
+}
+
+/// Something that happened to a job since the last [`JobRegistry::poll`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobEvent {
+    /// New output lines were appended to the job.
+    Output { id: JobId, count: usize },
+    /// The job's process exited.
+    Exited { id: JobId, exit_code: i32 },
+}
+
+/// Outcome of [`JobRegistry::interrupt`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interrupt {
+    /// A signal was sent; the script may still be shutting down.
+    Signalled(Signal),
+    /// The script was killed outright.
+    Killed,
+}
+
+/// All scripts started inside the TUI, in launch order.
+///
+/// Each job's output is read on background threads; the event loop calls
+/// [`JobRegistry::poll`] to collect it and learn which jobs have exited.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Vec<Job>,
+    next_id: JobId,
+}
+
+impl JobRegistry {
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.iter()
+    }
+
+    /// Number of jobs whose process is still alive.
+    pub fn running_count(&self) -> usize {
+        self.jobs.iter().filter(|j| j.is_running()).count()
+    }
+
+    /// A running job of the same script in the same directory as `spec`.
+    pub fn running_instance(&self, spec: &JobSpec) -> Option<JobId> {
+        self.jobs
+            .iter()
+            .find(|j| {
+                j.is_running() && j.spec.script_name == spec.script_name && j.spec.cwd == spec.cwd
+            })
+            .map(|j| j.id)
+    }
+
+    pub fn get(&self, id: JobId) -> Option<&Job> {
+        self.jobs.iter().find(|j| j.id == id)
+    }
+
+    fn get_mut(&mut self, id: JobId) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|j| j.id == id)
+    }
+
+    /// Starts `spec` as a new job via `pm` and the user's invocation template.
+    ///
+    /// A failure to spawn is recorded as a job that exited with code 1 and the
+    /// error as its output.
+    pub fn spawn(
+        &mut self,
+        spec: JobSpec,
+        pm: PackageManager,
+        template: Option<&str>,
+        strategy: &ExecutionStrategy,
+        inherit: &InheritEnv,
+    ) -> JobId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut job = Job {
+            id,
+            spec,
+            lines: Vec::new(),
+            started: Instant::now(),
+            finished: None,
+            interrupts: 0,
+            process: None,
+        };
+        job.spawn(pm, template, strategy, inherit);
+        self.jobs.push(job);
+        id
+    }
+
+    /// Kills the job if it is still running and starts it again with fresh output.
+    pub fn restart(
+        &mut self,
+        id: JobId,
+        pm: PackageManager,
+        template: Option<&str>,
+        strategy: &ExecutionStrategy,
+        inherit: &InheritEnv,
+    ) {
+        if let Some(job) = self.get_mut(id) {
+            if let Some(mut process) = job.process.take() {
+                process.kill();
+            }
+            job.spawn(pm, template, strategy, inherit);
+        }
+    }
+
+    /// Sends the next signal in the escalation sequence to a running job.
+    ///
+    /// Returns `None` if the job does not exist or has already exited.
+    pub fn interrupt(&mut self, id: JobId) -> Option<std::io::Result<Interrupt>> {
+        let job = self.get_mut(id)?;
+        let process = job.process.as_mut()?;
+
+        let result = match job.interrupts {
+            0 => process
+                .signal(Signal::Interrupt)
+                .map(|()| Interrupt::Signalled(Signal::Interrupt)),
+            1 => process
+                .signal(Signal::Terminate)
+                .map(|()| Interrupt::Signalled(Signal::Terminate)),
+            _ => {
+                process.kill();
+                Ok(Interrupt::Killed)
+            }
+        };
+        job.interrupts = job.interrupts.saturating_add(1);
+        Some(result)
+    }
+
+    /// Removes a job, killing it first if it is still running.
+    pub fn remove(&mut self, id: JobId) {
+        // Dropping the process kills it
+        self.jobs.retain(|j| j.id != id);
+    }
+
+    /// Collects output and exit statuses from all running jobs.
+    pub fn poll(&mut self) -> Vec<JobEvent> {
+        let mut events = Vec::new();
+        for job in &mut self.jobs {
+            let Some(ref mut process) = job.process else {
+                continue;
+            };
+
+            let (lines, exit_code) = process.poll();
+            if !lines.is_empty() {
+                events.push(JobEvent::Output {
+                    id: job.id,
+                    count: lines.len(),
+                });
+                job.push_lines(lines);
+            }
+            if let Some(exit_code) = exit_code {
+                job.finished = Some((exit_code, job.started.elapsed()));
+                job.process = None;
+                events.push(JobEvent::Exited {
+                    id: job.id,
+                    exit_code,
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const TEMPLATE: Option<&str> = Some("sh run.sh {script}");
+
+    fn spawn(jobs: &mut JobRegistry, dir: &Path, script_name: &str) -> JobId {
+        let spec = JobSpec {
+            script_name: script_name.to_string(),
+            cwd: dir.to_path_buf(),
+            env_files: vec![],
+            env: vec![],
+            args: String::new(),
+        };
+        jobs.spawn(
+            spec,
+            PackageManager::Npm,
+            TEMPLATE,
+            &ExecutionStrategy::default(),
+            &InheritEnv::default(),
+        )
+    }
+
+    fn poll_until_idle(jobs: &mut JobRegistry) -> Vec<JobEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = Vec::new();
+        while jobs.running_count() > 0 && Instant::now() < deadline {
+            events.extend(jobs.poll());
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        events
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_jobs_run_concurrently_and_report_exit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("run.sh"), "echo \"hello $1\"; exit 2\n").unwrap();
+        let mut jobs = JobRegistry::default();
+
+        let a = spawn(&mut jobs, tmp.path(), "lint");
+        let b = spawn(&mut jobs, tmp.path(), "test");
+        assert_ne!(a, b);
+        assert_eq!(jobs.len(), 2);
+
+        let events = poll_until_idle(&mut jobs);
+
+        assert!(events.contains(&JobEvent::Exited {
+            id: a,
+            exit_code: 2
+        }));
+        assert!(events.contains(&JobEvent::Exited {
+            id: b,
+            exit_code: 2
+        }));
+        assert_eq!(jobs.get(a).unwrap().lines[0].text, "hello lint");
+        assert_eq!(jobs.get(b).unwrap().lines[0].text, "hello test");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restart_and_remove() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("run.sh"), "echo run >> runs.log\n").unwrap();
+        let mut jobs = JobRegistry::default();
+
+        let id = spawn(&mut jobs, tmp.path(), "build");
+        poll_until_idle(&mut jobs);
+        jobs.restart(
+            id,
+            PackageManager::Npm,
+            TEMPLATE,
+            &ExecutionStrategy::default(),
+            &InheritEnv::default(),
+        );
+        assert!(jobs.get(id).unwrap().is_running());
+        poll_until_idle(&mut jobs);
+
+        let log = std::fs::read_to_string(tmp.path().join("runs.log")).unwrap();
+        assert_eq!(log.lines().count(), 2);
+
+        jobs.remove(id);
+        assert!(jobs.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_job_runs_between_its_package_json_hooks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("run.sh"), "echo \"run $1\"; exit 4\n").unwrap();
+        std::fs::write(
+            tmp.path().join("package.json"),
+            r#"{"nr": {"hooks": {"dev": {"before": "echo up", "after": "echo down $NR_EXIT_CODE"}}}}"#,
+        )
+        .unwrap();
+        let mut jobs = JobRegistry::default();
+
+        let id = spawn(&mut jobs, tmp.path(), "dev");
+        let events = poll_until_idle(&mut jobs);
+
+        assert!(events.contains(&JobEvent::Exited { id, exit_code: 4 }));
+        let stdout: Vec<&str> = jobs
+            .get(id)
+            .unwrap()
+            .lines
+            .iter()
+            .filter(|line| !line.is_stderr)
+            .map(|line| line.text.as_str())
+            .collect();
+        assert_eq!(stdout, vec!["up", "run dev", "down 4"]);
+    }
+
+    #[test]
+    fn test_interrupt_ignores_unknown_job() {
+        let mut jobs = JobRegistry::default();
+        assert!(jobs.interrupt(42).is_none());
+    }
+}
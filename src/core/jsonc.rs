@@ -0,0 +1,62 @@
+//! JSON with comments, as written in `deno.jsonc`, `rush.json` and friends.
+
+/// JSON with comments (`//`, `/* */`) and trailing commas, as plain JSON.
+pub fn strip_jsonc(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            '}' | ']' => {
+                // Drop a trailing comma before the closing bracket
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_comments_and_trailing_commas_outside_strings() {
+        let source = "{\n  // note\n  \"url\": \"http://a/*b*/\", /* c */\n  \"list\": [1, 2,],\n}";
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(source)).unwrap();
+        assert_eq!(value["url"], "http://a/*b*/");
+        assert_eq!(value["list"], serde_json::json!([1, 2]));
+    }
+}
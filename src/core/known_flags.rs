@@ -0,0 +1,542 @@
+/// CLI flags of a well-known tool, used to warn about typos in extra arguments.
+pub struct FlagTable {
+    pub tool: &'static str,
+    flags: &'static [&'static str],
+}
+
+/// Binaries with bundled flag tables. Flags cover every subcommand of the tool.
+const TABLES: [FlagTable; 5] = [
+    FlagTable {
+        tool: "vite",
+        flags: &[
+            "-h",
+            "--help",
+            "-v",
+            "--version",
+            "-c",
+            "--config",
+            "--base",
+            "-l",
+            "--logLevel",
+            "--clearScreen",
+            "--configLoader",
+            "-d",
+            "--debug",
+            "-f",
+            "--filter",
+            "-m",
+            "--mode",
+            "--host",
+            "--port",
+            "--open",
+            "--cors",
+            "--strictPort",
+            "--force",
+            "--target",
+            "--outDir",
+            "--assetsDir",
+            "--assetsInlineLimit",
+            "--ssr",
+            "--sourcemap",
+            "--minify",
+            "--manifest",
+            "--ssrManifest",
+            "--emptyOutDir",
+            "-w",
+            "--watch",
+            "--app",
+            "--profile",
+        ],
+    },
+    FlagTable {
+        tool: "vitest",
+        flags: &[
+            "-h",
+            "--help",
+            "-v",
+            "--version",
+            "-c",
+            "--config",
+            "-r",
+            "--root",
+            "--dir",
+            "-u",
+            "--update",
+            "-w",
+            "--watch",
+            "--run",
+            "-t",
+            "--testNamePattern",
+            "--ui",
+            "--open",
+            "--api",
+            "--silent",
+            "--hideSkippedTests",
+            "--reporter",
+            "--outputFile",
+            "--coverage",
+            "--mode",
+            "--workspace",
+            "--project",
+            "--isolate",
+            "--globals",
+            "--dom",
+            "--browser",
+            "--pool",
+            "--poolOptions",
+            "--fileParallelism",
+            "--maxWorkers",
+            "--minWorkers",
+            "--environment",
+            "--typecheck",
+            "--passWithNoTests",
+            "--logHeapUsage",
+            "--allowOnly",
+            "--dangerouslyIgnoreUnhandledErrors",
+            "--shard",
+            "--changed",
+            "--sequence",
+            "--inspect",
+            "--inspectBrk",
+            "--testTimeout",
+            "--hookTimeout",
+            "--teardownTimeout",
+            "--slowTestThreshold",
+            "--bail",
+            "--retry",
+            "--diff",
+            "--exclude",
+            "--expandSnapshotDiff",
+            "--disableConsoleIntercept",
+            "--printConsoleTrace",
+            "--includeTaskLocation",
+            "--clearScreen",
+            "--configLoader",
+            "--standalone",
+            "--clearCache",
+            "--mergeReports",
+            "--cache",
+            "--color",
+            "--execArgv",
+            "--vmMemoryLimit",
+            "--includeSource",
+            "--related",
+        ],
+    },
+    FlagTable {
+        tool: "jest",
+        flags: &[
+            "-h",
+            "--help",
+            "-v",
+            "--version",
+            "-c",
+            "--config",
+            "--all",
+            "--automock",
+            "-b",
+            "--bail",
+            "--cache",
+            "--cacheDirectory",
+            "--changedFilesWithAncestor",
+            "--changedSince",
+            "--ci",
+            "--clearCache",
+            "--clearMocks",
+            "--collectCoverage",
+            "--collectCoverageFrom",
+            "--color",
+            "--colors",
+            "--coverage",
+            "--coverageDirectory",
+            "--coveragePathIgnorePatterns",
+            "--coverageProvider",
+            "--coverageReporters",
+            "--coverageThreshold",
+            "--debug",
+            "--detectLeaks",
+            "--detectOpenHandles",
+            "--env",
+            "--errorOnDeprecated",
+            "-e",
+            "--expand",
+            "--filter",
+            "--findRelatedTests",
+            "--forceExit",
+            "--globalSetup",
+            "--globalTeardown",
+            "--globals",
+            "--ignoreProjects",
+            "--init",
+            "--injectGlobals",
+            "--json",
+            "--lastCommit",
+            "--listTests",
+            "--logHeapUsage",
+            "--maxConcurrency",
+            "-w",
+            "--maxWorkers",
+            "--moduleDirectories",
+            "--moduleFileExtensions",
+            "--moduleNameMapper",
+            "--modulePathIgnorePatterns",
+            "--modulePaths",
+            "--noStackTrace",
+            "--notify",
+            "--notifyMode",
+            "-o",
+            "--onlyChanged",
+            "-f",
+            "--onlyFailures",
+            "--openHandlesTimeout",
+            "--outputFile",
+            "--passWithNoTests",
+            "--preset",
+            "--prettierPath",
+            "--projects",
+            "--randomize",
+            "--reporters",
+            "--resetMocks",
+            "--resetModules",
+            "--resolver",
+            "--restoreMocks",
+            "--rootDir",
+            "--roots",
+            "-i",
+            "--runInBand",
+            "--runTestsByPath",
+            "--runner",
+            "--seed",
+            "--selectProjects",
+            "--setupFiles",
+            "--setupFilesAfterEnv",
+            "--shard",
+            "--showConfig",
+            "--showSeed",
+            "--silent",
+            "--snapshotSerializers",
+            "-t",
+            "--testNamePattern",
+            "--testEnvironment",
+            "--testEnvironmentOptions",
+            "--testFailureExitCode",
+            "--testLocationInResults",
+            "--testMatch",
+            "--testPathIgnorePatterns",
+            "--testPathPattern",
+            "--testPathPatterns",
+            "--testRegex",
+            "--testResultsProcessor",
+            "--testRunner",
+            "--testSequencer",
+            "--testTimeout",
+            "--transform",
+            "--transformIgnorePatterns",
+            "--unmockedModulePathPatterns",
+            "-u",
+            "--updateSnapshot",
+            "--useStderr",
+            "--verbose",
+            "--watch",
+            "--watchAll",
+            "--watchPathIgnorePatterns",
+            "--watchman",
+            "--workerIdleMemoryLimit",
+            "--workerThreads",
+        ],
+    },
+    FlagTable {
+        tool: "next",
+        flags: &[
+            "-h",
+            "--help",
+            "-v",
+            "--version",
+            "-p",
+            "--port",
+            "-H",
+            "--hostname",
+            "--turbo",
+            "--turbopack",
+            "--webpack",
+            "--experimental-https",
+            "--experimental-https-key",
+            "--experimental-https-cert",
+            "--experimental-https-ca",
+            "--experimental-upload-trace",
+            "--experimental-app-only",
+            "--experimental-build-mode",
+            "--experimental-debug-memory-usage",
+            "--experimental-test-proxy",
+            "--keepAliveTimeout",
+            "-d",
+            "--debug",
+            "--debug-prerender",
+            "--profile",
+            "--lint",
+            "--mangling",
+            "--inspect",
+            "--dir",
+            "--file",
+            "--strict",
+            "--fix",
+            "--ext",
+            "--max-warnings",
+            "--quiet",
+            "--cache",
+            "--cache-location",
+            "--output-file",
+            "--format",
+            "--resolve-plugins-relative-to",
+            "--rulesdir",
+            "--report-unused-disable-directives",
+        ],
+    },
+    FlagTable {
+        tool: "tsc",
+        flags: &[
+            "-h",
+            "--help",
+            "-v",
+            "--version",
+            "--all",
+            "--init",
+            "-p",
+            "--project",
+            "-b",
+            "--build",
+            "-w",
+            "--watch",
+            "--showConfig",
+            "--listFilesOnly",
+            "--listFiles",
+            "--listEmittedFiles",
+            "--explainFiles",
+            "--traceResolution",
+            "--diagnostics",
+            "--extendedDiagnostics",
+            "--generateCpuProfile",
+            "--generateTrace",
+            "--pretty",
+            "--preserveWatchOutput",
+            "--locale",
+            "--verbose",
+            "--dry",
+            "-f",
+            "--force",
+            "--clean",
+            "--stopBuildOnErrors",
+            "--noEmit",
+            "--noCheck",
+            "--noEmitOnError",
+            "--emitDeclarationOnly",
+            "-d",
+            "--declaration",
+            "--declarationMap",
+            "--declarationDir",
+            "--isolatedDeclarations",
+            "--sourceMap",
+            "--inlineSourceMap",
+            "--inlineSources",
+            "--sourceRoot",
+            "--mapRoot",
+            "--outDir",
+            "--outFile",
+            "--rootDir",
+            "--rootDirs",
+            "--baseUrl",
+            "-t",
+            "--target",
+            "-m",
+            "--module",
+            "--moduleResolution",
+            "--moduleDetection",
+            "--moduleSuffixes",
+            "--customConditions",
+            "--lib",
+            "--noLib",
+            "--noResolve",
+            "--types",
+            "--typeRoots",
+            "--jsx",
+            "--jsxFactory",
+            "--jsxFragmentFactory",
+            "--jsxImportSource",
+            "-i",
+            "--incremental",
+            "--composite",
+            "--tsBuildInfoFile",
+            "--skipLibCheck",
+            "--skipDefaultLibCheck",
+            "--allowJs",
+            "--checkJs",
+            "--maxNodeModuleJsDepth",
+            "--esModuleInterop",
+            "--allowSyntheticDefaultImports",
+            "--allowImportingTsExtensions",
+            "--allowArbitraryExtensions",
+            "--rewriteRelativeImportExtensions",
+            "--resolveJsonModule",
+            "--resolvePackageJsonExports",
+            "--resolvePackageJsonImports",
+            "--isolatedModules",
+            "--verbatimModuleSyntax",
+            "--erasableSyntaxOnly",
+            "--preserveSymlinks",
+            "--forceConsistentCasingInFileNames",
+            "--removeComments",
+            "--stripInternal",
+            "--noEmitHelpers",
+            "--importHelpers",
+            "--downlevelIteration",
+            "--newLine",
+            "--noErrorTruncation",
+            "--experimentalDecorators",
+            "--emitDecoratorMetadata",
+            "--useDefineForClassFields",
+            "--strict",
+            "--alwaysStrict",
+            "--noImplicitAny",
+            "--noImplicitThis",
+            "--noImplicitReturns",
+            "--noImplicitOverride",
+            "--strictNullChecks",
+            "--strictFunctionTypes",
+            "--strictBindCallApply",
+            "--strictPropertyInitialization",
+            "--strictBuiltinIteratorReturn",
+            "--useUnknownInCatchVariables",
+            "--exactOptionalPropertyTypes",
+            "--noUncheckedIndexedAccess",
+            "--noUncheckedSideEffectImports",
+            "--noPropertyAccessFromIndexSignature",
+            "--noFallthroughCasesInSwitch",
+            "--noUnusedLocals",
+            "--noUnusedParameters",
+            "--allowUnreachableCode",
+            "--allowUnusedLabels",
+            "--watchFile",
+            "--watchDirectory",
+            "--fallbackPolling",
+            "--synchronousWatchDirectory",
+            "--excludeDirectories",
+            "--excludeFiles",
+            "--assumeChangesOnlyAffectDirectDependencies",
+            "--libReplacement",
+        ],
+    },
+];
+
+/// The flag table for the tool that receives a script's extra arguments: the
+/// first known binary in the command's last segment (`tsc && vite build` → vite).
+pub fn flag_table_for(command: &str) -> Option<&'static FlagTable> {
+    let last_segment = command.rsplit(['&', '|', ';']).next().unwrap_or(command);
+    last_segment.split_whitespace().find_map(|token| {
+        let binary = token.rsplit('/').next().unwrap_or(token);
+        TABLES.iter().find(|table| table.tool == binary)
+    })
+}
+
+impl FlagTable {
+    /// Flags in `args` the tool doesn't have, in order. Flags are matched
+    /// ignoring case and dashes (`--strict-port` is `--strictPort`), values
+    /// after `=` and nested options (`--coverage.enabled`) are ignored, and
+    /// `--no-<flag>` counts as `<flag>`. Nothing after a bare `--` is checked.
+    pub fn unknown_flags(&self, args: &str) -> Vec<String> {
+        let mut unknown = Vec::new();
+        for word in args.split_whitespace() {
+            let word = word.trim_matches(|c| c == '"' || c == '\'');
+            if word == "--" {
+                break;
+            }
+            if !word.starts_with('-')
+                || word == "-"
+                || word[1..].starts_with(|c: char| c.is_ascii_digit())
+            {
+                continue;
+            }
+            let flag = word.split('=').next().unwrap_or(word);
+            if !self.knows(flag) && !unknown.iter().any(|f| f == flag) {
+                unknown.push(flag.to_string());
+            }
+        }
+        unknown
+    }
+
+    fn knows(&self, flag: &str) -> bool {
+        // Short flags are case sensitive (`-h` vs `-H`)
+        if !flag.starts_with("--") {
+            return self.flags.contains(&flag);
+        }
+        let name = flag.split('.').next().unwrap_or(flag);
+        let name = name.strip_prefix("--no-").unwrap_or(name);
+        let normalized = normalize(name);
+        self.flags
+            .iter()
+            .filter(|known| known.starts_with("--"))
+            .any(|known| normalize(known) == normalized)
+    }
+}
+
+fn normalize(flag: &str) -> String {
+    flag.chars()
+        .filter(|&c| c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_table_uses_tool_receiving_args() {
+        assert_eq!(flag_table_for("vite").map(|t| t.tool), Some("vite"));
+        assert_eq!(
+            flag_table_for("tsc -b && vite build --mode prod").map(|t| t.tool),
+            Some("vite")
+        );
+        assert_eq!(
+            flag_table_for("cross-env NODE_ENV=test ./node_modules/.bin/jest").map(|t| t.tool),
+            Some("jest")
+        );
+        assert!(flag_table_for("node scripts/build.js").is_none());
+    }
+
+    #[test]
+    fn test_unknown_flags_reports_typos() {
+        let vite = flag_table_for("vite").unwrap();
+
+        assert_eq!(
+            vite.unknown_flags("--prot 3000 --open --hots=0.0.0.0"),
+            vec!["--prot", "--hots"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_flags_accepts_spelling_variants() {
+        let vitest = flag_table_for("vitest run").unwrap();
+        assert!(
+            vitest
+                .unknown_flags("src --test-timeout=500 -u --no-isolate --coverage.enabled")
+                .is_empty()
+        );
+
+        let tsc = flag_table_for("tsc").unwrap();
+        assert!(
+            tsc.unknown_flags("--outdir dist -p tsconfig.json")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_flags_stops_at_separator_and_skips_numbers() {
+        let jest = flag_table_for("jest").unwrap();
+
+        assert!(
+            jest.unknown_flags("--maxWorkers -1 -- --anything")
+                .is_empty()
+        );
+        assert_eq!(jest.unknown_flags("-H"), vec!["-H"]);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::core::package_manager::PackageManager;
+use crate::core::workspaces::WorkspacePackage;
+use indexmap::IndexMap;
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// One script per line for `nr --list`: root scripts by name, then workspace
+/// scripts as `<package>:<script>` (the same scope prefix as script keys).
+pub fn format_plain(
+    root_scripts: &IndexMap<String, String>,
+    packages: &[WorkspacePackage],
+) -> String {
+    let mut out = String::new();
+    for name in root_scripts.keys() {
+        out.push_str(name);
+        out.push('\n');
+    }
+    for pkg in packages {
+        for name in pkg.scripts.keys() {
+            out.push_str(&format!("{}:{}\n", pkg.name, name));
+        }
+    }
+    out
+}
+
+/// The discovered project as a JSON document for `nr --json`.
+///
+/// `dir` is the package whose scripts are listed; workspace paths are relative
+/// to `monorepo_root`. Scripts keep their `package.json` order.
+pub fn to_json(
+    dir: &Path,
+    monorepo_root: Option<&Path>,
+    package_manager: PackageManager,
+    root_scripts: &IndexMap<String, String>,
+    packages: &[WorkspacePackage],
+) -> Value {
+    json!({
+        "dir": dir,
+        "monorepo_root": monorepo_root,
+        "package_manager": package_manager.command_name(),
+        "scripts": root_scripts,
+        "workspaces": packages
+            .iter()
+            .map(|pkg| json!({
+                "name": pkg.name,
+                "path": pkg.relative_path,
+                "version": pkg.version,
+                "private": pkg.private,
+                "scripts": pkg.scripts,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(pairs: &[(&str, &str)]) -> IndexMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn packages() -> Vec<WorkspacePackage> {
+        vec![WorkspacePackage {
+            name: "@mono/web".to_string(),
+            relative_path: "apps/web".to_string(),
+            scripts: scripts(&[("dev", "vite")]),
+            description: None,
+            version: Some("1.2.0".to_string()),
+            private: true,
+        }]
+    }
+
+    #[test]
+    fn test_format_plain_prefixes_workspace_scripts() {
+        let root = scripts(&[("build", "turbo build"), ("lint", "eslint .")]);
+
+        assert_eq!(
+            format_plain(&root, &packages()),
+            "build\nlint\n@mono/web:dev\n"
+        );
+    }
+
+    #[test]
+    fn test_to_json_preserves_script_order() {
+        let root = scripts(&[("test", "vitest"), ("build", "tsc")]);
+
+        let value = to_json(
+            Path::new("/repo"),
+            Some(Path::new("/repo")),
+            PackageManager::Pnpm,
+            &root,
+            &packages(),
+        );
+
+        assert_eq!(
+            value.to_string(),
+            r#"{"dir":"/repo","monorepo_root":"/repo","package_manager":"pnpm","scripts":{"test":"vitest","build":"tsc"},"workspaces":[{"name":"@mono/web","path":"apps/web","version":"1.2.0","private":true,"scripts":{"dev":"vite"}}]}"#
+        );
+    }
+}
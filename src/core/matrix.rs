@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// One run of an env matrix: a label and the env files it loads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixProfile {
+    pub label: String,
+    /// Env files in merge order (root → package).
+    pub env_files: Vec<PathBuf>,
+}
+
+/// Outcome of one profile's run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixResult {
+    pub label: String,
+    pub exit_code: i32,
+    pub duration: Duration,
+}
+
+/// Splits selected env files (in merge order) into matrix profiles.
+///
+/// Files named exactly `.env` are a shared base loaded by every profile; every
+/// other file (`.env.node18`, `.env.node20`, ...) becomes a profile of its own,
+/// loaded after the base so its values win.
+pub fn matrix_profiles(env_files: &[PathBuf]) -> Vec<MatrixProfile> {
+    let is_base = |path: &PathBuf| path.file_name().is_some_and(|name| name == ".env");
+    let base: Vec<PathBuf> = env_files.iter().filter(|p| is_base(p)).cloned().collect();
+
+    env_files
+        .iter()
+        .filter(|p| !is_base(p))
+        .map(|path| {
+            let mut files = base.clone();
+            files.push(path.clone());
+            MatrixProfile {
+                label: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string()),
+                env_files: files,
+            }
+        })
+        .collect()
+}
+
+/// Summary table printed after all profiles have run.
+pub fn format_summary(script_name: &str, results: &[MatrixResult]) -> String {
+    let label_width = results.iter().map(|r| r.label.len()).max().unwrap_or(0);
+    let passed = results.iter().filter(|r| r.exit_code == 0).count();
+
+    let mut out = format!("\nMatrix results for {}:\n", script_name);
+    for result in results {
+        let mark = if result.exit_code == 0 { "✔" } else { "✘" };
+        out.push_str(&format!(
+            "  {} {:<width$}  exit {:<3} {:>7.1}s\n",
+            mark,
+            result.label,
+            result.exit_code,
+            result.duration.as_secs_f64(),
+            width = label_width
+        ));
+    }
+    out.push_str(&format!(
+        "{} of {} profiles passed\n",
+        passed,
+        results.len()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_env_is_shared_by_every_profile() {
+        let files = vec![
+            PathBuf::from("/repo/.env"),
+            PathBuf::from("/repo/.env.node18"),
+            PathBuf::from("/repo/.env.node20"),
+        ];
+
+        let profiles = matrix_profiles(&files);
+
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].label, ".env.node18");
+        assert_eq!(
+            profiles[0].env_files,
+            vec![
+                PathBuf::from("/repo/.env"),
+                PathBuf::from("/repo/.env.node18")
+            ]
+        );
+        assert_eq!(profiles[1].label, ".env.node20");
+    }
+
+    #[test]
+    fn test_only_base_files_yield_no_profiles() {
+        assert!(matrix_profiles(&[PathBuf::from("/repo/.env")]).is_empty());
+        assert!(matrix_profiles(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let results = vec![
+            MatrixResult {
+                label: ".env.node18".to_string(),
+                exit_code: 0,
+                duration: Duration::from_millis(12_300),
+            },
+            MatrixResult {
+                label: ".env.bun".to_string(),
+                exit_code: 1,
+                duration: Duration::from_millis(800),
+            },
+        ];
+
+        assert_eq!(
+            format_summary("test", &results),
+            "\nMatrix results for test:\n  ✔ .env.node18  exit 0      12.3s\n  ✘ .env.bun     exit 1       0.8s\n1 of 2 profiles passed\n"
+        );
+    }
+}
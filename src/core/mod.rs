@@ -1,7 +1,40 @@
+pub mod bench;
+pub mod clipboard;
+pub mod deno;
+pub mod directories;
+pub mod doctor;
+pub mod dotenvx;
+pub mod editor;
 pub mod env_files;
+pub mod exit_code;
+pub mod features;
+pub mod hooks;
+pub mod inherit_env;
+pub mod jobs;
+pub mod jsonc;
+pub mod known_flags;
+pub mod listing;
+pub mod matrix;
+pub mod node_version;
+pub mod notify;
 pub mod package_json;
+pub mod package_json_edit;
 pub mod package_manager;
+pub mod parallel;
+pub mod plain_prompt;
+pub mod program;
+pub mod project_config;
 pub mod project_root;
+pub mod queue;
+pub mod required_env;
 pub mod runner;
+pub mod schedule;
+pub mod script_groups;
+pub mod script_health;
 pub mod scripts;
+pub mod secrets;
+pub mod tasks;
+pub mod tmux;
+pub mod update;
+pub mod watch;
 pub mod workspaces;
@@ -0,0 +1,314 @@
+//! The Node version a project asks for: `.nvmrc`, `.node-version`, a Volta
+//! pin or `engines.node` in `package.json`, checked against the active
+//! `node --version` before a script runs.
+
+use crate::core::program;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// What a run does when the active Node doesn't satisfy the project's
+/// version (the `node_version` config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeVersionCheck {
+    /// Print a warning and run anyway.
+    #[default]
+    Warn,
+    /// Don't check.
+    Off,
+    /// Run through `fnm exec --using=<version>`.
+    Fnm,
+    /// Run through `volta run --node <version>`.
+    Volta,
+}
+
+/// A Node version (or range) asked for by a project file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeRequirement {
+    /// Where it comes from: `.nvmrc`, `.node-version`, `volta` or `engines.node`.
+    pub source: &'static str,
+    /// e.g. `20`, `v20.11.0`, `lts/iron` or `>=18 <21`.
+    pub spec: String,
+}
+
+impl NodeRequirement {
+    /// The spec when it names a single (possibly partial) version, which
+    /// fnm and Volta can install and run with; `None` for ranges and aliases.
+    pub fn pinned(&self) -> Option<&str> {
+        let version = self.spec.strip_prefix('v').unwrap_or(&self.spec);
+        let parts: Vec<&str> = version.split('.').collect();
+        let numeric = parts.len() <= 3
+            && parts
+                .iter()
+                .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+        numeric.then_some(self.spec.as_str())
+    }
+}
+
+/// The active Node doesn't satisfy the project's requirement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMismatch {
+    pub requirement: NodeRequirement,
+    /// The active version, without the `v`.
+    pub active: String,
+}
+
+impl fmt::Display for NodeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Node {} is active, but {} asks for {}",
+            self.active, self.requirement.source, self.requirement.spec
+        )
+    }
+}
+
+/// The requirement closest to `cwd`: each directory up from it is searched
+/// for `.nvmrc`, `.node-version`, then a `volta.node` or `engines.node` in
+/// its `package.json`.
+pub fn find_requirement(cwd: &Path) -> Option<NodeRequirement> {
+    cwd.ancestors().find_map(requirement_in)
+}
+
+fn requirement_in(dir: &Path) -> Option<NodeRequirement> {
+    for source in [".nvmrc", ".node-version"] {
+        let spec = std::fs::read_to_string(dir.join(source))
+            .ok()
+            .and_then(|contents| contents.lines().next().map(|l| l.trim().to_string()))
+            .filter(|spec| !spec.is_empty());
+        if let Some(spec) = spec {
+            return Some(NodeRequirement { source, spec });
+        }
+    }
+
+    let contents = std::fs::read_to_string(dir.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    [("volta", "/volta/node"), ("engines.node", "/engines/node")]
+        .into_iter()
+        .find_map(|(source, pointer)| {
+            let spec = manifest.pointer(pointer)?.as_str()?.trim();
+            (!spec.is_empty()).then(|| NodeRequirement {
+                source,
+                spec: spec.to_string(),
+            })
+        })
+}
+
+/// The version `node --version` reports in `cwd`, without the `v`.
+pub fn active_version(cwd: &Path) -> Option<String> {
+    let output = program::command("node")
+        .arg("--version")
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version.trim();
+    (output.status.success() && !version.is_empty())
+        .then(|| version.trim_start_matches('v').to_string())
+}
+
+/// The requirement for `cwd` if the active Node doesn't satisfy it. Nothing
+/// is reported when there is no requirement, no Node, or the spec is an
+/// alias that can't be checked (`lts/*`, `node`).
+pub fn check(cwd: &Path) -> Option<NodeMismatch> {
+    let requirement = find_requirement(cwd)?;
+    let active = active_version(cwd)?;
+    match satisfies(&active, &requirement.spec) {
+        Some(false) => Some(NodeMismatch {
+            requirement,
+            active,
+        }),
+        _ => None,
+    }
+}
+
+type Version = (u64, u64, u64);
+
+/// Whether `version` satisfies `spec`: a version as in `.nvmrc` (`20`,
+/// `v20.11.0`, `20.x`) or an npm range (`>=18 <21`, `^20.1`, `~18.19`,
+/// `18 - 20`, `18.x || 20.x`). `None` if the spec can't be read.
+pub fn satisfies(version: &str, spec: &str) -> Option<bool> {
+    let version = parse_partial(version)?;
+    let version = (version.0?, version.1?, version.2?);
+    let mut satisfied = false;
+    for alternative in spec.split("||") {
+        let tokens = range_tokens(alternative);
+        let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        let all = match tokens.as_slice() {
+            [low, "-", high] => {
+                let low = parse_partial(low)?;
+                let high = parse_partial(high)?;
+                version >= lower(low) && version <= upper(high)
+            }
+            tokens if !tokens.is_empty() => {
+                let mut all = true;
+                for token in tokens {
+                    all &= comparator(version, token)?;
+                }
+                all
+            }
+            _ => return None,
+        };
+        satisfied |= all;
+    }
+    Some(satisfied)
+}
+
+/// Comparison operators of npm ranges, longest first.
+const OPERATORS: [&str; 7] = [">=", "<=", ">", "<", "=", "^", "~"];
+
+/// The whitespace-separated parts of a range, with a bare operator joined to
+/// the version after it (`>= 18` reads as `>=18`).
+fn range_tokens(range: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut operator = String::new();
+    for token in range.split_whitespace() {
+        if OPERATORS.contains(&token) {
+            operator.push_str(token);
+        } else {
+            tokens.push(std::mem::take(&mut operator) + token);
+        }
+    }
+    // A trailing operator is kept so the range fails to parse
+    if !operator.is_empty() {
+        tokens.push(operator);
+    }
+    tokens
+}
+
+/// A version whose missing or `x` parts are `None`.
+type Partial = (Option<u64>, Option<u64>, Option<u64>);
+
+fn parse_partial(text: &str) -> Option<Partial> {
+    let text = text.strip_prefix('v').unwrap_or(text);
+    let mut parts = text.split('.');
+    let mut next = || -> Option<Option<u64>> {
+        match parts.next() {
+            None | Some("x" | "X" | "*") => Some(None),
+            Some(part) => part.parse().ok().map(Some),
+        }
+    };
+    let partial = (next()?, next()?, next()?);
+    match partial {
+        (None, Some(_), _) | (_, None, Some(_)) => None,
+        partial if parts.next().is_none() => Some(partial),
+        _ => None,
+    }
+}
+
+/// The lowest version a partial matches.
+fn lower((major, minor, patch): Partial) -> Version {
+    (major.unwrap_or(0), minor.unwrap_or(0), patch.unwrap_or(0))
+}
+
+/// The highest version a partial matches.
+fn upper((major, minor, patch): Partial) -> Version {
+    (
+        major.unwrap_or(u64::MAX),
+        minor.unwrap_or(u64::MAX),
+        patch.unwrap_or(u64::MAX),
+    )
+}
+
+fn comparator(version: Version, token: &str) -> Option<bool> {
+    let (op, rest) = OPERATORS
+        .into_iter()
+        .find_map(|op| Some((op, token.strip_prefix(op)?)))
+        .unwrap_or(("", token));
+    let partial = parse_partial(rest)?;
+    let (major, minor, _) = partial;
+    Some(match op {
+        ">=" => version >= lower(partial),
+        ">" => version > upper(partial),
+        "<=" => version <= upper(partial),
+        "<" => version < lower(partial),
+        "^" => {
+            let below = match (major, minor) {
+                (Some(0), Some(minor)) => (0, minor + 1, 0),
+                (Some(major), _) => (major + 1, 0, 0),
+                _ => (u64::MAX, 0, 0),
+            };
+            version >= lower(partial) && version < below
+        }
+        "~" => {
+            let below = match (major, minor) {
+                (Some(major), Some(minor)) => (major, minor + 1, 0),
+                (Some(major), None) => (major + 1, 0, 0),
+                _ => (u64::MAX, 0, 0),
+            };
+            version >= lower(partial) && version < below
+        }
+        _ => version >= lower(partial) && version <= upper(partial),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_satisfies_nvmrc_versions() {
+        assert_eq!(satisfies("20.11.0", "20"), Some(true));
+        assert_eq!(satisfies("20.11.0", "v20.11.0"), Some(true));
+        assert_eq!(satisfies("20.11.1", "v20.11.0"), Some(false));
+        assert_eq!(satisfies("18.19.0", "20.x"), Some(false));
+        assert_eq!(satisfies("20.11.0", "lts/iron"), None);
+        assert_eq!(satisfies("20.11.0", "node"), None);
+    }
+
+    #[test]
+    fn test_satisfies_engines_ranges() {
+        assert_eq!(satisfies("20.11.0", ">=18"), Some(true));
+        assert_eq!(satisfies("16.20.0", ">=18"), Some(false));
+        assert_eq!(satisfies("20.11.0", ">=18 <21"), Some(true));
+        assert_eq!(satisfies("21.0.0", ">=18 <21"), Some(false));
+        assert_eq!(satisfies("20.0.0", ">18"), Some(true));
+        assert_eq!(satisfies("18.5.0", ">18"), Some(false));
+        assert_eq!(satisfies("20.11.0", "^20.1"), Some(true));
+        assert_eq!(satisfies("21.0.0", "^20.1"), Some(false));
+        assert_eq!(satisfies("18.19.1", "~18.19"), Some(true));
+        assert_eq!(satisfies("18.20.0", "~18.19"), Some(false));
+        assert_eq!(satisfies("19.9.0", "18 - 20"), Some(true));
+        assert_eq!(satisfies("20.11.0", "18.x || 20.x"), Some(true));
+        assert_eq!(satisfies("19.0.0", "18.x || 20.x"), Some(false));
+    }
+
+    #[test]
+    fn test_satisfies_operators_followed_by_a_space() {
+        assert_eq!(satisfies("20.11.0", ">= 18"), Some(true));
+        assert_eq!(satisfies("16.20.0", ">= 18"), Some(false));
+        assert_eq!(satisfies("21.0.0", ">= 18 < 21"), Some(false));
+        assert_eq!(satisfies("20.11.0", "^ 20.1 || ~ 18.19"), Some(true));
+        assert_eq!(satisfies("20.11.0", ">="), None);
+    }
+
+    #[test]
+    fn test_find_requirement_prefers_the_closest_directory() {
+        let tmp = TempDir::new().unwrap();
+        let pkg = tmp.path().join("apps/web");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(tmp.path().join(".nvmrc"), "v20.11.0\n").unwrap();
+        std::fs::write(
+            pkg.join("package.json"),
+            r#"{ "engines": { "node": ">=18" }, "volta": { "node": "18.19.0" } }"#,
+        )
+        .unwrap();
+
+        let requirement = find_requirement(&pkg).unwrap();
+        assert_eq!(requirement.source, "volta");
+        assert_eq!(requirement.pinned(), Some("18.19.0"));
+
+        let requirement = find_requirement(tmp.path()).unwrap();
+        assert_eq!(requirement.source, ".nvmrc");
+        assert_eq!(requirement.spec, "v20.11.0");
+        assert_eq!(requirement.pinned(), Some("v20.11.0"));
+
+        let range = NodeRequirement {
+            source: "engines.node",
+            spec: ">=18".to_string(),
+        };
+        assert_eq!(range.pinned(), None);
+    }
+}
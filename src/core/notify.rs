@@ -0,0 +1,163 @@
+use crate::core::runner::format_duration;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::time::Duration;
+
+pub use desktop::DesktopNotifier;
+
+/// Announcing long runs when they finish, configured under `notify` in
+/// `config.json`, e.g. `{ "after_secs": 30, "desktop": true }`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Ring the terminal bell for runs taking at least this long; unset is off.
+    pub after_secs: Option<u64>,
+    /// Also show a desktop notification (`notify-send` or `osascript`).
+    pub desktop: bool,
+}
+
+impl NotifyConfig {
+    /// Whether a run that took `duration` is announced.
+    pub fn is_long(&self, duration: Duration) -> bool {
+        self.after_secs
+            .is_some_and(|secs| duration >= Duration::from_secs(secs))
+    }
+}
+
+/// Rings the bell, and sends a desktop notification if configured, when a run
+/// of `script_name` took long enough to announce.
+pub fn notify_finished(
+    config: &NotifyConfig,
+    script_name: &str,
+    exit_code: i32,
+    duration: Duration,
+) {
+    if !config.is_long(duration) {
+        return;
+    }
+
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+
+    if config.desktop {
+        DesktopNotifier.notify("nr", &message(script_name, exit_code, duration));
+    }
+}
+
+/// e.g. `build finished in 42.1s` or `test failed with exit code 1 after 2m 05s`.
+fn message(script_name: &str, exit_code: i32, duration: Duration) -> String {
+    if exit_code == 0 {
+        format!("{} finished in {}", script_name, format_duration(duration))
+    } else {
+        format!(
+            "{} failed with exit code {} after {}",
+            script_name,
+            exit_code,
+            format_duration(duration)
+        )
+    }
+}
+
+/// Somewhere to announce finished runs besides the terminal bell.
+pub trait Notifier {
+    /// Shows `body` under `title`, without waiting for it to be seen.
+    fn notify(&self, title: &str, body: &str);
+}
+
+/// Desktop notifications through `notify-send` or `osascript`.
+#[cfg(feature = "desktop-notify")]
+mod desktop {
+    use super::Notifier;
+    use std::process::{Command, Stdio};
+
+    pub struct DesktopNotifier;
+
+    impl Notifier for DesktopNotifier {
+        fn notify(&self, title: &str, body: &str) {
+            let Some(argv) = desktop_command(title, body) else {
+                return;
+            };
+            // Don't hold up the TUI while the notifier runs
+            std::thread::spawn(move || {
+                let _ = Command::new(&argv[0])
+                    .args(&argv[1..])
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+            });
+        }
+    }
+
+    /// The platform's notification tool invocation, if there is one.
+    fn desktop_command(title: &str, body: &str) -> Option<Vec<String>> {
+        if cfg!(target_os = "macos") {
+            let script = format!(
+                "display notification {} with title {}",
+                applescript_string(body),
+                applescript_string(title)
+            );
+            Some(vec!["osascript".into(), "-e".into(), script])
+        } else if cfg!(unix) {
+            Some(vec!["notify-send".into(), title.into(), body.into()])
+        } else {
+            None
+        }
+    }
+
+    /// A double-quoted AppleScript string literal.
+    fn applescript_string(text: &str) -> String {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_applescript_string_escapes_quotes() {
+            assert_eq!(applescript_string(r#"say "hi""#), r#""say \"hi\"""#);
+        }
+    }
+}
+
+/// Builds without the `desktop-notify` feature only ring the bell.
+#[cfg(not(feature = "desktop-notify"))]
+mod desktop {
+    use super::Notifier;
+
+    pub struct DesktopNotifier;
+
+    impl Notifier for DesktopNotifier {
+        fn notify(&self, _title: &str, _body: &str) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_runs_over_the_threshold_are_long() {
+        let config = NotifyConfig {
+            after_secs: Some(30),
+            ..Default::default()
+        };
+        assert!(config.is_long(Duration::from_secs(30)));
+        assert!(!config.is_long(Duration::from_secs(29)));
+        assert!(!NotifyConfig::default().is_long(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_message_mentions_outcome_and_run_time() {
+        assert_eq!(
+            message("build", 0, Duration::from_millis(42_100)),
+            "build finished in 42.1s"
+        );
+        assert_eq!(
+            message("test", 1, Duration::from_secs(125)),
+            "test failed with exit code 1 after 2m 05s"
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use crate::core::hooks::ScriptHooks;
 use indexmap::IndexMap;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Shared representation of a `package.json` file.
@@ -13,10 +15,21 @@ use std::path::Path;
 #[derive(Deserialize, Default)]
 pub struct PackageJson {
     pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// Usually `true`, but some manifests say `"true"`.
+    private: Option<serde_json::Value>,
     scripts: Option<serde_json::Map<String, serde_json::Value>>,
     pub workspaces: Option<serde_json::Value>,
     #[serde(rename = "packageManager")]
     pub package_manager: Option<String>,
+    /// nr's own settings block, e.g. `"nr": { "tags": { "build": ["ci"] } }`.
+    nr: Option<serde_json::Value>,
+    /// Script descriptions, e.g. `"scripts-info": { "build": "Compile the app" }`.
+    #[serde(rename = "scripts-info")]
+    scripts_info: Option<serde_json::Value>,
+    /// ntl's settings block, with descriptions under `ntl.descriptions`.
+    ntl: Option<serde_json::Value>,
 }
 
 impl PackageJson {
@@ -27,17 +40,106 @@ impl PackageJson {
         serde_json::from_str(&contents).ok()
     }
 
-    /// Extract scripts as an ordered map, filtering out non-string values.
+    /// Whether the package is marked `"private": true` (not to be published).
+    pub fn is_private(&self) -> bool {
+        match self.private {
+            Some(serde_json::Value::Bool(private)) => private,
+            Some(serde_json::Value::String(ref private)) => private == "true",
+            _ => false,
+        }
+    }
+
+    /// Extract scripts as an ordered map, filtering out non-string values and
+    /// `"//..."` comment keys.
     pub fn scripts(&self) -> IndexMap<String, String> {
         match &self.scripts {
             Some(obj) => obj
                 .iter()
+                .filter(|(k, _)| !k.starts_with("//"))
                 .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
                 .collect(),
             None => IndexMap::new(),
         }
     }
 
+    /// Description per script name. Read from `scripts-info`, then
+    /// `ntl.descriptions`, then `"//build": "..."` comment keys in `scripts`;
+    /// the first one describing a script wins.
+    pub fn script_descriptions(&self) -> HashMap<String, String> {
+        let comments = self.scripts.iter().flatten().filter_map(|(key, value)| {
+            let name = key.strip_prefix("//")?.trim();
+            Some((name, value)).filter(|(name, _)| !name.is_empty())
+        });
+        let blocks = [
+            self.scripts_info.as_ref(),
+            self.ntl.as_ref().and_then(|ntl| ntl.get("descriptions")),
+        ];
+        let sources = blocks
+            .into_iter()
+            .flatten()
+            .filter_map(|block| block.as_object())
+            .flat_map(|block| block.iter().map(|(name, value)| (name.as_str(), value)))
+            .chain(comments);
+
+        let mut descriptions = HashMap::new();
+        for (name, value) in sources {
+            let Some(text) = value.as_str().map(str::trim).filter(|t| !t.is_empty()) else {
+                continue;
+            };
+            descriptions
+                .entry(name.to_string())
+                .or_insert_with(|| text.to_string());
+        }
+        descriptions
+    }
+
+    /// Tags per script name from the `nr.tags` block. A single string is
+    /// accepted for one tag; malformed entries are skipped.
+    pub fn script_tags(&self) -> HashMap<String, Vec<String>> {
+        let Some(tags) = self
+            .nr
+            .as_ref()
+            .and_then(|nr| nr.get("tags"))
+            .and_then(|t| t.as_object())
+        else {
+            return HashMap::new();
+        };
+
+        tags.iter()
+            .map(|(script, value)| {
+                let list = match value {
+                    serde_json::Value::String(tag) => vec![tag.to_lowercase()],
+                    serde_json::Value::Array(items) => items
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(str::to_lowercase)
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                (script.clone(), list)
+            })
+            .filter(|(_, list)| !list.is_empty())
+            .collect()
+    }
+
+    /// Commands to run around `script_name` from the `nr.hooks` block: those
+    /// for every script (`"*"`) first, then its own. A single string is
+    /// accepted for one command; malformed entries are skipped.
+    pub fn script_hooks(&self, script_name: &str) -> ScriptHooks {
+        let mut hooks = ScriptHooks::default();
+        let Some(block) = self.nr.as_ref().and_then(|nr| nr.get("hooks")) else {
+            return hooks;
+        };
+        for key in ["*", script_name] {
+            let Some(entry) = block.get(key) else {
+                continue;
+            };
+            hooks.before.extend(commands(entry.get("before")));
+            hooks.after.extend(commands(entry.get("after")));
+        }
+        hooks
+    }
+
     /// Extract workspace glob patterns from the `workspaces` field.
     ///
     /// Supports both array format (`["packages/*"]`) and
@@ -65,6 +167,19 @@ impl PackageJson {
     }
 }
 
+/// A command or a list of commands; anything else is none.
+fn commands(value: Option<&serde_json::Value>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::String(command)) => vec![command.clone()],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,9 +230,15 @@ mod tests {
     fn test_scripts_returns_empty_when_none() {
         let pkg = PackageJson {
             name: Some("test".to_string()),
+            version: None,
+            description: None,
+            private: None,
             scripts: None,
             workspaces: None,
             package_manager: None,
+            nr: None,
+            scripts_info: None,
+            ntl: None,
         };
 
         let scripts = pkg.scripts();
@@ -195,9 +316,15 @@ mod tests {
     fn test_workspace_patterns_returns_empty_when_none() {
         let pkg = PackageJson {
             name: Some("test".to_string()),
+            version: None,
+            description: None,
+            private: None,
             scripts: None,
             workspaces: None,
             package_manager: None,
+            nr: None,
+            scripts_info: None,
+            ntl: None,
         };
 
         let patterns = pkg.workspace_patterns();
@@ -219,4 +346,71 @@ mod tests {
         let patterns = pkg.workspace_patterns();
         assert!(patterns.is_empty());
     }
+
+    #[test]
+    fn test_script_hooks_from_nr_block() {
+        let pkg: PackageJson = serde_json::from_str(
+            r#"{
+                "nr": { "hooks": {
+                    "*": { "before": "node check-env.js" },
+                    "dev": { "before": ["docker compose up -d"], "after": "docker compose stop" }
+                } }
+            }"#,
+        )
+        .unwrap();
+
+        let hooks = pkg.script_hooks("dev");
+        assert_eq!(
+            hooks.before,
+            vec!["node check-env.js", "docker compose up -d"]
+        );
+        assert_eq!(hooks.after, vec!["docker compose stop"]);
+        assert_eq!(pkg.script_hooks("build").after, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_script_tags_from_nr_block() {
+        let pkg: PackageJson = serde_json::from_str(
+            r#"{
+                "scripts": { "build": "tsc", "deploy": "sh deploy.sh" },
+                "nr": { "tags": { "build": ["CI", "release"], "deploy": "prod", "dev": 3 } }
+            }"#,
+        )
+        .unwrap();
+
+        let tags = pkg.script_tags();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags["build"], vec!["ci", "release"]);
+        assert_eq!(tags["deploy"], vec!["prod"]);
+    }
+
+    #[test]
+    fn test_script_descriptions_from_conventions() {
+        let pkg: PackageJson = serde_json::from_str(
+            r#"{
+                "scripts": {
+                    "//": "general comment",
+                    "//build": "Compile (comment)",
+                    "build": "tsc",
+                    "// lint": "Check style",
+                    "lint": "eslint .",
+                    "dev": "vite",
+                    "test": "vitest"
+                },
+                "scripts-info": { "build": "Compile the app", "test": "" },
+                "ntl": { "descriptions": { "build": "Compile (ntl)", "dev": "Start the dev server" } }
+            }"#,
+        )
+        .unwrap();
+
+        let descriptions = pkg.script_descriptions();
+        assert_eq!(descriptions.len(), 3);
+        assert_eq!(descriptions["build"], "Compile the app");
+        assert_eq!(descriptions["dev"], "Start the dev server");
+        assert_eq!(descriptions["lint"], "Check style");
+
+        // Comment keys aren't scripts
+        let names: Vec<String> = pkg.scripts().into_keys().collect();
+        assert_eq!(names, vec!["build", "lint", "dev", "test"]);
+    }
 }
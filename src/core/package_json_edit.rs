@@ -0,0 +1,498 @@
+//! Edits to `package.json` scripts that keep the rest of the file byte-for-byte intact.
+//!
+//! Instead of round-tripping through `serde_json` (which would reformat the file),
+//! edits are applied to the source text: only the spans that change are rewritten,
+//! and new entries copy the indentation and separators already used in the file.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Errors from editing `package.json` source.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum EditError {
+    #[error("Invalid package.json at byte {0}")]
+    Syntax(usize),
+    #[error("\"scripts\" in package.json is not an object")]
+    ScriptsNotObject,
+    #[error("No script named '{0}'")]
+    ScriptNotFound(String),
+    #[error("A script named '{0}' already exists")]
+    ScriptExists(String),
+}
+
+/// Sets `name` to `command`, replacing an existing entry in place or appending a new
+/// one at the end of `scripts` (creating the `scripts` object if needed).
+pub fn set_script(source: &str, name: &str, command: &str) -> Result<String, EditError> {
+    let root = parse_root(source)?;
+    let entry_value = encode(command);
+
+    let Some(scripts) = root.member("scripts") else {
+        // No scripts object yet: append one to the root object
+        let indent = root
+            .member_indent(source)
+            .unwrap_or_else(|| "  ".to_string());
+        let value = format!(
+            "{{\n{indent}{indent}{}: {}\n{indent}}}",
+            encode(name),
+            entry_value
+        );
+        return Ok(insert_member(source, &root, "", &indent, "scripts", &value));
+    };
+
+    let object = scripts_object(source, scripts)?;
+    if let Some(existing) = object.member(name) {
+        return Ok(splice(
+            source,
+            existing.value_start,
+            existing.value_end,
+            &entry_value,
+        ));
+    }
+
+    let outer_indent = line_indent(source, scripts.key_start);
+    let indent = object
+        .member_indent(source)
+        .unwrap_or_else(|| format!("{}{}", outer_indent, indent_unit(source, &root)));
+    Ok(insert_member(
+        source,
+        &object,
+        &outer_indent,
+        &indent,
+        name,
+        &entry_value,
+    ))
+}
+
+/// Removes the script `name`, along with its separating comma and whitespace.
+pub fn remove_script(source: &str, name: &str) -> Result<String, EditError> {
+    let root = parse_root(source)?;
+    let scripts = root
+        .member("scripts")
+        .ok_or_else(|| EditError::ScriptNotFound(name.to_string()))?;
+    let object = scripts_object(source, scripts)?;
+    let index = object
+        .members
+        .iter()
+        .position(|m| m.key == name)
+        .ok_or_else(|| EditError::ScriptNotFound(name.to_string()))?;
+    let member = &object.members[index];
+
+    Ok(if index > 0 {
+        // Drop `,<ws>"name": value` after the previous entry
+        let prev = &object.members[index - 1];
+        splice(source, prev.value_end, member.value_end, "")
+    } else if let Some(next) = object.members.get(1) {
+        // First entry: drop up to the next key, keeping the leading whitespace
+        splice(source, member.key_start, next.key_start, "")
+    } else {
+        // Only entry: leave an empty object
+        splice(source, object.open + 1, object.close, "")
+    })
+}
+
+/// Renames the script `old` to `new`, keeping its position and command.
+pub fn rename_script(source: &str, old: &str, new: &str) -> Result<String, EditError> {
+    let root = parse_root(source)?;
+    let scripts = root
+        .member("scripts")
+        .ok_or_else(|| EditError::ScriptNotFound(old.to_string()))?;
+    let object = scripts_object(source, scripts)?;
+    if old != new && object.member(new).is_some() {
+        return Err(EditError::ScriptExists(new.to_string()));
+    }
+    let member = object
+        .member(old)
+        .ok_or_else(|| EditError::ScriptNotFound(old.to_string()))?;
+
+    Ok(splice(
+        source,
+        member.key_start,
+        member.key_end,
+        &encode(new),
+    ))
+}
+
+/// Reads `package.json` in `package_dir`, applies `edit` to its source and writes it back.
+pub fn update_package_json<F>(package_dir: &Path, edit: F) -> Result<()>
+where
+    F: FnOnce(&str) -> Result<String, EditError>,
+{
+    let path = package_dir.join("package.json");
+    let source =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let updated = edit(&source).with_context(|| format!("Failed to edit {}", path.display()))?;
+
+    fs::write(&path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+// -- Source scanning --
+
+/// A `"key": value` pair with byte offsets into the source.
+#[derive(Debug)]
+struct Member {
+    key: String,
+    key_start: usize,
+    key_end: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+/// An object's members plus the offsets of its `{` and `}`.
+#[derive(Debug)]
+struct Object {
+    open: usize,
+    close: usize,
+    members: Vec<Member>,
+}
+
+impl Object {
+    fn member(&self, key: &str) -> Option<&Member> {
+        self.members.iter().find(|m| m.key == key)
+    }
+
+    /// Leading whitespace of the first member's line, if it starts its own line.
+    fn member_indent(&self, source: &str) -> Option<String> {
+        let first = self.members.first()?;
+        let line_start = source[..first.key_start].rfind('\n').map_or(0, |i| i + 1);
+        (line_start > self.open).then(|| line_indent(source, first.key_start))
+    }
+}
+
+fn parse_root(source: &str) -> Result<Object, EditError> {
+    let start = skip_ws(source, 0);
+    parse_object(source, start)
+}
+
+fn scripts_object(source: &str, scripts: &Member) -> Result<Object, EditError> {
+    if source.as_bytes().get(scripts.value_start) != Some(&b'{') {
+        return Err(EditError::ScriptsNotObject);
+    }
+    parse_object(source, scripts.value_start)
+}
+
+fn parse_object(source: &str, open: usize) -> Result<Object, EditError> {
+    let bytes = source.as_bytes();
+    if bytes.get(open) != Some(&b'{') {
+        return Err(EditError::Syntax(open));
+    }
+
+    let mut members = Vec::new();
+    let mut pos = skip_ws(source, open + 1);
+    if bytes.get(pos) == Some(&b'}') {
+        return Ok(Object {
+            open,
+            close: pos,
+            members,
+        });
+    }
+
+    loop {
+        let key_start = pos;
+        let key_end = skip_string(source, pos)?;
+        let key: String = serde_json::from_str(&source[key_start..key_end])
+            .map_err(|_| EditError::Syntax(key_start))?;
+
+        pos = skip_ws(source, key_end);
+        if bytes.get(pos) != Some(&b':') {
+            return Err(EditError::Syntax(pos));
+        }
+        let value_start = skip_ws(source, pos + 1);
+        let value_end = skip_value(source, value_start)?;
+        members.push(Member {
+            key,
+            key_start,
+            key_end,
+            value_start,
+            value_end,
+        });
+
+        pos = skip_ws(source, value_end);
+        match bytes.get(pos) {
+            Some(b',') => pos = skip_ws(source, pos + 1),
+            Some(b'}') => {
+                return Ok(Object {
+                    open,
+                    close: pos,
+                    members,
+                });
+            }
+            _ => return Err(EditError::Syntax(pos)),
+        }
+    }
+}
+
+/// Returns the offset just past the JSON value starting at `pos`.
+fn skip_value(source: &str, pos: usize) -> Result<usize, EditError> {
+    let bytes = source.as_bytes();
+    match bytes.get(pos) {
+        Some(b'"') => skip_string(source, pos),
+        Some(b'{') => parse_object(source, pos).map(|o| o.close + 1),
+        Some(b'[') => {
+            let mut pos = skip_ws(source, pos + 1);
+            if bytes.get(pos) == Some(&b']') {
+                return Ok(pos + 1);
+            }
+            loop {
+                pos = skip_ws(source, skip_value(source, pos)?);
+                match bytes.get(pos) {
+                    Some(b',') => pos = skip_ws(source, pos + 1),
+                    Some(b']') => return Ok(pos + 1),
+                    _ => return Err(EditError::Syntax(pos)),
+                }
+            }
+        }
+        Some(_) => {
+            // Number, true, false or null
+            let end = source[pos..]
+                .find(|c: char| c.is_whitespace() || matches!(c, ',' | '}' | ']'))
+                .map_or(source.len(), |i| pos + i);
+            if end == pos {
+                Err(EditError::Syntax(pos))
+            } else {
+                Ok(end)
+            }
+        }
+        None => Err(EditError::Syntax(pos)),
+    }
+}
+
+/// Returns the offset just past the string literal starting at `pos`.
+fn skip_string(source: &str, pos: usize) -> Result<usize, EditError> {
+    let bytes = source.as_bytes();
+    if bytes.get(pos) != Some(&b'"') {
+        return Err(EditError::Syntax(pos));
+    }
+    let mut i = pos + 1;
+    while let Some(&b) = bytes.get(i) {
+        match b {
+            b'\\' => i += 2,
+            b'"' => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+    Err(EditError::Syntax(pos))
+}
+
+fn skip_ws(source: &str, pos: usize) -> usize {
+    source[pos..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(source.len(), |i| pos + i)
+}
+
+// -- Rewriting --
+
+/// Whitespace between the start of the line containing `pos` and the first non-blank.
+fn line_indent(source: &str, pos: usize) -> String {
+    let line_start = source[..pos].rfind('\n').map_or(0, |i| i + 1);
+    source[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// One level of indentation, taken from the root object (defaults to two spaces).
+fn indent_unit(source: &str, root: &Object) -> String {
+    root.member_indent(source)
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+/// Appends `"key": value` as the last member of `object`.
+fn insert_member(
+    source: &str,
+    object: &Object,
+    outer_indent: &str,
+    indent: &str,
+    key: &str,
+    value: &str,
+) -> String {
+    let separator = object
+        .members
+        .last()
+        .map(|m| &source[m.key_end..m.value_start])
+        .unwrap_or(": ");
+    let entry = format!("{}{}{}", encode(key), separator, value);
+
+    match object.members.last() {
+        Some(last) if object.member_indent(source).is_some() => splice(
+            source,
+            last.value_end,
+            last.value_end,
+            &format!(",\n{}{}", indent, entry),
+        ),
+        Some(last) => splice(
+            source,
+            last.value_end,
+            last.value_end,
+            &format!(", {}", entry),
+        ),
+        None => splice(
+            source,
+            object.open + 1,
+            object.close,
+            &format!("\n{}{}\n{}", indent, entry, outer_indent),
+        ),
+    }
+}
+
+fn splice(source: &str, start: usize, end: usize, replacement: &str) -> String {
+    format!("{}{}{}", &source[..start], replacement, &source[end..])
+}
+
+fn encode(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_SPACE: &str = r#"{
+  "name": "web",
+  "scripts": {
+    "dev": "vite",
+    "build": "vite build"
+  },
+  "dependencies": {}
+}
+"#;
+
+    #[test]
+    fn test_set_existing_script_replaces_only_value() {
+        let updated = set_script(TWO_SPACE, "dev", "vite --host").unwrap();
+        assert_eq!(
+            updated,
+            TWO_SPACE.replace(r#""dev": "vite""#, r#""dev": "vite --host""#)
+        );
+    }
+
+    #[test]
+    fn test_set_new_script_appends_with_matching_indent() {
+        let updated = set_script(TWO_SPACE, "lint", "eslint .").unwrap();
+        assert_eq!(
+            updated,
+            TWO_SPACE.replace(
+                r#""build": "vite build""#,
+                "\"build\": \"vite build\",\n    \"lint\": \"eslint .\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_set_script_preserves_tabs_and_missing_trailing_newline() {
+        let source = "{\n\t\"scripts\": {\n\t\t\"a\": \"x\"\n\t}\n}";
+        let updated = set_script(source, "b", "y").unwrap();
+        assert_eq!(
+            updated,
+            "{\n\t\"scripts\": {\n\t\t\"a\": \"x\",\n\t\t\"b\": \"y\"\n\t}\n}"
+        );
+    }
+
+    #[test]
+    fn test_set_script_creates_scripts_object() {
+        let source = "{\n    \"name\": \"web\"\n}\n";
+        let updated = set_script(source, "dev", "vite").unwrap();
+        assert_eq!(
+            updated,
+            "{\n    \"name\": \"web\",\n    \"scripts\": {\n        \"dev\": \"vite\"\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_set_script_into_empty_scripts_object() {
+        let source = "{\n  \"scripts\": {}\n}\n";
+        let updated = set_script(source, "dev", "vite").unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"scripts\": {\n    \"dev\": \"vite\"\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_set_script_on_single_line_object() {
+        let source = r#"{"scripts": {"a": "x"}}"#;
+        let updated = set_script(source, "b", "say \"hi\"").unwrap();
+        assert_eq!(updated, r#"{"scripts": {"a": "x", "b": "say \"hi\""}}"#);
+    }
+
+    #[test]
+    fn test_remove_middle_first_and_only_script() {
+        let source =
+            "{\n  \"scripts\": {\n    \"a\": \"1\",\n    \"b\": \"2\",\n    \"c\": \"3\"\n  }\n}\n";
+
+        assert_eq!(
+            remove_script(source, "b").unwrap(),
+            "{\n  \"scripts\": {\n    \"a\": \"1\",\n    \"c\": \"3\"\n  }\n}\n"
+        );
+        assert_eq!(
+            remove_script(source, "a").unwrap(),
+            "{\n  \"scripts\": {\n    \"b\": \"2\",\n    \"c\": \"3\"\n  }\n}\n"
+        );
+        assert_eq!(
+            remove_script(source, "c").unwrap(),
+            "{\n  \"scripts\": {\n    \"a\": \"1\",\n    \"b\": \"2\"\n  }\n}\n"
+        );
+
+        let single = "{\n  \"scripts\": {\n    \"a\": \"1\"\n  }\n}\n";
+        assert_eq!(
+            remove_script(single, "a").unwrap(),
+            "{\n  \"scripts\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_script_keeps_position() {
+        let updated = rename_script(TWO_SPACE, "dev", "start").unwrap();
+        assert_eq!(
+            updated,
+            TWO_SPACE.replace(r#""dev": "vite""#, r#""start": "vite""#)
+        );
+    }
+
+    #[test]
+    fn test_rename_to_existing_name_fails() {
+        assert_eq!(
+            rename_script(TWO_SPACE, "dev", "build"),
+            Err(EditError::ScriptExists("build".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_missing_script_and_bad_input_errors() {
+        assert_eq!(
+            remove_script(TWO_SPACE, "nope"),
+            Err(EditError::ScriptNotFound("nope".to_string()))
+        );
+        assert!(matches!(
+            set_script("{\"scripts\": [1]}", "a", "b"),
+            Err(EditError::ScriptsNotObject)
+        ));
+        assert!(matches!(
+            set_script("{\"scripts\": {", "a", "b"),
+            Err(EditError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_nested_values_are_skipped_correctly() {
+        let source =
+            r#"{"files": ["a", {"b": [1, true, null]}], "scripts": {"x": "y"}, "n": -1.5e3}"#;
+        let updated = set_script(source, "x", "z").unwrap();
+        assert_eq!(updated, source.replace(r#""x": "y""#, r#""x": "z""#));
+    }
+
+    #[test]
+    fn test_update_package_json_writes_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), TWO_SPACE).unwrap();
+
+        update_package_json(temp_dir.path(), |s| set_script(s, "dev", "vite --open")).unwrap();
+
+        let written = fs::read_to_string(temp_dir.path().join("package.json")).unwrap();
+        assert!(written.contains(r#""dev": "vite --open""#));
+        assert!(written.ends_with("}\n"));
+    }
+}
@@ -1,12 +1,22 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Invocation shape used when no template is configured for a package manager.
+pub const DEFAULT_RUN_TEMPLATE: &str = "{pm} {run}";
 
 /// Supported Node.js package managers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PackageManager {
     Bun,
     Pnpm,
+    /// Yarn 1.x ("classic").
     Yarn,
+    /// Yarn 2+ ("berry"), detected via `.yarnrc.yml` or the `packageManager` field.
+    YarnBerry,
     Npm,
+    /// `deno task`, for `tasks` in `deno.json` (and `package.json` scripts).
+    Deno,
 }
 
 impl PackageManager {
@@ -16,7 +26,122 @@ impl PackageManager {
             Self::Bun => vec!["run", script_name],
             Self::Pnpm => vec!["run", script_name],
             Self::Yarn => vec![script_name],
+            // Berry resolves `yarn <name>` against its own commands first, so be explicit
+            Self::YarnBerry => vec!["run", script_name],
             Self::Npm => vec!["run", script_name],
+            Self::Deno => vec!["task", script_name],
+        }
+    }
+
+    /// Full command line (program first) to run a script with extra arguments.
+    ///
+    /// `template` overrides the default [`DEFAULT_RUN_TEMPLATE`]. Whitespace-separated
+    /// tokens support these placeholders:
+    /// - `{pm}`: the package manager binary
+    /// - `{run}`: the default run arguments (`run build`, or just `build` for Yarn classic)
+    /// - `{script}`: the script name
+    /// - `{args}`: the extra arguments; appended at the end when omitted. With
+    ///   `{run}` they are passed the way the package manager needs (see
+    ///   [`script_args`](Self::script_args)), otherwise as typed
+    pub fn invocation(
+        &self,
+        script_name: &str,
+        extra_args: &[String],
+        template: Option<&str>,
+    ) -> Vec<String> {
+        self.expand_template(
+            &self.run_args(script_name),
+            script_name,
+            extra_args,
+            template,
+        )
+    }
+
+    /// Like [`invocation`](Self::invocation), for a workspace package's script
+    /// run from the monorepo root: `{run}` becomes the
+    /// [`workspace_run_args`](Self::workspace_run_args).
+    pub fn workspace_invocation(
+        &self,
+        package: &str,
+        script_name: &str,
+        extra_args: &[String],
+        template: Option<&str>,
+    ) -> Vec<String> {
+        self.expand_template(
+            &self.workspace_run_args(package, script_name),
+            script_name,
+            extra_args,
+            template,
+        )
+    }
+
+    fn expand_template(
+        &self,
+        run_args: &[&str],
+        script_name: &str,
+        extra_args: &[String],
+        template: Option<&str>,
+    ) -> Vec<String> {
+        let mut argv = Vec::new();
+        let mut args_placed = false;
+        let template = template.unwrap_or(DEFAULT_RUN_TEMPLATE);
+        let script_args = if template.split_whitespace().any(|token| token == "{run}") {
+            self.script_args(extra_args)
+        } else {
+            extra_args.to_vec()
+        };
+
+        for token in template.split_whitespace() {
+            match token {
+                "{run}" => argv.extend(run_args.iter().map(|arg| arg.to_string())),
+                "{args}" => {
+                    argv.extend(script_args.iter().cloned());
+                    args_placed = true;
+                }
+                other => argv.push(
+                    other
+                        .replace("{pm}", self.command_name())
+                        .replace("{script}", script_name),
+                ),
+            }
+        }
+
+        // A template without a program would spawn nothing; fall back to the default shape
+        if argv.is_empty() {
+            return self.expand_template(run_args, script_name, extra_args, None);
+        }
+
+        if !args_placed {
+            argv.extend(script_args);
+        }
+        argv
+    }
+
+    /// `extra_args` as the package manager needs them to hand them on to the
+    /// script. npm wants a `--` first, or it takes flags like `--watch` as its
+    /// own; the others forward arguments as they are, so a `--` typed out of
+    /// npm habit would reach the script and is dropped.
+    pub fn script_args(&self, extra_args: &[String]) -> Vec<String> {
+        let args = match extra_args.split_first() {
+            Some((first, rest)) if first == "--" => rest,
+            _ => extra_args,
+        };
+        match self {
+            Self::Npm if !args.is_empty() => std::iter::once("--".to_string())
+                .chain(args.iter().cloned())
+                .collect(),
+            _ => args.to_vec(),
+        }
+    }
+
+    /// Arguments to run a script inside a single workspace package, from the monorepo root.
+    pub fn workspace_run_args<'a>(&self, package: &'a str, script_name: &'a str) -> Vec<&'a str> {
+        match self {
+            Self::Bun => vec!["run", "--filter", package, script_name],
+            Self::Pnpm => vec!["--filter", package, "run", script_name],
+            Self::Yarn | Self::YarnBerry => vec!["workspace", package, "run", script_name],
+            Self::Npm => vec!["run", script_name, "--workspace", package],
+            Self::Deno => vec!["task", "--filter", package, script_name],
         }
     }
 
@@ -25,10 +150,52 @@ impl PackageManager {
         match self {
             Self::Bun => "bun",
             Self::Pnpm => "pnpm",
-            Self::Yarn => "yarn",
+            Self::Yarn | Self::YarnBerry => "yarn",
             Self::Npm => "npm",
+            Self::Deno => "deno",
         }
     }
+
+    /// Whether Corepack can provide this package manager at the version
+    /// `packageManager` declares.
+    pub fn supports_corepack(&self) -> bool {
+        matches!(self, Self::Pnpm | Self::Yarn | Self::YarnBerry | Self::Npm)
+    }
+
+    /// The version of this package manager on PATH (`pnpm --version`), run in
+    /// `cwd`; `None` if it isn't installed or fails. Runs are checked against
+    /// it every time, so it is asked once per directory and kept for the
+    /// session.
+    pub fn installed_version(&self, cwd: &Path) -> Option<String> {
+        static VERSIONS: Mutex<BTreeMap<(String, PathBuf), Option<String>>> =
+            Mutex::new(BTreeMap::new());
+
+        let key = (self.command_name().to_string(), cwd.to_path_buf());
+        if let Some(version) = VERSIONS.lock().ok()?.get(&key) {
+            return version.clone();
+        }
+        let version = self.ask_version(cwd);
+        VERSIONS.lock().ok()?.insert(key, version.clone());
+        version
+    }
+
+    fn ask_version(&self, cwd: &Path) -> Option<String> {
+        let output = crate::core::program::command(self.command_name())
+            .arg("--version")
+            .current_dir(cwd)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !version.is_empty()).then_some(version)
+    }
+
+    /// Whether `run` also runs the `pre<name>` / `post<name>` scripts.
+    ///
+    /// pnpm (since v7) and Yarn 2+ dropped implicit pre/post scripts.
+    pub fn runs_pre_post_scripts(&self) -> bool {
+        matches!(self, Self::Npm | Self::Yarn | Self::Bun)
+    }
 }
 
 impl std::fmt::Display for PackageManager {
@@ -42,32 +209,53 @@ impl std::fmt::Display for PackageManager {
 /// Priority order:
 /// 1. `bun.lockb` or `bun.lock` -> Bun
 /// 2. `pnpm-lock.yaml` -> Pnpm
-/// 3. `yarn.lock` -> Yarn
+/// 3. `yarn.lock` -> Yarn (classic or berry, see [`detect_yarn_flavor`])
 /// 4. `package-lock.json` -> Npm
-/// 5. `packageManager` field in `package.json` -> parse PM name
-/// 6. Fallback -> Npm
+/// 5. `deno.lock` -> Deno
+/// 6. `packageManager` field in `package.json` -> parse PM name
+/// 7. `deno.json` / `deno.jsonc` -> Deno
+/// 8. Fallback -> Npm
 pub fn detect_package_manager(project_root: &Path) -> PackageManager {
+    detect_in(project_root).unwrap_or(PackageManager::Npm)
+}
+
+/// The package manager for scripts run in `dir`, somewhere below `root`
+/// (whose package manager is `root_pm`): a nested lockfile or `packageManager`
+/// field between the two wins, e.g. a package that isn't part of the
+/// monorepo's workspaces and keeps its own `yarn.lock`.
+pub fn detect_package_manager_in(
+    dir: &Path,
+    root: &Path,
+    root_pm: PackageManager,
+) -> PackageManager {
+    dir.ancestors()
+        .take_while(|d| d.starts_with(root) && *d != root)
+        .find_map(detect_in)
+        .unwrap_or(root_pm)
+}
+
+/// Lockfile, then `packageManager` field, then Deno config, of the package in `dir`.
+fn detect_in(dir: &Path) -> Option<PackageManager> {
     // Lockfile-based detection (highest priority)
-    if project_root.join("bun.lockb").exists() || project_root.join("bun.lock").exists() {
-        return PackageManager::Bun;
+    if dir.join("bun.lockb").exists() || dir.join("bun.lock").exists() {
+        return Some(PackageManager::Bun);
     }
-    if project_root.join("pnpm-lock.yaml").exists() {
-        return PackageManager::Pnpm;
+    if dir.join("pnpm-lock.yaml").exists() {
+        return Some(PackageManager::Pnpm);
     }
-    if project_root.join("yarn.lock").exists() {
-        return PackageManager::Yarn;
+    if dir.join("yarn.lock").exists() {
+        return Some(detect_yarn_flavor(dir));
     }
-    if project_root.join("package-lock.json").exists() {
-        return PackageManager::Npm;
+    if dir.join("package-lock.json").exists() {
+        return Some(PackageManager::Npm);
     }
-
-    // packageManager field in package.json
-    if let Some(pm) = detect_from_package_json(project_root) {
-        return pm;
+    if dir.join("deno.lock").exists() {
+        return Some(PackageManager::Deno);
     }
 
-    // Fallback
-    PackageManager::Npm
+    // packageManager field in package.json
+    detect_from_package_json(dir)
+        .or_else(|| crate::core::deno::config_file(dir).map(|_| PackageManager::Deno))
 }
 
 /// Parse the `packageManager` field from `package.json` (e.g. `"pnpm@9.1.0"`).
@@ -81,12 +269,122 @@ fn detect_from_package_json(project_root: &Path) -> Option<PackageManager> {
     match name {
         "bun" => Some(PackageManager::Bun),
         "pnpm" => Some(PackageManager::Pnpm),
-        "yarn" => Some(PackageManager::Yarn),
+        "yarn" => Some(detect_yarn_flavor(project_root)),
         "npm" => Some(PackageManager::Npm),
         _ => None,
     }
 }
 
+/// The `packageManager` field closest to `dir` (looking up), split into name
+/// and version: `("pnpm", "9.1.0")` for `"pnpm@9.1.0+sha512.abc"`.
+pub fn declared_package_manager(dir: &Path) -> Option<(String, String)> {
+    let field = dir.ancestors().find_map(|d| {
+        crate::core::package_json::PackageJson::load(d).and_then(|pkg| pkg.package_manager)
+    })?;
+    let (name, version) = field.trim().split_once('@')?;
+    let version = version.split('+').next().unwrap_or(version);
+    Some((name.to_string(), version.to_string()))
+}
+
+/// A package manager on PATH at another version than `packageManager` declares.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionMismatch {
+    /// e.g. `pnpm@9.1.0`
+    pub declared: String,
+    pub installed: String,
+}
+
+/// Compares the installed version of `pm` with the one declared for `dir`.
+/// `None` when they match, nothing is declared (or it names another package
+/// manager), `pm` can't run, or Corepack doesn't provide `pm`.
+pub fn corepack_mismatch(pm: PackageManager, dir: &Path) -> Option<VersionMismatch> {
+    if !pm.supports_corepack() {
+        return None;
+    }
+    let (name, version) = declared_package_manager(dir)?;
+    if name != pm.command_name() {
+        return None;
+    }
+    let installed = pm.installed_version(dir)?;
+    (installed != version).then(|| VersionMismatch {
+        declared: format!("{}@{}", name, version),
+        installed,
+    })
+}
+
+/// Distinguish Yarn classic (1.x) from berry (2+).
+///
+/// Berry is assumed when any of the following holds:
+/// - `.yarnrc.yml` exists (classic uses `.yarnrc`)
+/// - `packageManager` pins `yarn@2` or later
+/// - `yarn.lock` starts with berry's `__metadata` block
+fn detect_yarn_flavor(project_root: &Path) -> PackageManager {
+    if project_root.join(".yarnrc.yml").is_file() {
+        return PackageManager::YarnBerry;
+    }
+
+    let pinned_major = crate::core::package_json::PackageJson::load(project_root)
+        .and_then(|pkg| pkg.package_manager)
+        .and_then(|field| yarn_major_version(&field));
+    if let Some(major) = pinned_major {
+        return if major >= 2 {
+            PackageManager::YarnBerry
+        } else {
+            PackageManager::Yarn
+        };
+    }
+
+    let berry_lockfile = std::fs::read_to_string(project_root.join("yarn.lock"))
+        .is_ok_and(|contents| contents.contains("__metadata:"));
+    if berry_lockfile {
+        PackageManager::YarnBerry
+    } else {
+        PackageManager::Yarn
+    }
+}
+
+/// Extract the major version from a `packageManager` value like `"yarn@4.1.0+sha256..."`.
+fn yarn_major_version(field: &str) -> Option<u32> {
+    let version = field.strip_prefix("yarn@")?;
+    version
+        .split(['.', '+', '-'])
+        .next()
+        .and_then(|major| major.parse().ok())
+}
+
+/// Splits an arguments string the way a POSIX shell would: quotes keep words
+/// together (`--name "my app"` is two arguments) and backslashes escape. With
+/// an unclosed quote it falls back to splitting on whitespace; see
+/// [`has_unclosed_quote`].
+pub fn split_args(args: &str) -> Vec<String> {
+    shell_words::split(args).unwrap_or_else(|_| args.split_whitespace().map(String::from).collect())
+}
+
+/// Whether `args` has a quote that is never closed, so [`split_args`] split it
+/// on whitespace instead.
+pub fn has_unclosed_quote(args: &str) -> bool {
+    shell_words::split(args).is_err()
+}
+
+/// Joins an argv into a line that can be pasted into a POSIX shell, quoting
+/// only the arguments that need it.
+pub fn shell_join(argv: &[String]) -> String {
+    argv.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,^".contains(c));
+            if plain {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +395,26 @@ mod tests {
         fs::write(dir.join(name), contents).unwrap();
     }
 
+    #[test]
+    fn reads_the_declared_package_manager_from_an_ancestor() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("package.json"),
+            r#"{ "packageManager": "pnpm@9.1.0+sha512.abc" }"#,
+        )
+        .unwrap();
+        let pkg = tmp.path().join("apps/web");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("package.json"), r#"{ "name": "web" }"#).unwrap();
+
+        assert_eq!(
+            declared_package_manager(&pkg),
+            Some(("pnpm".to_string(), "9.1.0".to_string()))
+        );
+        // Deno and Bun aren't provided by Corepack
+        assert_eq!(corepack_mismatch(PackageManager::Deno, &pkg), None);
+    }
+
     #[test]
     fn detects_bun_from_lockb() {
         let tmp = TempDir::new().unwrap();
@@ -132,6 +450,50 @@ mod tests {
         assert_eq!(detect_package_manager(tmp.path()), PackageManager::Npm);
     }
 
+    #[test]
+    fn detects_deno_from_lockfile_or_config() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "deno.json", "{}");
+        assert_eq!(detect_package_manager(tmp.path()), PackageManager::Deno);
+
+        // A Node lockfile next to deno.json (npm compatibility) wins over the config
+        write_file(tmp.path(), "package-lock.json", "");
+        assert_eq!(detect_package_manager(tmp.path()), PackageManager::Npm);
+
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "deno.lock", "");
+        assert_eq!(detect_package_manager(tmp.path()), PackageManager::Deno);
+        assert_eq!(
+            PackageManager::Deno.invocation("dev", &[], None),
+            vec!["deno", "task", "dev"]
+        );
+    }
+
+    #[test]
+    fn nested_lockfile_overrides_the_root_package_manager() {
+        let tmp = TempDir::new().unwrap();
+        let legacy = tmp.path().join("apps/legacy");
+        let web = tmp.path().join("apps/web/src");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::create_dir_all(&web).unwrap();
+        write_file(tmp.path(), "pnpm-lock.yaml", "");
+        write_file(&legacy, "yarn.lock", "");
+
+        let root_pm = detect_package_manager(tmp.path());
+        assert_eq!(
+            detect_package_manager_in(&legacy, tmp.path(), root_pm),
+            PackageManager::Yarn
+        );
+        assert_eq!(
+            detect_package_manager_in(&web, tmp.path(), root_pm),
+            PackageManager::Pnpm
+        );
+        assert_eq!(
+            detect_package_manager_in(tmp.path(), tmp.path(), root_pm),
+            PackageManager::Pnpm
+        );
+    }
+
     #[test]
     fn bun_lockfile_takes_priority_over_others() {
         let tmp = TempDir::new().unwrap();
@@ -166,11 +528,25 @@ mod tests {
         write_file(
             tmp.path(),
             "package.json",
-            r#"{"packageManager":"yarn@4.0.0"}"#,
+            r#"{"packageManager":"yarn@1.22.0"}"#,
         );
         assert_eq!(detect_package_manager(tmp.path()), PackageManager::Yarn);
     }
 
+    #[test]
+    fn detects_yarn_berry_from_package_manager_field() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"packageManager":"yarn@4.0.0"}"#,
+        );
+        assert_eq!(
+            detect_package_manager(tmp.path()),
+            PackageManager::YarnBerry
+        );
+    }
+
     #[test]
     fn detects_bun_from_package_manager_field() {
         let tmp = TempDir::new().unwrap();
@@ -182,6 +558,73 @@ mod tests {
         assert_eq!(detect_package_manager(tmp.path()), PackageManager::Bun);
     }
 
+    #[test]
+    fn detects_yarn_berry_from_yarnrc_yml() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "yarn.lock", "");
+        write_file(tmp.path(), ".yarnrc.yml", "nodeLinker: node-modules\n");
+        assert_eq!(
+            detect_package_manager(tmp.path()),
+            PackageManager::YarnBerry
+        );
+    }
+
+    #[test]
+    fn detects_yarn_berry_from_lockfile_metadata() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "yarn.lock",
+            "# This file is generated by running \"yarn install\"\n\n__metadata:\n  version: 8\n",
+        );
+        assert_eq!(
+            detect_package_manager(tmp.path()),
+            PackageManager::YarnBerry
+        );
+    }
+
+    #[test]
+    fn classic_yarn_lockfile_stays_classic() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "yarn.lock",
+            "# yarn lockfile v1\n\nleft-pad@^1.3.0:\n  version \"1.3.0\"\n",
+        );
+        assert_eq!(detect_package_manager(tmp.path()), PackageManager::Yarn);
+    }
+
+    #[test]
+    fn package_manager_pin_decides_yarn_flavor() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "yarn.lock", "");
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"packageManager":"yarn@1.22.19"}"#,
+        );
+        assert_eq!(detect_package_manager(tmp.path()), PackageManager::Yarn);
+
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"packageManager":"yarn@3.6.4+sha224.abc"}"#,
+        );
+        assert_eq!(
+            detect_package_manager(tmp.path()),
+            PackageManager::YarnBerry
+        );
+    }
+
+    #[test]
+    fn yarn_major_version_parses_pins() {
+        assert_eq!(yarn_major_version("yarn@1.22.19"), Some(1));
+        assert_eq!(yarn_major_version("yarn@4.0.0-rc.1"), Some(4));
+        assert_eq!(yarn_major_version("yarn@4+sha256.deadbeef"), Some(4));
+        assert_eq!(yarn_major_version("pnpm@9.0.0"), None);
+        assert_eq!(yarn_major_version("yarn"), None);
+    }
+
     #[test]
     fn lockfile_takes_priority_over_package_manager_field() {
         let tmp = TempDir::new().unwrap();
@@ -205,14 +648,132 @@ mod tests {
         assert_eq!(PackageManager::Bun.run_args("dev"), vec!["run", "dev"]);
         assert_eq!(PackageManager::Pnpm.run_args("dev"), vec!["run", "dev"]);
         assert_eq!(PackageManager::Yarn.run_args("dev"), vec!["dev"]);
+        assert_eq!(
+            PackageManager::YarnBerry.run_args("dev"),
+            vec!["run", "dev"]
+        );
         assert_eq!(PackageManager::Npm.run_args("dev"), vec!["run", "dev"]);
     }
 
+    #[test]
+    fn invocation_without_template_matches_run_args() {
+        assert_eq!(
+            PackageManager::Npm.invocation("build", &[], None),
+            vec!["npm", "run", "build"]
+        );
+        assert_eq!(
+            PackageManager::Yarn.invocation("build", &["--watch".to_string()], None),
+            vec!["yarn", "build", "--watch"]
+        );
+    }
+
+    #[test]
+    fn invocation_applies_template_placeholders() {
+        let args = vec!["--port".to_string(), "3000".to_string()];
+
+        assert_eq!(
+            PackageManager::Pnpm.invocation("dev", &args, Some("{pm} {run} --prefer-offline")),
+            vec!["pnpm", "run", "dev", "--prefer-offline", "--port", "3000"]
+        );
+        assert_eq!(
+            PackageManager::Npm.invocation("dev", &args, Some("dotenvx run -- {pm} {run} {args}")),
+            vec![
+                "dotenvx", "run", "--", "npm", "run", "dev", "--", "--port", "3000"
+            ]
+        );
+        assert_eq!(
+            PackageManager::Bun.invocation("dev", &[], Some("{pm} --bun run {script}")),
+            vec!["bun", "--bun", "run", "dev"]
+        );
+    }
+
+    #[test]
+    fn script_args_are_separated_only_for_npm() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            PackageManager::Npm.invocation("test", &args(&["--watch"]), None),
+            vec!["npm", "run", "test", "--", "--watch"]
+        );
+        assert_eq!(
+            PackageManager::Npm.invocation("test", &args(&["--", "--watch"]), None),
+            vec!["npm", "run", "test", "--", "--watch"]
+        );
+        assert_eq!(
+            PackageManager::Npm.workspace_invocation("web", "test", &args(&["-u"]), None),
+            vec!["npm", "run", "test", "--workspace", "web", "--", "-u"]
+        );
+        assert_eq!(
+            PackageManager::Pnpm.invocation("test", &args(&["--", "--watch"]), None),
+            vec!["pnpm", "run", "test", "--watch"]
+        );
+        assert_eq!(
+            PackageManager::Npm.invocation("test", &args(&["--"]), None),
+            vec!["npm", "run", "test"]
+        );
+    }
+
+    #[test]
+    fn invocation_with_empty_template_falls_back_to_default() {
+        assert_eq!(
+            PackageManager::Npm.invocation("dev", &[], Some("  ")),
+            vec!["npm", "run", "dev"]
+        );
+    }
+
+    #[test]
+    fn workspace_run_args_correct_for_each_pm() {
+        assert_eq!(
+            PackageManager::Pnpm.workspace_run_args("web", "dev"),
+            vec!["--filter", "web", "run", "dev"]
+        );
+        assert_eq!(
+            PackageManager::Yarn.workspace_run_args("web", "dev"),
+            vec!["workspace", "web", "run", "dev"]
+        );
+        assert_eq!(
+            PackageManager::YarnBerry.workspace_run_args("web", "dev"),
+            vec!["workspace", "web", "run", "dev"]
+        );
+        assert_eq!(
+            PackageManager::Npm.workspace_run_args("web", "dev"),
+            vec!["run", "dev", "--workspace", "web"]
+        );
+    }
+
+    #[test]
+    fn workspace_invocation_targets_the_package_from_the_root() {
+        let args = vec!["--port".to_string(), "3000".to_string()];
+        assert_eq!(
+            PackageManager::Pnpm.workspace_invocation("web", "dev", &args, None),
+            vec!["pnpm", "--filter", "web", "run", "dev", "--port", "3000"]
+        );
+        assert_eq!(
+            PackageManager::Npm.workspace_invocation(
+                "web",
+                "dev",
+                &[],
+                Some("dotenvx run -- {pm} {run}")
+            ),
+            vec![
+                "dotenvx",
+                "run",
+                "--",
+                "npm",
+                "run",
+                "dev",
+                "--workspace",
+                "web"
+            ]
+        );
+    }
+
     #[test]
     fn command_name_correct_for_each_pm() {
         assert_eq!(PackageManager::Bun.command_name(), "bun");
         assert_eq!(PackageManager::Pnpm.command_name(), "pnpm");
         assert_eq!(PackageManager::Yarn.command_name(), "yarn");
+        assert_eq!(PackageManager::YarnBerry.command_name(), "yarn");
         assert_eq!(PackageManager::Npm.command_name(), "npm");
     }
 
@@ -221,4 +782,29 @@ mod tests {
         assert_eq!(format!("{}", PackageManager::Bun), "bun");
         assert_eq!(format!("{}", PackageManager::Npm), "npm");
     }
+
+    #[test]
+    fn split_args_honors_quotes_and_escapes() {
+        assert_eq!(
+            split_args(r#"--name "my app" --tag=it\'s -- --watch"#),
+            vec!["--name", "my app", "--tag=it's", "--", "--watch"]
+        );
+        assert!(!has_unclosed_quote("--name 'my app'"));
+
+        // An unclosed quote is kept as typed
+        assert!(has_unclosed_quote("--name \"my app"));
+        assert_eq!(split_args("--name \"my app"), vec!["--name", "\"my", "app"]);
+    }
+
+    #[test]
+    fn shell_join_quotes_only_when_needed() {
+        let argv: Vec<String> = ["pnpm", "run", "build", "--", "--flag=a b", "it's", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            shell_join(&argv),
+            r"pnpm run build -- '--flag=a b' 'it'\''s' ''"
+        );
+    }
 }
@@ -0,0 +1,291 @@
+//! Running several queued scripts at once with interleaved, prefixed output,
+//! like `concurrently`.
+
+use crate::core::env_files::load_env_files;
+use crate::core::package_manager::PackageManager;
+use crate::core::queue::QueuedRun;
+use crate::core::runner::{
+    ExecutionStrategy, PipedRun, Signal, format_duration, node_template, spawn_piped, use_corepack,
+};
+use crate::store::config::Config;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How often running scripts are checked for output.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// ANSI colors cycled through for the `[name]` prefixes.
+const COLORS: &[u8] = &[36, 35, 33, 32, 34, 31];
+
+/// Outcome of one script in a parallel run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelResult {
+    pub label: String,
+    pub exit_code: i32,
+    pub duration: Duration,
+}
+
+/// Runs every script in `runs` at the same time and waits for all of them,
+/// printing each output line behind its run's `[key]` prefix. `pm_for` picks
+/// the package manager for a run's directory.
+///
+/// Ctrl+C is forwarded to all scripts that are still running; pressing it
+/// again escalates SIGINT → SIGTERM → SIGKILL.
+pub fn run_parallel(
+    pm_for: impl Fn(&Path) -> PackageManager,
+    runs: &[QueuedRun],
+    config: &Config,
+) -> Vec<ParallelResult> {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let width = runs.iter().map(|r| r.key.len()).max().unwrap_or(0);
+    let prefix = |i: usize| format_prefix(&runs[i].key, width, COLORS[i % COLORS.len()], color);
+
+    // Node version and Corepack checks may ask on the terminal, so they all
+    // happen before any output is interleaved
+    let setups: Vec<_> = runs
+        .iter()
+        .map(|run| {
+            let pm = pm_for(&run.cwd);
+            let template = node_template(&run.cwd, config.node_version, config.run_template(pm))
+                .or_else(|| config.run_template(pm).map(String::from));
+            let strategy = ExecutionStrategy {
+                corepack: use_corepack(pm, &run.cwd, config.corepack),
+                wrapper: config.run_through.clone(),
+            };
+            (pm, template, strategy)
+        })
+        .collect();
+
+    let started = Instant::now();
+    let mut processes: Vec<Option<PipedRun>> = Vec::new();
+    let mut results: Vec<Option<ParallelResult>> = Vec::new();
+    for (i, (run, (pm, template, strategy))) in runs.iter().zip(&setups).enumerate() {
+        let mut env_vars = load_env_files(&run.env_files).unwrap_or_default();
+        env_vars.extend(run.env.iter().cloned());
+        match spawn_piped(
+            *pm,
+            &run.script_name,
+            &run.cwd,
+            env_vars,
+            &run.args,
+            template.as_deref(),
+            strategy,
+            &config.inherit_env,
+        ) {
+            Ok(process) => {
+                processes.push(Some(process));
+                results.push(None);
+            }
+            Err(e) => {
+                eprintln!("{} Failed to start {}: {}", prefix(i), run.script_name, e);
+                processes.push(None);
+                results.push(Some(ParallelResult {
+                    label: run.key.clone(),
+                    exit_code: 1,
+                    duration: Duration::ZERO,
+                }));
+            }
+        }
+    }
+
+    let _catching = interrupt::Catch::install();
+    let mut interrupts = 0;
+    while results.iter().any(Option::is_none) {
+        if interrupt::take() {
+            interrupts += 1;
+            for process in processes.iter_mut().flatten() {
+                let _ = match interrupts {
+                    1 => process.signal(Signal::Interrupt),
+                    2 => process.signal(Signal::Terminate),
+                    _ => {
+                        process.kill();
+                        Ok(())
+                    }
+                };
+            }
+        }
+
+        for (i, slot) in processes.iter_mut().enumerate() {
+            let Some(process) = slot else {
+                continue;
+            };
+            let (lines, exit_code) = process.poll();
+            let mut stdout = std::io::stdout().lock();
+            for line in lines {
+                let _ = writeln!(stdout, "{} {}", prefix(i), line.text);
+            }
+            if let Some(code) = exit_code {
+                let duration = started.elapsed();
+                let _ = writeln!(
+                    stdout,
+                    "{} exited with code {} after {}",
+                    prefix(i),
+                    code,
+                    format_duration(duration)
+                );
+                results[i] = Some(ParallelResult {
+                    label: runs[i].key.clone(),
+                    exit_code: code,
+                    duration,
+                });
+                *slot = None;
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    results.into_iter().flatten().collect()
+}
+
+/// `[key]` padded to `width` so output lines up, colored when `color` is set.
+fn format_prefix(key: &str, width: usize, ansi_color: u8, color: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(key.len()));
+    if color {
+        format!("\x1b[{}m[{}]\x1b[0m{}", ansi_color, key, padding)
+    } else {
+        format!("[{}]{}", key, padding)
+    }
+}
+
+/// Summary table printed once every script has exited.
+pub fn format_summary(results: &[ParallelResult]) -> String {
+    let label_width = results.iter().map(|r| r.label.len()).max().unwrap_or(0);
+    let passed = results.iter().filter(|r| r.exit_code == 0).count();
+
+    let mut out = String::from("\nParallel results:\n");
+    for result in results {
+        let mark = if result.exit_code == 0 { "✔" } else { "✘" };
+        out.push_str(&format!(
+            "  {} {:<width$}  exit {:<3} {:>7.1}s\n",
+            mark,
+            result.label,
+            result.exit_code,
+            result.duration.as_secs_f64(),
+            width = label_width
+        ));
+    }
+    out.push_str(&format!("{} of {} scripts passed\n", passed, results.len()));
+    out
+}
+
+/// Catches Ctrl+C while scripts run so it can be forwarded to them: they lead
+/// their own process groups and don't see the terminal's SIGINT.
+#[cfg(unix)]
+mod interrupt {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_interrupt(_: libc::c_int) {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+
+    /// Catches SIGINT until dropped, then puts the previous handler back.
+    pub struct Catch(libc::sighandler_t);
+
+    impl Catch {
+        pub fn install() -> Self {
+            INTERRUPTED.store(false, Ordering::SeqCst);
+            // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+            Self(unsafe {
+                libc::signal(
+                    libc::SIGINT,
+                    on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+                )
+            })
+        }
+    }
+
+    impl Drop for Catch {
+        fn drop(&mut self) {
+            // SAFETY: the handler was returned by signal(2) for the same signal
+            unsafe {
+                libc::signal(libc::SIGINT, self.0);
+            }
+        }
+    }
+
+    /// Whether Ctrl+C was pressed since the last call.
+    pub fn take() -> bool {
+        INTERRUPTED.swap(false, Ordering::SeqCst)
+    }
+}
+
+#[cfg(not(unix))]
+mod interrupt {
+    pub struct Catch;
+
+    impl Catch {
+        pub fn install() -> Self {
+            Self
+        }
+    }
+
+    pub fn take() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_prefixes_line_up_and_color_only_when_asked() {
+        assert_eq!(format_prefix("api", 8, 36, false), "[api]     ");
+        assert_eq!(format_prefix("web:dev", 7, 35, false), "[web:dev]");
+        assert_eq!(format_prefix("api", 3, 36, true), "\x1b[36m[api]\x1b[0m");
+    }
+
+    #[test]
+    fn test_summary_counts_passed_scripts() {
+        let results = vec![
+            ParallelResult {
+                label: "root:api".into(),
+                exit_code: 0,
+                duration: Duration::from_millis(1500),
+            },
+            ParallelResult {
+                label: "web:dev".into(),
+                exit_code: 2,
+                duration: Duration::from_millis(300),
+            },
+        ];
+        let summary = format_summary(&results);
+        assert!(summary.contains("  ✔ root:api  exit 0       1.5s\n"));
+        assert!(summary.contains("  ✘ web:dev   exit 2       0.3s\n"));
+        assert!(summary.ends_with("1 of 2 scripts passed\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_runs_all_scripts_and_reports_each_exit_code() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("run.sh"),
+            "case $1 in ok) exit 0;; *) exit 3;; esac",
+        )
+        .unwrap();
+        let run = |name: &str| QueuedRun {
+            key: format!("root:{}", name),
+            script_name: name.to_string(),
+            cwd: PathBuf::from(tmp.path()),
+            env_files: vec![],
+            args: String::new(),
+            env: Vec::new(),
+        };
+
+        let config = Config {
+            run_templates: [("npm".to_string(), "sh run.sh {script} {args}".to_string())].into(),
+            ..Config::default()
+        };
+        let results = run_parallel(|_| PackageManager::Npm, &[run("ok"), run("fail")], &config);
+        let codes: Vec<(&str, i32)> = results
+            .iter()
+            .map(|r| (r.label.as_str(), r.exit_code))
+            .collect();
+        assert_eq!(codes, vec![("root:ok", 0), ("root:fail", 3)]);
+    }
+}
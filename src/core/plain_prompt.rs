@@ -0,0 +1,62 @@
+use indexmap::IndexMap;
+
+/// Numbered script list for the plain-text picker used without a terminal UI.
+pub fn format_menu(scripts: &IndexMap<String, String>) -> String {
+    let number_width = scripts.len().to_string().len();
+    let name_width = scripts.keys().map(String::len).max().unwrap_or(0);
+
+    scripts
+        .iter()
+        .enumerate()
+        .map(|(i, (name, command))| {
+            format!(
+                "{:>nw$}) {:<w$}  {}\n",
+                i + 1,
+                name,
+                command,
+                nw = number_width,
+                w = name_width
+            )
+        })
+        .collect()
+}
+
+/// Resolves an answer to the plain-text picker: a 1-based number from the menu
+/// or a script name.
+pub fn parse_choice<'a>(input: &str, scripts: &'a IndexMap<String, String>) -> Option<&'a str> {
+    let input = input.trim();
+    if let Some((name, _)) = scripts.get_key_value(input) {
+        return Some(name);
+    }
+    let number: usize = input.parse().ok()?;
+    scripts
+        .get_index(number.checked_sub(1)?)
+        .map(|(name, _)| name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts() -> IndexMap<String, String> {
+        [("build", "tsc"), ("test", "vitest")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_menu_numbers_scripts_in_order() {
+        assert_eq!(format_menu(&scripts()), "1) build  tsc\n2) test   vitest\n");
+    }
+
+    #[test]
+    fn test_parse_choice_accepts_number_or_name() {
+        let scripts = scripts();
+        assert_eq!(parse_choice("2\n", &scripts), Some("test"));
+        assert_eq!(parse_choice(" build ", &scripts), Some("build"));
+        assert_eq!(parse_choice("0", &scripts), None);
+        assert_eq!(parse_choice("3", &scripts), None);
+        assert_eq!(parse_choice("lint", &scripts), None);
+    }
+}
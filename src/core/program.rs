@@ -0,0 +1,75 @@
+//! Starting external programs by name on every platform. On Windows, npm,
+//! pnpm, Yarn and editors like `code` are batch scripts (`npm.cmd`) that
+//! `Command::new("npm")` doesn't find, so bare names are looked up in `PATH`
+//! with the extensions of `PATHEXT` first.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extensions tried when `PATHEXT` isn't set.
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// A [`Command`] for `program`: on Windows a bare name is resolved to the
+/// file `PATH` holds for it (`npm` → `C:\Program Files\nodejs\npm.cmd`),
+/// elsewhere it's passed on as is.
+pub fn command(program: impl AsRef<OsStr>) -> Command {
+    let program = program.as_ref();
+    if cfg!(windows) {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let pathext = std::env::var_os("PATHEXT").unwrap_or_else(|| DEFAULT_PATHEXT.into());
+        if let Some(resolved) = resolve(program, &path, &pathext) {
+            return Command::new(resolved);
+        }
+    }
+    Command::new(program)
+}
+
+/// The first `dir/program{ext}` of the `path` list that is a file, trying
+/// each extension of `pathext` (`;`-separated) in order. `None` for names
+/// that already have an extension or a directory.
+pub fn resolve(program: &OsStr, path: &OsStr, pathext: &OsStr) -> Option<PathBuf> {
+    let name = Path::new(program);
+    if name.extension().is_some() || name.components().count() != 1 {
+        return None;
+    }
+    let pathext = pathext.to_string_lossy();
+    let extensions: Vec<&str> = pathext.split(';').filter(|ext| !ext.is_empty()).collect();
+    std::env::split_paths(path).find_map(|dir| {
+        extensions.iter().find_map(|ext| {
+            let mut file = OsString::from(program);
+            file.push(ext.to_ascii_lowercase());
+            let candidate = dir.join(file);
+            candidate.is_file().then_some(candidate)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_finds_batch_scripts_on_path() {
+        let empty = TempDir::new().unwrap();
+        let nodejs = TempDir::new().unwrap();
+        std::fs::write(nodejs.path().join("npm.cmd"), "").unwrap();
+        std::fs::write(nodejs.path().join("node.exe"), "").unwrap();
+        let path = std::env::join_paths([empty.path(), nodejs.path()]).unwrap();
+        let pathext = OsStr::new(".COM;.EXE;.BAT;.CMD");
+
+        assert_eq!(
+            resolve(OsStr::new("npm"), &path, pathext),
+            Some(nodejs.path().join("npm.cmd"))
+        );
+        assert_eq!(
+            resolve(OsStr::new("node"), &path, pathext),
+            Some(nodejs.path().join("node.exe"))
+        );
+        assert_eq!(resolve(OsStr::new("pnpm"), &path, pathext), None);
+        // Names with an extension or a directory are left to the OS
+        assert_eq!(resolve(OsStr::new("npm.cmd"), &path, pathext), None);
+        assert_eq!(resolve(OsStr::new("bin/npm"), &path, pathext), None);
+    }
+}
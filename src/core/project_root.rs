@@ -1,35 +1,52 @@
+use crate::core::deno;
 use crate::core::package_json::PackageJson;
 use std::path::{Path, PathBuf};
 
 /// Result of project root discovery.
 #[derive(Debug)]
 pub struct ProjectRoot {
-    /// Path to the directory containing the nearest `package.json`.
+    /// Path to the directory containing the nearest `package.json` (or `deno.json`).
     pub nearest_pkg: PathBuf,
-    /// Path to the monorepo root (contains `workspaces` in package.json or pnpm-workspace.yaml).
+    /// Path to the monorepo root (contains `workspaces` in package.json, or a
+    /// pnpm-workspace.yaml, lerna.json or rush.json).
     pub monorepo_root: Option<PathBuf>,
 }
 
+/// Marker file that pins the project root to the directory containing it.
+pub const ROOT_MARKER: &str = ".nr-root";
+
 /// Errors that can occur during project root discovery.
 #[derive(Debug, thiserror::Error)]
 pub enum ProjectRootError {
     #[error(
-        "No package.json found in any parent directory.\n\n💡 To use nr, you need a Node.js project with package.json.\n\nCreate one by running:\n   npm init -y\n   # or\n   yarn init -y\n   # or\n   pnpm init\n   # or\n   bun init\n\nThen add scripts to your package.json and run 'nr' again."
+        "No package.json found in any parent directory.\n\n💡 To use nr, you need a Node.js project with package.json.\n\nCreate one by running:\n   npm init -y\n   # or\n   yarn init -y\n   # or\n   pnpm init\n   # or\n   bun init\n   # or, for Deno tasks in deno.json\n   deno init\n\nThen add scripts to your package.json and run 'nr' again."
     )]
     NotFound,
+
+    #[error("No package.json or deno.json found in project root {0}")]
+    MissingPackageJson(PathBuf),
 }
 
 /// Two-phase upward traversal from `cwd` to locate the nearest `package.json`
 /// and, optionally, a monorepo root above it.
 ///
-/// Phase 1: Walk `cwd.ancestors()` to find the first directory containing `package.json`.
+/// A `.nr-root` marker file in `cwd` or any ancestor short-circuits the search and
+/// pins the root to the marker's directory (see [`forced_project_root`]).
+///
+/// Phase 1: Walk `cwd.ancestors()` to find the first directory containing `package.json`
+///           or a Deno config (`deno.json` / `deno.jsonc`).
 /// Phase 2: Continue upward from that directory's parent looking for a `package.json`
-///           with a `"workspaces"` field, or a `pnpm-workspace.yaml` file.
+///           with a `"workspaces"` field, or a `pnpm-workspace.yaml`, `lerna.json` or
+///           `rush.json` file.
 pub fn find_project_root(cwd: &Path) -> Result<ProjectRoot, ProjectRootError> {
+    if let Some(marked) = cwd.ancestors().find(|dir| dir.join(ROOT_MARKER).is_file()) {
+        return forced_project_root(marked);
+    }
+
     // Phase 1: find nearest package.json
     let nearest_pkg = cwd
         .ancestors()
-        .find(|dir| dir.join("package.json").is_file())
+        .find(|dir| is_package_dir(dir))
         .map(Path::to_path_buf)
         .ok_or(ProjectRootError::NotFound)?;
 
@@ -46,9 +63,34 @@ pub fn find_project_root(cwd: &Path) -> Result<ProjectRoot, ProjectRootError> {
     })
 }
 
-/// Check if a directory itself is a monorepo root (has workspaces in package.json or pnpm-workspace.yaml).
+/// Uses `dir` as the project root as-is, e.g. from `--root` or a `.nr-root` marker.
+///
+/// Nested `package.json` files below `dir` are skipped, and no monorepo root is
+/// searched for above it: `dir` is the monorepo root only if it declares workspaces itself.
+pub fn forced_project_root(dir: &Path) -> Result<ProjectRoot, ProjectRootError> {
+    if !is_package_dir(dir) {
+        return Err(ProjectRootError::MissingPackageJson(dir.to_path_buf()));
+    }
+
+    let monorepo_root = is_monorepo_root(dir).then(|| dir.to_path_buf());
+    Ok(ProjectRoot {
+        nearest_pkg: dir.to_path_buf(),
+        monorepo_root,
+    })
+}
+
+/// Whether `dir` holds a project nr can run scripts of: a `package.json` or a Deno config.
+fn is_package_dir(dir: &Path) -> bool {
+    dir.join("package.json").is_file() || deno::config_file(dir).is_some()
+}
+
+/// Check if a directory itself is a monorepo root (has workspaces in package.json,
+/// or a pnpm-workspace.yaml, lerna.json or rush.json).
 fn is_monorepo_root(dir: &Path) -> bool {
-    if dir.join("pnpm-workspace.yaml").is_file() {
+    if ["pnpm-workspace.yaml", "lerna.json", "rush.json"]
+        .iter()
+        .any(|file| dir.join(file).is_file())
+    {
         return true;
     }
     PackageJson::load(dir).is_some_and(|pkg| pkg.workspaces.is_some())
@@ -56,7 +98,7 @@ fn is_monorepo_root(dir: &Path) -> bool {
 
 /// Starting from `start_dir`'s parent, walk upward looking for a monorepo root indicator:
 /// - A `package.json` containing a `"workspaces"` key
-/// - A `pnpm-workspace.yaml` file
+/// - A `pnpm-workspace.yaml`, `lerna.json` or `rush.json` file
 fn find_monorepo_root(start_dir: &Path) -> Option<PathBuf> {
     let parent = start_dir.parent()?;
 
@@ -156,6 +198,19 @@ mod tests {
         assert_eq!(result.monorepo_root.unwrap(), tmp.path());
     }
 
+    #[test]
+    fn detects_rush_monorepo_root_without_package_json() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "rush.json", r#"{"projects":[]}"#);
+
+        let pkg_dir = tmp.path().join("apps").join("api");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        write_file(&pkg_dir, "package.json", r#"{"name":"api"}"#);
+
+        let result = find_project_root(&pkg_dir).unwrap();
+        assert_eq!(result.monorepo_root.unwrap(), tmp.path());
+    }
+
     #[test]
     fn no_monorepo_when_parent_has_no_workspaces() {
         let tmp = TempDir::new().unwrap();
@@ -188,4 +243,64 @@ mod tests {
         assert_eq!(result.nearest_pkg, tmp.path());
         assert_eq!(result.monorepo_root.unwrap(), tmp.path());
     }
+
+    #[test]
+    fn marker_file_overrides_nested_package_json() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "package.json", r#"{"name":"app"}"#);
+        write_file(tmp.path(), ROOT_MARKER, "");
+
+        // Tooling package.json nested inside the project
+        let tooling = tmp.path().join("tools").join("eslint");
+        fs::create_dir_all(&tooling).unwrap();
+        write_file(&tooling, "package.json", r#"{"name":"eslint-config"}"#);
+
+        let result = find_project_root(&tooling).unwrap();
+        assert_eq!(result.nearest_pkg, tmp.path());
+        assert!(result.monorepo_root.is_none());
+    }
+
+    #[test]
+    fn forced_root_does_not_search_above() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"name":"monorepo","workspaces":["packages/*"]}"#,
+        );
+        let pkg_dir = tmp.path().join("packages").join("app");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        write_file(&pkg_dir, "package.json", r#"{"name":"app"}"#);
+
+        let result = forced_project_root(&pkg_dir).unwrap();
+        assert_eq!(result.nearest_pkg, pkg_dir);
+        assert!(result.monorepo_root.is_none());
+
+        let result = forced_project_root(tmp.path()).unwrap();
+        assert_eq!(result.monorepo_root.unwrap(), tmp.path());
+    }
+
+    #[test]
+    fn forced_root_requires_package_json() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), ROOT_MARKER, "");
+
+        let result = find_project_root(tmp.path());
+        assert!(matches!(
+            result.unwrap_err(),
+            ProjectRootError::MissingPackageJson(_)
+        ));
+    }
+
+    #[test]
+    fn deno_config_counts_as_a_project() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "deno.jsonc", r#"{ "tasks": {} }"#);
+        let child = tmp.path().join("routes");
+        fs::create_dir_all(&child).unwrap();
+
+        let result = find_project_root(&child).unwrap();
+        assert_eq!(result.nearest_pkg, tmp.path());
+        assert!(result.monorepo_root.is_none());
+    }
 }
@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+/// A script waiting in the execution queue, with the configuration it will run with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedRun {
+    /// Script key without project id (e.g. `root:build` or `@mono/web:dev`).
+    pub key: String,
+    pub script_name: String,
+    pub cwd: PathBuf,
+    /// Env files in merge order (root → package).
+    pub env_files: Vec<PathBuf>,
+    pub args: String,
+    /// Variables set on top of the env files (the configure flow's overrides).
+    pub env: Vec<(String, String)>,
+}
+
+/// Scripts that run one at a time, in order.
+///
+/// A paused queue runs a single item and then hands control back to the TUI,
+/// so the remaining items can be reordered before resuming.
+#[derive(Debug, Default)]
+pub struct ExecutionQueue {
+    pub items: Vec<QueuedRun>,
+    pub paused: bool,
+}
+
+impl ExecutionQueue {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Appends a run to the end of the queue.
+    pub fn push(&mut self, run: QueuedRun) {
+        self.items.push(run);
+    }
+
+    /// Takes the next run off the front of the queue.
+    pub fn pop_front(&mut self) -> Option<QueuedRun> {
+        if self.items.is_empty() {
+            None
+        } else {
+            Some(self.items.remove(0))
+        }
+    }
+
+    /// Removes the run at `index`, if any.
+    pub fn remove(&mut self, index: usize) -> Option<QueuedRun> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    /// Swaps the run at `index` with its predecessor. Returns the run's new index.
+    pub fn move_up(&mut self, index: usize) -> usize {
+        if index == 0 || index >= self.items.len() {
+            return index;
+        }
+        self.items.swap(index, index - 1);
+        index - 1
+    }
+
+    /// Swaps the run at `index` with its successor. Returns the run's new index.
+    pub fn move_down(&mut self, index: usize) -> usize {
+        if index + 1 >= self.items.len() {
+            return index;
+        }
+        self.items.swap(index, index + 1);
+        index + 1
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str) -> QueuedRun {
+        QueuedRun {
+            key: format!("root:{}", name),
+            script_name: name.to_string(),
+            cwd: PathBuf::from("/project"),
+            env_files: vec![],
+            args: String::new(),
+            env: Vec::new(),
+        }
+    }
+
+    fn names(queue: &ExecutionQueue) -> Vec<&str> {
+        queue.items.iter().map(|r| r.script_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_pop_front_is_fifo() {
+        let mut queue = ExecutionQueue::default();
+        queue.push(run("lint"));
+        queue.push(run("build"));
+
+        assert_eq!(queue.pop_front().unwrap().script_name, "lint");
+        assert_eq!(queue.pop_front().unwrap().script_name, "build");
+        assert!(queue.pop_front().is_none());
+    }
+
+    #[test]
+    fn test_move_up_and_down() {
+        let mut queue = ExecutionQueue::default();
+        queue.push(run("a"));
+        queue.push(run("b"));
+        queue.push(run("c"));
+
+        assert_eq!(queue.move_up(2), 1);
+        assert_eq!(names(&queue), vec!["a", "c", "b"]);
+
+        assert_eq!(queue.move_down(0), 1);
+        assert_eq!(names(&queue), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_move_at_edges_is_noop() {
+        let mut queue = ExecutionQueue::default();
+        queue.push(run("a"));
+        queue.push(run("b"));
+
+        assert_eq!(queue.move_up(0), 0);
+        assert_eq!(queue.move_down(1), 1);
+        assert_eq!(queue.move_down(5), 5);
+        assert_eq!(names(&queue), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_remove_out_of_range() {
+        let mut queue = ExecutionQueue::default();
+        queue.push(run("a"));
+
+        assert!(queue.remove(3).is_none());
+        assert_eq!(queue.remove(0).unwrap().script_name, "a");
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_pause() {
+        let mut queue = ExecutionQueue::default();
+        assert!(!queue.paused);
+        queue.toggle_pause();
+        assert!(queue.paused);
+        queue.toggle_pause();
+        assert!(!queue.paused);
+    }
+}
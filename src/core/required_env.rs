@@ -0,0 +1,89 @@
+use crate::core::inherit_env::InheritEnv;
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal, Write};
+
+/// Keys from `required` that a script would start without: not set (or empty)
+/// in `env_vars` from its env files nor in the environment it inherits.
+pub fn missing_env(
+    required: &[String],
+    env_vars: &HashMap<String, String>,
+    inherit: &InheritEnv,
+) -> Vec<String> {
+    missing_env_with(required, env_vars, inherit, |key| std::env::var(key).ok())
+}
+
+fn missing_env_with(
+    required: &[String],
+    env_vars: &HashMap<String, String>,
+    inherit: &InheritEnv,
+    inherited: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let is_set = |value: Option<String>| value.is_some_and(|v| !v.is_empty());
+    let mut missing: Vec<String> = Vec::new();
+    for key in required {
+        if missing.contains(key) || is_set(env_vars.get(key).cloned()) {
+            continue;
+        }
+        let passed_on = inherit.blocked([key.as_str()]).is_empty();
+        if !(passed_on && is_set(inherited(key))) {
+            missing.push(key.clone());
+        }
+    }
+    missing
+}
+
+/// Asks on the terminal for a value for each of `missing`.
+/// Returns `None` when stdin isn't a terminal or a value is left empty.
+pub fn prompt_missing(script_name: &str, missing: &[String]) -> Option<Vec<(String, String)>> {
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+    let mut values = Vec::new();
+    let mut stdin = std::io::stdin().lock();
+    for key in missing {
+        eprint!("{} (required by {}): ", key, script_name);
+        std::io::stderr().flush().ok();
+        let mut line = String::new();
+        stdin.read_line(&mut line).ok()?;
+        let value = line.trim_end_matches(['\r', '\n']);
+        if value.is_empty() {
+            return None;
+        }
+        values.push((key.clone(), value.to_string()));
+    }
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(list: &[&str]) -> Vec<String> {
+        list.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_missing_env_checks_env_files_then_inherited_env() {
+        let env_vars = HashMap::from([
+            ("API_URL".to_string(), "http://localhost".to_string()),
+            ("TOKEN".to_string(), String::new()),
+        ]);
+        let inherited = |key: &str| (key == "HOME" || key == "CI").then(|| "1".to_string());
+
+        let required = keys(&["API_URL", "TOKEN", "HOME", "SECRET", "TOKEN"]);
+        assert_eq!(
+            missing_env_with(&required, &env_vars, &InheritEnv::default(), inherited),
+            vec!["TOKEN", "SECRET"]
+        );
+
+        // A variable scripts don't inherit doesn't count as set
+        let inherit = InheritEnv {
+            deny: keys(&["CI"]),
+            ..Default::default()
+        };
+        assert_eq!(
+            missing_env_with(&keys(&["HOME", "CI"]), &env_vars, &inherit, inherited),
+            vec!["CI"]
+        );
+    }
+}
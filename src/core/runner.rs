@@ -1,75 +1,170 @@
-use crate::core::package_manager::PackageManager;
+use crate::core::hooks::{self, ScriptHooks};
+use crate::core::inherit_env::InheritEnv;
+use crate::core::node_version::{self, NodeVersionCheck};
+use crate::core::package_manager::{
+    DEFAULT_RUN_TEMPLATE, PackageManager, corepack_mismatch, shell_join, split_args,
+};
+use crate::core::program;
+use crate::core::tasks::TaskRunner;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
-use std::process::Command;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
 
-/// Execute a package.json script via the detected package manager.
-///
-/// Inherits stdin/stdout/stderr so the child process can interact with the terminal.
-/// Returns the process exit code (or `1` on spawn failure / missing exit code).
-pub fn run_script(pm: PackageManager, script_name: &str, cwd: &Path) -> i32 {
-    let status = Command::new(pm.command_name())
-        .args(pm.run_args(script_name))
-        .current_dir(cwd)
-        .stdin(std::process::Stdio::inherit())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status();
+/// A workspace package whose scripts run from the monorepo root with the
+/// package manager's own targeting (`pnpm --filter web run dev`) instead of
+/// from the package's directory; see the `run_from_root` config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceTarget {
+    pub root: PathBuf,
+    /// The package's `name`, which the package manager selects it by.
+    pub package: String,
+}
 
-    match status {
-        Ok(s) => s.code().unwrap_or(1),
-        Err(e) => {
-            eprintln!();
-            eprintln!(
-                "❌ Failed to run script: '{} {}'",
-                pm.command_name(),
-                script_name
-            );
-            eprintln!();
+/// How a run starts the package manager; the default starts the one on PATH
+/// directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionStrategy {
+    /// Through `corepack <pm>`, which runs the version `packageManager` declares.
+    pub corepack: bool,
+    /// A program the whole command runs through (the `run_through` config):
+    /// `shell` for the user's `$SHELL -lc`, or a command like `direnv exec .`,
+    /// where `{command}` stands for the command line as one argument.
+    pub wrapper: Option<String>,
+}
 
-            // Check if it's a command not found error
-            if e.kind() == std::io::ErrorKind::NotFound {
-                eprintln!(
-                    "🔍 Package manager '{}' not found in PATH",
-                    pm.command_name()
-                );
-                eprintln!();
-                eprintln!("💡 Install {} to continue:", pm);
-
-                match pm {
-                    PackageManager::Npm => {
-                        eprintln!("   - Download Node.js (includes npm): https://nodejs.org");
-                        eprintln!("   - Or use a version manager: nvm, fnm, volta");
-                    }
-                    PackageManager::Yarn => {
-                        eprintln!("   npm install -g yarn");
-                        eprintln!("   Or: https://yarnpkg.com/getting-started/install");
-                    }
-                    PackageManager::Pnpm => {
-                        eprintln!("   npm install -g pnpm");
-                        eprintln!("   Or: https://pnpm.io/installation");
-                    }
-                    PackageManager::Bun => {
-                        eprintln!("   curl -fsSL https://bun.sh/install | bash");
-                        eprintln!("   Or: https://bun.sh");
-                    }
-                }
-            } else {
-                eprintln!("Error: {}", e);
-                eprintln!();
-                eprintln!("💡 Common issues:");
-                eprintln!("   - Check if the package manager is in your PATH");
-                eprintln!(
-                    "   - Try running the script manually: {} {}",
-                    pm.command_name(),
-                    script_name
-                );
+impl ExecutionStrategy {
+    /// `argv` (a template expanded by [`PackageManager::invocation`]) as this
+    /// strategy starts it: Corepack goes in front of the package manager
+    /// wherever the template put it, then the wrapper in front of it all.
+    pub fn apply(&self, pm: PackageManager, mut argv: Vec<String>) -> Vec<String> {
+        if self.corepack {
+            if let Some(i) = argv.iter().position(|arg| arg == pm.command_name()) {
+                argv.insert(i, "corepack".to_string());
             }
-
-            eprintln!();
-            1
         }
+        match self.wrapper.as_deref() {
+            Some(wrapper) => wrap(wrapper, argv),
+            None => argv,
+        }
+    }
+}
+
+/// `argv` run through `wrapper` (see [`ExecutionStrategy::wrapper`]).
+fn wrap(wrapper: &str, argv: Vec<String>) -> Vec<String> {
+    let wrapper = match wrapper.trim() {
+        "shell" => shell_wrapper(std::env::var("SHELL").ok().as_deref()),
+        wrapper => wrapper.to_string(),
+    };
+    let tokens = split_args(&wrapper);
+    if tokens.iter().any(|token| token == "{command}") {
+        let command = shell_join(&argv);
+        tokens
+            .into_iter()
+            .map(|token| {
+                if token == "{command}" {
+                    command.clone()
+                } else {
+                    token
+                }
+            })
+            .collect()
+    } else {
+        tokens.into_iter().chain(argv).collect()
+    }
+}
+
+/// The wrapper `shell` stands for: `$SHELL -lc {command}` (`sh` when `SHELL`
+/// isn't set), or `cmd /C` on Windows. `-l` makes it a login shell, so it
+/// reads the user's profile (`.zprofile`, `.bash_profile`, ...) the way a
+/// new terminal does.
+fn shell_wrapper(shell: Option<&str>) -> String {
+    if cfg!(windows) {
+        return "cmd /C".to_string();
+    }
+    let shell = shell.filter(|shell| !shell.is_empty()).unwrap_or("sh");
+    format!("{} -lc {{command}}", shell_join(&[shell.to_string()]))
+}
+
+/// Whether runs go through Corepack when the package manager on PATH isn't
+/// the version `packageManager` declares (the `corepack` config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CorepackUse {
+    /// Ask on the terminal.
+    #[default]
+    Ask,
+    Always,
+    Never,
+}
+
+/// Whether a run of `pm` in `cwd` goes through Corepack: when the installed
+/// version differs from the declared one and `corepack` allows it (asking
+/// first with [`CorepackUse::Ask`]; without a terminal the run goes ahead
+/// directly).
+pub fn use_corepack(pm: PackageManager, cwd: &Path, corepack: CorepackUse) -> bool {
+    if corepack == CorepackUse::Never {
+        return false;
+    }
+    let Some(mismatch) = corepack_mismatch(pm, cwd) else {
+        return false;
+    };
+    eprintln!(
+        "⚠️  {} {} is installed, but package.json declares {}",
+        pm, mismatch.installed, mismatch.declared
+    );
+    corepack == CorepackUse::Always || confirm("Run it through corepack? [Y/n] ")
+}
+
+/// Like [`use_corepack`] for runs that can't ask, such as those started
+/// inside the TUI: only with [`CorepackUse::Always`], and without printing.
+pub fn use_corepack_unattended(pm: PackageManager, cwd: &Path, corepack: CorepackUse) -> bool {
+    corepack == CorepackUse::Always && corepack_mismatch(pm, cwd).is_some()
+}
+
+/// Asks a yes/no `question` on the terminal; yes unless answered `n`.
+/// `false` when stdin isn't a terminal.
+fn confirm(question: &str) -> bool {
+    use std::io::{IsTerminal, Write};
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("{}", question);
+    std::io::stderr().flush().ok();
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
     }
+    !answer.trim().eq_ignore_ascii_case("n")
+}
+
+/// Execute a package.json script via the detected package manager.
+///
+/// Inherits stdin/stdout/stderr so the child process can interact with the terminal.
+/// `template` is the user's invocation template for this package manager, if any,
+/// and `inherit` filters the environment passed on from nr.
+/// Returns the process exit code (or `1` on spawn failure / missing exit code).
+pub fn run_script(
+    pm: PackageManager,
+    script_name: &str,
+    cwd: &Path,
+    template: Option<&str>,
+    inherit: &InheritEnv,
+) -> i32 {
+    run_script_with_config(
+        pm,
+        script_name,
+        cwd,
+        HashMap::new(),
+        "",
+        template,
+        &ExecutionStrategy::default(),
+        inherit,
+    )
 }
 
 /// Execute a package.json script with additional environment variables and arguments.
@@ -77,26 +172,125 @@ pub fn run_script(pm: PackageManager, script_name: &str, cwd: &Path) -> i32 {
 /// This is the extended version of `run_script` that supports:
 /// - Custom environment variable injection (e.g., from .env files)
 /// - Additional arguments appended to the script command
+/// - Starting the package manager another way (see [`ExecutionStrategy`])
 ///
 /// Returns the process exit code (or `1` on spawn failure / missing exit code).
+#[allow(clippy::too_many_arguments)]
 pub fn run_script_with_config(
     pm: PackageManager,
     script_name: &str,
     cwd: &Path,
     env_vars: HashMap<String, String>,
     args: &str,
+    template: Option<&str>,
+    strategy: &ExecutionStrategy,
+    inherit: &InheritEnv,
+) -> i32 {
+    let extra_args = split_args(args);
+    let argv = strategy.apply(pm, pm.invocation(script_name, &extra_args, template));
+    run_command(pm, &argv, cwd, env_vars, inherit)
+}
+
+/// Execute a workspace package's script from the monorepo root, selecting the
+/// package with the package manager's workspace flags (see [`WorkspaceTarget`]).
+///
+/// Returns the process exit code (or `1` on spawn failure / missing exit code).
+#[allow(clippy::too_many_arguments)]
+pub fn run_in_workspace(
+    pm: PackageManager,
+    script_name: &str,
+    target: &WorkspaceTarget,
+    env_vars: HashMap<String, String>,
+    args: &str,
+    template: Option<&str>,
+    strategy: &ExecutionStrategy,
+    inherit: &InheritEnv,
 ) -> i32 {
-    let mut cmd = Command::new(pm.command_name());
-    cmd.args(pm.run_args(script_name));
+    let extra_args = split_args(args);
+    let argv = strategy.apply(
+        pm,
+        pm.workspace_invocation(&target.package, script_name, &extra_args, template),
+    );
+    run_command(pm, &argv, &target.root, env_vars, inherit)
+}
 
-    // Append additional arguments if provided
-    if !args.is_empty() {
-        for arg in args.split_whitespace() {
-            cmd.arg(arg);
+/// Checks the active Node against the version the project in `cwd` asks for
+/// (see [`node_version::check`]) before a run. On a mismatch it warns, or,
+/// when `check` names a version manager and the project pins a version,
+/// returns `template` wrapped to run with that version.
+pub fn node_template(
+    cwd: &Path,
+    check: NodeVersionCheck,
+    template: Option<&str>,
+) -> Option<String> {
+    if check == NodeVersionCheck::Off {
+        return None;
+    }
+    let mismatch = node_version::check(cwd)?;
+    let pinned = mismatch.requirement.pinned();
+    match pinned.and_then(|version| Some((version, with_node_manager(check, version, template)?))) {
+        Some((version, template)) => {
+            eprintln!("⬢ {}; running with Node {}", mismatch, version);
+            Some(template)
+        }
+        None => {
+            eprintln!("⚠️  {}", mismatch);
+            eprintln!(
+                "💡 Switch with nvm or fnm, or set node_version to \"fnm\" or \"volta\" in the nr config to run with it"
+            );
+            None
         }
     }
+}
+
+/// Like [`node_template`], but without printing, for runs whose output nr
+/// captures: the wrapped template when `check` names a version manager and
+/// the project pins a version the active Node doesn't match.
+pub fn quiet_node_template(
+    cwd: &Path,
+    check: NodeVersionCheck,
+    template: Option<&str>,
+) -> Option<String> {
+    if matches!(check, NodeVersionCheck::Off | NodeVersionCheck::Warn) {
+        return None;
+    }
+    let mismatch = node_version::check(cwd)?;
+    with_node_manager(check, mismatch.requirement.pinned()?, template)
+}
+
+/// `template` (or the default) run with Node `version` by the version manager
+/// of `check`, e.g. `fnm exec --using=20 -- {pm} {run}`.
+fn with_node_manager(
+    check: NodeVersionCheck,
+    version: &str,
+    template: Option<&str>,
+) -> Option<String> {
+    let wrapper = match check {
+        NodeVersionCheck::Fnm => format!("fnm exec --using={} --", version),
+        NodeVersionCheck::Volta => format!("volta run --node {}", version),
+        NodeVersionCheck::Warn | NodeVersionCheck::Off => return None,
+    };
+    Some(format!(
+        "{} {}",
+        wrapper,
+        template.unwrap_or(DEFAULT_RUN_TEMPLATE)
+    ))
+}
+
+/// Runs `argv` in `cwd` attached to the terminal, with `env_vars` on top of
+/// the inherited environment.
+fn run_command(
+    pm: PackageManager,
+    argv: &[String],
+    cwd: &Path,
+    env_vars: HashMap<String, String>,
+    inherit: &InheritEnv,
+) -> i32 {
+    let mut cmd = program::command(&argv[0]);
+    cmd.args(&argv[1..]);
 
     // Inject environment variables
+    inherit.apply(&mut cmd);
     cmd.envs(env_vars);
 
     cmd.current_dir(cwd)
@@ -104,66 +298,435 @@ pub fn run_script_with_config(
         .stdout(std::process::Stdio::inherit())
         .stderr(std::process::Stdio::inherit());
 
-    let status = cmd.status();
+    match cmd.status() {
+        Ok(s) => s.code().unwrap_or(1),
+        Err(e) => {
+            report_spawn_error(pm, argv, &e);
+            1
+        }
+    }
+}
+
+/// Runs a justfile / Makefile task in `cwd` attached to the terminal.
+///
+/// Returns the process exit code (or `1` on spawn failure / missing exit code).
+pub fn run_task(runner: TaskRunner, task: &str, cwd: &Path, inherit: &InheritEnv) -> i32 {
+    let argv = runner.invocation(task, &[]);
+    let mut cmd = program::command(&argv[0]);
+    inherit.apply(&mut cmd);
+    cmd.args(&argv[1..]).current_dir(cwd);
 
-    match status {
+    match cmd.status() {
         Ok(s) => s.code().unwrap_or(1),
         Err(e) => {
             eprintln!();
-            eprintln!(
-                "❌ Failed to run script: '{} {}'",
-                pm.command_name(),
-                script_name
-            );
+            eprintln!("❌ Failed to run task: '{}'", argv.join(" "));
+            if e.kind() == std::io::ErrorKind::NotFound {
+                eprintln!("🔍 '{}' not found in PATH", runner.command_name());
+            } else {
+                eprintln!("Error: {}", e);
+            }
             eprintln!();
+            1
+        }
+    }
+}
 
-            if e.kind() == std::io::ErrorKind::NotFound {
-                eprintln!(
-                    "🔍 Package manager '{}' not found in PATH",
-                    pm.command_name()
-                );
-                eprintln!();
-                eprintln!("💡 Install {} to continue:", pm);
-
-                match pm {
-                    PackageManager::Npm => {
-                        eprintln!("   - Download Node.js (includes npm): https://nodejs.org");
-                        eprintln!("   - Or use a version manager: nvm, fnm, volta");
-                    }
-                    PackageManager::Yarn => {
-                        eprintln!("   npm install -g yarn");
-                        eprintln!("   Or: https://yarnpkg.com/getting-started/install");
-                    }
-                    PackageManager::Pnpm => {
-                        eprintln!("   npm install -g pnpm");
-                        eprintln!("   Or: https://pnpm.io/installation");
-                    }
-                    PackageManager::Bun => {
-                        eprintln!("   curl -fsSL https://bun.sh/install | bash");
-                        eprintln!("   Or: https://bun.sh");
+/// Runs a script like [`run_script`] with its output discarded and no input,
+/// e.g. to time it. Returns the exit code (or `1` on spawn failure).
+pub fn run_script_quiet(
+    pm: PackageManager,
+    script_name: &str,
+    cwd: &Path,
+    template: Option<&str>,
+    inherit: &InheritEnv,
+) -> i32 {
+    let argv = pm.invocation(script_name, &[], template);
+
+    let mut cmd = program::command(&argv[0]);
+    inherit.apply(&mut cmd);
+    cmd.args(&argv[1..])
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    match cmd.status() {
+        Ok(s) => s.code().unwrap_or(1),
+        Err(e) => {
+            report_spawn_error(pm, &argv, &e);
+            1
+        }
+    }
+}
+
+/// Run time of a finished run, e.g. `850ms`, `12.3s` or `4m 05s`.
+pub fn format_duration(duration: Duration) -> String {
+    let ms = duration.as_millis() as u64;
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m {:02}s", ms / 60_000, ms / 1000 % 60),
+    }
+}
+
+/// A line of output captured from a piped run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLine {
+    pub text: String,
+    pub is_stderr: bool,
+}
+
+/// A signal forwarded to a piped run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Ask the script to stop, like Ctrl+C in a terminal (`SIGINT`).
+    Interrupt,
+    /// Terminate the script (`SIGTERM`).
+    Terminate,
+}
+
+/// A script running with piped stdout/stderr, for display inside the TUI.
+///
+/// Output is read on background threads and collected with [`PipedRun::poll`].
+/// On Unix the child leads its own process group, so signals reach the package
+/// manager and everything it spawned.
+pub struct PipedRun {
+    child: Child,
+    rx: Receiver<OutputLine>,
+    exit_code: Option<i32>,
+}
+
+impl PipedRun {
+    /// Drains output received since the last call and checks whether the child has exited.
+    ///
+    /// Returns the new lines and, once the process has exited, its exit code.
+    pub fn poll(&mut self) -> (Vec<OutputLine>, Option<i32>) {
+        if self.exit_code.is_none() {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                self.exit_code = Some(status.code().unwrap_or(1));
+            }
+        }
+
+        let mut lines: Vec<OutputLine> = self.rx.try_iter().collect();
+        if self.exit_code.is_some() {
+            // Collect the tail of the output; the timeout guards against grandchildren
+            // that keep the pipes open after the script itself has exited
+            while let Ok(line) = self.rx.recv_timeout(Duration::from_millis(100)) {
+                lines.push(line);
+            }
+        }
+        (lines, self.exit_code)
+    }
+
+    /// Process ID of the child.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Forwards `signal` to the child's process group.
+    ///
+    /// Platforms without signals fall back to killing the child.
+    pub fn signal(&mut self, signal: Signal) -> std::io::Result<()> {
+        if self.exit_code.is_some() {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        {
+            let sig = match signal {
+                Signal::Interrupt => libc::SIGINT,
+                Signal::Terminate => libc::SIGTERM,
+            };
+            signal_group(self.child.id(), sig)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = signal;
+            self.child.kill()
+        }
+    }
+
+    /// Kills the child process (and its process group) if it is still running.
+    pub fn kill(&mut self) {
+        if self.exit_code.is_none() {
+            #[cfg(unix)]
+            let _ = signal_group(self.child.id(), libc::SIGKILL);
+            let _ = self.child.kill();
+            if let Ok(status) = self.child.wait() {
+                self.exit_code = Some(status.code().unwrap_or(1));
+            }
+        }
+    }
+}
+
+impl Drop for PipedRun {
+    fn drop(&mut self) {
+        // Don't leave scripts running in the background once the pane lets go of them
+        self.kill();
+    }
+}
+
+#[cfg(unix)]
+fn signal_group(pid: u32, sig: libc::c_int) -> std::io::Result<()> {
+    // A negative PID addresses the whole process group led by the child
+    // SAFETY: kill(2) has no memory-safety preconditions
+    if unsafe { libc::kill(-(pid as libc::pid_t), sig) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Spawns a script like [`run_script_with_config`], but with stdin closed and
+/// stdout/stderr captured line by line instead of inherited. The script's
+/// `package.json` hooks run around it (see [`hooks::wrap_with_hooks`]).
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_piped(
+    pm: PackageManager,
+    script_name: &str,
+    cwd: &Path,
+    env_vars: HashMap<String, String>,
+    args: &str,
+    template: Option<&str>,
+    strategy: &ExecutionStrategy,
+    inherit: &InheritEnv,
+) -> std::io::Result<PipedRun> {
+    let extra_args = split_args(args);
+    let argv = strategy.apply(pm, pm.invocation(script_name, &extra_args, template));
+    let argv = hooks::wrap_with_hooks(&ScriptHooks::load(cwd, script_name), script_name, argv);
+
+    let mut cmd = program::command(&argv[0]);
+    inherit.apply(&mut cmd);
+    cmd.args(&argv[1..])
+        .envs(env_vars)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let mut child = cmd.spawn()?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, false, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, true, tx);
+    }
+
+    Ok(PipedRun {
+        child,
+        rx,
+        exit_code: None,
+    })
+}
+
+fn forward_lines(stream: impl Read + Send + 'static, is_stderr: bool, tx: Sender<OutputLine>) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        while let Ok(n) = reader.read_until(b'\n', &mut buf) {
+            if n == 0 {
+                break;
+            }
+            let text = clean_line(&String::from_utf8_lossy(&buf));
+            if tx.send(OutputLine { text, is_stderr }).is_err() {
+                break;
+            }
+            buf.clear();
+        }
+    });
+}
+
+/// Strips the line ending, ANSI escape sequences and carriage-return overwrites
+/// (progress bars) so the line renders cleanly in the TUI.
+fn clean_line(raw: &str) -> String {
+    let raw = raw.trim_end_matches(['\n', '\r']);
+    let raw = raw.rsplit('\r').next().unwrap_or(raw);
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI: ESC [ params final-byte; other escapes: ESC + one char
+            if chars.next_if_eq(&'[').is_some() {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
                     }
                 }
             } else {
-                eprintln!("Error: {}", e);
-                eprintln!();
-                eprintln!("💡 Common issues:");
-                eprintln!("   - Check if the package manager is in your PATH");
-                eprintln!(
-                    "   - Try running the script manually: {} {}",
-                    pm.command_name(),
-                    script_name
-                );
+                chars.next();
             }
+        } else if c == '\t' {
+            out.push_str("    ");
+        } else if !c.is_control() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Print actionable guidance when the child process could not be started.
+fn report_spawn_error(pm: PackageManager, argv: &[String], e: &std::io::Error) {
+    let program = argv[0].as_str();
+
+    eprintln!();
+    eprintln!("❌ Failed to run script: '{}'", argv.join(" "));
+    eprintln!();
 
+    // Check if it's a command not found error
+    if e.kind() == std::io::ErrorKind::NotFound {
+        if program != pm.command_name() {
+            // A run template or run_through wraps the package manager in another program
+            eprintln!("🔍 '{}' not found in PATH", program);
             eprintln!();
-            1
+            eprintln!(
+                "💡 Install it, or update the '{}' entry in run_templates (or run_through) of your nr config.json",
+                pm.command_name()
+            );
+            eprintln!();
+            return;
         }
+
+        eprintln!(
+            "🔍 Package manager '{}' not found in PATH",
+            pm.command_name()
+        );
+        eprintln!();
+        eprintln!("💡 Install {} to continue:", pm);
+
+        match pm {
+            PackageManager::Npm => {
+                eprintln!("   - Download Node.js (includes npm): https://nodejs.org");
+                eprintln!("   - Or use a version manager: nvm, fnm, volta");
+            }
+            PackageManager::Yarn => {
+                eprintln!("   npm install -g yarn");
+                eprintln!("   Or: https://yarnpkg.com/getting-started/install");
+            }
+            PackageManager::YarnBerry => {
+                eprintln!("   corepack enable");
+                eprintln!("   Or: https://yarnpkg.com/getting-started/install");
+            }
+            PackageManager::Pnpm => {
+                eprintln!("   npm install -g pnpm");
+                eprintln!("   Or: https://pnpm.io/installation");
+            }
+            PackageManager::Bun => {
+                eprintln!("   curl -fsSL https://bun.sh/install | bash");
+                eprintln!("   Or: https://bun.sh");
+            }
+            PackageManager::Deno => {
+                eprintln!("   curl -fsSL https://deno.land/install.sh | sh");
+                eprintln!("   Or: https://docs.deno.com/runtime/getting_started/installation");
+            }
+        }
+    } else {
+        eprintln!("Error: {}", e);
+        eprintln!();
+        eprintln!("💡 Common issues:");
+        eprintln!("   - Check if the package manager is in your PATH");
+        eprintln!("   - Try running the script manually: {}", argv.join(" "));
     }
+
+    eprintln!();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn test_corepack_goes_in_front_of_the_package_manager() {
+        let argv = PackageManager::Pnpm.invocation("dev", &[], Some("dotenvx run -- {pm} {run}"));
+        let corepack = ExecutionStrategy {
+            corepack: true,
+            wrapper: None,
+        };
+        assert_eq!(
+            corepack.apply(PackageManager::Pnpm, argv.clone()),
+            ["dotenvx", "run", "--", "corepack", "pnpm", "run", "dev"]
+        );
+        assert_eq!(
+            ExecutionStrategy::default().apply(PackageManager::Pnpm, argv.clone()),
+            argv
+        );
+    }
+
+    #[test]
+    fn test_wrapper_runs_the_command_through_another_program() {
+        let argv = PackageManager::Npm.invocation(
+            "dev",
+            &["--name".to_string(), "my app".to_string()],
+            None,
+        );
+        assert_eq!(
+            wrap("direnv exec .", argv.clone()),
+            [
+                "direnv", "exec", ".", "npm", "run", "dev", "--", "--name", "my app"
+            ]
+        );
+        assert_eq!(
+            wrap("zsh -ic {command}", argv),
+            ["zsh", "-ic", "npm run dev -- --name 'my app'"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_wrapper_uses_the_login_shell() {
+        assert_eq!(shell_wrapper(Some("/bin/zsh")), "/bin/zsh -lc {command}");
+        assert_eq!(shell_wrapper(None), "sh -lc {command}");
+    }
+
+    #[test]
+    fn test_with_node_manager() {
+        assert_eq!(
+            with_node_manager(NodeVersionCheck::Fnm, "20", None).as_deref(),
+            Some("fnm exec --using=20 -- {pm} {run}")
+        );
+        assert_eq!(
+            with_node_manager(
+                NodeVersionCheck::Volta,
+                "18.19.0",
+                Some("{pm} {run} --silent")
+            )
+            .as_deref(),
+            Some("volta run --node 18.19.0 {pm} {run} --silent")
+        );
+        assert_eq!(with_node_manager(NodeVersionCheck::Warn, "20", None), None);
+
+        let argv = PackageManager::Pnpm.invocation(
+            "dev",
+            &["--port".to_string(), "3000".to_string()],
+            with_node_manager(NodeVersionCheck::Fnm, "20", None).as_deref(),
+        );
+        assert_eq!(
+            argv,
+            [
+                "fnm",
+                "exec",
+                "--using=20",
+                "--",
+                "pnpm",
+                "run",
+                "dev",
+                "--port",
+                "3000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(850)), "850ms");
+        assert_eq!(format_duration(Duration::from_millis(12_340)), "12.3s");
+        assert_eq!(format_duration(Duration::from_secs(245)), "4m 05s");
+    }
 
     #[test]
     fn run_args_are_forwarded_correctly() {
@@ -187,6 +750,19 @@ mod tests {
         assert_eq!(code, 1);
     }
 
+    #[test]
+    fn templated_missing_program_returns_1() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let code = run_script(
+            PackageManager::Npm,
+            "test",
+            tmp.path(),
+            Some("__nr_nonexistent_binary__ {pm} {run}"),
+            &InheritEnv::default(),
+        );
+        assert_eq!(code, 1);
+    }
+
     #[test]
     fn test_run_script_with_config_constructs_command_correctly() {
         // Test that env vars and args are properly prepared
@@ -199,14 +775,141 @@ mod tests {
         cmd.envs(env_vars);
         cmd.arg("test");
 
-        // Just verify the command builds without errors
-        assert!(true);
+        // Verify the command carries the injected env vars and args
+        assert_eq!(cmd.get_envs().count(), 2);
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["test"]);
+    }
+
+    #[test]
+    fn test_clean_line_strips_escapes_and_progress() {
+        assert_eq!(clean_line("\u{1b}[32m✓\u{1b}[0m done\n"), "✓ done");
+        assert_eq!(clean_line("10%\r50%\r100%\r\n"), "100%");
+        assert_eq!(clean_line("a\tb"), "a    b");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_piped_captures_output_and_exit_code() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("run.sh"),
+            "echo \"out $0 $GREETING\"; echo err >&2; exit 4\n",
+        )
+        .unwrap();
+
+        let mut run = spawn_piped(
+            PackageManager::Npm,
+            "build",
+            tmp.path(),
+            HashMap::from([("GREETING".to_string(), "hi".to_string())]),
+            "",
+            Some("sh run.sh"),
+            &ExecutionStrategy::default(),
+            &InheritEnv::default(),
+        )
+        .unwrap();
+
+        let mut lines = Vec::new();
+        let exit_code = loop {
+            let (new_lines, code) = run.poll();
+            lines.extend(new_lines);
+            if let Some(code) = code {
+                break code;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+
+        assert_eq!(exit_code, 4);
+        assert!(lines.contains(&OutputLine {
+            text: "out run.sh hi".to_string(),
+            is_stderr: false,
+        }));
+        assert!(lines.contains(&OutputLine {
+            text: "err".to_string(),
+            is_stderr: true,
+        }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_piped_goes_through_run_through() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("run.sh"), "echo \"wrapped=$WRAPPED\"\n").unwrap();
+
+        let strategy = ExecutionStrategy {
+            corepack: false,
+            wrapper: Some("env WRAPPED=yes".to_string()),
+        };
+        let mut run = spawn_piped(
+            PackageManager::Npm,
+            "build",
+            tmp.path(),
+            HashMap::new(),
+            "",
+            Some("sh run.sh"),
+            &strategy,
+            &InheritEnv::default(),
+        )
+        .unwrap();
+
+        let mut lines = Vec::new();
+        loop {
+            let (new_lines, code) = run.poll();
+            lines.extend(new_lines);
+            if code.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(lines.iter().any(|l| l.text == "wrapped=yes"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signal_interrupts_piped_run() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("run.sh"),
+            "trap 'echo interrupted; exit 130' INT\necho ready\nwhile true; do sleep 0.05; done\n",
+        )
+        .unwrap();
+
+        let mut run = spawn_piped(
+            PackageManager::Npm,
+            "dev",
+            tmp.path(),
+            HashMap::new(),
+            "",
+            Some("sh run.sh"),
+            &ExecutionStrategy::default(),
+            &InheritEnv::default(),
+        )
+        .unwrap();
+
+        // Wait until the trap is installed before signalling
+        let mut lines = Vec::new();
+        while !lines.iter().any(|l: &OutputLine| l.text == "ready") {
+            lines.extend(run.poll().0);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        run.signal(Signal::Interrupt).unwrap();
+
+        let exit_code = loop {
+            let (new_lines, code) = run.poll();
+            lines.extend(new_lines);
+            if let Some(code) = code {
+                break code;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+
+        assert_eq!(exit_code, 130);
+        assert!(lines.iter().any(|l| l.text == "interrupted"));
     }
 
     #[test]
     fn test_args_parsing_splits_correctly() {
         let args = "-- --watch --coverage";
-        let parsed: Vec<&str> = args.split_whitespace().collect();
-        assert_eq!(parsed, vec!["--", "--watch", "--coverage"]);
+        assert_eq!(split_args(args), vec!["--", "--watch", "--coverage"]);
     }
 }
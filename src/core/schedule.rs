@@ -0,0 +1,150 @@
+use crate::core::queue::QueuedRun;
+use std::time::{Duration, Instant};
+
+/// A run waiting for its start time.
+#[derive(Debug, Clone)]
+pub struct ScheduledRun {
+    pub run: QueuedRun,
+    pub due: Instant,
+}
+
+/// Errors from parsing a schedule like `10m` or `14:30`.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("Enter a delay like 10m, 1h30m, 45s or a clock time like 14:30")]
+    Invalid,
+    #[error("Local time is unavailable on this system; use a delay like 10m instead")]
+    NoLocalTime,
+}
+
+/// Parses when a scheduled run should start, returning the delay from now.
+///
+/// Accepts delays (`90s`, `10m`, `1h30m`, optionally prefixed with `in `) and
+/// 24-hour clock times (`14:30`, `at 9:05`). A clock time that has already
+/// passed today means tomorrow. `local_now` is the current local time of day,
+/// needed only for clock times.
+pub fn parse_start(input: &str, local_now: Option<time::Time>) -> Result<Duration, ScheduleError> {
+    let input = input.trim().to_ascii_lowercase();
+
+    if let Some(delay) = parse_delay(input.strip_prefix("in ").unwrap_or(&input)) {
+        return Ok(delay);
+    }
+
+    let clock = input.strip_prefix("at ").unwrap_or(&input).trim();
+    let (hour, minute) = clock.split_once(':').ok_or(ScheduleError::Invalid)?;
+    let hour: u8 = hour.parse().map_err(|_| ScheduleError::Invalid)?;
+    let minute: u8 = minute.parse().map_err(|_| ScheduleError::Invalid)?;
+    let target = time::Time::from_hms(hour, minute, 0).map_err(|_| ScheduleError::Invalid)?;
+
+    let now = local_now.ok_or(ScheduleError::NoLocalTime)?;
+    let mut delta = target - now;
+    if delta.is_negative() || delta.is_zero() {
+        delta += time::Duration::DAY;
+    }
+    Ok(Duration::from_secs(delta.whole_seconds() as u64))
+}
+
+/// Parses `1h30m`, `10m`, `45s` or a bare number of minutes.
+fn parse_delay(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if let Ok(minutes) = input.parse::<u64>() {
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in input.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' | 's' => {
+                let value: u64 = number.parse().ok()?;
+                number.clear();
+                total += match c {
+                    'h' => value * 3600,
+                    'm' => value * 60,
+                    _ => value,
+                };
+            }
+            _ => return None,
+        }
+    }
+
+    number.is_empty().then(|| Duration::from_secs(total))
+}
+
+/// Current local time of day, if the platform can tell us the UTC offset.
+pub fn local_time_now() -> Option<time::Time> {
+    time::OffsetDateTime::now_local().ok().map(|now| now.time())
+}
+
+/// Formats a remaining duration for countdown display: `1h 05m`, `9m 59s`, `42s`.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m", h, m)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u8, minute: u8) -> Option<time::Time> {
+        Some(time::Time::from_hms(hour, minute, 0).unwrap())
+    }
+
+    #[test]
+    fn test_parse_delays() {
+        assert_eq!(parse_start("10m", None), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_start("in 1h30m", None), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_start("45s", None), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_start("5", None), Ok(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_parse_clock_time_later_today() {
+        assert_eq!(
+            parse_start("14:30", at(14, 0)),
+            Ok(Duration::from_secs(30 * 60))
+        );
+        assert_eq!(
+            parse_start("at 9:05", at(9, 0)),
+            Ok(Duration::from_secs(5 * 60))
+        );
+    }
+
+    #[test]
+    fn test_parse_clock_time_already_passed_means_tomorrow() {
+        assert_eq!(
+            parse_start("08:00", at(9, 0)),
+            Ok(Duration::from_secs(23 * 3600))
+        );
+    }
+
+    #[test]
+    fn test_parse_clock_time_without_local_time() {
+        assert_eq!(parse_start("14:30", None), Err(ScheduleError::NoLocalTime));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in ["", "soon", "10x", "1h30", "25:00", "12:60"] {
+            assert_eq!(parse_start(input, at(12, 0)), Err(ScheduleError::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(42)), "42s");
+        assert_eq!(format_countdown(Duration::from_secs(599)), "9m 59s");
+        assert_eq!(format_countdown(Duration::from_secs(3900)), "1h 05m");
+    }
+}
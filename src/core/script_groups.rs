@@ -0,0 +1,77 @@
+use crate::core::workspaces::WorkspacePackage;
+use std::collections::BTreeMap;
+
+/// A script name shared by several workspace packages (e.g. every `dev`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptGroup {
+    pub name: String,
+    /// Indices into the workspace packages declaring the script, in package order.
+    pub packages: Vec<usize>,
+}
+
+/// Groups the packages' scripts by name, most widely shared first, then
+/// alphabetically. Names declared by a single package are left out.
+pub fn group_by_name(packages: &[WorkspacePackage]) -> Vec<ScriptGroup> {
+    let mut by_name: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, pkg) in packages.iter().enumerate() {
+        for name in pkg.scripts.keys() {
+            by_name.entry(name).or_default().push(i);
+        }
+    }
+
+    let mut groups: Vec<ScriptGroup> = by_name
+        .into_iter()
+        .filter(|(_, packages)| packages.len() > 1)
+        .map(|(name, packages)| ScriptGroup {
+            name: name.to_string(),
+            packages,
+        })
+        .collect();
+    // Stable, so equally shared names stay alphabetical
+    groups.sort_by_key(|group| std::cmp::Reverse(group.packages.len()));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, scripts: &[&str]) -> WorkspacePackage {
+        WorkspacePackage {
+            name: name.to_string(),
+            relative_path: format!("apps/{}", name),
+            scripts: scripts
+                .iter()
+                .map(|s| (s.to_string(), "vite".to_string()))
+                .collect(),
+            description: None,
+            version: None,
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_groups_shared_names_most_shared_first() {
+        let packages = vec![
+            pkg("web", &["build", "dev", "lint"]),
+            pkg("docs", &["dev", "lint"]),
+            pkg("api", &["dev", "start"]),
+        ];
+
+        let groups = group_by_name(&packages);
+
+        assert_eq!(
+            groups,
+            vec![
+                ScriptGroup {
+                    name: "dev".to_string(),
+                    packages: vec![0, 1, 2],
+                },
+                ScriptGroup {
+                    name: "lint".to_string(),
+                    packages: vec![0, 1],
+                },
+            ]
+        );
+    }
+}
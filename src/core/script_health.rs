@@ -0,0 +1,156 @@
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// Binaries whose `run <name>` form invokes another script of the same package.
+const RUNNERS: [&str; 4] = ["npm", "pnpm", "yarn", "bun"];
+
+/// Flags that retarget a run at another package, so the referenced script lives elsewhere.
+const CROSS_PACKAGE_FLAGS: [&str; 8] = [
+    "--filter",
+    "-F",
+    "--workspace",
+    "--workspaces",
+    "-w",
+    "-ws",
+    "-r",
+    "--recursive",
+];
+
+/// Finds scripts whose commands call sibling scripts that don't exist.
+///
+/// Looks for `npm run <x>`, `npm run-script <x>`, `pnpm run <x>`, `yarn run <x>`,
+/// `bun run <x>` as well as `npm test` / `npm start` inside each command, split on
+/// shell separators (`&&`, `||`, `;`, `|`, `&`). Dynamic names (`$VAR`, globs) and
+/// runs targeting other workspace packages are skipped.
+///
+/// Returns a map of script name → referenced names that are missing, in command order.
+/// Scripts without problems are not included.
+pub fn find_missing_references(scripts: &IndexMap<String, String>) -> HashMap<String, Vec<String>> {
+    let mut result = HashMap::new();
+
+    for (name, command) in scripts {
+        let mut missing: Vec<String> = Vec::new();
+        for referenced in referenced_scripts(command) {
+            if !scripts.contains_key(&referenced) && !missing.contains(&referenced) {
+                missing.push(referenced);
+            }
+        }
+        if !missing.is_empty() {
+            result.insert(name.clone(), missing);
+        }
+    }
+
+    result
+}
+
+/// Extracts the script names a command invokes through a package manager.
+fn referenced_scripts(command: &str) -> Vec<String> {
+    command
+        .split(['&', '|', ';'])
+        .filter_map(|segment| {
+            let tokens: Vec<&str> = segment
+                .split_whitespace()
+                .map(|t| t.trim_matches(|c| c == '"' || c == '\''))
+                .collect();
+            referenced_in_segment(&tokens)
+        })
+        .collect()
+}
+
+fn referenced_in_segment(tokens: &[&str]) -> Option<String> {
+    // Skip leading env assignments like `NODE_ENV=production npm run build`
+    let start = tokens.iter().position(|t| !t.contains('='))?;
+    let (binary, rest) = tokens[start..].split_first()?;
+    if !RUNNERS.contains(binary) {
+        return None;
+    }
+    if rest.iter().any(|t| {
+        CROSS_PACKAGE_FLAGS.contains(t)
+            || t.starts_with("--filter=")
+            || t.starts_with("--workspace")
+    }) {
+        return None;
+    }
+
+    let mut positional = rest.iter().copied().filter(|t| !t.starts_with('-'));
+    let name = match (*binary, positional.next()?) {
+        (_, "run") | ("npm", "run-script") => positional.next()?,
+        ("npm", lifecycle @ ("test" | "start" | "stop" | "restart")) => lifecycle,
+        _ => return None,
+    };
+
+    let is_dynamic = name.contains(['$', '*', '{', '(']);
+    (!is_dynamic && !name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(pairs: &[(&str, &str)]) -> IndexMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn flags_missing_run_target() {
+        let scripts = scripts(&[("build", "tsc"), ("ci", "npm run lint && npm run build")]);
+
+        let missing = find_missing_references(&scripts);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing["ci"], vec!["lint"]);
+    }
+
+    #[test]
+    fn accepts_existing_references_for_every_pm() {
+        let scripts = scripts(&[
+            ("lint", "eslint ."),
+            ("a", "pnpm run lint"),
+            ("b", "yarn run lint"),
+            ("c", "bun run lint"),
+            ("d", "npm run-script lint"),
+            ("e", "npm run --silent lint"),
+        ]);
+
+        assert!(find_missing_references(&scripts).is_empty());
+    }
+
+    #[test]
+    fn detects_npm_lifecycle_shorthands() {
+        let scripts = scripts(&[("verify", "npm test; npm start")]);
+
+        let missing = find_missing_references(&scripts);
+        assert_eq!(missing["verify"], vec!["test", "start"]);
+    }
+
+    #[test]
+    fn skips_cross_package_and_dynamic_runs() {
+        let scripts = scripts(&[
+            ("all", "pnpm -r run build"),
+            ("web", "pnpm --filter web run dev"),
+            ("ws", "npm run test --workspaces"),
+            ("dyn", "npm run build:$TARGET"),
+            ("yarn-ws", "yarn workspace web run dev"),
+        ]);
+
+        assert!(find_missing_references(&scripts).is_empty());
+    }
+
+    #[test]
+    fn ignores_env_assignments_and_quotes() {
+        let scripts = scripts(&[("release", "NODE_ENV=production npm run 'bundle'")]);
+
+        let missing = find_missing_references(&scripts);
+        assert_eq!(missing["release"], vec!["bundle"]);
+    }
+
+    #[test]
+    fn reports_each_missing_name_once() {
+        let scripts = scripts(&[("twice", "npm run gone || npm run gone")]);
+
+        let missing = find_missing_references(&scripts);
+        assert_eq!(missing["twice"], vec!["gone"]);
+    }
+}
@@ -2,13 +2,138 @@ use crate::core::package_json::PackageJson;
 use indexmap::IndexMap;
 use std::path::Path;
 
-/// Load scripts from a `package.json` in the given directory, preserving insertion order.
+/// Load scripts from a `package.json` in the given directory, preserving insertion order,
+/// followed by the tasks of a `deno.json` there that don't share a script's name.
 ///
-/// Returns an empty map if the file cannot be read, parsed, or has no `scripts` field.
+/// Returns an empty map if neither file can be read, parsed, or declares any.
 pub fn load_scripts(package_dir: &Path) -> IndexMap<String, String> {
-    PackageJson::load(package_dir)
+    let mut scripts = PackageJson::load(package_dir)
         .map(|pkg| pkg.scripts())
-        .unwrap_or_default()
+        .unwrap_or_default();
+    for (name, command) in crate::core::deno::load_tasks(package_dir) {
+        scripts.entry(name).or_insert(command);
+    }
+    scripts
+}
+
+/// Scripts left out of the lists: names matching the config's `hide_scripts`
+/// globs (`preinstall`, `post*`, `husky:*`).
+#[derive(Debug, Clone)]
+pub struct HiddenScripts {
+    patterns: globset::GlobSet,
+}
+
+impl Default for HiddenScripts {
+    fn default() -> Self {
+        Self {
+            patterns: globset::GlobSet::empty(),
+        }
+    }
+}
+
+impl HiddenScripts {
+    /// Compiles `patterns`; invalid ones are ignored.
+    pub fn new(patterns: &[String]) -> Self {
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in patterns {
+            if let Ok(glob) = globset::Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        Self {
+            patterns: builder
+                .build()
+                .unwrap_or_else(|_| globset::GlobSet::empty()),
+        }
+    }
+
+    pub fn is_hidden(&self, script_name: &str) -> bool {
+        self.patterns.is_match(script_name)
+    }
+
+    /// `scripts` without the hidden ones.
+    pub fn filter(&self, scripts: &IndexMap<String, String>) -> IndexMap<String, String> {
+        scripts
+            .iter()
+            .filter(|(name, _)| !self.is_hidden(name))
+            .map(|(name, command)| (name.clone(), command.clone()))
+            .collect()
+    }
+}
+
+/// Names of the `pre<name>` / `post<name>` scripts that package managers run
+/// around `script_name`.
+pub fn pre_post_names(script_name: &str) -> (String, String) {
+    (
+        format!("pre{}", script_name),
+        format!("post{}", script_name),
+    )
+}
+
+/// Finds the 1-based line of `script_name`'s entry in the `scripts` object of a
+/// `package.json` source. Returns `None` if there is no such entry.
+pub fn find_script_line(content: &str, script_name: &str) -> Option<usize> {
+    let mut line = 1;
+    // Open containers: `{` or `[`
+    let mut stack: Vec<char> = Vec::new();
+    let mut in_scripts = false;
+    let mut expect_key = false;
+    let mut last_key: Option<String> = None;
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '"' => {
+                let start_line = line;
+                let mut value = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        _ => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                    }
+                }
+                if expect_key {
+                    if in_scripts && stack.len() == 2 && value == script_name {
+                        return Some(start_line);
+                    }
+                    last_key = Some(value);
+                    expect_key = false;
+                }
+            }
+            '{' => {
+                if stack == ['{'] && last_key.as_deref() == Some("scripts") {
+                    in_scripts = true;
+                }
+                stack.push(c);
+                expect_key = true;
+            }
+            '[' => {
+                stack.push(c);
+                expect_key = false;
+            }
+            '}' | ']' => {
+                stack.pop();
+                if stack.len() < 2 {
+                    in_scripts = false;
+                }
+            }
+            ',' => expect_key = stack.last() == Some(&'{'),
+            _ => {}
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
@@ -45,6 +170,30 @@ mod tests {
         assert_eq!(scripts["build"], "tsc && vite build");
     }
 
+    #[test]
+    fn finds_script_line_inside_scripts_object() {
+        let content = r#"{
+  "name": "web",
+  "build": "not a script",
+  "scripts": {
+    "dev": "vite",
+    "build": "vite build"
+  },
+  "config": { "build": "ignored" }
+}"#;
+
+        assert_eq!(find_script_line(content, "dev"), Some(5));
+        assert_eq!(find_script_line(content, "build"), Some(6));
+        assert_eq!(find_script_line(content, "lint"), None);
+    }
+
+    #[test]
+    fn finds_script_line_with_escapes_and_arrays() {
+        let content = "{\"files\": [\"a\", \"b\"], \"scripts\": {\"say\": \"echo \\\"hi\\\"\",\n\"test\": \"vitest\"}}";
+
+        assert_eq!(find_script_line(content, "test"), Some(2));
+    }
+
     #[test]
     fn returns_empty_map_when_no_scripts_field() {
         let tmp = TempDir::new().unwrap();
@@ -93,4 +242,17 @@ mod tests {
         let scripts = load_scripts(tmp.path());
         assert!(scripts.is_empty());
     }
+
+    #[test]
+    fn hides_scripts_matching_globs() {
+        let hidden = HiddenScripts::new(&["preinstall".to_string(), "husky:*".to_string()]);
+        let mut scripts = IndexMap::new();
+        for name in ["preinstall", "husky:pre-commit", "build", "install"] {
+            scripts.insert(name.to_string(), "echo".to_string());
+        }
+
+        let visible: Vec<String> = hidden.filter(&scripts).into_keys().collect();
+        assert_eq!(visible, vec!["build", "install"]);
+        assert!(!HiddenScripts::default().is_hidden("preinstall"));
+    }
 }
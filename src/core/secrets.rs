@@ -0,0 +1,112 @@
+//! Env values kept off the screen: the values of keys matching the `mask_env`
+//! globs are shown with only their first and last characters.
+
+use std::borrow::Cow;
+
+/// Globs masked when the config has no `mask_env`.
+pub const DEFAULT_PATTERNS: [&str; 6] = [
+    "*TOKEN*",
+    "*SECRET*",
+    "*PASSWORD*",
+    "*PASSWD*",
+    "*CREDENTIAL*",
+    "*_KEY",
+];
+
+/// Values shorter than this are masked completely.
+const MIN_REVEALED_LEN: usize = 8;
+
+/// Env keys whose values are masked, matched case-insensitively.
+#[derive(Debug, Clone)]
+pub struct SecretEnv {
+    patterns: globset::GlobSet,
+}
+
+impl Default for SecretEnv {
+    fn default() -> Self {
+        Self::from_globs(DEFAULT_PATTERNS.iter().copied())
+    }
+}
+
+impl SecretEnv {
+    /// Compiles the config's `mask_env`, or the defaults when it's unset.
+    /// Invalid globs are ignored.
+    pub fn new(patterns: Option<&[String]>) -> Self {
+        match patterns {
+            Some(patterns) => Self::from_globs(patterns.iter().map(String::as_str)),
+            None => Self::default(),
+        }
+    }
+
+    fn from_globs<'a>(patterns: impl Iterator<Item = &'a str>) -> Self {
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in patterns {
+            if let Ok(glob) = globset::GlobBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+            {
+                builder.add(glob);
+            }
+        }
+        Self {
+            patterns: builder
+                .build()
+                .unwrap_or_else(|_| globset::GlobSet::empty()),
+        }
+    }
+
+    pub fn is_secret(&self, key: &str) -> bool {
+        self.patterns.is_match(key)
+    }
+
+    /// `value` as it may be shown for `key`: masked if `key` is a secret.
+    pub fn display<'a>(&self, key: &str, value: &'a str) -> Cow<'a, str> {
+        if self.is_secret(key) {
+            Cow::Owned(mask(value))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+}
+
+/// `ghp_1234abcd` → `g****d`; short values become `****`, empty ones stay empty.
+pub fn mask(value: &str) -> String {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next_back()) {
+        (None, _) => String::new(),
+        (Some(first), Some(last)) if value.chars().count() >= MIN_REVEALED_LEN => {
+            format!("{}****{}", first, last)
+        }
+        _ => "****".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns_mask_credentials() {
+        let secrets = SecretEnv::default();
+        assert!(secrets.is_secret("GITHUB_TOKEN"));
+        assert!(secrets.is_secret("db_password"));
+        assert!(secrets.is_secret("STRIPE_SECRET_KEY"));
+        assert!(secrets.is_secret("AWS_ACCESS_KEY"));
+        assert!(!secrets.is_secret("PORT"));
+        assert!(!secrets.is_secret("KEYBOARD"));
+
+        assert_eq!(secrets.display("GITHUB_TOKEN", "ghp_1234abcd"), "g****d");
+        assert_eq!(secrets.display("API_KEY", "short"), "****");
+        assert_eq!(secrets.display("PORT", "3000"), "3000");
+        assert_eq!(mask(""), "");
+    }
+
+    #[test]
+    fn test_configured_patterns_replace_the_defaults() {
+        let secrets = SecretEnv::new(Some(&["STRIPE_*".to_string()]));
+        assert!(secrets.is_secret("stripe_live"));
+        assert!(!secrets.is_secret("GITHUB_TOKEN"));
+
+        assert!(!SecretEnv::new(Some(&[])).is_secret("GITHUB_TOKEN"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Task runners besides the package manager: recipes of a `justfile` and
+//! targets of a `Makefile` in the project root, listed after the scripts when
+//! enabled with the `task_runners` config and run with `just <recipe>` /
+//! `make <target>`.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskRunner {
+    Just,
+    Make,
+}
+
+impl TaskRunner {
+    pub const ALL: [TaskRunner; 2] = [TaskRunner::Just, TaskRunner::Make];
+
+    /// The program running the tasks.
+    pub fn command_name(self) -> &'static str {
+        match self {
+            TaskRunner::Just => "just",
+            TaskRunner::Make => "make",
+        }
+    }
+
+    /// Heading of the runner's section in the script list.
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskRunner::Just => "justfile",
+            TaskRunner::Make => "Makefile",
+        }
+    }
+
+    /// File names the runner reads, in the order it looks for them.
+    fn file_names(self) -> &'static [&'static str] {
+        match self {
+            TaskRunner::Just => &["justfile", ".justfile", "Justfile"],
+            TaskRunner::Make => &["GNUmakefile", "makefile", "Makefile"],
+        }
+    }
+
+    /// The runner's file in `dir`, if there is one.
+    pub fn file(self, dir: &Path) -> Option<PathBuf> {
+        self.file_names()
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Public tasks in `dir`'s file, in declaration order, with their commands.
+    /// Empty when there is no file or it can't be read.
+    pub fn load_tasks(self, dir: &Path) -> IndexMap<String, String> {
+        let Some(source) = self
+            .file(dir)
+            .and_then(|path| std::fs::read_to_string(path).ok())
+        else {
+            return IndexMap::new();
+        };
+        match self {
+            TaskRunner::Just => parse_justfile(&source),
+            TaskRunner::Make => parse_makefile(&source),
+        }
+    }
+
+    /// Full command line running `task` with `extra_args` (recipe arguments
+    /// for `just`, variables or more targets for `make`).
+    pub fn invocation(self, task: &str, extra_args: &[String]) -> Vec<String> {
+        let mut argv = vec![self.command_name().to_string(), task.to_string()];
+        argv.extend(extra_args.iter().cloned());
+        argv
+    }
+}
+
+/// Recipes of a justfile and their bodies joined with `&&`; a recipe without
+/// a body shows its dependencies. Private recipes (`_name`, `[private]`) are
+/// left out.
+fn parse_justfile(source: &str) -> IndexMap<String, String> {
+    let mut tasks = IndexMap::new();
+    let mut current: Option<(String, Vec<String>, String)> = None;
+    let mut private = false;
+
+    for line in source.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, body, _)) = current.as_mut() {
+                let command = line.trim().trim_start_matches(['@', '-']);
+                if !command.is_empty() && !command.starts_with('#') {
+                    body.push(command.to_string());
+                }
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        finish_task(&mut tasks, current.take());
+
+        if trimmed.starts_with('[') {
+            private |= trimmed.contains("private");
+            continue;
+        }
+        let header = trimmed.trim_start_matches('@');
+        let Some(colon) = unquoted_colon(header) else {
+            private = false;
+            continue;
+        };
+        let signature = &header[..colon];
+        let rest = &header[colon + 1..];
+        let name = signature.split_whitespace().next().unwrap_or_default();
+        let is_recipe = !rest.starts_with('=')
+            && is_identifier(name)
+            && !matches!(name, "alias" | "set" | "export" | "import" | "mod");
+        if is_recipe && !private && !name.starts_with('_') {
+            let dependencies = rest.split('#').next().unwrap_or_default().trim();
+            current = Some((name.to_string(), Vec::new(), dependencies.to_string()));
+        }
+        private = false;
+    }
+    finish_task(&mut tasks, current);
+    tasks
+}
+
+/// Targets of a Makefile and their recipes joined with `&&`; a target
+/// without a recipe shows its prerequisites. Special (`.PHONY`), pattern
+/// (`%.o`) and variable targets are left out.
+fn parse_makefile(source: &str) -> IndexMap<String, String> {
+    let mut tasks = IndexMap::new();
+    let mut current: Option<(Vec<String>, Vec<String>, String)> = None;
+
+    let finish = |tasks: &mut IndexMap<String, String>,
+                  current: Option<(Vec<String>, Vec<String>, String)>| {
+        if let Some((targets, body, prerequisites)) = current {
+            for target in targets {
+                finish_task(tasks, Some((target, body.clone(), prerequisites.clone())));
+            }
+        }
+    };
+
+    for line in source.lines() {
+        if let Some(command) = line.strip_prefix('\t') {
+            if let Some((_, body, _)) = current.as_mut() {
+                let command = command.trim().trim_start_matches(['@', '-', '+']);
+                if !command.is_empty() && !command.starts_with('#') {
+                    body.push(command.trim_end_matches('\\').trim().to_string());
+                }
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        finish(&mut tasks, current.take());
+
+        let Some(colon) = trimmed.find(':') else {
+            continue;
+        };
+        let (targets, rest) = (
+            &trimmed[..colon],
+            trimmed[colon + 1..].trim_start_matches(':'),
+        );
+        // `A := b`, `A ::= b`, and `A = b:c`
+        if rest.starts_with('=') || targets.contains('=') {
+            continue;
+        }
+        let targets: Vec<String> = targets
+            .split_whitespace()
+            .filter(|t| !t.starts_with('.') && !t.contains(['%', '$']))
+            .map(String::from)
+            .collect();
+        if !targets.is_empty() {
+            let prerequisites = rest.split([';', '#']).next().unwrap_or_default().trim();
+            current = Some((targets, Vec::new(), prerequisites.to_string()));
+        }
+    }
+    finish(&mut tasks, current);
+    tasks
+}
+
+/// Adds a parsed task unless one of that name came first.
+fn finish_task(tasks: &mut IndexMap<String, String>, task: Option<(String, Vec<String>, String)>) {
+    let Some((name, body, dependencies)) = task else {
+        return;
+    };
+    let command = if body.is_empty() {
+        dependencies
+    } else {
+        body.join(" && ")
+    };
+    tasks.entry(name).or_insert(command);
+}
+
+/// Position of the first `:` outside quotes (parameter defaults may hold some).
+fn unquoted_colon(header: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in header.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, ':') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_justfile_recipes() {
+        let tasks = parse_justfile(
+            r#"set dotenv-load
+version := "1.0"
+alias b := build
+
+# Build the app
+build target="debug:fast": lint
+    cargo build --profile {{target}}
+    @echo done
+
+[private]
+helper:
+    echo hidden
+
+_internal:
+    echo hidden
+
+all: build test
+test *args:
+    # not a command
+    cargo test {{args}}
+"#,
+        );
+        let names: Vec<&str> = tasks.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["build", "all", "test"]);
+        assert_eq!(
+            tasks["build"],
+            "cargo build --profile {{target}} && echo done"
+        );
+        assert_eq!(tasks["all"], "build test");
+        assert_eq!(tasks["test"], "cargo test {{args}}");
+    }
+
+    #[test]
+    fn test_parses_makefile_targets() {
+        let tasks = parse_makefile(
+            ".PHONY: build test\nCC := gcc\nURL = http://example.com\n\nbuild: src/main.c\n\t@$(CC) -o app src/main.c\n\ntest lint: build\n\t-./run-checks\n%.o: %.c\n\t$(CC) -c $<\n\nall: build test # everything\n",
+        );
+        let names: Vec<&str> = tasks.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["build", "test", "lint", "all"]);
+        assert_eq!(tasks["build"], "$(CC) -o app src/main.c");
+        assert_eq!(tasks["lint"], "./run-checks");
+        assert_eq!(tasks["all"], "build test");
+    }
+
+    #[test]
+    fn test_invocation_passes_the_task_and_args() {
+        assert_eq!(
+            TaskRunner::Make.invocation("build", &["DEBUG=1".to_string()]),
+            vec!["make", "build", "DEBUG=1"]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::process::Command;
+
+use crate::core::package_manager::shell_join;
+
+/// Pane `nr popup` was started from: `$TMUX_PANE`, or the active pane when run
+/// from a key binding (`run-shell` doesn't set it).
+pub fn current_pane() -> Result<String> {
+    if std::env::var_os("TMUX").is_none() {
+        bail!("nr popup needs to run inside tmux");
+    }
+    if let Ok(pane) = std::env::var("TMUX_PANE") {
+        return Ok(pane);
+    }
+
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "#{pane_id}"])
+        .output()
+        .context("Failed to run tmux")?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `tmux display-popup` argv that runs `nr --picker` in `cwd`, typing the chosen
+/// script into `pane`. `picker_args` are forwarded to the inner `nr` (e.g. `--root`).
+pub fn popup_command(
+    nr_exe: &Path,
+    pane: &str,
+    picker_args: &[String],
+    cwd: &Path,
+    height: u16,
+) -> Vec<String> {
+    let mut inner = vec![
+        nr_exe.display().to_string(),
+        "--picker".to_string(),
+        "--tmux-target".to_string(),
+        pane.to_string(),
+    ];
+    inner.extend(picker_args.iter().cloned());
+
+    vec![
+        "tmux".to_string(),
+        "display-popup".to_string(),
+        "-E".to_string(),
+        "-d".to_string(),
+        cwd.display().to_string(),
+        "-w".to_string(),
+        "80%".to_string(),
+        // Popup borders take a row above and below the picker
+        "-h".to_string(),
+        (height + 2).to_string(),
+        shell_join(&inner),
+    ]
+}
+
+/// `tmux send-keys` argv pairs that type `command` into `pane` literally and
+/// then press Enter.
+pub fn send_keys_commands(pane: &str, command: &str) -> [Vec<String>; 2] {
+    let send = |keys: &[&str]| {
+        ["tmux", "send-keys", "-t", pane]
+            .iter()
+            .chain(keys)
+            .map(|s| s.to_string())
+            .collect()
+    };
+    [send(&["-l", command]), send(&["Enter"])]
+}
+
+/// Runs `command` in `pane` as if typed there, so it lands in that shell's history.
+pub fn send_to_pane(pane: &str, command: &str) -> Result<()> {
+    for argv in send_keys_commands(pane, command) {
+        let status = Command::new(&argv[0])
+            .args(&argv[1..])
+            .status()
+            .context("Failed to run tmux")?;
+        if !status.success() {
+            bail!("tmux send-keys to pane {} failed", pane);
+        }
+    }
+    Ok(())
+}
+
+/// Opens the popup and waits for it to close.
+pub fn open_popup(argv: &[String]) -> Result<()> {
+    let status = Command::new(&argv[0])
+        .args(&argv[1..])
+        .status()
+        .context("Failed to run tmux")?;
+    if !status.success() {
+        bail!("tmux display-popup failed (it needs tmux 3.2 or newer)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_popup_command_runs_picker_for_pane() {
+        let argv = popup_command(
+            Path::new("/usr/local/bin/nr"),
+            "%3",
+            &["--root".to_string(), "apps/my web".to_string()],
+            Path::new("/repo"),
+            15,
+        );
+
+        assert_eq!(
+            argv,
+            vec![
+                "tmux",
+                "display-popup",
+                "-E",
+                "-d",
+                "/repo",
+                "-w",
+                "80%",
+                "-h",
+                "17",
+                "/usr/local/bin/nr --picker --tmux-target %3 --root 'apps/my web'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_send_keys_types_command_literally_then_enter() {
+        let [typed, enter] = send_keys_commands("%3", "pnpm run build");
+
+        assert_eq!(
+            typed,
+            vec!["tmux", "send-keys", "-t", "%3", "-l", "pnpm run build"]
+        );
+        assert_eq!(enter, vec!["tmux", "send-keys", "-t", "%3", "Enter"]);
+    }
+}
@@ -0,0 +1,442 @@
+//! Checking GitHub for newer nr releases, and `nr self-update` for binaries
+//! installed from the release archives (shell script or manual download).
+
+use crate::store::update_check::{UpdateCheck, load_update_check, save_update_check};
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+
+pub use releases::GitHub;
+
+/// GitHub is asked at most this often; the last answer is reused in between.
+pub const CHECK_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Version of this build, e.g. `0.2.2`.
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Whether release `latest` (`0.3.0` or `v0.3.0`) is newer than `current`.
+/// Unparseable versions are never newer.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    // Pre-release and build suffixes don't take part in the comparison
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Where new versions of nr come from: [`GitHub`] releases, or a stub that
+/// fails without going online in builds without the `self-update` feature.
+pub trait Releases {
+    /// Version of the latest release, without the leading `v`.
+    fn latest(&self) -> Result<String>;
+
+    /// Replaces the binary at `exe` with the one of release `version`.
+    fn install(&self, version: &str, exe: &Path) -> Result<()>;
+}
+
+/// The latest of `releases` if it is newer than this build. It is asked only
+/// when the answer saved in `config_dir` is older than [`CHECK_INTERVAL_MS`];
+/// failed checks count too, so being offline doesn't mean asking every start.
+pub fn newer_release(releases: &impl Releases, config_dir: &Path, now_ms: u64) -> Option<String> {
+    let previous = load_update_check(config_dir);
+    let latest = match previous {
+        Some(check) if now_ms.saturating_sub(check.checked_at) < CHECK_INTERVAL_MS => check.latest,
+        previous => {
+            let latest = releases.latest().unwrap_or_else(|_| {
+                previous.map_or_else(|| current_version().to_string(), |check| check.latest)
+            });
+            save_update_check(
+                config_dir,
+                &UpdateCheck {
+                    checked_at: now_ms,
+                    latest: latest.clone(),
+                },
+            );
+            latest
+        }
+    };
+    is_newer(&latest, current_version()).then_some(latest)
+}
+
+/// Runs [`newer_release`] on a background thread; the receiver gets the new
+/// version, if there is one.
+#[cfg(feature = "self-update")]
+pub fn check_in_background(config_dir: PathBuf, now_ms: u64) -> Receiver<String> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        if let Some(latest) = newer_release(&GitHub, &config_dir, now_ms) {
+            let _ = tx.send(latest);
+        }
+    });
+    rx
+}
+
+/// Builds without the `self-update` feature never check.
+#[cfg(not(feature = "self-update"))]
+pub fn check_in_background(_config_dir: PathBuf, _now_ms: u64) -> Receiver<String> {
+    channel().1
+}
+
+/// Replaces the running binary with the latest of `releases` if it is newer.
+/// Returns what happened, for printing.
+pub fn self_update(releases: &impl Releases) -> Result<String> {
+    let latest = releases.latest()?;
+    if !is_newer(&latest, current_version()) {
+        return Ok(format!("nr {} is up to date", current_version()));
+    }
+
+    let exe = std::env::current_exe()
+        .and_then(|path| path.canonicalize())
+        .context("Failed to locate the nr binary")?;
+    if let Some(command) = managed_update_command(&exe) {
+        bail!(
+            "nr {} is available, but this nr is managed by a package manager; update it with `{}`",
+            latest,
+            command
+        );
+    }
+    releases.install(&latest, &exe)?;
+
+    Ok(format!("Updated nr {} → {}", current_version(), latest))
+}
+
+/// Deletes the binary a self-update on Windows renamed to `nr.old` to get
+/// it out of the way; it can only go once that nr has exited.
+pub fn remove_replaced_exe() {
+    if !cfg!(windows) {
+        return;
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::fs::remove_file(exe.with_extension("old"));
+    }
+}
+
+/// How to update an nr installed by a package manager, which `self-update`
+/// leaves alone so the two don't get out of step.
+fn managed_update_command(exe: &Path) -> Option<&'static str> {
+    let path = exe.to_string_lossy().replace('\\', "/");
+    if path.contains("/Cellar/") || path.contains("/homebrew/") || path.contains("/linuxbrew/") {
+        Some("brew upgrade nr")
+    } else if path.contains("/scoop/") {
+        Some("scoop update nr")
+    } else if path.contains("/.cargo/bin/") {
+        Some("cargo install --git https://github.com/juicyjusung/nr --force")
+    } else {
+        None
+    }
+}
+
+/// The release archives on GitHub, fetched with curl.
+#[cfg(feature = "self-update")]
+mod releases {
+    use super::Releases;
+    use anyhow::{Context, Result, bail};
+    use sha2::{Digest, Sha256};
+    use std::path::Path;
+    use std::process::Command;
+
+    const REPO: &str = "juicyjusung/nr";
+
+    /// The releases of the nr repository on GitHub.
+    pub struct GitHub;
+
+    impl Releases for GitHub {
+        fn latest(&self) -> Result<String> {
+            let url = format!("https://api.github.com/repos/{}/releases/latest", REPO);
+            let body = curl(&["-H", "Accept: application/vnd.github+json", &url])?;
+            let release: serde_json::Value =
+                serde_json::from_slice(&body).context("Unexpected response from GitHub")?;
+            let tag = release["tag_name"]
+                .as_str()
+                .context("GitHub returned no release tag")?;
+            Ok(tag.trim_start_matches('v').to_string())
+        }
+
+        /// Downloads the release archive for this platform and swaps in the
+        /// binary inside.
+        fn install(&self, version: &str, exe: &Path) -> Result<()> {
+            let target =
+                target_triple().context("There are no prebuilt nr binaries for this platform")?;
+
+            let dir = std::env::temp_dir().join(format!("nr-update-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).context("Failed to create a download directory")?;
+            let result = download_and_replace(version, target, &dir, exe);
+            let _ = std::fs::remove_dir_all(&dir);
+            result
+        }
+    }
+
+    fn download_and_replace(version: &str, target: &str, dir: &Path, exe: &Path) -> Result<()> {
+        let asset = asset_name(version, target);
+        let archive = dir.join(&asset);
+        let base_url = format!("https://github.com/{}/releases/download/v{}", REPO, version);
+        curl(&[
+            "-o",
+            &archive.to_string_lossy(),
+            &format!("{}/{}", base_url, asset),
+        ])?;
+
+        // Never unpack what doesn't match the checksums published with the release
+        let checksums = curl(&[&format!("{}/{}", base_url, checksums_name(version))])?;
+        let content = std::fs::read(&archive)
+            .with_context(|| format!("Failed to read {}", archive.display()))?;
+        verify_checksum(&content, &asset, &String::from_utf8_lossy(&checksums))?;
+
+        // bsdtar (macOS, Windows) and GNU tar both unpack .tar.gz; bsdtar also .zip
+        let status = Command::new("tar")
+            .arg("-xf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir)
+            .status()
+            .context("Failed to run tar")?;
+        if !status.success() {
+            bail!("Failed to unpack {}", archive.display());
+        }
+
+        let binary = dir
+            .join(format!("nr-v{}-{}", version, target))
+            .join(if cfg!(windows) { "nr.exe" } else { "nr" });
+        replace_exe(&binary, exe)
+    }
+
+    /// Checks `content` against the SHA-256 listed for `asset` in `checksums`,
+    /// the `sha256sum` output the release workflow publishes.
+    fn verify_checksum(content: &[u8], asset: &str, checksums: &str) -> Result<()> {
+        let expected = checksums
+            .lines()
+            .find_map(|line| {
+                let (hash, file) = line.split_once(char::is_whitespace)?;
+                // `sha256sum -b` marks binary files with a `*`
+                let file = file.trim_start().trim_start_matches('*');
+                (file == asset).then(|| hash.to_lowercase())
+            })
+            .with_context(|| format!("The release lists no checksum for {}", asset))?;
+        let actual: String = Sha256::digest(content)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        if actual != expected {
+            bail!(
+                "Checksum mismatch for {} (expected {}, got {}); nr was not updated",
+                asset,
+                expected,
+                actual
+            );
+        }
+        Ok(())
+    }
+
+    /// Moves `binary` into place at `exe` without ever leaving `exe` missing.
+    fn replace_exe(binary: &Path, exe: &Path) -> Result<()> {
+        let staged = exe.with_file_name(".nr-update");
+        std::fs::copy(binary, &staged)
+            .with_context(|| format!("Failed to write next to {}", exe.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+        }
+        // Windows can't replace a running binary, but it can rename it; the
+        // renamed one is removed on the next start (see `remove_replaced_exe`)
+        #[cfg(windows)]
+        std::fs::rename(exe, exe.with_extension("old"))?;
+        std::fs::rename(&staged, exe)
+            .with_context(|| format!("Failed to replace {}", exe.display()))
+    }
+
+    /// Release archive name, as built by the release workflow.
+    fn asset_name(version: &str, target: &str) -> String {
+        let extension = if target.contains("windows") {
+            "zip"
+        } else {
+            "tar.gz"
+        };
+        format!("nr-v{}-{}.{}", version, target, extension)
+    }
+
+    /// Checksum file published with each release, as built by the release workflow.
+    fn checksums_name(version: &str) -> String {
+        format!("nr-v{}-checksums.sha256", version)
+    }
+
+    /// Target triple of the release archive for this platform.
+    fn target_triple() -> Option<&'static str> {
+        match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+            ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+            ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+            ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+            ("windows", "x86_64") => Some("x86_64-pc-windows-msvc"),
+            ("windows", "aarch64") => Some("aarch64-pc-windows-msvc"),
+            _ => None,
+        }
+    }
+
+    /// Fetches `args` with curl, failing on HTTP errors. Returns the body.
+    fn curl(args: &[&str]) -> Result<Vec<u8>> {
+        let output = Command::new("curl")
+            .args(["-fsSL", "--max-time", "30"])
+            .args(args)
+            .output()
+            .context("Failed to run curl")?;
+        if !output.status.success() {
+            bail!(
+                "Couldn't reach GitHub: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_archive_must_match_its_published_checksum() {
+            let archive = b"nr release archive";
+            let hash: String = Sha256::digest(archive)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let checksums = format!(
+                "0000  nr-v0.3.0-x86_64-unknown-linux-gnu.tar.gz\n{}  nr-v0.3.0-aarch64-apple-darwin.tar.gz\n",
+                hash
+            );
+
+            assert!(
+                verify_checksum(archive, "nr-v0.3.0-aarch64-apple-darwin.tar.gz", &checksums)
+                    .is_ok()
+            );
+            let mismatch = verify_checksum(
+                b"tampered",
+                "nr-v0.3.0-aarch64-apple-darwin.tar.gz",
+                &checksums,
+            )
+            .unwrap_err();
+            assert!(mismatch.to_string().starts_with("Checksum mismatch"));
+            assert!(
+                verify_checksum(archive, "nr-v0.3.0-x86_64-pc-windows-msvc.zip", &checksums)
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn test_asset_names_match_the_release_workflow() {
+            assert_eq!(
+                asset_name("0.3.0", "aarch64-apple-darwin"),
+                "nr-v0.3.0-aarch64-apple-darwin.tar.gz"
+            );
+            assert_eq!(
+                asset_name("0.3.0", "x86_64-pc-windows-msvc"),
+                "nr-v0.3.0-x86_64-pc-windows-msvc.zip"
+            );
+        }
+    }
+}
+
+/// Builds without the `self-update` feature never go online.
+#[cfg(not(feature = "self-update"))]
+mod releases {
+    use super::Releases;
+    use crate::core::features::disabled;
+    use anyhow::Result;
+    use std::path::Path;
+
+    pub struct GitHub;
+
+    impl Releases for GitHub {
+        fn latest(&self) -> Result<String> {
+            Err(disabled("self-update"))
+        }
+
+        fn install(&self, _version: &str, _exe: &Path) -> Result<()> {
+            Err(disabled("self-update"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Releases that can't be reached.
+    struct Offline;
+
+    impl Releases for Offline {
+        fn latest(&self) -> Result<String> {
+            bail!("offline")
+        }
+
+        fn install(&self, _version: &str, _exe: &Path) -> Result<()> {
+            bail!("offline")
+        }
+    }
+
+    #[test]
+    fn test_versions_compare_numerically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("v1.0.0", "0.2.2"));
+        assert!(!is_newer("0.2.2", "0.2.2"));
+        assert!(!is_newer("0.2.1", "0.2.2"));
+        assert!(!is_newer("1.0.0-rc.1", "1.0.0"));
+        assert!(!is_newer("nightly", "0.2.2"));
+    }
+
+    #[test]
+    fn test_recent_check_is_reused_without_asking_github() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = 1_700_000_000_000;
+        save_update_check(
+            temp_dir.path(),
+            &UpdateCheck {
+                checked_at: now - CHECK_INTERVAL_MS / 2,
+                latest: "999.0.0".to_string(),
+            },
+        );
+        assert_eq!(
+            newer_release(&Offline, temp_dir.path(), now),
+            Some("999.0.0".to_string())
+        );
+
+        save_update_check(
+            temp_dir.path(),
+            &UpdateCheck {
+                checked_at: now,
+                latest: current_version().to_string(),
+            },
+        );
+        assert_eq!(newer_release(&Offline, temp_dir.path(), now), None);
+    }
+
+    #[test]
+    fn test_failed_check_counts_as_a_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = 1_700_000_000_000;
+        assert_eq!(newer_release(&Offline, temp_dir.path(), now), None);
+
+        let check = load_update_check(temp_dir.path()).unwrap();
+        assert_eq!(check.checked_at, now);
+        assert_eq!(check.latest, current_version());
+    }
+
+    #[test]
+    fn test_package_manager_installs_are_left_to_them() {
+        assert_eq!(
+            managed_update_command(Path::new("/opt/homebrew/Cellar/nr/0.2.2/bin/nr")),
+            Some("brew upgrade nr")
+        );
+        assert!(managed_update_command(Path::new("/home/me/.cargo/bin/nr")).is_some());
+        assert_eq!(managed_update_command(Path::new("/usr/local/bin/nr")), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! Watch mode: re-run a script whenever files matching the `watch` globs change.
+
+use std::time::Duration;
+
+/// Globs watched when the config doesn't set `watch`.
+pub const DEFAULT_GLOBS: &[&str] = &["src/**"];
+
+/// Quiet time after the last change before re-running, so a save touching
+/// several files (or an editor's write-rename dance) triggers one run.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The configured `watch` globs, or [`DEFAULT_GLOBS`] when there are none.
+pub fn watch_globs(configured: &[String]) -> Vec<String> {
+    if configured.is_empty() {
+        DEFAULT_GLOBS.iter().map(|p| p.to_string()).collect()
+    } else {
+        configured.to_vec()
+    }
+}
+
+/// File watching proper, on top of `notify`.
+#[cfg(feature = "watch")]
+mod files {
+    use anyhow::{Context, Result, bail};
+    use globset::{Glob, GlobSet, GlobSetBuilder};
+    use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+    use std::path::{Path, PathBuf};
+    use std::sync::mpsc::{Receiver, channel};
+    use std::time::Instant;
+
+    use super::{DEBOUNCE, watch_globs};
+
+    /// Changes to files below a script's directory that match a set of globs.
+    pub struct FileWatch {
+        root: PathBuf,
+        globs: GlobSet,
+        /// Human-readable globs, e.g. `src/**, package.json`.
+        pub label: String,
+        events: Receiver<notify::Result<notify::Event>>,
+        debounce: Debounce,
+        // Stops watching when dropped
+        _watcher: RecommendedWatcher,
+    }
+
+    impl FileWatch {
+        /// Watches `patterns` (relative to `root`; [`DEFAULT_GLOBS`] when empty).
+        /// Only the directories the globs can match in are watched, so
+        /// `node_modules` and friends cost nothing.
+        pub fn start(root: &Path, patterns: &[String]) -> Result<Self> {
+            let patterns = watch_globs(patterns);
+
+            let mut builder = GlobSetBuilder::new();
+            for pattern in &patterns {
+                builder
+                    .add(Glob::new(pattern).with_context(|| format!("Invalid glob {}", pattern))?);
+            }
+            let globs = builder.build().context("Invalid watch globs")?;
+
+            // Events carry resolved paths (e.g. /private/var on macOS)
+            let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+            let (tx, events) = channel();
+            let mut watcher = notify::recommended_watcher(tx).context("Failed to watch files")?;
+            let mut watching = 0;
+            for (dir, mode) in watch_roots(&patterns) {
+                let dir = root.join(dir);
+                if dir.is_dir() {
+                    watcher
+                        .watch(&dir, mode)
+                        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+                    watching += 1;
+                }
+            }
+            if watching == 0 {
+                bail!(
+                    "Nothing to watch: no directory matches {}",
+                    patterns.join(", ")
+                );
+            }
+
+            Ok(Self {
+                root,
+                globs,
+                label: patterns.join(", "),
+                events,
+                debounce: Debounce::default(),
+                _watcher: watcher,
+            })
+        }
+
+        /// The changed file (relative to the root) once changes have settled for
+        /// [`DEBOUNCE`]; `None` while nothing changed or changes keep coming.
+        pub fn poll(&mut self, now: Instant) -> Option<PathBuf> {
+            while let Ok(event) = self.events.try_recv() {
+                let Ok(event) = event else {
+                    continue;
+                };
+                if !matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) {
+                    continue;
+                }
+                for path in event.paths {
+                    let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+                    if self.globs.is_match(relative) {
+                        self.debounce.changed(relative.to_path_buf(), now);
+                    }
+                }
+            }
+            self.debounce.settled(now)
+        }
+    }
+
+    /// Directories to watch for `patterns`: the part of each glob before its first
+    /// wildcard, recursively unless the glob stays within that directory.
+    fn watch_roots(patterns: &[String]) -> Vec<(PathBuf, RecursiveMode)> {
+        let mut roots: Vec<(PathBuf, RecursiveMode)> = Vec::new();
+        for pattern in patterns {
+            let parts: Vec<&str> = pattern.split('/').collect();
+            let literal = parts
+                .iter()
+                .take_while(|part| !part.contains(['*', '?', '[', '{']))
+                .count()
+                // The last part names files, not a directory to watch
+                .min(parts.len() - 1);
+            let dir: PathBuf = parts[..literal].iter().collect();
+            let mode = if parts.len() - literal > 1 || pattern.contains("**") {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+
+            match roots.iter_mut().find(|(existing, _)| *existing == dir) {
+                Some((_, existing)) if mode == RecursiveMode::Recursive => *existing = mode,
+                Some(_) => {}
+                None => roots.push((dir, mode)),
+            }
+        }
+        roots
+    }
+
+    /// Holds back changes until none has arrived for [`DEBOUNCE`].
+    #[derive(Debug, Default)]
+    struct Debounce {
+        /// The most recent change and when it happened.
+        pending: Option<(PathBuf, Instant)>,
+    }
+
+    impl Debounce {
+        fn changed(&mut self, path: PathBuf, now: Instant) {
+            self.pending = Some((path, now));
+        }
+
+        fn settled(&mut self, now: Instant) -> Option<PathBuf> {
+            let (_, at) = self.pending.as_ref()?;
+            if now.saturating_duration_since(*at) < DEBOUNCE {
+                return None;
+            }
+            self.pending.take().map(|(path, _)| path)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::time::Duration;
+
+        #[test]
+        fn test_watch_roots_stop_at_the_first_wildcard() {
+            let patterns: Vec<String> = ["src/**", "src/*.ts", "package.json", "config/*.json"]
+                .iter()
+                .map(|p| p.to_string())
+                .collect();
+            assert_eq!(
+                watch_roots(&patterns),
+                vec![
+                    (PathBuf::from("src"), RecursiveMode::Recursive),
+                    (PathBuf::new(), RecursiveMode::NonRecursive),
+                    (PathBuf::from("config"), RecursiveMode::NonRecursive),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_debounce_waits_for_changes_to_settle() {
+            let start = Instant::now();
+            let mut debounce = Debounce::default();
+            assert_eq!(debounce.settled(start), None);
+
+            debounce.changed(PathBuf::from("src/a.ts"), start);
+            debounce.changed(PathBuf::from("src/b.ts"), start + DEBOUNCE / 2);
+            assert_eq!(debounce.settled(start + DEBOUNCE), None);
+            assert_eq!(
+                debounce.settled(start + DEBOUNCE * 2),
+                Some(PathBuf::from("src/b.ts"))
+            );
+            assert_eq!(debounce.settled(start + DEBOUNCE * 3), None);
+        }
+
+        #[test]
+        fn test_reports_matching_changes_only() {
+            let tmp = tempfile::TempDir::new().unwrap();
+            std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+            let mut watch = FileWatch::start(tmp.path(), &["src/**/*.ts".to_string()]).unwrap();
+
+            std::fs::write(tmp.path().join("src/notes.md"), "skip").unwrap();
+            std::fs::write(tmp.path().join("src/main.ts"), "run").unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let mut changed = None;
+            while changed.is_none() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+                changed = watch.poll(Instant::now() + DEBOUNCE);
+            }
+            assert_eq!(changed, Some(PathBuf::from("src/main.ts")));
+        }
+
+        #[test]
+        fn test_missing_directories_are_an_error() {
+            let tmp = tempfile::TempDir::new().unwrap();
+            assert!(FileWatch::start(tmp.path(), &[]).is_err());
+        }
+    }
+}
+
+/// Builds without the `watch` feature can't watch files; starting a watch
+/// says so instead.
+#[cfg(not(feature = "watch"))]
+mod files {
+    use anyhow::Result;
+    use std::path::{Path, PathBuf};
+    use std::time::Instant;
+
+    pub struct FileWatch {
+        pub label: String,
+    }
+
+    impl FileWatch {
+        pub fn start(_root: &Path, _patterns: &[String]) -> Result<Self> {
+            Err(crate::core::features::disabled("watch"))
+        }
+
+        pub fn poll(&mut self, _now: Instant) -> Option<PathBuf> {
+            None
+        }
+    }
+}
+
+pub use files::FileWatch;
@@ -1,8 +1,11 @@
+use crate::core::jsonc::strip_jsonc;
 use crate::core::package_json::PackageJson;
 use indexmap::IndexMap;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 /// A package discovered inside a monorepo workspace.
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkspacePackage {
     /// The `name` field from `package.json` (or directory name as fallback).
     pub name: String,
@@ -10,42 +13,103 @@ pub struct WorkspacePackage {
     pub relative_path: String,
     /// Scripts declared in this package's `package.json`.
     pub scripts: IndexMap<String, String>,
+    /// The `description` field, or else the first heading of its README.
+    pub description: Option<String>,
+    /// The `version` field.
+    pub version: Option<String>,
+    /// Marked `"private": true` (not published).
+    pub private: bool,
+}
+
+/// Progress of a workspace scan running on another thread.
+#[derive(Debug)]
+pub enum ScanUpdate {
+    /// A package found so far, in no particular order.
+    Found(WorkspacePackage),
+    /// Every package, sorted, once the scan is complete.
+    Done(Vec<WorkspacePackage>),
 }
 
 /// Scan a monorepo root for workspace packages.
 ///
-/// Reads workspace glob patterns from either `package.json` `"workspaces"` field
-/// or `pnpm-workspace.yaml`, then finds matching directories containing `package.json`.
+/// Reads workspace glob patterns from the `package.json` `"workspaces"` field,
+/// `pnpm-workspace.yaml`, `lerna.json` or Rush's `rush.json`, then finds matching
+/// directories containing `package.json`.
 pub fn scan_workspaces(monorepo_root: &Path) -> Vec<WorkspacePackage> {
+    scan_workspaces_with_excludes(monorepo_root, &[])
+}
+
+/// Scan a monorepo root for workspace packages, skipping excluded directories.
+///
+/// This is the extended version of `scan_workspaces`: `exclude` holds extra glob
+/// patterns (relative to the monorepo root, e.g. `"fixtures/**"`), on top of any
+/// `!pattern` entries in the workspace patterns themselves. Excluded directories
+/// are neither listed nor descended into.
+pub fn scan_workspaces_with_excludes(
+    monorepo_root: &Path,
+    exclude: &[String],
+) -> Vec<WorkspacePackage> {
+    scan_workspaces_streaming(monorepo_root, exclude, |_| {})
+}
+
+/// [`scan_workspaces_with_excludes`], handing each package to `found` as soon
+/// as its `package.json` is read, so a large monorepo can be shown while the
+/// scan goes on.
+pub fn scan_workspaces_streaming(
+    monorepo_root: &Path,
+    exclude: &[String],
+    mut found: impl FnMut(&WorkspacePackage),
+) -> Vec<WorkspacePackage> {
     let patterns = read_workspace_patterns(monorepo_root);
+    let (negated, patterns): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
     if patterns.is_empty() {
         return Vec::new();
     }
 
-    let mut packages = Vec::new();
+    let includes = build_glob_set(patterns.iter().map(String::as_str));
+    let excludes = build_glob_set(
+        negated
+            .iter()
+            .map(|p| &p[1..])
+            .chain(exclude.iter().map(String::as_str)),
+    );
+    // `**` can match at any depth; otherwise no deeper than the longest pattern
+    let max_depth = if patterns.iter().any(|p| p.contains("**")) {
+        None
+    } else {
+        patterns
+            .iter()
+            .map(|p| normalize_pattern(p).split('/').count())
+            .max()
+    };
 
-    for pattern in &patterns {
-        let matched_dirs = expand_glob_pattern(monorepo_root, pattern);
-        for dir in matched_dirs {
-            let pkg_path = dir.join("package.json");
-            if !pkg_path.is_file() {
-                continue;
-            }
+    let mut matched_dirs = Vec::new();
+    collect_matching_dirs(
+        monorepo_root,
+        monorepo_root,
+        &includes,
+        &excludes,
+        1,
+        max_depth,
+        &mut matched_dirs,
+    );
 
-            let relative = dir
-                .strip_prefix(monorepo_root)
-                .unwrap_or(&dir)
-                .to_string_lossy()
-                .replace('\\', "/");
+    let mut packages = Vec::new();
+    for dir in matched_dirs {
+        if !dir.join("package.json").is_file() {
+            continue;
+        }
 
-            let (name, scripts) = read_package_info(&dir);
+        let relative = dir
+            .strip_prefix(monorepo_root)
+            .unwrap_or(&dir)
+            .to_string_lossy()
+            .replace('\\', "/");
 
-            packages.push(WorkspacePackage {
-                name,
-                relative_path: relative,
-                scripts,
-            });
-        }
+        let package = read_package(&dir, relative);
+        found(&package);
+        packages.push(package);
     }
 
     // Sort by relative path for deterministic output
@@ -53,7 +117,70 @@ pub fn scan_workspaces(monorepo_root: &Path) -> Vec<WorkspacePackage> {
     packages
 }
 
-/// Extract workspace patterns from package.json or pnpm-workspace.yaml.
+/// The directories new packages would appear in, relative to `monorepo_root`:
+/// for each workspace pattern, its fixed part before the last component or
+/// the first one with a glob (`apps` for `apps/*`, `.` for `*`).
+pub fn pattern_roots(monorepo_root: &Path) -> BTreeSet<String> {
+    read_workspace_patterns(monorepo_root)
+        .iter()
+        .filter(|p| !p.starts_with('!'))
+        .map(|pattern| {
+            let components: Vec<&str> = normalize_pattern(pattern).split('/').collect();
+            let fixed: Vec<&str> = components[..components.len() - 1]
+                .iter()
+                .take_while(|c| !c.contains(['*', '?', '[', '{']))
+                .copied()
+                .collect();
+            if fixed.is_empty() {
+                ".".to_string()
+            } else {
+                fixed.join("/")
+            }
+        })
+        .collect()
+}
+
+/// Whether a workspace pattern of `monorepo_root` (other than a negation)
+/// uses `**`, so packages can appear at any depth below it.
+pub fn uses_globstar(monorepo_root: &Path) -> bool {
+    read_workspace_patterns(monorepo_root)
+        .iter()
+        .any(|p| !p.starts_with('!') && p.contains("**"))
+}
+
+/// Compiles workspace globs; invalid patterns are ignored. `*` stops at `/`
+/// like in npm and pnpm; only `**` crosses directories. `dir/**` also
+/// matches `dir` itself, so excluding it skips the whole directory.
+fn build_glob_set<'a>(patterns: impl Iterator<Item = &'a str>) -> globset::GlobSet {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns.map(normalize_pattern) {
+        if let Ok(glob) = workspace_glob(pattern) {
+            builder.add(glob);
+        }
+        if let Some(dir) = pattern.strip_suffix("/**") {
+            if let Ok(glob) = workspace_glob(dir) {
+                builder.add(glob);
+            }
+        }
+    }
+    builder
+        .build()
+        .unwrap_or_else(|_| globset::GlobSet::empty())
+}
+
+fn workspace_glob(pattern: &str) -> Result<globset::Glob, globset::Error> {
+    globset::GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+}
+
+/// `packages/*` for `./packages/*/`.
+fn normalize_pattern(pattern: &str) -> &str {
+    pattern.trim_start_matches("./").trim_end_matches('/')
+}
+
+/// Extract workspace patterns from package.json, pnpm-workspace.yaml, lerna.json
+/// or rush.json, in that order.
 fn read_workspace_patterns(monorepo_root: &Path) -> Vec<String> {
     // Try package.json first
     if let Some(pkg) = PackageJson::load(monorepo_root) {
@@ -68,7 +195,11 @@ fn read_workspace_patterns(monorepo_root: &Path) -> Vec<String> {
         return patterns;
     }
 
-    Vec::new()
+    if let Some(patterns) = read_patterns_from_lerna(monorepo_root) {
+        return patterns;
+    }
+
+    read_projects_from_rush(monorepo_root).unwrap_or_default()
 }
 
 /// Read workspace patterns from `pnpm-workspace.yaml`.
@@ -85,36 +216,50 @@ fn read_patterns_from_pnpm_workspace(monorepo_root: &Path) -> Option<Vec<String>
     )
 }
 
-/// Expand a single glob pattern relative to `root` into matching directories.
-///
-/// Uses `globset::Glob` for matching. Since globset does not walk the filesystem,
-/// we manually traverse directories and test each path against the compiled glob.
-fn expand_glob_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
-    // Build glob matcher
-    let glob = match globset::Glob::new(pattern) {
-        Ok(g) => g.compile_matcher(),
-        Err(_) => return Vec::new(),
-    };
+/// Read the `packages` globs of `lerna.json`; Lerna defaults to `packages/*`.
+fn read_patterns_from_lerna(monorepo_root: &Path) -> Option<Vec<String>> {
+    let contents = std::fs::read_to_string(monorepo_root.join("lerna.json")).ok()?;
+    let val: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    match val.get("packages").and_then(|p| p.as_array()) {
+        Some(packages) => Some(
+            packages
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+        ),
+        None => Some(vec!["packages/*".to_string()]),
+    }
+}
 
-    // Calculate the maximum directory depth from the pattern
-    // e.g., "packages/*" => depth 2, "apps/*/packages/*" => depth 4
-    let max_depth = pattern.split('/').count();
+/// Read the `projectFolder` of each project in `rush.json` (JSON with comments).
+/// They are plain paths, which match themselves as globs.
+fn read_projects_from_rush(monorepo_root: &Path) -> Option<Vec<String>> {
+    let contents = std::fs::read_to_string(monorepo_root.join("rush.json")).ok()?;
+    let val: serde_json::Value = serde_json::from_str(&strip_jsonc(&contents)).ok()?;
+    let projects = val.get("projects")?.as_array()?;
 
-    let mut results = Vec::new();
-    collect_matching_dirs(root, root, &glob, 0, max_depth, &mut results);
-    results
+    Some(
+        projects
+            .iter()
+            .filter_map(|p| p.get("projectFolder")?.as_str())
+            .map(|folder| folder.trim_end_matches('/').to_string())
+            .collect(),
+    )
 }
 
-/// Recursively walk directories, checking each path against the glob matcher.
+/// Recursively walk directories below `root` (skipping hidden ones and
+/// `node_modules`), collecting those matching `includes`. Excluded directories
+/// are not descended into; `depth` counts from 1 for `root`'s children.
 fn collect_matching_dirs(
     root: &Path,
     current: &Path,
-    glob: &globset::GlobMatcher,
+    includes: &globset::GlobSet,
+    excludes: &globset::GlobSet,
     depth: usize,
-    max_depth: usize,
+    max_depth: Option<usize>,
     results: &mut Vec<PathBuf>,
 ) {
-    if depth > max_depth {
+    if max_depth.is_some_and(|max| depth > max) {
         return;
     }
 
@@ -137,36 +282,81 @@ fn collect_matching_dirs(
         }
 
         let relative = path.strip_prefix(root).unwrap_or(&path);
-        let relative_str = relative.to_string_lossy();
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
 
-        if glob.is_match(relative_str.as_ref()) {
-            results.push(path.clone());
+        if excludes.is_match(&relative_str) {
+            continue;
         }
 
-        // Continue recursing if we haven't hit max depth
-        if depth + 1 < max_depth {
-            collect_matching_dirs(root, &path, glob, depth + 1, max_depth, results);
+        if includes.is_match(&relative_str) {
+            results.push(path.clone());
         }
+
+        collect_matching_dirs(
+            root,
+            &path,
+            includes,
+            excludes,
+            depth + 1,
+            max_depth,
+            results,
+        );
     }
 }
 
-/// Read the package name and scripts from a `package.json` file.
+/// Read the package name, scripts, version and private flag from a `package.json` file.
 /// Falls back to using the directory name if `name` is missing.
-fn read_package_info(dir: &Path) -> (String, IndexMap<String, String>) {
+fn read_package(dir: &Path, relative_path: String) -> WorkspacePackage {
     let fallback_name = dir
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
-    let pkg = match PackageJson::load(dir) {
-        Some(p) => p,
-        None => return (fallback_name, IndexMap::new()),
+    let Some(pkg) = PackageJson::load(dir) else {
+        return WorkspacePackage {
+            name: fallback_name,
+            relative_path,
+            scripts: IndexMap::new(),
+            description: None,
+            version: None,
+            private: false,
+        };
     };
 
     let scripts = pkg.scripts();
+    let private = pkg.is_private();
     let name = pkg.name.unwrap_or(fallback_name);
+    let description = pkg
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .or_else(|| readme_heading(dir, &name));
+
+    WorkspacePackage {
+        name,
+        relative_path,
+        scripts,
+        description,
+        version: pkg.version.filter(|v| !v.trim().is_empty()),
+        private,
+    }
+}
 
-    (name, scripts)
+/// The README file names a package description may come from, in the
+/// order they are tried.
+pub const README_FILES: &[&str] = &["README.md", "readme.md", "Readme.md"];
+
+/// First Markdown heading of the package's README, unless it only repeats
+/// the package name.
+fn readme_heading(dir: &Path, name: &str) -> Option<String> {
+    let contents = README_FILES
+        .iter()
+        .find_map(|file| std::fs::read_to_string(dir.join(file)).ok())?;
+    let heading = contents.lines().find_map(|line| {
+        let text = line.trim_start_matches('#');
+        (text.len() < line.len() && text.starts_with(' ')).then(|| text.trim())
+    })?;
+    Some(heading.to_string()).filter(|h| !h.is_empty() && h.trim_matches('`') != name)
 }
 
 #[cfg(test)]
@@ -223,6 +413,55 @@ mod tests {
         assert_eq!(app.scripts["dev"], "vite");
     }
 
+    #[test]
+    fn reads_description_or_readme_heading() {
+        let tmp = TempDir::new().unwrap();
+        setup_monorepo_npm(&tmp);
+        let app = tmp.path().join("packages").join("app");
+        write_file(
+            &app,
+            "package.json",
+            r#"{"name":"@mono/app","description":"Customer dashboard"}"#,
+        );
+        write_file(&app, "README.md", "# Dashboard\n");
+        let lib = tmp.path().join("packages").join("lib");
+        write_file(
+            &lib,
+            "README.md",
+            "<img src=\"logo.svg\">\n\n## Shared UI kit\n",
+        );
+
+        let pkgs = scan_workspaces(tmp.path());
+        assert_eq!(pkgs[0].description.as_deref(), Some("Customer dashboard"));
+        assert_eq!(pkgs[1].description.as_deref(), Some("Shared UI kit"));
+
+        // A heading that only repeats the package name says nothing new
+        write_file(&lib, "README.md", "# @mono/lib\n");
+        assert_eq!(scan_workspaces(tmp.path())[1].description, None);
+    }
+
+    #[test]
+    fn reads_version_and_private_flag() {
+        let tmp = TempDir::new().unwrap();
+        setup_monorepo_npm(&tmp);
+        write_file(
+            &tmp.path().join("packages").join("app"),
+            "package.json",
+            r#"{"name":"@mono/app","version":"2.1.0","private":true}"#,
+        );
+        write_file(
+            &tmp.path().join("packages").join("lib"),
+            "package.json",
+            r#"{"name":"@mono/lib","private":"true"}"#,
+        );
+
+        let pkgs = scan_workspaces(tmp.path());
+        assert_eq!(pkgs[0].version.as_deref(), Some("2.1.0"));
+        assert!(pkgs[0].private);
+        assert_eq!(pkgs[1].version, None);
+        assert!(pkgs[1].private);
+    }
+
     #[test]
     fn scans_pnpm_workspaces() {
         let tmp = TempDir::new().unwrap();
@@ -330,4 +569,144 @@ mod tests {
         let app = pkgs.iter().find(|p| p.name == "@mono/app").unwrap();
         assert_eq!(app.relative_path, "packages/app");
     }
+
+    #[test]
+    fn skips_configured_excludes() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"name":"monorepo","workspaces":["packages/*/*"]}"#,
+        );
+        for dir in [
+            "packages/apps/web",
+            "packages/fixtures/broken",
+            "packages/vendor/lib",
+        ] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"dev":"node ."}}"#);
+        }
+
+        let pkgs = scan_workspaces_with_excludes(
+            tmp.path(),
+            &["**/fixtures".to_string(), "packages/vendor/".to_string()],
+        );
+        let paths: Vec<&str> = pkgs.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["packages/apps/web"]);
+    }
+
+    #[test]
+    fn respects_negated_pnpm_patterns() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "package.json", r#"{"name":"monorepo"}"#);
+        write_file(
+            tmp.path(),
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'packages/*'\n  - '!packages/legacy'\n",
+        );
+        for dir in ["packages/core", "packages/legacy"] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"build":"tsup"}}"#);
+        }
+
+        let pkgs = scan_workspaces(tmp.path());
+        assert_eq!(pkgs.len(), 1);
+        assert_eq!(pkgs[0].relative_path, "packages/core");
+    }
+
+    #[test]
+    fn reads_lerna_packages() {
+        let tmp = TempDir::new().unwrap();
+        write_file(tmp.path(), "package.json", r#"{"name":"monorepo"}"#);
+        write_file(tmp.path(), "lerna.json", r#"{"packages":["modules/*"]}"#);
+        for dir in ["modules/ui", "packages/ignored"] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"build":"tsc"}}"#);
+        }
+
+        let pkgs = scan_workspaces(tmp.path());
+        let paths: Vec<&str> = pkgs.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["modules/ui"]);
+    }
+
+    #[test]
+    fn reads_rush_projects() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "rush.json",
+            r#"{
+  // Rush
+  "rushVersion": "5.100.0",
+  "projects": [
+    { "packageName": "@rush/api", "projectFolder": "apps/api" },
+    { "packageName": "@rush/utils", "projectFolder": "libraries/utils/" },
+  ]
+}"#,
+        );
+        for dir in ["apps/api", "apps/unlisted", "libraries/utils"] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"build":"tsc"}}"#);
+        }
+
+        let pkgs = scan_workspaces(tmp.path());
+        let paths: Vec<&str> = pkgs.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["apps/api", "libraries/utils"]);
+    }
+
+    #[test]
+    fn globstar_finds_nested_packages_once() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"name":"monorepo","workspaces":["packages/**","packages/*","!packages/ignored/**"]}"#,
+        );
+        for dir in [
+            "packages/core",
+            "packages/tools/cli",
+            "packages/ignored/inner",
+            "packages/core/node_modules/dep",
+        ] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"build":"tsc"}}"#);
+        }
+        write_file(
+            &tmp.path().join("packages/ignored"),
+            "package.json",
+            r#"{"scripts":{"build":"tsc"}}"#,
+        );
+
+        let pkgs = scan_workspaces(tmp.path());
+        let paths: Vec<&str> = pkgs.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["packages/core", "packages/tools/cli"]);
+    }
+
+    #[test]
+    fn single_star_stays_one_level_next_to_globstar() {
+        let tmp = TempDir::new().unwrap();
+        write_file(
+            tmp.path(),
+            "package.json",
+            r#"{"name":"monorepo","workspaces":["packages/*","tools/**"]}"#,
+        );
+        for dir in [
+            "packages/foo",
+            "packages/foo/test/fixtures/bar",
+            "tools/lint/config",
+        ] {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            write_file(&path, "package.json", r#"{"scripts":{"build":"tsc"}}"#);
+        }
+
+        let pkgs = scan_workspaces(tmp.path());
+        let paths: Vec<&str> = pkgs.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["packages/foo", "tools/lint/config"]);
+    }
 }
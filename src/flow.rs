@@ -0,0 +1,766 @@
+//! The configure flow (Tab): pick env files (and set single variables on top
+//! of them), edit the arguments, confirm. Scripts with saved presets start
+//! with a preset picker.
+//!
+//! [`AppMode`](crate::app::AppMode) says which step is active; each step's
+//! data lives in its own struct here, and [`ConfigureFlow`] carries all three
+//! so going back (Esc) finds a step as it was left.
+
+use crate::core::directories::dir_label;
+use crate::core::env_files::{EnvFile, EnvFileList, EnvScope, load_env_file};
+use crate::core::hooks::ScriptHooks;
+use crate::fuzzy::fuzzy_filter;
+use crate::store::script_configs::Preset;
+use crossterm::event::KeyCode;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// State of a configure flow, from Tab until it's run or cancelled.
+#[derive(Debug, Default)]
+pub struct ConfigureFlow {
+    pub env: EnvSelect,
+    /// Variables set on top of the env files, edited from the env step (e).
+    pub overrides: EnvOverrides,
+    pub args: ArgsEdit,
+    pub confirm: Confirm,
+    /// The working directory picker, while open from the confirm step (Ctrl+D).
+    pub dir_pick: Option<DirPick>,
+    /// The script's presets, offered before the env step.
+    pub preset_pick: Option<PresetPick>,
+    /// The merged environment, while previewed from the env step (p).
+    pub env_preview: Option<EnvPreview>,
+}
+
+impl ConfigureFlow {
+    /// A flow over `files` with `checked` pre-selected and `args` as the
+    /// arguments to start from.
+    pub fn new(files: EnvFileList, checked: HashSet<PathBuf>, args: String) -> Self {
+        Self {
+            env: EnvSelect {
+                files,
+                checked,
+                ..Default::default()
+            },
+            overrides: EnvOverrides::default(),
+            args: ArgsEdit::default(),
+            confirm: Confirm {
+                args,
+                ..Default::default()
+            },
+            dir_pick: None,
+            preset_pick: None,
+            env_preview: None,
+        }
+    }
+
+    /// Checks `preset`'s env files (those still there) and starts from its arguments.
+    pub fn apply_preset(&mut self, preset: &Preset) {
+        self.env.checked = self
+            .env
+            .files
+            .all_files()
+            .filter(|f| preset.env_files.contains(&f.display_name))
+            .map(|f| f.path.clone())
+            .collect();
+        self.confirm.args = preset.args.clone();
+    }
+
+    /// What Ctrl+S on the confirm step saves under `name`.
+    pub fn to_preset(&self, name: String) -> Preset {
+        Preset {
+            name,
+            env_files: self
+                .env
+                .checked_files()
+                .map(|f| f.display_name.clone())
+                .collect(),
+            args: self.confirm.args.clone(),
+        }
+    }
+
+    /// Enter on the env step: edit the arguments, starting from the confirmed ones.
+    pub fn begin_args(&mut self) {
+        self.args = ArgsEdit::new(&self.confirm.args);
+    }
+
+    /// Enter on the args step: the edited arguments are the ones to run with.
+    pub fn commit_args(&mut self) {
+        self.confirm.args = self.args.input.clone();
+    }
+
+    pub fn snapshot(&self) -> FlowSnapshot {
+        FlowSnapshot {
+            env_files: self
+                .env
+                .checked_files()
+                .map(|f| f.display_name.clone())
+                .collect(),
+            env_selected: self.env.selected,
+            args: self.args.input.clone(),
+            args_cursor: self.args.cursor,
+            confirm_args: self.confirm.args.clone(),
+        }
+    }
+}
+
+/// The flow's state in a [`UiSnapshot`](crate::app::UiSnapshot).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlowSnapshot {
+    /// Checked env files in display order.
+    pub env_files: Vec<String>,
+    pub env_selected: usize,
+    pub args: String,
+    pub args_cursor: usize,
+    /// Arguments the run will use.
+    pub confirm_args: String,
+}
+
+/// Step 1: which env files to load.
+#[derive(Debug, Default)]
+pub struct EnvSelect {
+    pub files: EnvFileList,
+    /// Highlighted row, indexing [`EnvFileList::all_files`].
+    pub selected: usize,
+    pub scroll_offset: usize,
+    pub checked: HashSet<PathBuf>,
+    /// The env file being created (n), while its name is typed.
+    pub new_file: Option<NewEnvFile>,
+}
+
+impl EnvSelect {
+    fn len(&self) -> usize {
+        self.files.package_files.len() + self.files.root_files.len()
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Checks or unchecks the highlighted file.
+    pub fn toggle_selected(&mut self) {
+        let Some(file) = self.files.all_files().nth(self.selected) else {
+            return;
+        };
+        if !self.checked.remove(&file.path) {
+            self.checked.insert(file.path.clone());
+        }
+    }
+
+    /// Highlights the file at `path`, if it's listed.
+    pub fn select_path(&mut self, path: &Path) {
+        if let Some(index) = self.files.all_files().position(|f| f.path == path) {
+            self.selected = index;
+        }
+    }
+
+    /// Checked files in display order.
+    pub fn checked_files(&self) -> impl Iterator<Item = &EnvFile> {
+        self.files
+            .all_files()
+            .filter(|f| self.checked.contains(&f.path))
+    }
+
+    /// Checked files in merge order (root → package, so package overrides root).
+    pub fn merge_order_paths(&self) -> Vec<PathBuf> {
+        self.files
+            .all_files_merge_order()
+            .filter(|f| self.checked.contains(&f.path))
+            .map(|f| f.path.clone())
+            .collect()
+    }
+
+    /// The environment the checked files merge into, with the file each
+    /// variable comes from, and `overrides` on top.
+    pub fn preview(&self, overrides: &[(String, String)]) -> EnvPreview {
+        let mut vars: BTreeMap<String, PreviewVar> = BTreeMap::new();
+        let mut merged: HashMap<String, String> = HashMap::new();
+        let mut sources: Vec<(String, Vec<(String, String)>)> = self
+            .files
+            .all_files_merge_order()
+            .filter(|f| self.checked.contains(&f.path))
+            .filter_map(|file| {
+                let source = match file.scope {
+                    EnvScope::Package(_) => file.display_name.clone(),
+                    EnvScope::Root(_) => format!("{} (root)", file.display_name),
+                };
+                let loaded = load_env_file(&file.path, &merged).ok()?;
+                merged.extend(loaded.iter().cloned());
+                Some((source, loaded))
+            })
+            .collect();
+        sources.push((OVERRIDE_SOURCE.to_string(), overrides.to_vec()));
+        for (source, loaded) in sources {
+            for (key, value) in loaded {
+                match vars.get_mut(&key) {
+                    Some(var) => {
+                        var.value = value;
+                        // A key set twice in one file doesn't override itself
+                        if var.source != source {
+                            let previous = std::mem::replace(&mut var.source, source.clone());
+                            var.overridden.push(previous);
+                        }
+                    }
+                    None => {
+                        vars.insert(
+                            key.clone(),
+                            PreviewVar {
+                                key,
+                                value,
+                                source: source.clone(),
+                                overridden: Vec::new(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        EnvPreview {
+            vars: vars.into_values().collect(),
+            scroll: 0,
+        }
+    }
+}
+
+/// Label of the overrides in the env preview, where they win over every file.
+pub const OVERRIDE_SOURCE: &str = "override";
+
+/// `KEY=value` pairs set for a run on top of its env files, in the order
+/// they were added, with the row editor over them.
+#[derive(Debug, Default)]
+pub struct EnvOverrides {
+    pub vars: Vec<(String, String)>,
+    /// Highlighted row; `vars.len()` is the row for adding a variable.
+    pub selected: usize,
+    /// The `KEY=value` being typed, while adding or editing the highlighted row.
+    pub input: Option<String>,
+}
+
+impl EnvOverrides {
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected < self.vars.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Starts typing over the highlighted variable, or a new one on the last row.
+    pub fn begin_edit(&mut self) {
+        self.input = Some(
+            self.vars
+                .get(self.selected)
+                .map(|(key, value)| format!("{}={}", key, value))
+                .unwrap_or_default(),
+        );
+    }
+
+    /// Sets the typed `KEY=value`, replacing the edited row or an existing
+    /// variable of that name. An input without a key is kept for fixing.
+    pub fn commit_edit(&mut self) -> Result<(), &'static str> {
+        let Some(ref input) = self.input else {
+            return Ok(());
+        };
+        let Some((key, value)) = input.split_once('=') else {
+            return Err("Type KEY=value");
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err("Type KEY=value");
+        }
+        let var = (key.to_string(), value.to_string());
+
+        if self.selected < self.vars.len() {
+            self.vars.remove(self.selected);
+        }
+        match self.vars.iter().position(|(k, _)| *k == var.0) {
+            Some(index) => {
+                self.vars[index] = var;
+                self.selected = index;
+            }
+            None => {
+                let index = self.selected.min(self.vars.len());
+                self.vars.insert(index, var);
+                self.selected = index;
+            }
+        }
+        self.input = None;
+        Ok(())
+    }
+
+    /// Drops the highlighted variable.
+    pub fn remove_selected(&mut self) {
+        if self.selected < self.vars.len() {
+            self.vars.remove(self.selected);
+        }
+    }
+}
+
+/// A new env file in the script's directory, named from the env step.
+#[derive(Debug, Default)]
+pub struct NewEnvFile {
+    pub name: String,
+    /// The `.env.example` next to it, to start from.
+    pub example: Option<PathBuf>,
+    /// Start as a copy of `example` (toggled with Tab).
+    pub from_example: bool,
+    /// Why the last name couldn't be created.
+    pub error: Option<String>,
+}
+
+/// The merged environment of the checked env files, shown over the env step.
+#[derive(Debug, Default)]
+pub struct EnvPreview {
+    /// Variables sorted by key.
+    pub vars: Vec<PreviewVar>,
+    /// First visible row.
+    pub scroll: usize,
+}
+
+impl EnvPreview {
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.vars.len() {
+            self.scroll += 1;
+        }
+    }
+}
+
+/// A variable of the merged environment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewVar {
+    pub key: String,
+    pub value: String,
+    /// The file whose value is used: the last one loaded that sets it.
+    pub source: String,
+    /// Files loaded earlier that set it too, in load order.
+    pub overridden: Vec<String>,
+}
+
+/// Step 2: the arguments text field, with args history browsing.
+#[derive(Debug, Default)]
+pub struct ArgsEdit {
+    pub input: String,
+    /// Byte offset of the cursor in `input`.
+    pub cursor: usize,
+    /// Args history entry shown, `None` while editing.
+    pub history_index: Option<usize>,
+}
+
+impl ArgsEdit {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            cursor: input.len(),
+            history_index: None,
+        }
+    }
+
+    /// The rest of the first `history` entry that starts with the input, shown
+    /// as ghost text while the cursor is at the end (fish-style).
+    pub fn suggestion<'a>(&self, history: &'a [String]) -> Option<&'a str> {
+        if self.input.is_empty() || self.cursor != self.input.len() || self.history_index.is_some()
+        {
+            return None;
+        }
+        history
+            .iter()
+            .find(|entry| entry.len() > self.input.len() && entry.starts_with(&self.input))
+            .map(|entry| &entry[self.input.len()..])
+    }
+
+    /// Applies an editing or history key. `saved` is what Up returns to after
+    /// the newest history entry; Tab or → at the end takes the [`Self::suggestion`].
+    pub fn handle_key(&mut self, code: KeyCode, history: &[String], saved: &str) {
+        match code {
+            KeyCode::Up => {
+                // Up moves to newer entries, back to `saved` past the newest
+                if let Some(idx) = self.history_index {
+                    if idx == 0 {
+                        self.history_index = None;
+                        self.input = saved.to_string();
+                    } else {
+                        self.input = history[idx - 1].clone();
+                        self.history_index = Some(idx - 1);
+                    }
+                }
+                self.cursor = self.input.len();
+            }
+            KeyCode::Down => {
+                // Down moves to older entries
+                let next = match self.history_index {
+                    None if !history.is_empty() => Some(0),
+                    Some(idx) if idx + 1 < history.len() => Some(idx + 1),
+                    _ => None,
+                };
+                if let Some(idx) = next {
+                    self.input = history[idx].clone();
+                    self.history_index = Some(idx);
+                }
+                self.cursor = self.input.len();
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right if self.cursor < self.input.len() => self.cursor += 1,
+            KeyCode::Right | KeyCode::Tab => {
+                if let Some(rest) = self.suggestion(history) {
+                    self.input.push_str(rest);
+                    self.cursor = self.input.len();
+                }
+            }
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.input.len(),
+            KeyCode::Char(c) => {
+                self.input.insert(self.cursor, c);
+                self.cursor += 1;
+                self.history_index = None;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.input.remove(self.cursor - 1);
+                self.cursor -= 1;
+                self.history_index = None;
+            }
+            KeyCode::Delete if self.cursor < self.input.len() => {
+                self.input.remove(self.cursor);
+                self.history_index = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Step 3: what the run will use, shown for confirmation.
+#[derive(Debug, Default)]
+pub struct Confirm {
+    /// Arguments passed after the script name.
+    pub args: String,
+    /// Run in the output pane and again whenever watched files change (Ctrl+L).
+    pub watch: bool,
+    /// Directory to run in instead of the script's own (Ctrl+D).
+    pub cwd: Option<PathBuf>,
+    /// Name being typed to save the configuration as a preset (Ctrl+S).
+    pub preset_name: Option<String>,
+    /// The `package.json` hooks of the script in the run's directory, loaded
+    /// on entering the step.
+    pub hooks: ScriptHooks,
+    /// Keys the templates (`.env.example`) of the selected env files list but
+    /// neither the files nor the overrides set; worked out on entering the step.
+    pub missing_keys: Vec<(String, Vec<String>)>,
+}
+
+/// Step 0, for scripts with presets: start from the last used configuration
+/// (the first row) or from one of the presets.
+#[derive(Debug, Default)]
+pub struct PresetPick {
+    /// The presets saved in nr, then those shared in the config.
+    pub presets: Vec<Preset>,
+    /// How many of `presets` are saved in nr; only those can be deleted.
+    pub saved: usize,
+    /// Highlighted row; 0 is the last used configuration, `i + 1` is `presets[i]`.
+    pub selected: usize,
+}
+
+impl PresetPick {
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected < self.presets.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// The highlighted preset, `None` on the last used row.
+    pub fn chosen(&self) -> Option<&Preset> {
+        self.presets.get(self.selected.checked_sub(1)?)
+    }
+
+    /// Whether the highlighted preset comes from the config.
+    pub fn is_shared(&self) -> bool {
+        self.selected > self.saved
+    }
+
+    /// Drops the highlighted preset and returns its name; shared presets
+    /// are kept.
+    pub fn remove_selected(&mut self) -> Option<String> {
+        let index = self.selected.checked_sub(1).filter(|_| !self.is_shared())?;
+        let removed = self.presets.remove(index);
+        self.saved -= 1;
+        self.selected = self.selected.min(self.presets.len());
+        Some(removed.name)
+    }
+
+    /// The presets saved in nr.
+    pub fn saved_presets(&self) -> &[Preset] {
+        &self.presets[..self.saved]
+    }
+}
+
+/// Fuzzy picker over the directories below the project root, for choosing
+/// the working directory without typing a path.
+#[derive(Debug, Default)]
+pub struct DirPick {
+    pub root: PathBuf,
+    /// Directories relative to `root`; the empty path is `root` itself.
+    pub dirs: Vec<PathBuf>,
+    labels: Vec<String>,
+    pub query: String,
+    /// Indices into `dirs` matching `query`, best match first.
+    pub matches: Vec<usize>,
+    /// Highlighted row, indexing `matches`.
+    pub selected: usize,
+}
+
+impl DirPick {
+    /// A picker over `dirs` (see [`list_directories`](crate::core::directories::list_directories))
+    /// with `current` highlighted if it is listed.
+    pub fn new(root: PathBuf, dirs: Vec<PathBuf>, current: &Path) -> Self {
+        let labels = dirs.iter().map(|dir| dir_label(dir)).collect();
+        let selected = current
+            .strip_prefix(&root)
+            .ok()
+            .and_then(|relative| dirs.iter().position(|dir| dir == relative))
+            .unwrap_or(0);
+        Self {
+            root,
+            matches: (0..dirs.len()).collect(),
+            dirs,
+            labels,
+            query: String::new(),
+            selected,
+        }
+    }
+
+    /// How the `index`th directory is shown, e.g. `./` or `apps/web`.
+    pub fn label(&self, index: usize) -> &str {
+        &self.labels[index]
+    }
+
+    /// Applies a navigation or query editing key.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.matches.len() => self.selected += 1,
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refilter();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            _ => {}
+        }
+    }
+
+    fn refilter(&mut self) {
+        self.matches = fuzzy_filter(&self.labels, &self.query, |label| label);
+        self.selected = 0;
+    }
+
+    /// The highlighted directory as a full path.
+    pub fn chosen(&self) -> Option<PathBuf> {
+        let index = *self.matches.get(self.selected)?;
+        Some(self.root.join(&self.dirs[index]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> Vec<String> {
+        vec!["--newest".to_string(), "--older".to_string()]
+    }
+
+    #[test]
+    fn test_env_preview_marks_overridden_values() {
+        let root = tempfile::TempDir::new().unwrap();
+        let pkg = root.path().join("apps/web");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(root.path().join(".env"), "API_URL=root\nLOG=info").unwrap();
+        std::fs::write(pkg.join(".env"), "API_URL=package").unwrap();
+        std::fs::write(pkg.join(".env.local"), "API_URL=local\nPORT=3000").unwrap();
+        let files = crate::core::env_files::scan_env_files(&pkg, &Some(root.path().to_path_buf()));
+        let checked = files.all_files().map(|f| f.path.clone()).collect();
+        let env = EnvSelect {
+            files,
+            checked,
+            ..Default::default()
+        };
+
+        let preview = env.preview(&[("PORT".to_string(), "4000".to_string())]);
+        let keys: Vec<&str> = preview.vars.iter().map(|v| v.key.as_str()).collect();
+        assert_eq!(keys, vec!["API_URL", "LOG", "PORT"]);
+        assert_eq!(
+            preview.vars[0],
+            PreviewVar {
+                key: "API_URL".to_string(),
+                value: "local".to_string(),
+                source: ".env.local".to_string(),
+                overridden: vec![".env (root)".to_string(), ".env".to_string()],
+            }
+        );
+        assert_eq!(preview.vars[1].source, ".env (root)");
+        assert!(preview.vars[1].overridden.is_empty());
+        assert_eq!(preview.vars[2].value, "4000");
+        assert_eq!(preview.vars[2].source, OVERRIDE_SOURCE);
+        assert_eq!(preview.vars[2].overridden, vec![".env.local".to_string()]);
+    }
+
+    #[test]
+    fn test_env_overrides_add_edit_and_replace() {
+        let mut overrides = EnvOverrides::default();
+        overrides.begin_edit();
+        overrides.input = Some("API_URL=http://localhost".to_string());
+        overrides.commit_edit().unwrap();
+        overrides.move_down();
+        overrides.begin_edit();
+        overrides.input = Some("DEBUG".to_string());
+        assert_eq!(overrides.commit_edit(), Err("Type KEY=value"));
+        overrides.input = Some("DEBUG=a=b".to_string());
+        overrides.commit_edit().unwrap();
+        assert_eq!(
+            overrides.vars,
+            vec![
+                ("API_URL".to_string(), "http://localhost".to_string()),
+                ("DEBUG".to_string(), "a=b".to_string()),
+            ]
+        );
+
+        // Editing a row prefills it; a name already set replaces that variable
+        overrides.move_up();
+        overrides.begin_edit();
+        assert_eq!(overrides.input.as_deref(), Some("API_URL=http://localhost"));
+        overrides.input = Some("DEBUG=1".to_string());
+        overrides.commit_edit().unwrap();
+        assert_eq!(overrides.vars, vec![("DEBUG".to_string(), "1".to_string())]);
+
+        overrides.remove_selected();
+        assert!(overrides.vars.is_empty());
+    }
+
+    #[test]
+    fn test_preset_pick_only_deletes_saved_presets() {
+        let preset = |name: &str| Preset {
+            name: name.to_string(),
+            env_files: Vec::new(),
+            args: String::new(),
+        };
+        let mut pick = PresetPick {
+            presets: vec![preset("mine"), preset("shared")],
+            saved: 1,
+            selected: 2,
+        };
+        assert!(pick.is_shared());
+        assert_eq!(pick.remove_selected(), None);
+
+        pick.move_up();
+        assert_eq!(pick.remove_selected(), Some("mine".to_string()));
+        assert!(pick.saved_presets().is_empty());
+        assert_eq!(pick.presets, vec![preset("shared")]);
+        assert_eq!(pick.selected, 1);
+        assert!(pick.is_shared());
+    }
+
+    #[test]
+    fn test_dir_pick_starts_at_the_current_directory_and_filters() {
+        let dirs = vec![
+            PathBuf::new(),
+            PathBuf::from("apps"),
+            PathBuf::from("apps/api"),
+            PathBuf::from("apps/web"),
+        ];
+        let mut pick = DirPick::new(PathBuf::from("/repo"), dirs, Path::new("/repo/apps/web"));
+        assert_eq!(pick.chosen(), Some(PathBuf::from("/repo/apps/web")));
+
+        for c in "api".chars() {
+            pick.handle_key(KeyCode::Char(c));
+        }
+        assert_eq!(pick.label(pick.matches[0]), "apps/api");
+        assert_eq!(pick.chosen(), Some(PathBuf::from("/repo/apps/api")));
+
+        for c in "zzz".chars() {
+            pick.handle_key(KeyCode::Char(c));
+        }
+        assert_eq!(pick.chosen(), None);
+    }
+
+    #[test]
+    fn test_args_suggestion_completes_from_history() {
+        let recent = vec![
+            "--port 3000".to_string(),
+            "--watch".to_string(),
+            "--watchAll".to_string(),
+        ];
+        let mut args = ArgsEdit::new("");
+        assert_eq!(args.suggestion(&recent), None);
+
+        args.handle_key(KeyCode::Char('-'), &recent, "");
+        args.handle_key(KeyCode::Char('-'), &recent, "");
+        args.handle_key(KeyCode::Char('w'), &recent, "");
+        assert_eq!(args.suggestion(&recent), Some("atch"));
+
+        // Only at the end of the input
+        args.handle_key(KeyCode::Left, &recent, "");
+        assert_eq!(args.suggestion(&recent), None);
+        args.handle_key(KeyCode::Right, &recent, "");
+        args.handle_key(KeyCode::Tab, &recent, "");
+        assert_eq!(args.input, "--watch");
+        assert_eq!(args.cursor, 7);
+        assert_eq!(args.suggestion(&recent), Some("All"));
+    }
+
+    #[test]
+    fn test_args_history_browsing_returns_to_saved_args() {
+        let mut args = ArgsEdit::new("--saved");
+        assert_eq!(args.cursor, 7);
+
+        args.handle_key(KeyCode::Down, &history(), "--saved");
+        args.handle_key(KeyCode::Down, &history(), "--saved");
+        assert_eq!(args.input, "--older");
+        assert_eq!(args.history_index, Some(1));
+
+        // Past the oldest entry stays put
+        args.handle_key(KeyCode::Down, &history(), "--saved");
+        assert_eq!(args.history_index, Some(1));
+
+        args.handle_key(KeyCode::Up, &history(), "--saved");
+        args.handle_key(KeyCode::Up, &history(), "--saved");
+        assert_eq!(args.input, "--saved");
+        assert_eq!(args.history_index, None);
+
+        // Editing a history entry leaves history browsing
+        args.handle_key(KeyCode::Down, &history(), "--saved");
+        args.handle_key(KeyCode::Backspace, &history(), "--saved");
+        assert_eq!(args.input, "--newes");
+        assert_eq!(args.history_index, None);
+    }
+
+    #[test]
+    fn test_flow_steps_keep_their_state() {
+        let mut flow = ConfigureFlow::new(EnvFileList::default(), HashSet::new(), "-w".into());
+
+        flow.begin_args();
+        assert_eq!(flow.args.input, "-w");
+        flow.args.handle_key(KeyCode::Char('x'), &[], "");
+        flow.commit_args();
+        assert_eq!(flow.confirm.args, "-wx");
+
+        // Back to env and forward again starts from the confirmed args
+        flow.begin_args();
+        assert_eq!(flow.args.input, "-wx");
+        assert_eq!(flow.args.cursor, 3);
+    }
+}
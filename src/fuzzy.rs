@@ -27,10 +27,81 @@ where
         })
         .collect();
 
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
     scored.into_iter().map(|(i, _)| i).collect()
 }
 
+/// A search query with its `prefix:value` words taken out: with prefix
+/// `cmd`, `cmd:eslint fix` has the filter `eslint` and the text `fix`.
+#[derive(Debug, Default, PartialEq)]
+pub struct PrefixedQuery {
+    /// Words without a known prefix, joined by spaces.
+    pub text: String,
+    filters: Vec<(String, String)>,
+}
+
+impl PrefixedQuery {
+    pub fn parse(query: &str, prefixes: &[&str]) -> Self {
+        let mut text = Vec::new();
+        let mut filters = Vec::new();
+        for word in query.split_whitespace() {
+            let known = word
+                .split_once(':')
+                .filter(|(prefix, _)| prefixes.contains(prefix));
+            match known {
+                Some((prefix, value)) => filters.push((prefix.to_string(), value.to_string())),
+                None => text.push(word),
+            }
+        }
+        PrefixedQuery {
+            text: text.join(" "),
+            filters,
+        }
+    }
+
+    /// The values given for `prefix`, joined by spaces (empty when none).
+    pub fn filter(&self, prefix: &str) -> String {
+        let values: Vec<&str> = self
+            .filters
+            .iter()
+            .filter(|(p, value)| p == prefix && !value.is_empty())
+            .map(|(_, value)| value.as_str())
+            .collect();
+        values.join(" ")
+    }
+
+    /// Whether `prefix:` was typed, even without a value (`fav:`).
+    pub fn has(&self, prefix: &str) -> bool {
+        self.filters.iter().any(|(p, _)| p == prefix)
+    }
+}
+
+/// Char positions in `text` that `query` matched, in ascending order, for
+/// highlighting why an item is listed. Empty when the query is empty or
+/// doesn't match.
+pub fn match_indices(text: &str, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+
+    let mut buf = Vec::new();
+    let haystack = Utf32Str::new(text, &mut buf);
+    let mut indices = Vec::new();
+    if pattern
+        .indices(haystack, &mut matcher, &mut indices)
+        .is_none()
+    {
+        return Vec::new();
+    }
+    // One entry per pattern word, so words matching the same chars repeat them
+    indices.sort_unstable();
+    indices.dedup();
+    indices.into_iter().map(|i| i as usize).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +167,33 @@ mod tests {
         assert_eq!(result[0], 1); // "build" exact
     }
 
+    #[test]
+    fn test_match_indices_marks_matched_chars() {
+        assert_eq!(match_indices("test:unit", "tun"), vec![0, 5, 6]);
+        assert_eq!(match_indices("Build", "bu"), vec![0, 1]);
+        assert_eq!(match_indices("build", ""), Vec::<usize>::new());
+        assert_eq!(match_indices("build", "zzz"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_match_indices_merges_words() {
+        assert_eq!(
+            match_indices("start:dev", "dev start"),
+            vec![0, 1, 2, 3, 4, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn test_prefixed_query_takes_out_known_prefixes() {
+        let query = PrefixedQuery::parse("cmd:eslint fix fav: cmd:src url:x", &["cmd", "fav"]);
+
+        assert_eq!(query.text, "fix url:x");
+        assert_eq!(query.filter("cmd"), "eslint src");
+        assert_eq!(query.filter("fav"), "");
+        assert!(query.has("fav"));
+        assert!(!query.has("path"));
+    }
+
     #[test]
     fn test_with_struct() {
         struct Script {
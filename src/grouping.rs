@@ -0,0 +1,204 @@
+use crate::core::tasks::TaskRunner;
+use crate::sort::SortableScript;
+use std::collections::{HashMap, HashSet};
+
+/// A row of the script list: a script, the header of scripts sharing a
+/// `prefix:` (`test`, `test:unit`, `test:e2e`), or the heading of a task
+/// runner's tasks after the scripts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListRow {
+    Group {
+        prefix: String,
+        /// Scripts in the group.
+        len: usize,
+        expanded: bool,
+    },
+    Section {
+        runner: TaskRunner,
+        /// Tasks in the section.
+        len: usize,
+    },
+    /// Index into the scripts; `grouped` rows are drawn indented under their header.
+    Script { index: usize, grouped: bool },
+}
+
+impl ListRow {
+    /// The script shown on this row, if it isn't a header.
+    pub fn script(&self) -> Option<usize> {
+        match *self {
+            ListRow::Script { index, .. } => Some(index),
+            ListRow::Group { .. } | ListRow::Section { .. } => None,
+        }
+    }
+}
+
+/// `test` for `test:unit` (and for `test` itself).
+pub fn prefix_of(name: &str) -> &str {
+    name.split(':').next().unwrap_or(name)
+}
+
+/// One row per script, in `sorted` order.
+pub fn flat_rows(sorted: &[usize]) -> Vec<ListRow> {
+    sorted
+        .iter()
+        .map(|&index| ListRow::Script {
+            index,
+            grouped: false,
+        })
+        .collect()
+}
+
+/// Rows for the scripts in `sorted` order, with scripts sharing a prefix
+/// gathered under a header where the first of them would be. Only the
+/// `expanded` groups list their scripts; a prefix used once isn't grouped.
+pub fn group_by_prefix(
+    scripts: &[SortableScript],
+    sorted: &[usize],
+    expanded: &HashSet<String>,
+) -> Vec<ListRow> {
+    let mut members: HashMap<&str, Vec<usize>> = HashMap::new();
+    for &i in sorted {
+        members
+            .entry(prefix_of(&scripts[i].name))
+            .or_default()
+            .push(i);
+    }
+
+    let mut rows = Vec::new();
+    let mut emitted: HashSet<&str> = HashSet::new();
+    for &i in sorted {
+        let prefix = prefix_of(&scripts[i].name);
+        let group = &members[prefix];
+        if group.len() < 2 {
+            rows.push(ListRow::Script {
+                index: i,
+                grouped: false,
+            });
+            continue;
+        }
+        if !emitted.insert(prefix) {
+            continue;
+        }
+        let is_expanded = expanded.contains(prefix);
+        rows.push(ListRow::Group {
+            prefix: prefix.to_string(),
+            len: group.len(),
+            expanded: is_expanded,
+        });
+        if is_expanded {
+            rows.extend(group.iter().map(|&index| ListRow::Script {
+                index,
+                grouped: true,
+            }));
+        }
+    }
+    rows
+}
+
+/// Rows for the justfile / Makefile tasks in `sorted`: a section per runner,
+/// in [`TaskRunner::ALL`] order, listing its tasks in `sorted` order.
+pub fn task_rows(scripts: &[SortableScript], sorted: &[usize]) -> Vec<ListRow> {
+    let mut rows = Vec::new();
+    for runner in TaskRunner::ALL {
+        let tasks: Vec<usize> = sorted
+            .iter()
+            .copied()
+            .filter(|&i| scripts[i].runner == Some(runner))
+            .collect();
+        if tasks.is_empty() {
+            continue;
+        }
+        rows.push(ListRow::Section {
+            runner,
+            len: tasks.len(),
+        });
+        rows.extend(flat_rows(&tasks));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(names: &[&str]) -> Vec<SortableScript> {
+        names
+            .iter()
+            .map(|name| SortableScript {
+                key: format!("root:{}", name),
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn group(prefix: &str, len: usize, expanded: bool) -> ListRow {
+        ListRow::Group {
+            prefix: prefix.to_string(),
+            len,
+            expanded,
+        }
+    }
+
+    fn script(index: usize, grouped: bool) -> ListRow {
+        ListRow::Script { index, grouped }
+    }
+
+    #[test]
+    fn test_groups_shared_prefixes_at_first_member() {
+        let scripts = scripts(&["dev", "test:unit", "build", "test", "test:e2e"]);
+        let sorted = [0, 1, 2, 3, 4];
+
+        let rows = group_by_prefix(&scripts, &sorted, &HashSet::new());
+
+        assert_eq!(
+            rows,
+            vec![script(0, false), group("test", 3, false), script(2, false)]
+        );
+    }
+
+    #[test]
+    fn test_expanded_group_lists_members_in_sorted_order() {
+        let scripts = scripts(&["build:web", "lint", "build:api"]);
+        let expanded: HashSet<String> = ["build".to_string()].into();
+
+        let rows = group_by_prefix(&scripts, &[2, 1, 0], &expanded);
+
+        assert_eq!(
+            rows,
+            vec![
+                group("build", 2, true),
+                script(2, true),
+                script(0, true),
+                script(1, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_task_rows_list_each_runner_under_its_section() {
+        let mut scripts = scripts(&["build", "test", "lint", "fmt"]);
+        scripts[0].runner = Some(TaskRunner::Make);
+        scripts[2].runner = Some(TaskRunner::Just);
+        scripts[3].runner = Some(TaskRunner::Make);
+
+        let rows = task_rows(&scripts, &[3, 2, 1, 0]);
+
+        assert_eq!(
+            rows,
+            vec![
+                ListRow::Section {
+                    runner: TaskRunner::Just,
+                    len: 1
+                },
+                script(2, false),
+                ListRow::Section {
+                    runner: TaskRunner::Make,
+                    len: 2
+                },
+                script(3, false),
+                script(0, false),
+            ]
+        );
+    }
+}
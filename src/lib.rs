@@ -6,7 +6,9 @@
 
 pub mod app;
 pub mod core;
+pub mod flow;
 pub mod fuzzy;
+pub mod grouping;
 pub mod sort;
 pub mod store;
 pub mod ui;
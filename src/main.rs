@@ -1,10 +1,65 @@
 use anyhow::{Context, Result};
 use nr::{app, core, store};
+use std::io::{BufRead, IsTerminal};
+use std::path::Path;
 use std::process;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How often the event loop wakes up without input to update countdowns.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Tick rate while scripts run inside the TUI (output pane or Jobs tab).
+const OUTPUT_TICK_RATE: Duration = Duration::from_millis(50);
+
+/// Rows drawn by `--inline` when `inline_height` isn't configured.
+const DEFAULT_INLINE_HEIGHT: u16 = 15;
+
+/// Fewest inline rows that still fit the header, tabs, search, one list row and status bar.
+const MIN_INLINE_HEIGHT: u16 = 6;
+
+/// Fewest `--picker` rows: the query and one script.
+const MIN_PICKER_HEIGHT: u16 = 2;
+
+/// nr's flags that take the next argument as their value.
+const VALUE_FLAGS: [&str; 3] = ["--root", "--tmux-target", "--bench"];
+
+/// First positional arguments that are nr commands rather than script names.
+const SUBCOMMANDS: [&str; 3] = ["popup", "doctor", "self-update"];
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {:?}", e);
+        process::exit(core::exit_code::ERROR);
+    }
+}
+
+fn run() -> Result<()> {
+    core::update::remove_replaced_exe();
 
-fn main() -> Result<()> {
     // 0. Handle CLI arguments
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+    // nr's own flags end at the first positional argument; the rest belongs to the script
+    let mut script_argv = split_script_argv(&mut args);
+    let subcommand = script_argv
+        .first()
+        .filter(|a| SUBCOMMANDS.contains(&a.as_str()))
+        .cloned();
+    if subcommand.is_some() {
+        args.extend(script_argv.drain(..).skip(1));
+    }
+    let root_override = take_value_arg(&mut args, "--root")?.map(std::path::PathBuf::from);
+    let tmux_target = take_value_arg(&mut args, "--tmux-target")?;
+    let bench_script = take_value_arg(&mut args, "--bench")?;
+    // `nr popup`: reopen nr in a tmux popup (handled once the config is loaded)
+    let wants_popup = subcommand.as_deref() == Some("popup");
+    // `nr doctor`: check the config files
+    let wants_doctor = subcommand.as_deref() == Some("doctor");
+    // `nr self-update`: replace this binary with the latest release
+    if subcommand.as_deref() == Some("self-update") {
+        println!("{}", core::update::self_update(&core::update::GitHub)?);
+        return Ok(());
+    }
     if args.iter().any(|a| a == "--version" || a == "-V") {
         println!("nr {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
@@ -16,30 +71,73 @@ fn main() -> Result<()> {
     let wants_reset_configs = args.iter().any(|a| a == "--reset-configs");
     let wants_any_reset =
         wants_reset || wants_reset_favorites || wants_reset_recents || wants_reset_configs;
+    let wants_inline = args.iter().any(|a| a == "--inline");
+    let wants_picker = args.iter().any(|a| a == "--picker");
+    let wants_ascii = args.iter().any(|a| a == "--ascii");
+    let wants_json = args.iter().any(|a| a == "--json");
+    let wants_list = wants_json || args.iter().any(|a| a == "--list");
+    let wants_last = args.iter().any(|a| a == "--last");
+    let cancel_code = core::exit_code::on_cancel(args.iter().any(|a| a == "--fail-on-cancel"));
+
+    // `nr <script> [args...]`: the first positional argument is a script to run directly
+    let direct_run = (!script_argv.is_empty()).then(|| {
+        let script_args = core::package_manager::shell_join(&script_argv[1..]);
+        (script_argv.remove(0), script_args)
+    });
 
     if args.iter().any(|a| a == "--help" || a == "-h") {
         println!("nr — TUI-based npm script runner with fuzzy search");
         println!();
-        println!("USAGE: nr");
+        println!("USAGE: nr [OPTIONS] [SCRIPT [ARGS...]]");
+        println!("       nr popup");
+        println!("       nr doctor");
+        println!("       nr self-update");
         println!();
         println!("Run in a directory containing package.json to interactively");
-        println!("browse and execute npm scripts.");
+        println!("browse and execute npm scripts. With SCRIPT, run it directly");
+        println!("without the TUI, passing any ARGS through to the script;");
+        println!("options after SCRIPT belong to the script, not to nr.");
+        println!("`nr popup` opens the picker in a tmux popup and runs the chosen");
+        println!("script in the pane it was started from.");
+        println!("`nr doctor` checks the global and project config files.");
+        println!("`nr self-update` installs the latest release over this binary.");
         println!();
         println!("OPTIONS:");
         println!("  -h, --help            Print this help message");
         println!("  -V, --version         Print version");
+        println!("  --root <PATH>         Use PATH as the project root instead of detecting it");
+        println!("  --inline              Draw below the prompt instead of taking over the screen");
+        println!("  --picker              Pick a script and print its name instead of running it");
+        println!("  --tmux-target <PANE>  With --picker, run the chosen script in a tmux pane");
+        println!("  --ascii               Draw ASCII symbols instead of Unicode ones");
+        println!("  --list                List scripts (workspace scripts as <package>:<script>)");
+        println!("  --json                Print the project's scripts and workspaces as JSON");
+        println!("  --last                Run the last run again with the same args and env files");
+        println!("  --bench <SCRIPT>      Time SCRIPT with each installed package manager");
+        println!("  --fail-on-cancel      Exit with 130 when leaving without running anything");
         println!("  --reset               Clear favorites and recents for current project");
         println!("  --reset-favorites     Clear favorites for current project");
         println!("  --reset-recents       Clear recents for current project");
+        println!();
+        println!("EXIT STATUS:");
+        println!("  0    Success, or nothing was run (see --fail-on-cancel)");
+        println!("  N    The script's own exit code when it fails");
+        println!("  125  nr failed (no project found, no terminal, ...)");
+        println!("  127  No such script");
+        println!("  130  Cancelled");
         return Ok(());
     }
 
     // 1. Core discovery (before TUI)
     let cwd = std::env::current_dir().context("Failed to get current directory")?;
-    let root = core::project_root::find_project_root(&cwd)?;
+    let root = match root_override.clone() {
+        Some(dir) => core::project_root::forced_project_root(&cwd.join(dir))?,
+        None => core::project_root::find_project_root(&cwd)?,
+    };
 
     let pm_root = root.monorepo_root.as_ref().unwrap_or(&root.nearest_pkg);
-    let proj_id = store::project_id::project_id(pm_root);
+    let proj_id =
+        store::project_id::resolve_project_id(pm_root, &store::config_path::get_projects_dir());
 
     // Handle reset commands (no TUI needed)
     if wants_any_reset {
@@ -54,8 +152,85 @@ fn main() -> Result<()> {
     }
 
     let package_manager = core::package_manager::detect_package_manager(pm_root);
+    // Scripts of the nearest package may use their own (nested lockfile)
+    let nearest_pm = core::package_manager::detect_package_manager_in(
+        &root.nearest_pkg,
+        pm_root,
+        package_manager,
+    );
     let scripts = core::scripts::load_scripts(&root.nearest_pkg);
 
+    let project_dir = store::config_path::ensure_project_dir(&proj_id);
+    let config_dir = store::config_path::get_config_dir();
+    let config_files = core::project_config::config_files(&config_dir, pm_root, &project_dir);
+    let (mut config, config_issues) = store::config::load_config(&config_files);
+    if wants_doctor {
+        print!(
+            "{}",
+            core::doctor::config_report(&config_files, &config_issues)
+        );
+        print!("{}", core::features::report());
+        process::exit(if config_issues.is_empty() { 0 } else { 1 });
+    }
+    for issue in &config_issues {
+        eprintln!("⚠️  Ignoring nr config {}", issue);
+    }
+    config.ascii |= wants_ascii;
+    let run_template = config.run_template(nearest_pm).map(String::from);
+
+    if wants_popup {
+        let pane = core::tmux::current_pane()?;
+        let picker_args: Vec<String> = root_override
+            .iter()
+            .flat_map(|dir| ["--root".to_string(), dir.display().to_string()])
+            .collect();
+        let exe = std::env::current_exe().context("Failed to locate the nr executable")?;
+        let height = config
+            .inline_height
+            .unwrap_or(DEFAULT_INLINE_HEIGHT)
+            .max(MIN_PICKER_HEIGHT);
+        return core::tmux::open_popup(&core::tmux::popup_command(
+            &exe,
+            &pane,
+            &picker_args,
+            &cwd,
+            height,
+        ));
+    }
+
+    // Machine-readable listing for other tools and shell completions (no TUI)
+    if wants_list {
+        let hidden = core::scripts::HiddenScripts::new(&config.hide_scripts);
+        let scripts = hidden.filter(&scripts);
+        let mut packages = match root.monorepo_root {
+            Some(ref monorepo_root) => core::workspaces::scan_workspaces_with_excludes(
+                monorepo_root,
+                &config.workspace_exclude,
+            ),
+            None => Vec::new(),
+        };
+        for pkg in &mut packages {
+            pkg.scripts = hidden.filter(&pkg.scripts);
+        }
+        if wants_json {
+            let value = core::listing::to_json(
+                &root.nearest_pkg,
+                root.monorepo_root.as_deref(),
+                package_manager,
+                &scripts,
+                &packages,
+            );
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        } else {
+            print!("{}", core::listing::format_plain(&scripts, &packages));
+        }
+        return Ok(());
+    }
+
+    if wants_last {
+        process::exit(run_last(&project_dir, pm_root, package_manager, &config));
+    }
+
     if scripts.is_empty() {
         eprintln!(
             "❌ No scripts found in {}/package.json",
@@ -72,31 +247,118 @@ fn main() -> Result<()> {
         eprintln!("   }}");
         eprintln!();
         eprintln!("📖 Learn more: https://docs.npmjs.com/cli/v10/using-npm/scripts");
-        process::exit(1);
+        process::exit(core::exit_code::NO_SCRIPT);
     }
 
-    let workspace_packages = root
-        .monorepo_root
-        .as_ref()
-        .map(|r| core::workspaces::scan_workspaces(r))
-        .unwrap_or_default();
+    if let Some(script_name) = bench_script {
+        let exit_code = run_bench(
+            &script_name,
+            &scripts,
+            &root.nearest_pkg,
+            &project_dir,
+            nearest_pm,
+            &config,
+        );
+        process::exit(exit_code);
+    }
 
-    let project_dir = store::config_path::ensure_project_dir(&proj_id);
+    if let Some((script_name, script_args)) = direct_run {
+        let exit_code = run_direct(
+            &script_name,
+            &script_args,
+            &scripts,
+            &root.nearest_pkg,
+            &project_dir,
+            nearest_pm,
+            &config,
+        );
+        process::exit(exit_code);
+    }
+
+    // `script=$(nr --picker)`: draw on stderr so only the chosen name reaches stdout
+    if wants_picker {
+        if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+            anyhow::bail!("--picker needs a terminal");
+        }
+        let height = config
+            .inline_height
+            .unwrap_or(DEFAULT_INLINE_HEIGHT)
+            .max(MIN_PICKER_HEIGHT);
+        let app = app::App::new(
+            scripts,
+            Vec::new(),
+            root.nearest_pkg,
+            root.monorepo_root,
+            &project_dir,
+            String::new(),
+            String::new(),
+            package_manager.to_string(),
+            package_manager,
+        )
+        .with_config(config)
+        .with_picker();
+
+        install_panic_hook();
+        let Some(script_name) = run_picker(app, height)? else {
+            process::exit(cancel_code);
+        };
+        match tmux_target {
+            Some(pane) => {
+                let argv = nearest_pm.invocation(&script_name, &[], run_template.as_deref());
+                core::tmux::send_to_pane(&pane, &core::package_manager::shell_join(&argv))?;
+            }
+            None => println!("{}", script_name),
+        }
+        return Ok(());
+    }
+
+    // Piped output or a dumb terminal can't host the TUI; fall back to plain text
+    let has_tty = std::io::stdin().is_terminal()
+        && std::io::stdout().is_terminal()
+        && std::env::var("TERM").ok().is_none_or(|term| term != "dumb");
+    if !has_tty {
+        let exit_code = run_plain_picker(
+            &core::scripts::HiddenScripts::new(&config.hide_scripts).filter(&scripts),
+            &root.nearest_pkg,
+            &project_dir,
+            nearest_pm,
+            &config,
+            cancel_code,
+        );
+        process::exit(exit_code);
+    }
+
+    let (workspace_packages, workspace_scan) = match root.monorepo_root {
+        Some(ref monorepo_root) => {
+            load_workspace_packages(monorepo_root, &project_dir, &config.workspace_exclude)
+        }
+        None => (Some(Vec::new()), None),
+    };
+    // Without an index, packages stream in while the TUI is up
+    let scanning = workspace_packages.is_none();
 
     let project_name = core::package_json::PackageJson::load(&root.nearest_pkg)
         .and_then(|pkg| pkg.name)
         .unwrap_or_else(|| "unknown".to_string());
     let project_path = pm_root.to_string_lossy().to_string();
     let pm_name = package_manager.to_string();
+    let update_check = config
+        .update_check
+        .then(|| core::update::check_in_background(config_dir, store::recents::now_ms()));
 
     // 2. Install panic hook so terminal is restored on panic
     install_panic_hook();
 
     // 3. Initialize TUI
-    let mut terminal = ratatui::init();
+    let screen = match config.inline_height {
+        Some(height) => Screen::Inline(height.max(MIN_INLINE_HEIGHT)),
+        None if wants_inline => Screen::Inline(DEFAULT_INLINE_HEIGHT),
+        None => Screen::Fullscreen,
+    };
+    let mut terminal = screen.init();
     let mut app = app::App::new(
         scripts,
-        workspace_packages,
+        workspace_packages.unwrap_or_default(),
         root.nearest_pkg,
         root.monorepo_root,
         &project_dir,
@@ -104,59 +366,718 @@ fn main() -> Result<()> {
         project_path,
         pm_name,
         package_manager,
-    );
+    )
+    .with_config(config)
+    .with_config_issues(config_issues);
+    if let Some(rx) = workspace_scan {
+        app = if scanning {
+            app.with_workspace_scan(rx)
+        } else {
+            app.with_workspace_refresh(rx)
+        };
+    }
+    if let Some(rx) = update_check {
+        app = app.with_update_check(rx);
+    }
 
-    // 4. Event loop
-    let action = loop {
-        terminal.draw(|frame| app.render(frame))?;
+    loop {
+        // 4. Event loop
+        let action = loop {
+            terminal.draw(|frame| app.render(frame))?;
 
-        if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
-            // Skip release/repeat events on some terminals
-            if key.kind != crossterm::event::KeyEventKind::Press {
-                continue;
+            // Wake up periodically so scheduled runs count down and fire on time,
+            // and more often while a script streams output into the pane
+            let tick_rate = if app.jobs_running() {
+                OUTPUT_TICK_RATE
+            } else {
+                TICK_RATE
+            };
+            let mut result = app::Action::Continue;
+            if crossterm::event::poll(tick_rate)? {
+                match crossterm::event::read()? {
+                    // Skip release/repeat events on some terminals
+                    crossterm::event::Event::Key(key)
+                        if key.kind == crossterm::event::KeyEventKind::Press =>
+                    {
+                        result = app.handle_key(key);
+                    }
+                    crossterm::event::Event::Mouse(mouse) => {
+                        result = app.handle_mouse(mouse, Instant::now());
+                    }
+                    _ => {}
+                }
+            }
+            if matches!(result, app::Action::Continue) {
+                result = app.tick(Instant::now());
             }
-            let result = app.handle_key(key);
             match result {
                 app::Action::Quit => break app::Action::Quit,
-                app::Action::RunScript { .. } => break result,
+                app::Action::RunScript { .. }
+                | app::Action::RunQueue
+                | app::Action::RunParallel { .. }
+                | app::Action::RunAllPackages { .. }
+                | app::Action::RunMatrix { .. }
+                | app::Action::RunTask { .. }
+                | app::Action::OpenEditor { .. }
+                | app::Action::PrintEnv { .. }
+                | app::Action::PrintCommand { .. }
+                | app::Action::PrintScript { .. } => break result,
                 app::Action::Continue => {}
             }
+        };
+
+        // 5. Restore terminal
+        screen.restore(&mut terminal);
+        app.save_session();
+
+        // 6. Execute script (after TUI cleanup)
+        match action {
+            app::Action::RunScript {
+                script_name,
+                cwd,
+                env_files,
+                args,
+                env,
+            } => {
+                app.save_favorites();
+                store::recents::save_recents(&project_dir, &app.recents);
+
+                let started = Instant::now();
+                let exit_code = execute(
+                    app.package_manager_for(&cwd),
+                    &script_name,
+                    &cwd,
+                    app.workspace_target(&cwd).as_ref(),
+                    &env_files,
+                    &env,
+                    &args,
+                    &app.config,
+                );
+                app.record_outcome(
+                    &script_name,
+                    &cwd,
+                    &args,
+                    &env_files,
+                    exit_code,
+                    started.elapsed(),
+                );
+
+                // Pending scheduled runs keep nr alive; come back to wait for them
+                if app.scheduled.is_empty() {
+                    process::exit(exit_code);
+                }
+                terminal = screen.init();
+            }
+            app::Action::RunQueue => {
+                app.save_favorites();
+
+                // Run one at a time; stop on the first failure. A paused queue
+                // runs a single item and then returns to the queue panel.
+                while let Some(run) = app.queue.pop_front() {
+                    println!("▶ {} ({} left in queue)", run.key, app.queue.len());
+                    app.record_run(&run);
+                    store::recents::save_recents(&project_dir, &app.recents);
+
+                    let started = Instant::now();
+                    let exit_code = execute(
+                        app.package_manager_for(&run.cwd),
+                        &run.script_name,
+                        &run.cwd,
+                        app.workspace_target(&run.cwd).as_ref(),
+                        &run.env_files,
+                        &run.env,
+                        &run.args,
+                        &app.config,
+                    );
+                    app.record_outcome(
+                        &run.script_name,
+                        &run.cwd,
+                        &run.args,
+                        &run.env_files,
+                        exit_code,
+                        started.elapsed(),
+                    );
+                    if exit_code != 0 {
+                        eprintln!(
+                            "❌ {} exited with code {}; queue stopped with {} item(s) left",
+                            run.key,
+                            exit_code,
+                            app.queue.len()
+                        );
+                        process::exit(exit_code);
+                    }
+                    if app.queue.paused {
+                        break;
+                    }
+                }
+
+                if app.queue.is_empty() && app.scheduled.is_empty() {
+                    return Ok(());
+                }
+
+                terminal = screen.init();
+                app.open_queue();
+            }
+            app::Action::RunParallel { runs } => {
+                app.save_favorites();
+                for run in &runs {
+                    app.record_run(run);
+                }
+                store::recents::save_recents(&project_dir, &app.recents);
+
+                println!("▶ Running {} scripts in parallel", runs.len());
+                let results = core::parallel::run_parallel(
+                    |cwd| app.package_manager_for(cwd),
+                    &runs,
+                    &app.config,
+                );
+                for (run, result) in runs.iter().zip(&results) {
+                    app.record_outcome(
+                        &run.script_name,
+                        &run.cwd,
+                        &run.args,
+                        &run.env_files,
+                        result.exit_code,
+                        result.duration,
+                    );
+                }
+                print!("{}", core::parallel::format_summary(&results));
+
+                if app.queue.is_empty() && app.scheduled.is_empty() {
+                    let failed = results.iter().find(|r| r.exit_code != 0);
+                    process::exit(failed.map_or(0, |r| r.exit_code));
+                }
+                terminal = screen.init();
+                app.open_queue();
+            }
+            app::Action::RunAllPackages { runs } => {
+                app.save_favorites();
+
+                // One package at a time; stop on the first failure, like the queue
+                let mut exit_code = 0;
+                for (i, run) in runs.iter().enumerate() {
+                    println!("▶ {} ({}/{})", run.key, i + 1, runs.len());
+                    let started = Instant::now();
+                    exit_code = execute(
+                        app.package_manager_for(&run.cwd),
+                        &run.script_name,
+                        &run.cwd,
+                        app.workspace_target(&run.cwd).as_ref(),
+                        &run.env_files,
+                        &run.env,
+                        &run.args,
+                        &app.config,
+                    );
+                    app.record_outcome(
+                        &run.script_name,
+                        &run.cwd,
+                        &run.args,
+                        &run.env_files,
+                        exit_code,
+                        started.elapsed(),
+                    );
+                    if exit_code != 0 {
+                        eprintln!(
+                            "❌ {} exited with code {}; {} package(s) skipped",
+                            run.key,
+                            exit_code,
+                            runs.len() - i - 1
+                        );
+                        break;
+                    }
+                }
+
+                if app.scheduled.is_empty() {
+                    process::exit(exit_code);
+                }
+                terminal = screen.init();
+            }
+            app::Action::RunTask { runner, task, cwd } => {
+                app.save_favorites();
+
+                let exit_code =
+                    core::runner::run_task(runner, &task, &cwd, &app.config.inherit_env);
+                if app.scheduled.is_empty() {
+                    process::exit(exit_code);
+                }
+                terminal = screen.init();
+            }
+            app::Action::RunMatrix {
+                script_name,
+                cwd,
+                args,
+                profiles,
+            } => {
+                app.save_favorites();
+                store::recents::save_recents(&project_dir, &app.recents);
+
+                // Every profile runs even if an earlier one fails, so the summary is complete
+                let mut results = Vec::new();
+                for (i, profile) in profiles.iter().enumerate() {
+                    println!(
+                        "▶ {} with {} ({}/{})",
+                        script_name,
+                        profile.label,
+                        i + 1,
+                        profiles.len()
+                    );
+                    let started = Instant::now();
+                    let exit_code = execute(
+                        app.package_manager_for(&cwd),
+                        &script_name,
+                        &cwd,
+                        app.workspace_target(&cwd).as_ref(),
+                        &profile.env_files,
+                        &[],
+                        &args,
+                        &app.config,
+                    );
+                    app.record_outcome(
+                        &script_name,
+                        &cwd,
+                        &args,
+                        &profile.env_files,
+                        exit_code,
+                        started.elapsed(),
+                    );
+                    results.push(core::matrix::MatrixResult {
+                        label: profile.label.clone(),
+                        exit_code,
+                        duration: started.elapsed(),
+                    });
+                }
+                print!("{}", core::matrix::format_summary(&script_name, &results));
+
+                if app.scheduled.is_empty() {
+                    let failed = results.iter().find(|r| r.exit_code != 0);
+                    process::exit(failed.map_or(0, |r| r.exit_code));
+                }
+                terminal = screen.init();
+            }
+            app::Action::OpenEditor { path, line } => {
+                let argv =
+                    core::editor::editor_command(&core::editor::resolve_editor(), &path, line);
+                match core::program::command(&argv[0]).args(&argv[1..]).status() {
+                    // Pick up whatever was edited
+                    Ok(_) => app.reload(),
+                    Err(e) => {
+                        app.status_message =
+                            Some(format!("Failed to open editor '{}': {}", argv[0], e));
+                    }
+                }
+                terminal = screen.init();
+            }
+            app::Action::PrintEnv { env_files, env } => {
+                let mut env_vars = core::env_files::load_env_files(&env_files).unwrap_or_default();
+                env_vars.extend(env);
+                print!("{}", core::env_files::format_exports(&env_vars));
+                return Ok(());
+            }
+            app::Action::PrintCommand { command } => {
+                println!("{}", command);
+                return Ok(());
+            }
+            app::Action::PrintScript { script_name } => {
+                println!("{}", script_name);
+                return Ok(());
+            }
+            // Scripts run in the pane count as having run something
+            app::Action::Quit | app::Action::Continue if app.jobs.is_empty() => {
+                process::exit(cancel_code)
+            }
+            app::Action::Quit | app::Action::Continue => return Ok(()),
         }
-    };
+    }
+}
+
+/// Runs a script named on the command line without the TUI, recording it in recents.
+/// Returns the exit code to exit with.
+fn run_direct(
+    script_name: &str,
+    script_args: &str,
+    scripts: &indexmap::IndexMap<String, String>,
+    cwd: &std::path::Path,
+    project_dir: &std::path::Path,
+    package_manager: core::package_manager::PackageManager,
+    config: &store::config::Config,
+) -> i32 {
+    if !scripts.contains_key(script_name) {
+        eprintln!(
+            "❌ No script named '{}' in {}/package.json",
+            script_name,
+            cwd.display()
+        );
+        let names: Vec<&String> = scripts.keys().collect();
+        let suggestions: Vec<&str> = nr::fuzzy::fuzzy_filter(&names, script_name, |s| s.as_str())
+            .into_iter()
+            .take(3)
+            .map(|i| names[i].as_str())
+            .collect();
+        if !suggestions.is_empty() {
+            eprintln!();
+            eprintln!("💡 Did you mean: {}?", suggestions.join(", "));
+        }
+        return core::exit_code::NO_SCRIPT;
+    }
 
-    // 5. Restore terminal
-    ratatui::restore();
+    let run = core::queue::QueuedRun {
+        key: format!("root:{}", script_name),
+        script_name: script_name.to_string(),
+        cwd: cwd.to_path_buf(),
+        env_files: vec![],
+        args: script_args.to_string(),
+        env: Vec::new(),
+    };
+    record_run(project_dir, &run);
 
-    // 6. Execute script (after TUI cleanup)
-    if let app::Action::RunScript {
+    let started = Instant::now();
+    let exit_code = execute(
+        package_manager,
         script_name,
         cwd,
-        env_files,
-        args,
-    } = action
-    {
-        store::favorites::save_favorites(&project_dir, &app.favorites);
-        store::recents::save_recents(&project_dir, &app.recents);
-
-        let exit_code = if env_files.is_empty() && args.is_empty() {
+        None,
+        &[],
+        &[],
+        script_args,
+        config,
+    );
+    record_outcome(project_dir, &run, exit_code, started.elapsed());
+    exit_code
+}
+
+/// `nr --bench <script>`: times the script with each installed package manager,
+/// prints the comparison and saves it for the script preview.
+/// Returns the exit code to exit with.
+fn run_bench(
+    script_name: &str,
+    scripts: &indexmap::IndexMap<String, String>,
+    cwd: &std::path::Path,
+    project_dir: &std::path::Path,
+    detected: core::package_manager::PackageManager,
+    config: &store::config::Config,
+) -> i32 {
+    if !scripts.contains_key(script_name) {
+        eprintln!(
+            "❌ No script named '{}' in {}/package.json",
+            script_name,
+            cwd.display()
+        );
+        return core::exit_code::NO_SCRIPT;
+    }
+
+    let managers = core::bench::installed_managers(detected);
+    if managers.len() < 2 {
+        eprintln!(
+            "⚠️  Only {} is installed; timing it without a comparison",
+            detected
+        );
+    }
+
+    let mut timings = Vec::new();
+    for pm in managers {
+        eprintln!(
+            "▶ {} with {} (1 cold + {} warm runs, output hidden)",
+            script_name,
+            pm,
+            core::bench::WARM_RUNS
+        );
+        timings.push(core::bench::bench(
+            pm,
+            script_name,
+            cwd,
+            config.run_template(pm),
+            &config.inherit_env,
+        ));
+    }
+    print!("{}", core::bench::format_comparison(script_name, &timings));
+
+    let mut results = store::bench::load_bench(project_dir);
+    results.insert(
+        format!("root:{}", script_name),
+        timings
+            .iter()
+            .map(store::bench::BenchRecord::from)
+            .collect(),
+    );
+    store::bench::save_bench(project_dir, &results);
+
+    i32::from(timings.iter().any(|t| t.exit_code != 0))
+}
+
+/// `nr --last`: runs the project's most recent run again, with the args and
+/// env files it used, without the TUI. Returns the exit code to exit with.
+fn run_last(
+    project_dir: &std::path::Path,
+    pm_root: &std::path::Path,
+    package_manager: core::package_manager::PackageManager,
+    config: &store::config::Config,
+) -> i32 {
+    let Some(run) = store::history::load_history(project_dir)
+        .first()
+        .map(store::history::HistoryEntry::to_run)
+    else {
+        eprintln!("❌ Nothing has been run in this project yet");
+        return core::exit_code::NO_SCRIPT;
+    };
+
+    if run.args.is_empty() {
+        eprintln!("▶ {}", run.key);
+    } else {
+        eprintln!("▶ {} {}", run.key, run.args);
+    }
+    record_run(project_dir, &run);
+
+    let started = Instant::now();
+    let exit_code = execute(
+        core::package_manager::detect_package_manager_in(&run.cwd, pm_root, package_manager),
+        &run.script_name,
+        &run.cwd,
+        None,
+        &run.env_files,
+        &run.env,
+        &run.args,
+        config,
+    );
+    record_outcome(project_dir, &run, exit_code, started.elapsed());
+    exit_code
+}
+
+/// Records a run started outside the TUI in recents, history and next runs,
+/// and the command it runs for the changed badge.
+fn record_run(project_dir: &std::path::Path, run: &core::queue::QueuedRun) {
+    if let Some(command) = core::scripts::load_scripts(&run.cwd).get(&run.script_name) {
+        let mut ran = store::ran_commands::load_ran_commands(project_dir);
+        store::ran_commands::record_command(&mut ran, &run.key, command);
+        store::ran_commands::save_ran_commands(project_dir, &ran);
+    }
+    let mut recents = store::recents::load_recents(project_dir);
+    store::recents::record_execution(&mut recents, &run.key);
+    store::recents::save_recents(project_dir, &recents);
+    let mut history = store::history::load_history(project_dir);
+    let mut next_runs = store::next_runs::load_next_runs(project_dir);
+    store::next_runs::record_transition(&mut next_runs, &history, &run.key);
+    store::next_runs::save_next_runs(project_dir, &next_runs);
+    store::history::record_run(&mut history, run);
+    store::history::save_history(project_dir, &history);
+}
+
+/// Stores how a run started outside the TUI ended on its History entry.
+fn record_outcome(
+    project_dir: &std::path::Path,
+    run: &core::queue::QueuedRun,
+    exit_code: i32,
+    duration: Duration,
+) {
+    let mut history = store::history::load_history(project_dir);
+    store::history::record_outcome(
+        &mut history,
+        &run.script_name,
+        &run.cwd,
+        &run.args,
+        &run.env_files,
+        exit_code,
+        duration,
+    );
+    store::history::save_history(project_dir, &history);
+}
+
+/// Non-TUI selection: a numbered prompt on stderr when stdin is a terminal,
+/// otherwise just the script list on stdout. Returns the exit code to exit with,
+/// `cancel_code` when no script is chosen.
+fn run_plain_picker(
+    scripts: &indexmap::IndexMap<String, String>,
+    cwd: &std::path::Path,
+    project_dir: &std::path::Path,
+    package_manager: core::package_manager::PackageManager,
+    config: &store::config::Config,
+    cancel_code: i32,
+) -> i32 {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        // Nobody to ask; print what could be run
+        print!("{}", core::listing::format_plain(scripts, &[]));
+        return 0;
+    }
+
+    eprint!("{}", core::plain_prompt::format_menu(scripts));
+    eprint!("Run which script? (number or name, empty to cancel) ");
+    let mut answer = String::new();
+    if stdin.lock().read_line(&mut answer).is_err() || answer.trim().is_empty() {
+        return cancel_code;
+    }
+
+    match core::plain_prompt::parse_choice(&answer, scripts) {
+        Some(script_name) => run_direct(
+            script_name,
+            "",
+            scripts,
+            cwd,
+            project_dir,
+            package_manager,
+            config,
+        ),
+        None => {
+            eprintln!("❌ No script '{}'", answer.trim());
+            core::exit_code::NO_SCRIPT
+        }
+    }
+}
+
+/// Runs the `--picker` UI in `height` rows on stderr, keeping stdout free for
+/// the result. Returns the chosen script, or `None` when cancelled.
+fn run_picker(mut app: app::App, height: u16) -> Result<Option<String>> {
+    crossterm::terminal::enable_raw_mode()?;
+    let mut terminal = ratatui::Terminal::with_options(
+        ratatui::backend::CrosstermBackend::new(std::io::stderr()),
+        ratatui::TerminalOptions {
+            viewport: ratatui::Viewport::Inline(height),
+        },
+    )?;
+
+    crossterm::execute!(std::io::stderr(), crossterm::event::EnableMouseCapture)?;
+
+    let picked = loop {
+        terminal.draw(|frame| app.render(frame))?;
+        let action = match crossterm::event::read()? {
+            crossterm::event::Event::Key(key)
+                if key.kind == crossterm::event::KeyEventKind::Press =>
+            {
+                app.handle_key(key)
+            }
+            crossterm::event::Event::Mouse(mouse) => app.handle_mouse(mouse, Instant::now()),
+            _ => continue,
+        };
+        match action {
+            app::Action::PrintScript { script_name } => break Some(script_name),
+            app::Action::Quit => break None,
+            _ => {}
+        }
+    };
+
+    terminal.clear().ok();
+    crossterm::execute!(std::io::stderr(), crossterm::event::DisableMouseCapture).ok();
+    crossterm::terminal::disable_raw_mode().ok();
+    Ok(picked)
+}
+
+/// Runs one script attached to the terminal: in `cwd`, or from the monorepo
+/// root when `workspace` is given (the `run_from_root` config), then its
+/// post-run hooks, using the config's run template and environment filter.
+/// The env files are loaded with the env overrides on top, and any
+/// `required_env` keys the run would be missing are asked for first.
+/// Returns the script's exit code.
+#[allow(clippy::too_many_arguments)]
+fn execute(
+    package_manager: core::package_manager::PackageManager,
+    script_name: &str,
+    cwd: &std::path::Path,
+    workspace: Option<&core::runner::WorkspaceTarget>,
+    env_files: &[std::path::PathBuf],
+    env: &[(String, String)],
+    args: &str,
+    config: &store::config::Config,
+) -> i32 {
+    let node_template = core::runner::node_template(
+        cwd,
+        config.node_version,
+        config.run_template(package_manager),
+    );
+    let run_template = node_template
+        .as_deref()
+        .or(config.run_template(package_manager));
+
+    // Load and merge env files, then the overrides on top
+    let mut env_vars = if env_files.is_empty() {
+        std::collections::HashMap::new()
+    } else {
+        core::env_files::load_env_files(env_files).unwrap_or_default()
+    };
+    env_vars.extend(env.iter().cloned());
+    let missing = core::required_env::missing_env(
+        &config.required_env_for(script_name),
+        &env_vars,
+        &config.inherit_env,
+    );
+    if !missing.is_empty() {
+        match core::required_env::prompt_missing(script_name, &missing) {
+            Some(values) => env_vars.extend(values),
+            None => {
+                eprintln!(
+                    "❌ {} requires {} (see required_env in the nr config)",
+                    script_name,
+                    missing.join(", ")
+                );
+                return core::exit_code::CANCELLED;
+            }
+        }
+    }
+
+    let strategy = core::runner::ExecutionStrategy {
+        corepack: core::runner::use_corepack(package_manager, cwd, config.corepack),
+        wrapper: config.run_through.clone(),
+    };
+
+    let hooks = core::hooks::ScriptHooks::load(cwd, script_name);
+    let hook_env = env_vars.clone();
+
+    let started = Instant::now();
+    let run = || {
+        if let Some(target) = workspace {
+            core::runner::run_in_workspace(
+                package_manager,
+                script_name,
+                target,
+                env_vars,
+                args,
+                run_template,
+                &strategy,
+                &config.inherit_env,
+            )
+        } else if env_vars.is_empty()
+            && args.is_empty()
+            && strategy == core::runner::ExecutionStrategy::default()
+        {
             // Fast path: no configuration
-            core::runner::run_script(package_manager, &script_name, &cwd)
+            core::runner::run_script(
+                package_manager,
+                script_name,
+                cwd,
+                run_template,
+                &config.inherit_env,
+            )
         } else {
-            // Load and merge env files
-            let env_vars = core::env_files::load_env_files(&env_files).unwrap_or_default();
             core::runner::run_script_with_config(
                 package_manager,
-                &script_name,
-                &cwd,
+                script_name,
+                cwd,
                 env_vars,
-                &args,
+                args,
+                run_template,
+                &strategy,
+                &config.inherit_env,
             )
-        };
-
-        process::exit(exit_code);
-    }
+        }
+    };
+    let exit_code = core::hooks::run_with_hooks(
+        &hooks,
+        script_name,
+        cwd,
+        &hook_env,
+        &config.inherit_env,
+        run,
+    );
+    core::notify::notify_finished(&config.notify, script_name, exit_code, started.elapsed());
 
-    Ok(())
+    core::hooks::run_post_hooks(
+        &config.post_run,
+        package_manager,
+        script_name,
+        cwd,
+        exit_code,
+        run_template,
+        &config.inherit_env,
+    );
+    exit_code
 }
 
 fn handle_reset(
@@ -166,65 +1087,158 @@ fn handle_reset(
     reset_recents: bool,
     reset_configs: bool,
 ) -> Result<()> {
-    let favorites_path = project_dir.join("favorites.json");
-    let recents_path = project_dir.join("recents.json");
-    let script_configs_path = project_dir.join("script_configs.json");
-    let args_history_path = project_dir.join("args_history.json");
+    use store::reset::{ResetTarget, reset};
 
-    let clear_favorites = reset_all || reset_favorites;
-    let clear_recents = reset_all || reset_recents;
-    let clear_configs = reset_all || reset_configs;
+    let targets = [
+        (ResetTarget::Favorites, reset_all || reset_favorites),
+        (ResetTarget::Recents, reset_all || reset_recents),
+        (ResetTarget::Configs, reset_all || reset_configs),
+    ];
 
     let mut cleared = Vec::new();
-
-    if clear_favorites {
-        if favorites_path.exists() {
-            std::fs::remove_file(&favorites_path).context("Failed to remove favorites.json")?;
-            cleared.push("favorites");
-        } else {
-            cleared.push("favorites (already empty)");
+    for (target, wanted) in targets {
+        if wanted {
+            cleared.extend(reset(project_dir, target)?);
         }
     }
 
-    if clear_recents {
-        if recents_path.exists() {
-            std::fs::remove_file(&recents_path).context("Failed to remove recents.json")?;
-            cleared.push("recents");
-        } else {
-            cleared.push("recents (already empty)");
-        }
+    if cleared.is_empty() {
+        println!("Nothing to reset.");
+    } else {
+        println!("Reset complete: {}", cleared.join(", "));
     }
 
-    if clear_configs {
-        if script_configs_path.exists() {
-            std::fs::remove_file(&script_configs_path)
-                .context("Failed to remove script_configs.json")?;
-            cleared.push("script configs");
-        } else {
-            cleared.push("script configs (already empty)");
+    Ok(())
+}
+
+/// Workspace packages for the TUI: the saved index if there is one, and a
+/// background scan that replaces it once done. Without an index (`None`),
+/// the scan also reports each package as it finds it. No scan runs while
+/// the manifests the index was scanned from are unchanged.
+fn load_workspace_packages(
+    monorepo_root: &Path,
+    project_dir: &Path,
+    exclude: &[String],
+) -> (
+    Option<Vec<core::workspaces::WorkspacePackage>>,
+    Option<mpsc::Receiver<core::workspaces::ScanUpdate>>,
+) {
+    use store::workspace_cache::WorkspaceStamp;
+
+    let indexed = store::workspace_index::load_workspace_index(project_dir);
+    if let Some(ref packages) = indexed {
+        let stamp = WorkspaceStamp::current(monorepo_root, packages, exclude);
+        if stamp.is_some() && store::workspace_cache::load_workspace_cache(project_dir) == stamp {
+            return (indexed, None);
         }
+    }
+    let rx = store::workspace_index::scan_in_background(
+        monorepo_root,
+        project_dir,
+        exclude,
+        indexed.is_none(),
+    );
+    (indexed, Some(rx))
+}
 
-        if args_history_path.exists() {
-            std::fs::remove_file(&args_history_path)
-                .context("Failed to remove args_history.json")?;
-            cleared.push("args history");
+/// Splits `args` at its first positional argument (the script name, or a
+/// subcommand), skipping the values of [`VALUE_FLAGS`], and returns that
+/// argument and everything after it untouched.
+fn split_script_argv(args: &mut Vec<String>) -> Vec<String> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
         } else {
-            cleared.push("args history (already empty)");
+            return args.split_off(i);
         }
     }
+    Vec::new()
+}
 
-    if cleared.is_empty() {
-        println!("Nothing to reset.");
-    } else {
-        println!("Reset complete: {}", cleared.join(", "));
+/// Removes `<flag> <value>` / `<flag>=<value>` (e.g. `--root <path>`) from
+/// `args`, returning the value.
+fn take_value_arg(args: &mut Vec<String>, flag: &str) -> Result<Option<String>> {
+    let prefix = format!("{}=", flag);
+    let Some(i) = args
+        .iter()
+        .position(|a| a == flag || a.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+
+    let arg = args.remove(i);
+    let value = match arg.strip_prefix(&prefix) {
+        Some(value) => value.to_string(),
+        None if i < args.len() => args.remove(i),
+        None => anyhow::bail!("{} requires a value", flag),
+    };
+    Ok(Some(value))
+}
+
+/// Where the TUI draws.
+#[derive(Clone, Copy)]
+enum Screen {
+    /// The whole alternate screen (default).
+    Fullscreen,
+    /// A fixed number of rows below the prompt, fzf-style, leaving the scrollback visible.
+    Inline(u16),
+}
+
+impl Screen {
+    fn init(self) -> ratatui::DefaultTerminal {
+        let terminal = match self {
+            Screen::Fullscreen => ratatui::init(),
+            Screen::Inline(height) => ratatui::init_with_options(ratatui::TerminalOptions {
+                viewport: ratatui::Viewport::Inline(height),
+            }),
+        };
+        crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture).ok();
+        // Lets terminals speaking the kitty keyboard protocol report
+        // modified keys such as Shift+Enter (quick run)
+        if crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false) {
+            crossterm::execute!(
+                std::io::stdout(),
+                crossterm::event::PushKeyboardEnhancementFlags(
+                    crossterm::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                )
+            )
+            .ok();
+        }
+        terminal
     }
 
-    Ok(())
+    /// Hands the terminal back to the shell. Inline mode erases the picker so
+    /// script output starts where it was drawn.
+    fn restore(self, terminal: &mut ratatui::DefaultTerminal) {
+        crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture).ok();
+        crossterm::execute!(
+            std::io::stdout(),
+            crossterm::event::PopKeyboardEnhancementFlags
+        )
+        .ok();
+        match self {
+            Screen::Fullscreen => ratatui::restore(),
+            Screen::Inline(_) => {
+                terminal.clear().ok();
+                crossterm::terminal::disable_raw_mode().ok();
+            }
+        }
+    }
 }
 
 fn install_panic_hook() {
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
+        crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture).ok();
+        crossterm::execute!(
+            std::io::stdout(),
+            crossterm::event::PopKeyboardEnhancementFlags
+        )
+        .ok();
         ratatui::restore();
         original_hook(panic_info);
     }));
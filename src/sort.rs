@@ -1,25 +1,170 @@
-use crate::fuzzy::fuzzy_filter;
+use crate::core::tasks::TaskRunner;
+use crate::core::workspaces::WorkspacePackage;
+use crate::fuzzy::{PrefixedQuery, fuzzy_filter};
+use crate::store::bench::BenchRecord;
+use crate::store::favorites::package_key;
 use crate::store::recents::{self, RecentEntry};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SortableScript {
     pub key: String,
     pub name: String,
     pub command: String,
+    /// Sibling scripts this command runs (`npm run <x>`) that don't exist.
+    pub missing_refs: Vec<String>,
+    /// Lowercase tags from `package.json` (`nr.tags`) and the TUI, matched by `#tag` queries.
+    pub tags: Vec<String>,
+    /// From `package.json` conventions (`scripts-info`, `ntl.descriptions`, `"//name"` keys).
+    pub description: Option<String>,
+    /// The command differs from the one it last ran with from nr.
+    pub changed: bool,
+    /// Timings per package manager from the latest `nr --bench` of this script.
+    pub bench: Vec<BenchRecord>,
+    /// How long its latest finished run took (from the run history).
+    pub last_duration: Option<Duration>,
+    /// The runner of a justfile / Makefile task; `None` for package scripts.
+    pub runner: Option<TaskRunner>,
+    /// Path (relative to the monorepo root) of the workspace package the
+    /// script is from, in the list of every package's scripts.
+    pub package: Option<String>,
+}
+
+impl SortableScript {
+    /// What the list shows and searches match: the name, or `package:name`
+    /// (the key) in the list of every package's scripts.
+    pub fn label(&self) -> &str {
+        if self.package.is_some() {
+            &self.key
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// Order of a script list while no text is searched for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    /// Favorites, then the most frequently and recently run, then by name.
+    #[default]
+    Frecency,
+    /// Alphabetically by name.
+    Name,
+    /// As listed in `package.json`.
+    Declared,
+}
+
+impl SortMode {
+    /// The mode after this one when cycling through them.
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Frecency => SortMode::Name,
+            SortMode::Name => SortMode::Declared,
+            SortMode::Declared => SortMode::Frecency,
+        }
+    }
+
+    /// How the list is ordered, for status messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Frecency => "favorites and recent use",
+            SortMode::Name => "name",
+            SortMode::Declared => "package.json order",
+        }
+    }
 }
 
 /// Returns indices into the original `scripts` slice, in display order.
+///
+/// See `ScriptQuery` for the query syntax. Free text is fuzzy-matched against
+/// script names, then commands: scripts matching only by command are listed
+/// after every name match.
 pub fn sort_scripts(
     scripts: &[SortableScript],
     favorites: &HashSet<String>,
     recents: &[RecentEntry],
     query: &str,
 ) -> Vec<usize> {
-    if query.is_empty() {
-        sort_scripts_no_query(scripts, favorites, recents)
+    sort_scripts_by(scripts, favorites, recents, query, SortMode::Frecency)
+}
+
+/// [`sort_scripts`] with the list ordered by `mode` when no text is searched
+/// for; search results are always in relevance order.
+pub fn sort_scripts_by(
+    scripts: &[SortableScript],
+    favorites: &HashSet<String>,
+    recents: &[RecentEntry],
+    query: &str,
+    mode: SortMode,
+) -> Vec<usize> {
+    let query = ScriptQuery::parse(query);
+    let mut indices = if !query.text.is_empty() || !query.command.is_empty() {
+        sort_scripts_with_query(scripts, favorites, recents, &query.text, &query.command)
     } else {
-        sort_scripts_with_query(scripts, favorites, recents, query)
+        match mode {
+            SortMode::Frecency => sort_scripts_no_query(scripts, favorites, recents),
+            SortMode::Name => {
+                let mut indices: Vec<usize> = (0..scripts.len()).collect();
+                indices.sort_by(|&a, &b| scripts[a].label().cmp(scripts[b].label()));
+                indices
+            }
+            SortMode::Declared => (0..scripts.len()).collect(),
+        }
+    };
+    if !query.tags.is_empty() {
+        indices.retain(|&i| query.tags.iter().all(|tag| scripts[i].tags.contains(tag)));
+    }
+    if query.favorites_only {
+        indices.retain(|&i| favorites.contains(&scripts[i].key));
+    }
+    indices
+}
+
+/// A script list search: `#ci` keeps scripts tagged `ci`, `fav:` keeps
+/// favorites, `cmd:eslint` fuzzy-matches commands only, and the remaining
+/// words match names, then commands. Filters being typed (a lone `#`, or
+/// `cmd:` without a value) filter nothing yet.
+#[derive(Debug, Default, PartialEq)]
+pub struct ScriptQuery {
+    /// Lowercase tags the script must all carry.
+    pub tags: Vec<String>,
+    pub favorites_only: bool,
+    /// Matched against commands only.
+    pub command: String,
+    /// Matched against names, then commands.
+    pub text: String,
+}
+
+impl ScriptQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut tags = Vec::new();
+        let mut rest = Vec::new();
+        for word in query.split_whitespace() {
+            match word.strip_prefix('#') {
+                Some("") => {}
+                Some(tag) => tags.push(tag.to_lowercase()),
+                None => rest.push(word),
+            }
+        }
+
+        let prefixed = PrefixedQuery::parse(&rest.join(" "), &["cmd", "fav"]);
+        // `fav:build` is `fav:` plus `build`
+        let mut text = prefixed.filter("fav");
+        if !prefixed.text.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&prefixed.text);
+        }
+        ScriptQuery {
+            tags,
+            favorites_only: prefixed.has("fav"),
+            command: prefixed.filter("cmd"),
+            text,
+        }
     }
 }
 
@@ -53,7 +198,7 @@ fn sort_scripts_no_query(
             (false, true) => return std::cmp::Ordering::Greater,
             (true, true) => {
                 // Both favorites: alphabetical by name
-                return script_a.name.cmp(&script_b.name);
+                return script_a.label().cmp(script_b.label());
             }
             (false, false) => {}
         }
@@ -74,20 +219,101 @@ fn sort_scripts_no_query(
         }
 
         // Finally alphabetical by name
-        script_a.name.cmp(&script_b.name)
+        script_a.label().cmp(script_b.label())
     });
 
     indices
 }
 
+/// Orders `indices` into `packages`: starred packages first, then the ones
+/// whose scripts run most often and recently; ties keep the workspace order.
+pub fn sort_packages(
+    indices: &mut [usize],
+    packages: &[WorkspacePackage],
+    favorites: &HashSet<String>,
+    recents: &[RecentEntry],
+) {
+    let now = recents::now_ms();
+    let rank = |i: usize| {
+        let key = package_key(&packages[i].name);
+        let score = recents
+            .iter()
+            .find(|entry| entry.key == key)
+            .map_or(0.0, |entry| {
+                recents::frecency_score(entry.count, entry.last_run, now)
+            });
+        (!favorites.contains(&key), score)
+    };
+    indices.sort_by(|&a, &b| {
+        let (not_fav_a, score_a) = rank(a);
+        let (not_fav_b, score_b) = rank(b);
+        not_fav_a.cmp(&not_fav_b).then_with(|| {
+            score_b
+                .partial_cmp(&score_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    });
+}
+
+/// The favorite among `names` (scripts of `scope`) that runs most often and
+/// recently, falling back to the alphabetically first one.
+pub fn top_favorite<'a>(
+    scope: &str,
+    names: impl IntoIterator<Item = &'a String>,
+    favorites: &HashSet<String>,
+    recents: &[RecentEntry],
+) -> Option<&'a String> {
+    let now = recents::now_ms();
+    let score = |name: &str| {
+        let key = format!("{}:{}", scope, name);
+        recents
+            .iter()
+            .find(|entry| entry.key == key)
+            .map_or(0.0, |entry| {
+                recents::frecency_score(entry.count, entry.last_run, now)
+            })
+    };
+
+    names
+        .into_iter()
+        .filter(|name| favorites.contains(&format!("{}:{}", scope, name)))
+        .map(|name| (score(name), name))
+        .max_by(|(score_a, a), (score_b, b)| {
+            score_a
+                .partial_cmp(score_b)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.cmp(a))
+        })
+        .map(|(_, name)| name)
+}
+
+/// `text` matches names, then commands; `command` only commands. Either may be empty.
 fn sort_scripts_with_query(
     scripts: &[SortableScript],
     favorites: &HashSet<String>,
     recents: &[RecentEntry],
-    query: &str,
+    text: &str,
+    command: &str,
 ) -> Vec<usize> {
-    // Get fuzzy-matched indices in relevance order
-    let matched = fuzzy_filter(scripts, query, |s| &s.name);
+    // Get fuzzy-matched indices in relevance order: names, then commands
+    let (mut name_matched, mut command_matched) = if text.is_empty() {
+        (Vec::new(), fuzzy_filter(scripts, command, |s| &s.command))
+    } else {
+        let name_matched = fuzzy_filter(scripts, text, SortableScript::label);
+        let by_name: HashSet<usize> = name_matched.iter().copied().collect();
+        let command_matched: Vec<usize> = fuzzy_filter(scripts, text, |s| &s.command)
+            .into_iter()
+            .filter(|i| !by_name.contains(i))
+            .collect();
+        (name_matched, command_matched)
+    };
+    if !text.is_empty() && !command.is_empty() {
+        let by_command: HashSet<usize> = fuzzy_filter(scripts, command, |s| &s.command)
+            .into_iter()
+            .collect();
+        name_matched.retain(|i| by_command.contains(i));
+        command_matched.retain(|i| by_command.contains(i));
+    }
 
     // Build recent scores map
     let now = recents::now_ms();
@@ -100,8 +326,7 @@ fn sort_scripts_with_query(
     }
 
     // Stable sort by: relevance (already done by fuzzy_filter), then favorite, then recent
-    let mut indices = matched;
-    indices.sort_by(|&a, &b| {
+    let by_preference = |&a: &usize, &b: &usize| {
         let script_a = &scripts[a];
         let script_b = &scripts[b];
 
@@ -128,8 +353,13 @@ fn sort_scripts_with_query(
         score_b
             .partial_cmp(&score_a)
             .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    };
 
+    let mut indices = name_matched;
+    indices.sort_by(by_preference);
+    let mut by_command = command_matched;
+    by_command.sort_by(by_preference);
+    indices.extend(by_command);
     indices
 }
 
@@ -141,7 +371,8 @@ mod tests {
         SortableScript {
             key: key.to_string(),
             name: name.to_string(),
-            command: "echo test".to_string(),
+            command: "echo".to_string(),
+            ..Default::default()
         }
     }
 
@@ -174,6 +405,23 @@ mod tests {
         assert_eq!(result[0], 1);
     }
 
+    #[test]
+    fn test_sort_modes_order_the_list_without_a_query() {
+        let scripts = vec![
+            make_script("test", "test"),
+            make_script("build", "build"),
+            make_script("dev", "dev"),
+        ];
+        let favorites = HashSet::from(["dev".to_string()]);
+        let sort = |query: &str, mode| sort_scripts_by(&scripts, &favorites, &[], query, mode);
+
+        assert_eq!(sort("", SortMode::Frecency), vec![2, 1, 0]);
+        assert_eq!(sort("", SortMode::Name), vec![1, 2, 0]);
+        assert_eq!(sort("", SortMode::Declared), vec![0, 1, 2]);
+        // Search results stay in relevance order
+        assert_eq!(sort("tes", SortMode::Declared), vec![0]);
+    }
+
     #[test]
     fn test_no_query_favorites_alphabetical() {
         let scripts = vec![
@@ -337,4 +585,114 @@ mod tests {
         let result = sort_scripts(&scripts, &favorites, &recents, "zzz");
         assert_eq!(result, Vec::<usize>::new());
     }
+
+    #[test]
+    fn test_tag_filter_combines_with_fuzzy_query() {
+        let mut build = make_script("build", "build");
+        build.tags = vec!["ci".to_string()];
+        let mut test = make_script("test", "test");
+        test.tags = vec!["ci".to_string(), "slow".to_string()];
+        let scripts = vec![build, test, make_script("dev", "dev")];
+        let favorites = HashSet::new();
+
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "#ci"), vec![0, 1]);
+        assert_eq!(
+            sort_scripts(&scripts, &favorites, &[], "#CI #slow"),
+            vec![1]
+        );
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "#ci bui"), vec![0]);
+        // A bare `#` doesn't hide anything while the tag is being typed
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "#").len(), 3);
+    }
+
+    #[test]
+    fn test_query_matches_commands_after_names() {
+        let mut unit = make_script("unit", "unit");
+        unit.command = "vitest run".to_string();
+        let mut e2e = make_script("e2e", "e2e");
+        e2e.command = "playwright test".to_string();
+        let scripts = vec![unit, make_script("vitest:ui", "vitest:ui"), e2e];
+        let mut favorites = HashSet::new();
+        favorites.insert("unit".to_string());
+
+        let result = sort_scripts(&scripts, &favorites, &[], "vitest");
+
+        // The name match comes first even though `unit` is a favorite
+        assert_eq!(result, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_cmd_prefix_matches_commands_only() {
+        let mut lint = make_script("lint", "lint");
+        lint.command = "eslint .".to_string();
+        let mut fix = make_script("fix", "fix");
+        fix.command = "eslint --fix .".to_string();
+        let mut eslint_check = make_script("eslint:check", "eslint:check");
+        eslint_check.command = "node check.js".to_string();
+        let scripts = vec![lint, fix, eslint_check];
+        let favorites = HashSet::new();
+
+        let mut result = sort_scripts(&scripts, &favorites, &[], "cmd:eslint");
+        result.sort();
+        assert_eq!(result, vec![0, 1]);
+        assert_eq!(
+            sort_scripts(&scripts, &favorites, &[], "cmd:eslint fi"),
+            vec![1]
+        );
+        // `cmd:` without a value is still being typed
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "cmd:").len(), 3);
+    }
+
+    #[test]
+    fn test_fav_prefix_keeps_favorites() {
+        let scripts = vec![
+            make_script("build", "build"),
+            make_script("build:prod", "build:prod"),
+            make_script("test", "test"),
+        ];
+        let favorites: HashSet<String> = ["build:prod", "test"].map(String::from).into();
+
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "fav:"), vec![1, 2]);
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "fav:bui"), vec![1]);
+        assert_eq!(sort_scripts(&scripts, &favorites, &[], "bui fav:"), vec![1]);
+    }
+
+    #[test]
+    fn test_script_query_parse() {
+        let query = ScriptQuery::parse("#CI cmd:vitest fav: unit");
+
+        assert_eq!(
+            query,
+            ScriptQuery {
+                tags: vec!["ci".to_string()],
+                favorites_only: true,
+                command: "vitest".to_string(),
+                text: "unit".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_top_favorite_prefers_frecent_then_alphabetical() {
+        let names = ["build", "dev", "lint", "test"].map(String::from);
+        let favorites: HashSet<String> = ["web:dev", "web:lint", "web:test", "api:build"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            top_favorite("web", &names, &favorites, &[]).map(String::as_str),
+            Some("dev")
+        );
+
+        let recents = vec![
+            make_recent("web:test", 5, 10),
+            make_recent("web:build", 50, 10),
+        ];
+        assert_eq!(
+            top_favorite("web", &names, &favorites, &recents).map(String::as_str),
+            Some("test")
+        );
+        assert_eq!(top_favorite("docs", &names, &favorites, &recents), None);
+    }
 }
@@ -1,13 +1,18 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
 const MAX_HISTORY_ENTRIES: usize = 20;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
 pub struct ArgsHistory {
+    /// Arguments of every script, the fallback for scripts with few of their own.
     pub entries: Vec<String>,
+    /// Arguments per script, keyed like the script configs, most recent first.
+    pub by_script: HashMap<String, Vec<String>>,
 }
 
 impl ArgsHistory {
@@ -15,30 +20,52 @@ impl ArgsHistory {
         Self::default()
     }
 
-    /// Adds an entry to the history, removing duplicates and capping at MAX_HISTORY_ENTRIES.
+    /// Adds an entry to `script_key`'s history and to the global one, removing
+    /// duplicates and capping each at MAX_HISTORY_ENTRIES.
     /// The most recent entry appears first in the list.
-    pub fn add_entry(&mut self, entry: String) {
+    pub fn add_entry(&mut self, script_key: &str, entry: String) {
         // Skip empty entries
         if entry.trim().is_empty() {
             return;
         }
 
-        // Remove existing duplicate if present
-        self.entries.retain(|e| e != &entry);
-
-        // Insert at the beginning (most recent first)
-        self.entries.insert(0, entry);
-
-        // Cap at max size
-        if self.entries.len() > MAX_HISTORY_ENTRIES {
-            self.entries.truncate(MAX_HISTORY_ENTRIES);
-        }
+        push_entry(
+            self.by_script.entry(script_key.to_string()).or_default(),
+            entry.clone(),
+        );
+        push_entry(&mut self.entries, entry);
     }
 
-    /// Returns the entries in order (most recent first)
+    /// Returns the global entries in order (most recent first)
     pub fn get_entries(&self) -> &[String] {
         &self.entries
     }
+
+    /// What ↑↓ browses for `script_key`: its own entries, most recent first,
+    /// then the other scripts' entries. Returns the list and how many of its
+    /// entries are the script's own.
+    pub fn entries_for(&self, script_key: &str) -> (Vec<String>, usize) {
+        let mut entries = self.by_script.get(script_key).cloned().unwrap_or_default();
+        let own = entries.len();
+        for entry in &self.entries {
+            if !entries.contains(entry) {
+                entries.push(entry.clone());
+            }
+        }
+        (entries, own)
+    }
+}
+
+/// Moves `entry` to the front of `entries`, capped at MAX_HISTORY_ENTRIES.
+fn push_entry(entries: &mut Vec<String>, entry: String) {
+    // Remove existing duplicate if present
+    entries.retain(|e| e != &entry);
+
+    // Insert at the beginning (most recent first)
+    entries.insert(0, entry);
+
+    // Cap at max size
+    entries.truncate(MAX_HISTORY_ENTRIES);
 }
 
 /// Loads args history from disk.
@@ -88,9 +115,9 @@ mod tests {
     fn test_add_entry_inserts_at_beginning() {
         let mut history = ArgsHistory::new();
 
-        history.add_entry("first".to_string());
-        history.add_entry("second".to_string());
-        history.add_entry("third".to_string());
+        history.add_entry("root:test", "first".to_string());
+        history.add_entry("root:test", "second".to_string());
+        history.add_entry("root:test", "third".to_string());
 
         assert_eq!(history.entries.len(), 3);
         assert_eq!(history.entries[0], "third");
@@ -102,9 +129,9 @@ mod tests {
     fn test_add_entry_removes_duplicates() {
         let mut history = ArgsHistory::new();
 
-        history.add_entry("first".to_string());
-        history.add_entry("second".to_string());
-        history.add_entry("first".to_string()); // Duplicate
+        history.add_entry("root:test", "first".to_string());
+        history.add_entry("root:test", "second".to_string());
+        history.add_entry("root:test", "first".to_string()); // Duplicate
 
         assert_eq!(history.entries.len(), 2);
         assert_eq!(history.entries[0], "first"); // Most recent
@@ -116,7 +143,7 @@ mod tests {
         let mut history = ArgsHistory::new();
 
         for i in 0..25 {
-            history.add_entry(format!("entry_{}", i));
+            history.add_entry("root:test", format!("entry_{}", i));
         }
 
         assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
@@ -128,9 +155,9 @@ mod tests {
     fn test_add_entry_skips_empty() {
         let mut history = ArgsHistory::new();
 
-        history.add_entry("".to_string());
-        history.add_entry("   ".to_string());
-        history.add_entry("valid".to_string());
+        history.add_entry("root:test", "".to_string());
+        history.add_entry("root:test", "   ".to_string());
+        history.add_entry("root:test", "valid".to_string());
 
         assert_eq!(history.entries.len(), 1);
         assert_eq!(history.entries[0], "valid");
@@ -142,8 +169,8 @@ mod tests {
         let config_dir = temp_dir.path();
 
         let mut history = ArgsHistory::new();
-        history.add_entry("-- --watch".to_string());
-        history.add_entry("-- --coverage".to_string());
+        history.add_entry("root:test", "-- --watch".to_string());
+        history.add_entry("root:test", "-- --coverage".to_string());
 
         // Save
         save_args_history(config_dir, &history).unwrap();
@@ -177,11 +204,43 @@ mod tests {
         assert!(config_dir.join("args_history.json").exists());
     }
 
+    #[test]
+    fn test_entries_for_lists_the_scripts_own_first() {
+        let mut history = ArgsHistory::new();
+        history.add_entry("root:test", "--coverage".to_string());
+        history.add_entry("root:dev", "--port 3000".to_string());
+        history.add_entry("root:test", "--watch".to_string());
+
+        assert_eq!(
+            history.entries_for("root:test"),
+            (
+                vec![
+                    "--watch".to_string(),
+                    "--coverage".to_string(),
+                    "--port 3000".to_string()
+                ],
+                2
+            )
+        );
+        // A script without its own history falls back to everyone's
+        assert_eq!(history.entries_for("root:lint").1, 0);
+        assert_eq!(history.entries_for("root:lint").0[0], "--watch");
+    }
+
+    #[test]
+    fn test_loads_the_global_list_of_older_versions() {
+        let history: ArgsHistory = serde_json::from_str(r#"{"entries": ["--watch"]}"#).unwrap();
+        assert_eq!(
+            history.entries_for("root:test"),
+            (vec!["--watch".to_string()], 0)
+        );
+    }
+
     #[test]
     fn test_get_entries() {
         let mut history = ArgsHistory::new();
-        history.add_entry("a".to_string());
-        history.add_entry("b".to_string());
+        history.add_entry("root:test", "a".to_string());
+        history.add_entry("root:test", "b".to_string());
 
         let entries = history.get_entries();
         assert_eq!(entries.len(), 2);
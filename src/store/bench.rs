@@ -0,0 +1,72 @@
+use crate::core::bench::BenchTiming;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One package manager's timing of a script from `nr --bench`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchRecord {
+    /// Package manager binary (`npm`, `pnpm`, `yarn`, `bun`).
+    pub pm: String,
+    pub cold_ms: u64,
+    /// `None` when the cold run failed.
+    pub warm_ms: Option<u64>,
+    pub exit_code: i32,
+}
+
+impl From<&BenchTiming> for BenchRecord {
+    fn from(timing: &BenchTiming) -> Self {
+        Self {
+            pm: timing.pm.command_name().to_string(),
+            cold_ms: timing.cold.as_millis() as u64,
+            warm_ms: timing.warm.map(|d| d.as_millis() as u64),
+            exit_code: timing.exit_code,
+        }
+    }
+}
+
+/// Latest benchmark of each script, keyed by script key (e.g. `root:build`).
+pub type BenchResults = HashMap<String, Vec<BenchRecord>>;
+
+/// Loads benchmark results from the config directory.
+/// Returns an empty map if the file doesn't exist or is corrupted.
+pub fn load_bench(config_dir: &Path) -> BenchResults {
+    std::fs::read_to_string(config_dir.join("bench.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves benchmark results to the config directory.
+pub fn save_bench(config_dir: &Path, results: &BenchResults) {
+    let json = serde_json::to_string_pretty(results).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(config_dir.join("bench.json"), json).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::package_manager::PackageManager;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load_bench() {
+        let temp_dir = TempDir::new().unwrap();
+        let timing = BenchTiming {
+            pm: PackageManager::Pnpm,
+            cold: Duration::from_millis(900),
+            warm: Some(Duration::from_millis(600)),
+            exit_code: 0,
+        };
+        let mut results = BenchResults::new();
+        results.insert("root:build".to_string(), vec![BenchRecord::from(&timing)]);
+
+        save_bench(temp_dir.path(), &results);
+
+        let loaded = load_bench(temp_dir.path());
+        assert_eq!(loaded, results);
+        assert_eq!(loaded["root:build"][0].pm, "pnpm");
+        assert_eq!(loaded["root:build"][0].warm_ms, Some(600));
+    }
+}
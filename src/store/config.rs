@@ -0,0 +1,502 @@
+use crate::core::hooks::PostRunHook;
+use crate::core::inherit_env::InheritEnv;
+use crate::core::node_version::NodeVersionCheck;
+use crate::core::notify::NotifyConfig;
+use crate::core::package_manager::PackageManager;
+use crate::core::project_config;
+use crate::core::runner::CorepackUse;
+use crate::core::tasks::TaskRunner;
+use crate::store::keymap::{KeyAction, KeySpec};
+use crate::store::script_configs::Preset;
+use crate::ui::theme::{ThemeConfig, deserialize_theme};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User settings read from `config.json`.
+///
+/// Settings are layered: `~/.config/nr/config.json` applies to every project,
+/// a checked-in `nr.config.json` at the project root overrides it for everyone
+/// working on the project, and `~/.config/nr/projects/{project_id}/config.json`
+/// overrides both for one user. Every field is optional so a partial file only
+/// changes what it mentions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// Invocation templates keyed by package manager binary (`npm`, `pnpm`, `yarn`, `bun`).
+    ///
+    /// e.g. `{ "pnpm": "{pm} {run} --prefer-offline", "npm": "dotenvx run -- {pm} {run}" }`
+    pub run_templates: HashMap<String, String>,
+
+    /// Hooks that run after a script exits (shell commands or other scripts).
+    pub post_run: Vec<PostRunHook>,
+
+    /// Where the TUI starts: tab and initial search query.
+    pub startup: StartupConfig,
+
+    /// Run scripts inside the TUI with a live output pane instead of handing over the terminal.
+    pub run_in_pane: bool,
+
+    /// Header line with `{placeholders}`: `{project}`, `{path}`, `{pm}`, `{packages}`,
+    /// `{scripts}`, `{jobs}` (running) and `{saved}`; `"{project}  {path}  {pm}"` when unset.
+    pub header_format: Option<String>,
+
+    /// Glob patterns (relative to the monorepo root) skipped when scanning workspaces.
+    pub workspace_exclude: Vec<String>,
+
+    /// Draw the TUI in this many rows below the prompt instead of on the alternate screen.
+    pub inline_height: Option<u16>,
+
+    /// Script list keys per action, replacing the defaults, e.g. `{ "toggle_favorite": "ctrl+f" }`.
+    pub keymap: HashMap<KeyAction, KeySpec>,
+
+    /// Colors: a preset (`dark`, `light`, `solarized`), optionally with single colors replaced.
+    #[serde(deserialize_with = "deserialize_theme")]
+    pub theme: ThemeConfig,
+
+    /// Draw ASCII stand-ins for Unicode symbols (★ ❯ ⏎ ...), like `--ascii`.
+    pub ascii: bool,
+
+    /// Start with the script list grouped by `prefix:` (toggled with Ctrl+E).
+    pub group_scripts: bool,
+
+    /// Script names (globs) left out of the lists, e.g. `["preinstall", "post*", "husky:*"]`.
+    /// Ctrl+U shows them for the session.
+    pub hide_scripts: Vec<String>,
+
+    /// Variables of nr's own environment that scripts don't inherit, e.g. `{ "deny": ["CI"] }`.
+    pub inherit_env: InheritEnv,
+
+    /// Env keys scripts need, keyed by script name (`"*"` for every script), e.g.
+    /// `{ "deploy": ["API_TOKEN"] }`. nr asks for any the run would be missing.
+    pub required_env: HashMap<String, Vec<String>>,
+
+    /// Bell (and desktop notification) when a run takes long, e.g.
+    /// `{ "after_secs": 30, "desktop": true }`.
+    pub notify: NotifyConfig,
+
+    /// Globs (relative to the script's directory) whose changes re-run a script
+    /// started in watch mode; `["src/**"]` when empty.
+    pub watch: Vec<String>,
+
+    /// Check GitHub for a newer nr once a day and mention it in the status bar.
+    pub update_check: bool,
+
+    /// Run workspace packages' scripts from the monorepo root with the package
+    /// manager's own targeting (`pnpm --filter web run dev`) instead of from
+    /// the package's directory.
+    pub run_from_root: bool,
+
+    /// What starting a script in the pane or the background does while the
+    /// same script already runs there as a job: `ask`, `allow` or `prevent`.
+    pub duplicate_jobs: DuplicateJobs,
+
+    /// Task runners whose tasks in the project root are listed after the
+    /// scripts, e.g. `["just", "make"]` for a `justfile` and a `Makefile`.
+    pub task_runners: Vec<TaskRunner>,
+
+    /// Reload the scripts when `package.json` changes on disk, like F5 does.
+    pub reload_on_change: bool,
+
+    /// Directories, relative to the script's directory and to the monorepo
+    /// root, also searched for `.env*` files, e.g. `["config/env"]`.
+    pub env_dirs: Vec<String>,
+
+    /// Env keys (globs, any case) whose values the TUI masks, e.g. `["*TOKEN*", "STRIPE_*"]`.
+    /// Tokens, secrets, passwords, credentials and `*_KEY` when unset; `[]` shows every value.
+    pub mask_env: Option<Vec<String>>,
+
+    /// What a run does when the active Node doesn't match `.nvmrc`, `.node-version`,
+    /// a Volta pin or `engines.node`: `warn`, `off`, or switch with `fnm` or `volta`.
+    pub node_version: NodeVersionCheck,
+
+    /// Run through `corepack <pm>` when the package manager on PATH isn't the
+    /// version `packageManager` declares: `ask`, `always` or `never`. Runs
+    /// inside the TUI can't ask, so there only `always` uses it.
+    pub corepack: CorepackUse,
+
+    /// A program every run goes through, so shell init (asdf, nvm, direnv)
+    /// applies: `shell` for `$SHELL -lc`, or e.g. `direnv exec .`.
+    pub run_through: Option<String>,
+
+    /// Env files (by file name, e.g. `.env.test`) checked for a script instead
+    /// of the last used ones, keyed by script name.
+    pub env_files: HashMap<String, Vec<String>>,
+
+    /// Presets offered for a script after those saved in nr, keyed by script
+    /// name, e.g. `{ "test": [{ "name": "CI", "env_files": [".env.ci"], "args": "--coverage" }] }`.
+    pub presets: HashMap<String, Vec<Preset>>,
+
+    /// Script names (globs) that only run once their name is typed in, e.g.
+    /// `["db:reset", "deploy:prod"]`.
+    pub dangerous_scripts: Vec<String>,
+}
+
+/// Initial TUI state, e.g. `{ "tab": "packages", "query": "apps/" }`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct StartupConfig {
+    pub tab: Option<StartupTab>,
+    /// Pre-filled search query for the starting tab.
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupTab {
+    Scripts,
+    Packages,
+}
+
+/// Handling of a second instance of a running job, e.g. a dev server
+/// holding its port.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateJobs {
+    /// Ask whether to show the running job or start another.
+    #[default]
+    Ask,
+    Allow,
+    /// Show the running job instead.
+    Prevent,
+}
+
+impl Config {
+    /// Returns the user-defined invocation template for `pm`, if any.
+    pub fn run_template(&self, pm: PackageManager) -> Option<&str> {
+        self.run_templates
+            .get(pm.command_name())
+            .map(String::as_str)
+            .filter(|t| !t.trim().is_empty())
+    }
+
+    /// Post-run hooks configured for `script_name`, in the order they run.
+    pub fn post_run_hooks(&self, script_name: &str) -> Vec<&PostRunHook> {
+        self.post_run
+            .iter()
+            .filter(|h| h.matches_script(script_name))
+            .collect()
+    }
+
+    /// Whether `script_name` matches `dangerous_scripts`.
+    pub fn is_dangerous(&self, script_name: &str) -> bool {
+        self.dangerous_scripts.iter().any(|pattern| {
+            globset::Glob::new(pattern)
+                .is_ok_and(|glob| glob.compile_matcher().is_match(script_name))
+        })
+    }
+
+    /// Env keys `script_name` requires: those for every script (`"*"`), then its own.
+    pub fn required_env_for(&self, script_name: &str) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for scope in ["*", script_name] {
+            for key in self.required_env.get(scope).into_iter().flatten() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+        keys
+    }
+}
+
+/// A problem found in one config file, e.g.
+/// `theme.accent: unknown color "blu"`. The key is left out of the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub file: PathBuf,
+    /// Path of the offending key (`post_run[0].when`); empty when the whole
+    /// file couldn't be used.
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.key.is_empty() {
+            write!(f, "{}: {}", self.file.display(), self.message)
+        } else {
+            write!(f, "{}: {}: {}", self.file.display(), self.key, self.message)
+        }
+    }
+}
+
+/// Loads the config `files` merged in order, each overriding the ones before
+/// it (see [`crate::core::project_config::config_files`]).
+///
+/// Missing files are treated as empty. Unreadable or invalid files, unknown
+/// keys, values of the wrong type and keys a checked-in `nr.config.json`
+/// can't set are reported as issues, and only the offending file or key is
+/// left out.
+pub fn load_config(files: &[PathBuf]) -> (Config, Vec<ConfigIssue>) {
+    let mut merged = serde_json::Value::Object(serde_json::Map::new());
+    let mut issues = Vec::new();
+
+    for path in files {
+        match read_layer(path) {
+            Ok(Some(layer)) => merge_json(&mut merged, check_layer(path, layer, &mut issues)),
+            Ok(None) => {}
+            Err(message) => issues.push(ConfigIssue {
+                file: path.clone(),
+                key: String::new(),
+                message,
+            }),
+        }
+    }
+
+    match serde_json::from_value(merged) {
+        Ok(config) => (config, issues),
+        Err(e) => {
+            // Each layer checked out on its own, so only merging them can get here
+            issues.push(ConfigIssue {
+                file: files.last().cloned().unwrap_or_default(),
+                key: String::new(),
+                message: format!("doesn't combine with the other config files: {}", e),
+            });
+            (Config::default(), issues)
+        }
+    }
+}
+
+fn read_layer(path: &Path) -> std::result::Result<Option<serde_json::Value>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| format!("can't be read: {}", e))?;
+    let value = serde_json::from_str(&content).map_err(|e| format!("invalid JSON: {}", e))?;
+    Ok(Some(value))
+}
+
+/// Keeps the keys of `layer` that make a valid config, recording an issue for
+/// each of the others.
+fn check_layer(
+    path: &Path,
+    layer: serde_json::Value,
+    issues: &mut Vec<ConfigIssue>,
+) -> serde_json::Value {
+    let mut issue = |key: String, message: String| {
+        issues.push(ConfigIssue {
+            file: path.to_path_buf(),
+            key,
+            message,
+        })
+    };
+    let serde_json::Value::Object(entries) = layer else {
+        issue(String::new(), "expected a JSON object".to_string());
+        return serde_json::Value::Object(serde_json::Map::new());
+    };
+
+    let known = serde_json::to_value(Config::default()).unwrap_or_default();
+    let checked_in = path
+        .file_name()
+        .is_some_and(|name| name == project_config::FILE_NAME);
+    let mut valid = serde_json::Map::new();
+    for (key, value) in entries {
+        if known.get(&key).is_none() {
+            issue(key, "unknown key".to_string());
+            continue;
+        }
+        if checked_in && !project_config::SHARED_KEYS.contains(&key.as_str()) {
+            issue(
+                key,
+                format!(
+                    "can't be shared in {}; set it in your own config",
+                    project_config::FILE_NAME
+                ),
+            );
+            continue;
+        }
+        let single = serde_json::Value::Object(serde_json::Map::from_iter([(key, value)]));
+        match serde_path_to_error::deserialize::<_, Config>(&single) {
+            Ok(_) => {
+                if let serde_json::Value::Object(entry) = single {
+                    valid.extend(entry);
+                }
+            }
+            Err(e) => issue(e.path().to_string(), e.into_inner().to_string()),
+        }
+    }
+    serde_json::Value::Object(valid)
+}
+
+/// Recursively merges `overlay` into `base`; objects merge key by key, anything else is replaced.
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn load(global_dir: &Path, project_dir: &Path) -> (Config, Vec<ConfigIssue>) {
+        load_config(&[
+            global_dir.join("config.json"),
+            project_dir.join("config.json"),
+        ])
+    }
+
+    #[test]
+    fn test_load_missing_files_returns_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let (config, _) = load(temp_dir.path(), &temp_dir.path().join("project"));
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_project_layer_overrides_global() {
+        let global = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+
+        fs::write(
+            global.path().join("config.json"),
+            r#"{"run_templates": {"npm": "{pm} {run} --global", "pnpm": "{pm} {run} --offline"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("config.json"),
+            r#"{"run_templates": {"npm": "{pm} {run} --project"}}"#,
+        )
+        .unwrap();
+
+        let (config, _) = load(global.path(), project.path());
+        assert_eq!(
+            config.run_template(PackageManager::Npm),
+            Some("{pm} {run} --project")
+        );
+        assert_eq!(
+            config.run_template(PackageManager::Pnpm),
+            Some("{pm} {run} --offline")
+        );
+    }
+
+    #[test]
+    fn test_invalid_json_skips_only_that_file() {
+        let global = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        fs::write(global.path().join("config.json"), r#"{"ascii": true}"#).unwrap();
+        fs::write(project.path().join("config.json"), "{ nope").unwrap();
+
+        let (config, issues) = load(global.path(), project.path());
+        assert!(config.ascii);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].file, project.path().join("config.json"));
+        assert_eq!(issues[0].key, "");
+        assert_eq!(
+            issues[0].message,
+            "invalid JSON: key must be a string at line 1 column 3"
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys_are_reported_and_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("config.json"),
+            r#"{
+                "run_in_pane": true,
+                "post_run": [{"run": "echo done", "when": "sometimes"}],
+                "inline_heigth": 12
+            }"#,
+        )
+        .unwrap();
+
+        let (config, issues) = load(temp_dir.path(), &temp_dir.path().join("project"));
+        assert!(config.run_in_pane);
+        assert!(config.post_run.is_empty());
+
+        let found: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|i| (i.key.as_str(), i.message.as_str()))
+            .collect();
+        assert_eq!(found[0].0, "post_run[0].when");
+        assert!(found[0].1.starts_with("unknown variant `sometimes`"));
+        assert_eq!(found[1], ("inline_heigth", "unknown key"));
+    }
+
+    #[test]
+    fn test_blank_template_is_ignored() {
+        let mut config = Config::default();
+        config
+            .run_templates
+            .insert("yarn".to_string(), "   ".to_string());
+
+        assert_eq!(config.run_template(PackageManager::Yarn), None);
+        assert_eq!(config.run_template(PackageManager::YarnBerry), None);
+    }
+
+    #[test]
+    fn test_startup_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("config.json"),
+            r#"{"startup": {"tab": "packages", "query": "apps/"}}"#,
+        )
+        .unwrap();
+
+        let (config, _) = load(temp_dir.path(), &temp_dir.path().join("project"));
+        assert_eq!(config.startup.tab, Some(StartupTab::Packages));
+        assert_eq!(config.startup.query.as_deref(), Some("apps/"));
+    }
+
+    #[test]
+    fn test_post_run_hooks_filtered_by_script() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("config.json"),
+            r#"{"post_run": [
+                {"run": "notify-send done"},
+                {"script": "cleanup", "when": "success", "scripts": ["build"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let (config, _) = load(temp_dir.path(), &temp_dir.path().join("project"));
+        assert_eq!(config.post_run_hooks("build").len(), 2);
+        assert_eq!(config.post_run_hooks("dev").len(), 1);
+    }
+
+    #[test]
+    fn test_dangerous_scripts_match_globs() {
+        let config = Config {
+            dangerous_scripts: vec!["db:reset".to_string(), "deploy:*".to_string()],
+            ..Config::default()
+        };
+        assert!(config.is_dangerous("db:reset"));
+        assert!(config.is_dangerous("deploy:prod"));
+        assert!(!config.is_dangerous("db:migrate"));
+        assert!(!Config::default().is_dangerous("deploy:prod"));
+    }
+
+    #[test]
+    fn test_required_env_combines_wildcard_and_script_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("config.json"),
+            r#"{"required_env": {"*": ["NODE_ENV"], "deploy": ["API_TOKEN", "NODE_ENV"]}}"#,
+        )
+        .unwrap();
+
+        let (config, _) = load(temp_dir.path(), &temp_dir.path().join("project"));
+        assert_eq!(
+            config.required_env_for("deploy"),
+            vec!["NODE_ENV", "API_TOKEN"]
+        );
+        assert_eq!(config.required_env_for("dev"), vec!["NODE_ENV"]);
+    }
+}
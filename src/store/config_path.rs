@@ -23,10 +23,15 @@ fn ensure_config_dir() -> PathBuf {
     dir
 }
 
+/// Returns the directory holding the per-project directories.
+pub fn get_projects_dir() -> PathBuf {
+    get_config_dir().join("projects")
+}
+
 /// Returns the project-specific config directory path.
 /// Data is isolated per project under `~/.config/nr/projects/{project_id}/`.
 pub fn get_project_dir(project_id: &str) -> PathBuf {
-    get_config_dir().join("projects").join(project_id)
+    get_projects_dir().join(project_id)
 }
 
 /// Ensures the project-specific config directory exists, creating it if necessary.
@@ -67,6 +67,11 @@ pub fn toggle_favorite(favorites: &mut HashSet<String>, key: &str) -> bool {
     }
 }
 
+/// Key of a workspace package in the favorites and recents, e.g. `pkg:@mono/web`.
+pub fn package_key(package_name: &str) -> String {
+    format!("pkg:{}", package_name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
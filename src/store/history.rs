@@ -0,0 +1,227 @@
+use crate::core::queue::QueuedRun;
+use crate::store::recents::now_ms;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Maximum number of distinct invocations to keep
+const MAX_HISTORY: usize = 500;
+
+/// One distinct way a script was run: the same script with the same args and
+/// env files collapses into a single entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Script key without project id (e.g. `root:build` or `@mono/web:dev`).
+    pub key: String,
+    pub script_name: String,
+    pub cwd: PathBuf,
+    pub args: String,
+    /// Env files in merge order (root → package).
+    pub env_files: Vec<PathBuf>,
+    pub last_run: u64, // Unix timestamp (milliseconds)
+    pub count: u32,
+    /// How the last run ended; `None` while it runs or if nr exited first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl HistoryEntry {
+    /// The run this entry replays, with the same args and env files.
+    pub fn to_run(&self) -> QueuedRun {
+        QueuedRun {
+            key: self.key.clone(),
+            script_name: self.script_name.clone(),
+            cwd: self.cwd.clone(),
+            env_files: self.env_files.clone(),
+            args: self.args.clone(),
+            env: Vec::new(),
+        }
+    }
+}
+
+/// Loads the run history from the config directory, most recent first.
+/// Returns an empty Vec if the file doesn't exist or is corrupted.
+pub fn load_history(config_dir: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(config_dir.join("history.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the run history to the config directory.
+pub fn save_history(config_dir: &Path, history: &[HistoryEntry]) {
+    let json = serde_json::to_string_pretty(&history).unwrap_or_else(|_| "[]".to_string());
+    std::fs::write(config_dir.join("history.json"), json).ok();
+}
+
+/// Records a run at the front of the history, merging it with an earlier
+/// entry for the same script, args and env files.
+pub fn record_run(history: &mut Vec<HistoryEntry>, run: &QueuedRun) {
+    let count = match history.iter().position(|e| {
+        e.key == run.key && e.cwd == run.cwd && e.args == run.args && e.env_files == run.env_files
+    }) {
+        Some(i) => history.remove(i).count + 1,
+        None => 1,
+    };
+
+    history.insert(
+        0,
+        HistoryEntry {
+            key: run.key.clone(),
+            script_name: run.script_name.clone(),
+            cwd: run.cwd.clone(),
+            args: run.args.clone(),
+            env_files: run.env_files.clone(),
+            last_run: now_ms(),
+            count,
+            exit_code: None,
+            duration_ms: None,
+        },
+    );
+    history.truncate(MAX_HISTORY);
+}
+
+/// Stores how the latest run of `script_name` in `cwd` with `args` and
+/// `env_files` ended, on the entry [`record_run`] made when it started.
+pub fn record_outcome(
+    history: &mut [HistoryEntry],
+    script_name: &str,
+    cwd: &Path,
+    args: &str,
+    env_files: &[PathBuf],
+    exit_code: i32,
+    duration: Duration,
+) {
+    if let Some(entry) = history.iter_mut().find(|e| {
+        e.script_name == script_name && e.cwd == cwd && e.args == args && e.env_files == env_files
+    }) {
+        entry.exit_code = Some(exit_code);
+        entry.duration_ms = Some(duration.as_millis() as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(key: &str, args: &str, env_files: &[&str]) -> QueuedRun {
+        QueuedRun {
+            key: key.to_string(),
+            script_name: key.rsplit(':').next().unwrap().to_string(),
+            cwd: PathBuf::from("/project"),
+            env_files: env_files.iter().map(PathBuf::from).collect(),
+            args: args.to_string(),
+            env: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_run_merges_identical_invocations() {
+        let mut history = Vec::new();
+        record_run(&mut history, &run("root:test", "--coverage", &[]));
+        record_run(&mut history, &run("root:test", "", &[]));
+        record_run(&mut history, &run("root:test", "--coverage", &[]));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].args, "--coverage");
+        assert_eq!(history[0].count, 2);
+        assert_eq!(history[1].args, "");
+    }
+
+    #[test]
+    fn test_record_run_distinguishes_env_files() {
+        let mut history = Vec::new();
+        record_run(&mut history, &run("root:dev", "", &["/project/.env"]));
+        record_run(
+            &mut history,
+            &run("root:dev", "", &["/project/.env.staging"]),
+        );
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[0].to_run(),
+            run("root:dev", "", &["/project/.env.staging"])
+        );
+    }
+
+    #[test]
+    fn test_record_outcome_updates_latest_matching_run() {
+        let mut history = Vec::new();
+        let test = run("root:test", "--coverage", &[]);
+        record_run(&mut history, &test);
+        record_run(&mut history, &run("root:lint", "", &[]));
+
+        record_outcome(
+            &mut history,
+            "test",
+            Path::new("/project"),
+            "--coverage",
+            &[],
+            1,
+            Duration::from_millis(1500),
+        );
+        assert_eq!(history[1].exit_code, Some(1));
+        assert_eq!(history[1].duration_ms, Some(1500));
+        assert_eq!(history[0].exit_code, None);
+
+        // Running it again clears the old outcome until the new one is known
+        record_run(&mut history, &test);
+        assert_eq!(history[0].count, 2);
+        assert_eq!(history[0].exit_code, None);
+    }
+
+    #[test]
+    fn test_load_history_without_outcomes() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("history.json"),
+            r#"[{"key": "root:dev", "script_name": "dev", "cwd": "/project",
+                 "args": "", "env_files": [], "last_run": 1, "count": 1}]"#,
+        )
+        .unwrap();
+
+        let history = load_history(temp_dir.path());
+        assert_eq!(history[0].exit_code, None);
+        assert_eq!(history[0].duration_ms, None);
+    }
+
+    #[test]
+    fn test_record_run_caps_history() {
+        let mut history = Vec::new();
+        for i in 0..MAX_HISTORY + 5 {
+            record_run(
+                &mut history,
+                &run("root:test", &format!("--shard={}", i), &[]),
+            );
+        }
+
+        assert_eq!(history.len(), MAX_HISTORY);
+        assert_eq!(history[0].args, format!("--shard={}", MAX_HISTORY + 4));
+    }
+
+    #[test]
+    fn test_save_and_load_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut history = Vec::new();
+        record_run(
+            &mut history,
+            &run("root:build", "--prod", &["/project/.env"]),
+        );
+
+        save_history(temp_dir.path(), &history);
+
+        assert_eq!(load_history(temp_dir.path()), history);
+    }
+
+    #[test]
+    fn test_load_history_missing_or_corrupted() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_history(temp_dir.path()).is_empty());
+
+        std::fs::write(temp_dir.path().join("history.json"), "not json").unwrap();
+        assert!(load_history(temp_dir.path()).is_empty());
+    }
+}
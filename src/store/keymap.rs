@@ -0,0 +1,455 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Script list actions that can be bound to keys in the `keymap` config section,
+/// e.g. `"keymap": { "toggle_favorite": "ctrl+f", "run": ["enter", "ctrl+r"] }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Run,
+    /// Run with the last used env files and arguments, skipping the configure flow.
+    QuickRun,
+    Configure,
+    ToggleFavorite,
+    Enqueue,
+    OpenQueue,
+    Schedule,
+    Background,
+    ShowLocation,
+    OpenEditor,
+    CopyCommand,
+    PrintCommand,
+    EditTags,
+    /// Open the quick dial: the top favorite script of every package.
+    QuickDial,
+    /// Group the script list by `prefix:` (collapsible), or back to a flat list.
+    ToggleGrouping,
+    /// Group the scripts of all packages by name, to run one in several packages.
+    ScriptGroups,
+    /// Run the selected script name in every workspace package that defines it.
+    RunAllPackages,
+    /// Search the scripts of every workspace package in one list (`package:script`).
+    AllScripts,
+    /// Show the scripts hidden by the `hide_scripts` config, or hide them again.
+    ToggleHidden,
+    /// Order the list by recent use, by name, or as in `package.json`.
+    CycleSort,
+    /// Run the script that usually follows the most recent run.
+    RunSuggested,
+    /// Run the most recent run again, with the same args and env files.
+    RunLast,
+    /// Read `package.json` and the workspace packages again.
+    Reload,
+    /// Open the settings screen (clear favorites, recents, saved configs).
+    Settings,
+    Help,
+    /// Quit, or leave the package / history / jobs view.
+    Quit,
+}
+
+impl KeyAction {
+    /// Every action, in the order the help overlay lists them.
+    pub const ALL: [KeyAction; 26] = [
+        KeyAction::Run,
+        KeyAction::QuickRun,
+        KeyAction::Configure,
+        KeyAction::ToggleFavorite,
+        KeyAction::Enqueue,
+        KeyAction::OpenQueue,
+        KeyAction::Schedule,
+        KeyAction::Background,
+        KeyAction::ShowLocation,
+        KeyAction::OpenEditor,
+        KeyAction::CopyCommand,
+        KeyAction::PrintCommand,
+        KeyAction::EditTags,
+        KeyAction::QuickDial,
+        KeyAction::ToggleGrouping,
+        KeyAction::ScriptGroups,
+        KeyAction::RunAllPackages,
+        KeyAction::AllScripts,
+        KeyAction::ToggleHidden,
+        KeyAction::CycleSort,
+        KeyAction::RunSuggested,
+        KeyAction::RunLast,
+        KeyAction::Reload,
+        KeyAction::Settings,
+        KeyAction::Help,
+        KeyAction::Quit,
+    ];
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            KeyAction::Run => &["enter"],
+            KeyAction::QuickRun => &["shift+enter"],
+            KeyAction::Configure => &["tab"],
+            KeyAction::ToggleFavorite => &["space"],
+            KeyAction::Enqueue => &["ctrl+a"],
+            KeyAction::OpenQueue => &["ctrl+q"],
+            KeyAction::Schedule => &["ctrl+t"],
+            KeyAction::Background => &["ctrl+b"],
+            KeyAction::ShowLocation => &["ctrl+l"],
+            KeyAction::OpenEditor => &["ctrl+o"],
+            KeyAction::CopyCommand => &["ctrl+y"],
+            KeyAction::PrintCommand => &["ctrl+p"],
+            KeyAction::EditTags => &["ctrl+g"],
+            KeyAction::QuickDial => &["ctrl+d"],
+            KeyAction::ToggleGrouping => &["ctrl+e"],
+            KeyAction::ScriptGroups => &["ctrl+k"],
+            KeyAction::RunAllPackages => &["ctrl+w"],
+            KeyAction::AllScripts => &["ctrl+x"],
+            KeyAction::ToggleHidden => &["ctrl+u"],
+            KeyAction::CycleSort => &["ctrl+s"],
+            KeyAction::RunSuggested => &["ctrl+n"],
+            KeyAction::RunLast => &["ctrl+r"],
+            KeyAction::Reload => &["f5"],
+            KeyAction::Settings => &["f2"],
+            KeyAction::Help => &["?"],
+            KeyAction::Quit => &["esc"],
+        }
+    }
+
+    /// What the action does, for the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            KeyAction::Run => "Run the selected script",
+            KeyAction::QuickRun => "Run with the last used env files and arguments",
+            KeyAction::Configure => "Configure & run (env files, then arguments)",
+            KeyAction::ToggleFavorite => "Toggle favorite",
+            KeyAction::Enqueue => "Add to the execution queue",
+            KeyAction::OpenQueue => "Open the queue panel",
+            KeyAction::Schedule => "Schedule to run later",
+            KeyAction::Background => "Run in the background (Jobs tab)",
+            KeyAction::ShowLocation => "Show where the script is defined",
+            KeyAction::OpenEditor => "Open package.json in $EDITOR at the script",
+            KeyAction::CopyCommand => "Copy the resolved command",
+            KeyAction::PrintCommand => "Print the resolved command and exit",
+            KeyAction::EditTags => "Edit the script's tags (search #tag to filter)",
+            KeyAction::QuickDial => "Quick dial: each package's top favorite",
+            KeyAction::ToggleGrouping => "Group scripts by prefix (Enter expands a group)",
+            KeyAction::ScriptGroups => "Scripts by name across packages (start one in several)",
+            KeyAction::RunAllPackages => "Run the script in every package that defines it",
+            KeyAction::AllScripts => "Search the scripts of every package at once",
+            KeyAction::ToggleHidden => "Show or hide the scripts hidden by `hide_scripts`",
+            KeyAction::CycleSort => "Sort by recent use, name or package.json order",
+            KeyAction::RunSuggested => {
+                "Run the suggested next script (what usually follows the last run)"
+            }
+            KeyAction::RunLast => "Run the last run again (same args and env files)",
+            KeyAction::Reload => "Reload package.json and the workspace packages",
+            KeyAction::Settings => "Settings: clear favorites, recents or saved configs",
+            KeyAction::Help => "Show this help (F1 works everywhere)",
+            KeyAction::Quit => "Quit or go back",
+        }
+    }
+}
+
+/// A key with modifiers, written like `ctrl+r`, `alt+enter`, `space` or `?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Whether `key` is this binding. Shift is part of the character itself
+    /// (`?`, `K`), so it is ignored for character keys.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let modifiers = match key.code {
+            KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        let code = match (key.code, modifiers.contains(KeyModifiers::CONTROL)) {
+            (KeyCode::Char(c), true) => KeyCode::Char(c.to_ascii_lowercase()),
+            (code, _) => code,
+        };
+        self.code == code && self.modifiers == modifiers
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parts: Vec<&str> = value.split('+').collect();
+        // `ctrl++` binds the plus key
+        let (key, modifier_names) = match parts.as_slice() {
+            [rest @ .., "", ""] if !rest.is_empty() => ("+", rest),
+            [rest @ .., key] => (*key, rest),
+            [] => unreachable!("split yields at least one part"),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names {
+            modifiers |= match name.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{}' in key '{}'", name, value)),
+            };
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => match (lower.strip_prefix('f'), key.chars().count()) {
+                (Some(n), _) if n.parse::<u8>().is_ok_and(|n| (1..=12).contains(&n)) => {
+                    KeyCode::F(n.parse().unwrap())
+                }
+                (_, 1) => {
+                    let c = key.chars().next().unwrap();
+                    if modifiers.contains(KeyModifiers::CONTROL) {
+                        KeyCode::Char(c.to_ascii_lowercase())
+                    } else {
+                        KeyCode::Char(c)
+                    }
+                }
+                _ => return Err(format!("unknown key '{}'", value)),
+            },
+        };
+
+        // Shift on a character is already in the character (see `matches`)
+        if let KeyCode::Char(_) = code {
+            modifiers -= KeyModifiers::SHIFT;
+        }
+        Ok(KeyBinding { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        KeyBinding::try_from(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for KeyBinding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// One key or a list of keys for an action in the config.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(KeyBinding),
+    Many(Vec<KeyBinding>),
+}
+
+// Hand-written instead of `untagged` so a bad key name is reported as such
+impl<'de> Deserialize<'de> for KeySpec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SpecVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SpecVisitor {
+            type Value = KeySpec;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a key like \"ctrl+r\" or a list of keys")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<KeySpec, E> {
+                KeyBinding::try_from(value.to_string())
+                    .map(KeySpec::One)
+                    .map_err(E::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<KeySpec, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(key) = seq.next_element()? {
+                    keys.push(key);
+                }
+                Ok(KeySpec::Many(keys))
+            }
+        }
+
+        deserializer.deserialize_any(SpecVisitor)
+    }
+}
+
+impl KeySpec {
+    fn keys(&self) -> &[KeyBinding] {
+        match self {
+            KeySpec::One(key) => std::slice::from_ref(key),
+            KeySpec::Many(keys) => keys,
+        }
+    }
+}
+
+/// Resolved key bindings for the script list: configured actions replace their
+/// default keys, and configured keys win over another action's defaults.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, KeyAction)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&HashMap::new())
+    }
+}
+
+impl Keymap {
+    pub fn new(overrides: &HashMap<KeyAction, KeySpec>) -> Self {
+        let mut bindings = Vec::new();
+        for action in KeyAction::ALL {
+            if let Some(spec) = overrides.get(&action) {
+                bindings.extend(spec.keys().iter().map(|&key| (key, action)));
+            }
+        }
+        for action in KeyAction::ALL {
+            if !overrides.contains_key(&action) {
+                bindings.extend(action.default_keys().iter().map(|key| {
+                    let key = KeyBinding::try_from(key.to_string()).expect("valid default key");
+                    (key, action)
+                }));
+            }
+        }
+        Keymap { bindings }
+    }
+
+    /// The action bound to `key`, if any.
+    pub fn action_for(&self, key: &KeyEvent) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(key))
+            .map(|&(_, action)| action)
+    }
+
+    /// Keys that trigger `action`, for display (e.g. `Ctrl+A`).
+    pub fn keys_for(&self, action: KeyAction) -> Vec<String> {
+        let keys = self.bindings.iter().filter(|&&(key, a)| {
+            // Hidden when another action's configured key shadows it
+            a == action && self.action_for(&KeyEvent::new(key.code, key.modifiers)) == Some(action)
+        });
+        keys.map(|(key, _)| key.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> KeyBinding {
+        KeyBinding::try_from(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_key_names() {
+        assert_eq!(
+            key("ctrl+R"),
+            KeyBinding {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL
+            }
+        );
+        assert_eq!(key("alt+enter").code, KeyCode::Enter);
+        assert_eq!(key("F5").code, KeyCode::F(5));
+        assert_eq!(key("shift+?"), key("?"));
+        assert_eq!(key("ctrl++").code, KeyCode::Char('+'));
+        assert!(KeyBinding::try_from("hyper+x".to_string()).is_err());
+        assert!(KeyBinding::try_from("ctrl+foo".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_default_keymap() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            keymap.action_for(&KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+            Some(KeyAction::Enqueue)
+        );
+        // `?` arrives with Shift held on most layouts
+        assert_eq!(
+            keymap.action_for(&KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            Some(KeyAction::Help)
+        );
+        assert_eq!(keymap.action_for(&KeyEvent::from(KeyCode::Char('a'))), None);
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults_and_shadow_other_actions() {
+        let overrides: HashMap<KeyAction, KeySpec> =
+            serde_json::from_str(r#"{"toggle_favorite": ["ctrl+a", "ctrl+f"]}"#).unwrap();
+        let keymap = Keymap::new(&overrides);
+
+        assert_eq!(
+            keymap.action_for(&KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+            Some(KeyAction::ToggleFavorite)
+        );
+        assert_eq!(keymap.action_for(&KeyEvent::from(KeyCode::Char(' '))), None);
+        assert_eq!(
+            keymap.keys_for(KeyAction::ToggleFavorite),
+            vec!["Ctrl+A", "Ctrl+F"]
+        );
+        assert!(keymap.keys_for(KeyAction::Enqueue).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_key_is_a_config_error() {
+        let result: Result<HashMap<KeyAction, KeySpec>, _> =
+            serde_json::from_str(r#"{"run": "ctrl+nope"}"#);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("unknown key 'ctrl+nope'")
+        );
+        let result: Result<HashMap<KeyAction, KeySpec>, _> =
+            serde_json::from_str(r#"{"launch": "enter"}"#);
+        assert!(result.is_err());
+    }
+}
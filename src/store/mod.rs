@@ -1,7 +1,20 @@
 pub mod args_history;
+pub mod bench;
+pub mod config;
 pub mod config_path;
 pub mod favorites;
 pub mod global_env;
+pub mod history;
+pub mod keymap;
+pub mod next_runs;
 pub mod project_id;
+pub mod ran_commands;
 pub mod recents;
+pub mod reset;
 pub mod script_configs;
+pub mod session;
+pub mod tags;
+pub mod update_check;
+pub mod view_prefs;
+pub mod workspace_cache;
+pub mod workspace_index;
@@ -0,0 +1,129 @@
+use crate::store::history::HistoryEntry;
+use crate::store::recents::now_ms;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How often each script was followed by another, e.g. `root:build` →
+/// `{ "root:preview": 4, "root:test": 1 }`.
+pub type NextRuns = HashMap<String, HashMap<String, u32>>;
+
+/// Runs further apart than this aren't counted as one following the other.
+const MAX_GAP_MS: u64 = 60 * 60 * 1000;
+
+/// A follow-up must have happened this often before it's suggested.
+const MIN_COUNT: u32 = 2;
+
+/// Loads the transition counts from the config directory.
+/// Returns an empty map if the file doesn't exist or is corrupted.
+pub fn load_next_runs(config_dir: &Path) -> NextRuns {
+    std::fs::read_to_string(config_dir.join("next_runs.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the transition counts to the config directory.
+pub fn save_next_runs(config_dir: &Path, next_runs: &NextRuns) {
+    let json = serde_json::to_string_pretty(next_runs).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(config_dir.join("next_runs.json"), json).ok();
+}
+
+/// Counts `key` as the follow-up of the most recent run in `history`. Call
+/// before recording `key` itself in the history.
+pub fn record_transition(next_runs: &mut NextRuns, history: &[HistoryEntry], key: &str) {
+    let Some(previous) = history.first() else {
+        return;
+    };
+    if previous.key == key || now_ms().saturating_sub(previous.last_run) > MAX_GAP_MS {
+        return;
+    }
+    *next_runs
+        .entry(previous.key.clone())
+        .or_default()
+        .entry(key.to_string())
+        .or_default() += 1;
+}
+
+/// The script most often run after `key` (alphabetically first on ties), once
+/// it has followed `key` at least twice.
+pub fn suggest_next<'a>(next_runs: &'a NextRuns, key: &str) -> Option<&'a str> {
+    next_runs
+        .get(key)?
+        .iter()
+        .filter(|&(_, &count)| count >= MIN_COUNT)
+        .max_by(|(a_key, a_count), (b_key, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_key.cmp(a_key))
+        })
+        .map(|(next, _)| next.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::queue::QueuedRun;
+    use crate::store::history::record_run;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn run(history: &mut Vec<HistoryEntry>, next_runs: &mut NextRuns, key: &str) {
+        record_transition(next_runs, history, key);
+        record_run(
+            history,
+            &QueuedRun {
+                key: key.to_string(),
+                script_name: key.rsplit(':').next().unwrap().to_string(),
+                cwd: PathBuf::from("/project"),
+                env_files: vec![],
+                args: String::new(),
+                env: Vec::new(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_suggests_most_frequent_follow_up() {
+        let mut history = Vec::new();
+        let mut next_runs = NextRuns::new();
+        for key in [
+            "root:build",
+            "root:preview",
+            "root:build",
+            "root:test",
+            "root:build",
+            "root:preview",
+        ] {
+            run(&mut history, &mut next_runs, key);
+        }
+
+        assert_eq!(next_runs["root:build"]["root:preview"], 2);
+        assert_eq!(suggest_next(&next_runs, "root:build"), Some("root:preview"));
+        // `test` followed `build` only once
+        assert_eq!(suggest_next(&next_runs, "root:test"), None);
+    }
+
+    #[test]
+    fn test_ignores_reruns_and_stale_runs() {
+        let mut history = Vec::new();
+        let mut next_runs = NextRuns::new();
+        run(&mut history, &mut next_runs, "root:dev");
+        run(&mut history, &mut next_runs, "root:dev");
+        history[0].last_run -= MAX_GAP_MS + 1;
+        run(&mut history, &mut next_runs, "root:lint");
+
+        assert!(next_runs.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let mut next_runs = NextRuns::new();
+        next_runs
+            .entry("root:build".to_string())
+            .or_default()
+            .insert("root:preview".to_string(), 3);
+
+        save_next_runs(dir.path(), &next_runs);
+
+        assert_eq!(load_next_runs(dir.path()), next_runs);
+    }
+}
@@ -10,8 +10,40 @@ use std::path::Path;
 /// # Returns
 /// An 8-character hexadecimal string representing the project ID
 pub fn project_id(project_root: &Path) -> String {
+    let path = project_root.to_string_lossy();
+    if cfg!(windows) {
+        hash_id(&windows_key(&path))
+    } else {
+        hash_id(&path)
+    }
+}
+
+/// The id of the project at `project_root` whose data lives in `projects_dir`.
+///
+/// Windows ids used to hash the path as it was spelled; a project that
+/// already has data under that older id keeps using it, so its favorites,
+/// history and settings aren't lost.
+pub fn resolve_project_id(project_root: &Path, projects_dir: &Path) -> String {
+    let legacy = hash_id(&project_root.to_string_lossy());
+    pick_id(projects_dir, project_id(project_root), legacy)
+}
+
+/// `current`, unless only `legacy` has a directory in `projects_dir`.
+fn pick_id(projects_dir: &Path, current: String, legacy: String) -> String {
+    if legacy != current
+        && !projects_dir.join(&current).exists()
+        && projects_dir.join(&legacy).is_dir()
+    {
+        legacy
+    } else {
+        current
+    }
+}
+
+/// First 8 hex characters of the SHA-256 of `key`.
+fn hash_id(key: &str) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(project_root.to_string_lossy().as_bytes());
+    hasher.update(key.as_bytes());
     let result = hasher.finalize();
     format!(
         "{:02x}{:02x}{:02x}{:02x}",
@@ -19,6 +51,19 @@ pub fn project_id(project_root: &Path) -> String {
     )
 }
 
+/// A Windows path in one spelling, as the file system doesn't tell them
+/// apart: without the `\\?\` prefix of canonical paths, with `\`
+/// separators, lowercase and without a trailing separator.
+fn windows_key(path: &str) -> String {
+    let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+    let path = path.replace('/', "\\").to_lowercase();
+    match path.trim_end_matches('\\') {
+        // Keep the root of a drive as `c:\`
+        drive if drive.ends_with(':') => path,
+        trimmed => trimmed.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,6 +77,42 @@ mod tests {
         assert_eq!(id1, id2);
     }
 
+    #[test]
+    fn test_windows_key_spells_paths_one_way() {
+        let key = windows_key(r"C:\Users\me\shop");
+        assert_eq!(key, r"c:\users\me\shop");
+        assert_eq!(windows_key(r"\\?\C:\Users\me\shop\"), key);
+        assert_eq!(windows_key("c:/users/me/shop"), key);
+        assert_eq!(windows_key(r"C:\"), r"c:\");
+    }
+
+    #[test]
+    fn test_existing_legacy_id_is_kept() {
+        let projects = tempfile::TempDir::new().unwrap();
+        let (current, legacy) = ("c0ffee00".to_string(), "0ld1d000".to_string());
+
+        // Nothing stored yet: the current id
+        assert_eq!(
+            pick_id(projects.path(), current.clone(), legacy.clone()),
+            current
+        );
+
+        // Data under the legacy id keeps being used
+        std::fs::create_dir(projects.path().join(&legacy)).unwrap();
+        assert_eq!(
+            pick_id(projects.path(), current.clone(), legacy.clone()),
+            legacy
+        );
+
+        // Unless the current id has data too
+        std::fs::create_dir(projects.path().join(&current)).unwrap();
+        assert_eq!(pick_id(projects.path(), current.clone(), legacy), current);
+
+        // Outside Windows both ids are the same
+        let root = Path::new("/home/user/project");
+        assert_eq!(resolve_project_id(root, projects.path()), project_id(root));
+    }
+
     #[test]
     fn test_project_id_is_8_chars() {
         let path = Path::new("/home/user/project");
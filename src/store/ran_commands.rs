@@ -0,0 +1,80 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Hash of each script's command as it was when last run, keyed by script key
+/// (e.g. `root:build`), so scripts edited since can be flagged.
+pub type RanCommands = HashMap<String, String>;
+
+/// Loads the ran command hashes from the config directory.
+/// Returns an empty map if the file doesn't exist or is corrupted.
+pub fn load_ran_commands(config_dir: &Path) -> RanCommands {
+    std::fs::read_to_string(config_dir.join("ran_commands.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the ran command hashes to the config directory.
+pub fn save_ran_commands(config_dir: &Path, ran: &RanCommands) {
+    let json = serde_json::to_string_pretty(ran).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(config_dir.join("ran_commands.json"), json).ok();
+}
+
+/// Remembers `command` as the one `key` ran with.
+pub fn record_command(ran: &mut RanCommands, key: &str, command: &str) {
+    ran.insert(key.to_string(), command_hash(command));
+}
+
+/// Whether `key`'s command is no longer the one it last ran with.
+/// Scripts never run from nr haven't changed.
+pub fn has_changed(ran: &RanCommands, key: &str, command: &str) -> bool {
+    ran.get(key)
+        .is_some_and(|hash| *hash != command_hash(command))
+}
+
+/// First 16 hex characters of the command's SHA-256.
+fn command_hash(command: &str) -> String {
+    Sha256::digest(command.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_changed_only_after_a_run_with_another_command() {
+        let mut ran = RanCommands::new();
+        assert!(!has_changed(&ran, "root:build", "tsc"));
+
+        record_command(&mut ran, "root:build", "tsc");
+        assert!(!has_changed(&ran, "root:build", "tsc"));
+        assert!(has_changed(
+            &ran,
+            "root:build",
+            "tsc -p tsconfig.build.json"
+        ));
+
+        record_command(&mut ran, "root:build", "tsc -p tsconfig.build.json");
+        assert!(!has_changed(
+            &ran,
+            "root:build",
+            "tsc -p tsconfig.build.json"
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load_ran_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut ran = RanCommands::new();
+        record_command(&mut ran, "@mono/web:dev", "vite");
+
+        save_ran_commands(temp_dir.path(), &ran);
+
+        assert_eq!(load_ran_commands(temp_dir.path()), ran);
+    }
+}
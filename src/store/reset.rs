@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Per-project state that `--reset*` and the settings screen can clear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetTarget {
+    Favorites,
+    Recents,
+    /// Saved env/args per script and the arguments history.
+    Configs,
+}
+
+impl ResetTarget {
+    pub const ALL: [ResetTarget; 3] = [
+        ResetTarget::Favorites,
+        ResetTarget::Recents,
+        ResetTarget::Configs,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResetTarget::Favorites => "Favorites",
+            ResetTarget::Recents => "Recents",
+            ResetTarget::Configs => "Script configs & args history",
+        }
+    }
+
+    /// Store files holding this state, each with the name reported when it's cleared.
+    fn files(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            ResetTarget::Favorites => &[("favorites.json", "favorites")],
+            ResetTarget::Recents => &[("recents.json", "recents")],
+            ResetTarget::Configs => &[
+                ("script_configs.json", "script configs"),
+                ("args_history.json", "args history"),
+            ],
+        }
+    }
+}
+
+/// Deletes `target`'s files from `project_dir`. Returns what was cleared, e.g.
+/// `["favorites"]`, or `["favorites (already empty)"]` when there was no file.
+pub fn reset(project_dir: &Path, target: ResetTarget) -> Result<Vec<String>> {
+    let mut cleared = Vec::new();
+    for (file, name) in target.files() {
+        let path = project_dir.join(file);
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", file))?;
+            cleared.push(name.to_string());
+        } else {
+            cleared.push(format!("{} (already empty)", name));
+        }
+    }
+    Ok(cleared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reset_removes_only_target_files() {
+        let dir = TempDir::new().unwrap();
+        for file in ["favorites.json", "recents.json", "script_configs.json"] {
+            std::fs::write(dir.path().join(file), "{}").unwrap();
+        }
+
+        let cleared = reset(dir.path(), ResetTarget::Configs).unwrap();
+
+        assert_eq!(
+            cleared,
+            vec!["script configs", "args history (already empty)"]
+        );
+        assert!(!dir.path().join("script_configs.json").exists());
+        assert!(dir.path().join("favorites.json").exists());
+        assert!(dir.path().join("recents.json").exists());
+    }
+}
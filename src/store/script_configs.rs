@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -10,6 +11,34 @@ pub struct ScriptConfig {
     pub args: String,
     #[serde(with = "systemtime_serde")]
     pub last_used: SystemTime,
+    /// Saved combinations to pick from before the env step, in saving order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presets: Vec<Preset>,
+    /// Variables set on top of the env files, e.g. `{ "DEBUG": "app:*" }`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub env: IndexMap<String, String>,
+}
+
+impl ScriptConfig {
+    /// Adds `preset`, replacing the one with the same name.
+    pub fn save_preset(&mut self, preset: Preset) {
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+}
+
+/// A named set of env files and arguments for a script, e.g. "coverage CI".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Preset {
+    pub name: String,
+    /// Env files by file name (e.g. `.env.test`), as the last used env files
+    /// are remembered.
+    #[serde(default)]
+    pub env_files: Vec<String>,
+    #[serde(default)]
+    pub args: String,
 }
 
 pub type ScriptConfigs = HashMap<String, ScriptConfig>;
@@ -93,6 +122,8 @@ mod tests {
             ScriptConfig {
                 args: "-- --watch".to_string(),
                 last_used: SystemTime::now(),
+                presets: Vec::new(),
+                env: IndexMap::new(),
             },
         );
         configs.insert(
@@ -100,6 +131,8 @@ mod tests {
             ScriptConfig {
                 args: "".to_string(),
                 last_used: SystemTime::now(),
+                presets: Vec::new(),
+                env: IndexMap::new(),
             },
         );
 
@@ -138,11 +171,44 @@ mod tests {
         assert!(config_dir.join("script_configs.json").exists());
     }
 
+    #[test]
+    fn test_save_preset_replaces_the_same_name() {
+        let preset = |name: &str, args: &str| Preset {
+            name: name.to_string(),
+            env_files: vec![".env.test".to_string()],
+            args: args.to_string(),
+        };
+        let mut config = ScriptConfig {
+            args: String::new(),
+            last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
+        };
+        config.save_preset(preset("unit watch", "--watch"));
+        config.save_preset(preset("coverage CI", "--coverage"));
+        config.save_preset(preset("unit watch", "--watch --silent"));
+
+        assert_eq!(
+            config.presets,
+            vec![
+                preset("unit watch", "--watch --silent"),
+                preset("coverage CI", "--coverage")
+            ]
+        );
+
+        // Configs saved before presets existed still load
+        let old: ScriptConfig =
+            serde_json::from_str(r#"{"args": "--watch", "last_used": 0}"#).unwrap();
+        assert!(old.presets.is_empty());
+    }
+
     #[test]
     fn test_systemtime_serialization() {
         let config = ScriptConfig {
             args: "test".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -0,0 +1,53 @@
+use crate::app::Tab;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Where the TUI was left, restored on the next launch in the same project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tab: Option<Tab>,
+    /// Search query of the tab's list (the package list in the Packages tab).
+    pub query: String,
+    /// Relative path of the package highlighted in the Packages tab.
+    pub package: Option<String>,
+    /// The package's script list was open.
+    pub package_open: bool,
+}
+
+/// Loads the last session from the project config directory.
+/// Returns the default (Scripts tab, no query) if the file doesn't exist or is corrupted.
+pub fn load_session(config_dir: &Path) -> Session {
+    std::fs::read_to_string(config_dir.join("session.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the session to the project config directory.
+pub fn save_session(config_dir: &Path, session: &Session) {
+    if let Ok(json) = serde_json::to_string_pretty(session) {
+        std::fs::write(config_dir.join("session.json"), json).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load_session() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(load_session(temp_dir.path()), Session::default());
+
+        let session = Session {
+            tab: Some(Tab::Packages),
+            query: "apps/".to_string(),
+            package: Some("apps/web".to_string()),
+            package_open: true,
+        };
+        save_session(temp_dir.path(), &session);
+        assert_eq!(load_session(temp_dir.path()), session);
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Tags attached to scripts in the TUI, keyed by script key (e.g. `root:build`).
+pub type ScriptTags = HashMap<String, Vec<String>>;
+
+/// Loads script tags from the config directory.
+/// Returns an empty map if the file doesn't exist or is corrupted.
+pub fn load_tags(config_dir: &Path) -> ScriptTags {
+    std::fs::read_to_string(config_dir.join("tags.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves script tags to the config directory, dropping scripts without tags.
+pub fn save_tags(config_dir: &Path, tags: &ScriptTags) {
+    let tags: HashMap<&String, &Vec<String>> = tags.iter().filter(|(_, t)| !t.is_empty()).collect();
+    let json = serde_json::to_string_pretty(&tags).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(config_dir.join("tags.json"), json).ok();
+}
+
+/// Parses tags typed as `ci release` or `#ci, #release`: lowercased, without
+/// the leading `#`, deduplicated in order.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|t| t.trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(parse_tags("CI  #release, ci"), vec!["ci", "release"]);
+        assert!(parse_tags(" # ").is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_tags_skips_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut tags = ScriptTags::new();
+        tags.insert("root:build".to_string(), vec!["ci".to_string()]);
+        tags.insert("root:dev".to_string(), vec![]);
+
+        save_tags(temp_dir.path(), &tags);
+        let loaded = load_tags(temp_dir.path());
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded["root:build"], vec!["ci"]);
+    }
+}
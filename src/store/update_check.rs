@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Result of the last check for a newer nr release.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpdateCheck {
+    /// When the check ran, in ms since the epoch.
+    pub checked_at: u64,
+    /// Latest released version, e.g. `0.3.0`.
+    pub latest: String,
+}
+
+/// Loads the last update check from the config directory.
+/// Returns `None` if the file doesn't exist or is corrupted.
+pub fn load_update_check(config_dir: &Path) -> Option<UpdateCheck> {
+    std::fs::read_to_string(config_dir.join("update_check.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// Saves the update check to the config directory.
+pub fn save_update_check(config_dir: &Path, check: &UpdateCheck) {
+    if let Ok(json) = serde_json::to_string_pretty(check) {
+        std::fs::create_dir_all(config_dir).ok();
+        std::fs::write(config_dir.join("update_check.json"), json).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load_update_check() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(load_update_check(temp_dir.path()), None);
+
+        let check = UpdateCheck {
+            checked_at: 1_700_000_000_000,
+            latest: "0.3.0".to_string(),
+        };
+        save_update_check(temp_dir.path(), &check);
+        assert_eq!(load_update_check(temp_dir.path()), Some(check));
+    }
+}
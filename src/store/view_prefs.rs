@@ -0,0 +1,68 @@
+use crate::sort::SortMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Key of the Scripts tab's list in [`ViewPrefsMap`].
+pub const SCRIPTS_VIEW: &str = "scripts";
+
+/// Key of the list of every workspace package's scripts in [`ViewPrefsMap`].
+pub const ALL_SCRIPTS_VIEW: &str = "all_scripts";
+
+/// List settings toggled in the TUI, kept separately for each view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewPrefs {
+    pub sort: SortMode,
+    /// Show the scripts hidden by the `hide_scripts` config.
+    pub show_hidden: bool,
+    /// Group by prefix; `None` follows the `group_scripts` config.
+    pub group: Option<bool>,
+}
+
+/// Settings per view: [`SCRIPTS_VIEW`], [`ALL_SCRIPTS_VIEW`], or [`package_view`]
+/// for a package's scripts.
+pub type ViewPrefsMap = HashMap<String, ViewPrefs>;
+
+/// Key of a workspace package's script list, e.g. `package:apps/web`.
+pub fn package_view(relative_path: &str) -> String {
+    format!("package:{}", relative_path)
+}
+
+/// Loads the view settings from the project config directory.
+/// Returns an empty map if the file doesn't exist or is corrupted.
+pub fn load_view_prefs(config_dir: &Path) -> ViewPrefsMap {
+    std::fs::read_to_string(config_dir.join("view_prefs.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the view settings to the project config directory.
+pub fn save_view_prefs(config_dir: &Path, prefs: &ViewPrefsMap) {
+    let json = serde_json::to_string_pretty(prefs).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(config_dir.join("view_prefs.json"), json).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load_view_prefs() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut prefs = ViewPrefsMap::new();
+        prefs.insert(
+            package_view("apps/web"),
+            ViewPrefs {
+                sort: SortMode::Name,
+                show_hidden: true,
+                group: None,
+            },
+        );
+
+        save_view_prefs(temp_dir.path(), &prefs);
+        assert_eq!(load_view_prefs(temp_dir.path()), prefs);
+    }
+}
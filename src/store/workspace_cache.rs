@@ -0,0 +1,232 @@
+use crate::core::workspaces::{README_FILES, WorkspacePackage, pattern_roots, uses_globstar};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// File name of the cache stamp inside the project directory.
+const CACHE_FILE: &str = "workspaces_cache.json";
+
+/// Files at the monorepo root whose changes can add, remove or move packages.
+const ROOT_MANIFESTS: &[&str] = &[
+    "package.json",
+    "pnpm-workspace.yaml",
+    "lerna.json",
+    "rush.json",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+];
+
+/// What the saved workspace index was scanned from: the `workspace_exclude`
+/// globs and the modification times of the root manifests, of every
+/// package's `package.json` and README, of the directories holding packages
+/// and of those the workspace patterns point at (`apps` for `apps/*`);
+/// directories change when a package directory is added or removed, even in
+/// one that held no package yet. A package without a description also stamps
+/// its own directory, which changes when a README is added. While it still
+/// matches, the index is current and startup skips the rescan.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceStamp {
+    pub exclude: Vec<String>,
+    /// Modification time in ms since the epoch, keyed by path relative to the
+    /// monorepo root. Missing files are left out.
+    pub mtimes: BTreeMap<String, u64>,
+}
+
+impl WorkspaceStamp {
+    /// The current stamp of `packages` under `monorepo_root`, or `None` when
+    /// a workspace pattern uses `**`: packages can then appear at any depth,
+    /// so only a scan tells whether the index is current.
+    pub fn current(
+        monorepo_root: &Path,
+        packages: &[WorkspacePackage],
+        exclude: &[String],
+    ) -> Option<Self> {
+        if uses_globstar(monorepo_root) {
+            return None;
+        }
+        let package_files = packages.iter().flat_map(|pkg| {
+            std::iter::once("package.json")
+                .chain(README_FILES.iter().copied())
+                .map(|file| format!("{}/{}", pkg.relative_path, file))
+        });
+        let mut package_dirs: BTreeSet<String> = packages
+            .iter()
+            .filter_map(|pkg| Some(pkg.relative_path.rsplit_once('/')?.0.to_string()))
+            .collect();
+        package_dirs.extend(pattern_roots(monorepo_root));
+        package_dirs.extend(
+            packages
+                .iter()
+                .filter(|pkg| pkg.description.is_none())
+                .map(|pkg| pkg.relative_path.clone()),
+        );
+        let mtimes = ROOT_MANIFESTS
+            .iter()
+            .map(|file| file.to_string())
+            .chain(package_files)
+            .chain(package_dirs)
+            .filter_map(|file| {
+                let modified = std::fs::metadata(monorepo_root.join(&file))
+                    .and_then(|meta| meta.modified())
+                    .ok()?;
+                let ms = modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64;
+                Some((file, ms))
+            })
+            .collect();
+
+        Some(Self {
+            exclude: exclude.to_vec(),
+            mtimes,
+        })
+    }
+}
+
+/// Loads the stamp saved with the last workspace scan.
+/// Returns `None` if the file doesn't exist or is corrupted.
+pub fn load_workspace_cache(config_dir: &Path) -> Option<WorkspaceStamp> {
+    std::fs::read_to_string(config_dir.join(CACHE_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// Saves the stamp of a finished scan. Errors are ignored; it is only a cache.
+pub fn save_workspace_cache(config_dir: &Path, stamp: &WorkspaceStamp) {
+    if let Ok(json) = serde_json::to_string_pretty(stamp) {
+        std::fs::create_dir_all(config_dir).ok();
+        std::fs::write(config_dir.join(CACHE_FILE), json).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn touch(path: &Path, at: SystemTime) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(at)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_stamp_changes_with_package_manifests() {
+        let root = TempDir::new().unwrap();
+        let store = TempDir::new().unwrap();
+        std::fs::write(
+            root.path().join("package.json"),
+            r#"{"workspaces": ["apps/*"]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.path().join("apps/web")).unwrap();
+        std::fs::write(root.path().join("apps/web/package.json"), "{}").unwrap();
+        let packages = vec![WorkspacePackage {
+            name: "web".to_string(),
+            relative_path: "apps/web".to_string(),
+            scripts: Default::default(),
+            description: None,
+            version: None,
+            private: false,
+        }];
+
+        let stamp = WorkspaceStamp::current(root.path(), &packages, &[]).unwrap();
+        assert_eq!(
+            stamp.mtimes.keys().collect::<Vec<_>>(),
+            vec!["apps", "apps/web", "apps/web/package.json", "package.json"]
+        );
+        save_workspace_cache(store.path(), &stamp);
+        assert_eq!(load_workspace_cache(store.path()), Some(stamp.clone()));
+
+        touch(
+            &root.path().join("apps/web/package.json"),
+            SystemTime::now() + Duration::from_secs(60),
+        );
+        assert_ne!(
+            WorkspaceStamp::current(root.path(), &packages, &[]),
+            Some(stamp.clone())
+        );
+        assert_ne!(
+            WorkspaceStamp::current(root.path(), &packages, &["apps/**".to_string()])
+                .unwrap()
+                .exclude,
+            stamp.exclude
+        );
+    }
+
+    #[test]
+    fn test_stamp_covers_pattern_directories_without_packages() {
+        let root = TempDir::new().unwrap();
+        std::fs::write(
+            root.path().join("package.json"),
+            r#"{"workspaces": ["apps/*", "tools/cli", "!apps/legacy"]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.path().join("apps")).unwrap();
+        std::fs::create_dir_all(root.path().join("tools")).unwrap();
+
+        let stamp = WorkspaceStamp::current(root.path(), &[], &[]).unwrap();
+        assert_eq!(
+            stamp.mtimes.keys().collect::<Vec<_>>(),
+            vec!["apps", "package.json", "tools"]
+        );
+    }
+
+    #[test]
+    fn test_stamp_changes_with_readme_of_described_package() {
+        let root = TempDir::new().unwrap();
+        std::fs::write(
+            root.path().join("package.json"),
+            r#"{"workspaces": ["apps/*"]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.path().join("apps/web")).unwrap();
+        std::fs::write(root.path().join("apps/web/package.json"), "{}").unwrap();
+        std::fs::write(root.path().join("apps/web/README.md"), "# Dashboard\n").unwrap();
+        let packages = vec![WorkspacePackage {
+            name: "web".to_string(),
+            relative_path: "apps/web".to_string(),
+            scripts: Default::default(),
+            description: Some("Dashboard".to_string()),
+            version: None,
+            private: false,
+        }];
+
+        let stamp = WorkspaceStamp::current(root.path(), &packages, &[]).unwrap();
+        assert!(stamp.mtimes.contains_key("apps/web/README.md"));
+        assert!(!stamp.mtimes.contains_key("apps/web"));
+
+        touch(
+            &root.path().join("apps/web/README.md"),
+            SystemTime::now() + Duration::from_secs(60),
+        );
+        assert_ne!(
+            WorkspaceStamp::current(root.path(), &packages, &[]),
+            Some(stamp)
+        );
+    }
+
+    #[test]
+    fn test_no_stamp_for_globstar_patterns() {
+        let root = TempDir::new().unwrap();
+        std::fs::write(
+            root.path().join("package.json"),
+            r#"{"workspaces": ["apps/*", "tools/**", "!apps/legacy/**"]}"#,
+        )
+        .unwrap();
+        assert_eq!(WorkspaceStamp::current(root.path(), &[], &[]), None);
+
+        std::fs::write(
+            root.path().join("package.json"),
+            r#"{"workspaces": ["apps/*", "!apps/legacy/**"]}"#,
+        )
+        .unwrap();
+        assert!(WorkspaceStamp::current(root.path(), &[], &[]).is_some());
+    }
+}
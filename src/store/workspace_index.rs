@@ -0,0 +1,205 @@
+use crate::core::workspaces::{ScanUpdate, WorkspacePackage, scan_workspaces_streaming};
+use crate::store::workspace_cache::{WorkspaceStamp, save_workspace_cache};
+use indexmap::IndexMap;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+
+/// File name of the persisted workspace index inside the project directory.
+const INDEX_FILE: &str = "workspaces.idx";
+
+/// Leading bytes of an index file; bump the version when the layout changes.
+const MAGIC: &[u8; 4] = b"NRWI";
+const VERSION: u8 = 3;
+
+/// Loads the workspace packages saved by the previous run.
+/// Returns `None` if the index is missing, from an older version, or corrupted.
+///
+/// The index is a compact binary file (length-prefixed UTF-8 strings) so that
+/// monorepos with thousands of packages show up without rescanning the tree.
+pub fn load_workspace_index(config_dir: &Path) -> Option<Vec<WorkspacePackage>> {
+    let bytes = std::fs::read(config_dir.join(INDEX_FILE)).ok()?;
+    decode(&bytes)
+}
+
+/// Saves workspace packages for the next startup. Errors are ignored; the
+/// index is only a cache.
+pub fn save_workspace_index(config_dir: &Path, packages: &[WorkspacePackage]) {
+    std::fs::write(config_dir.join(INDEX_FILE), encode(packages)).ok();
+}
+
+/// Scans the workspaces of `monorepo_root` on a background thread, saves the
+/// index and its stamp, and sends the packages. With `stream`, each package
+/// is also sent as it is found.
+pub fn scan_in_background(
+    monorepo_root: &Path,
+    config_dir: &Path,
+    exclude: &[String],
+    stream: bool,
+) -> Receiver<ScanUpdate> {
+    let (tx, rx) = mpsc::channel();
+    let (monorepo_root, config_dir, exclude) = (
+        monorepo_root.to_path_buf(),
+        config_dir.to_path_buf(),
+        exclude.to_vec(),
+    );
+    std::thread::spawn(move || {
+        let packages = scan_workspaces_streaming(&monorepo_root, &exclude, |package| {
+            if stream {
+                let _ = tx.send(ScanUpdate::Found(package.clone()));
+            }
+        });
+        save_workspace_index(&config_dir, &packages);
+        if let Some(stamp) = WorkspaceStamp::current(&monorepo_root, &packages, &exclude) {
+            save_workspace_cache(&config_dir, &stamp);
+        }
+        let _ = tx.send(ScanUpdate::Done(packages));
+    });
+    rx
+}
+
+fn encode(packages: &[WorkspacePackage]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    write_len(&mut out, packages.len());
+    for pkg in packages {
+        write_str(&mut out, &pkg.name);
+        write_str(&mut out, &pkg.relative_path);
+        write_str(&mut out, pkg.description.as_deref().unwrap_or(""));
+        write_str(&mut out, pkg.version.as_deref().unwrap_or(""));
+        out.push(pkg.private as u8);
+        write_len(&mut out, pkg.scripts.len());
+        for (name, command) in &pkg.scripts {
+            write_str(&mut out, name);
+            write_str(&mut out, command);
+        }
+    }
+    out
+}
+
+fn decode(bytes: &[u8]) -> Option<Vec<WorkspacePackage>> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+        return None;
+    }
+
+    let count = reader.length()?;
+    let mut packages = Vec::with_capacity(count.min(bytes.len()));
+    for _ in 0..count {
+        let name = reader.string()?;
+        let relative_path = reader.string()?;
+        let description = Some(reader.string()?).filter(|d| !d.is_empty());
+        let version = Some(reader.string()?).filter(|v| !v.is_empty());
+        let private = reader.take(1)? != [0];
+        let script_count = reader.length()?;
+        let mut scripts = IndexMap::with_capacity(script_count.min(bytes.len()));
+        for _ in 0..script_count {
+            let script = reader.string()?;
+            scripts.insert(script, reader.string()?);
+        }
+        packages.push(WorkspacePackage {
+            name,
+            relative_path,
+            scripts,
+            description,
+            version,
+            private,
+        });
+    }
+
+    // Trailing bytes mean the file isn't what we wrote
+    (reader.pos == bytes.len()).then_some(packages)
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    write_len(out, s.len());
+    out.extend_from_slice(s.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn length(&mut self) -> Option<usize> {
+        let bytes = self.take(4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?) as usize)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.length()?;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn package(name: &str, path: &str, scripts: &[(&str, &str)]) -> WorkspacePackage {
+        WorkspacePackage {
+            name: name.to_string(),
+            relative_path: path.to_string(),
+            scripts: scripts
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            description: None,
+            version: None,
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_preserves_packages_and_script_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let packages = vec![
+            package(
+                "@mono/web",
+                "apps/web",
+                &[("dev", "vite"), ("build", "vite build")],
+            ),
+            WorkspacePackage {
+                description: Some("Shared UI kit".to_string()),
+                version: None,
+                private: false,
+                ..package("@mono/ü", "packages/ü", &[])
+            },
+        ];
+
+        save_workspace_index(temp_dir.path(), &packages);
+        let loaded = load_workspace_index(temp_dir.path()).unwrap();
+
+        assert_eq!(loaded, packages);
+        let scripts: Vec<&String> = loaded[0].scripts.keys().collect();
+        assert_eq!(scripts, vec!["dev", "build"]);
+    }
+
+    #[test]
+    fn test_missing_index_returns_none() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_workspace_index(temp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_corrupted_index_returns_none() {
+        let bytes = encode(&[package("a", "packages/a", &[("dev", "vite")])]);
+
+        assert!(decode(&bytes[..bytes.len() - 1]).is_none());
+        assert!(decode(b"NRWI\x09").is_none());
+        // A huge declared count must fail cleanly instead of allocating
+        assert!(decode(b"NRWI\x02\xff\xff\xff\xff").is_none());
+    }
+}
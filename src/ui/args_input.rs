@@ -1,18 +1,24 @@
+use crate::ui::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_args_input(
     frame: &mut Frame,
     area: Rect,
+    theme: &Theme,
     input: &str,
     cursor_pos: usize,
+    suggestion: Option<&str>,
     history: &[String],
+    own_history: usize,
     history_index: Option<usize>,
+    warning: Option<&str>,
 ) {
     // Calculate modal size (centered, 60% width, 50% height)
     let modal_width = (area.width as f32 * 0.6) as u16;
@@ -34,7 +40,7 @@ pub fn render_args_input(
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Additional Arguments ")
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     frame.render_widget(block, modal_area);
 
     // Split modal into: input field + examples + history list + status bar
@@ -51,10 +57,7 @@ pub fn render_args_input(
 
     // Render input field with cursor at position
     let input_text = if input.is_empty() {
-        vec![Span::styled(
-            "█",
-            Style::default().bg(Color::White).fg(Color::Black),
-        )]
+        vec![Span::styled(theme.symbols.text_cursor, theme.cursor())]
     } else {
         let mut spans = Vec::new();
         let chars: Vec<char> = input.chars().collect();
@@ -66,10 +69,7 @@ pub fn render_args_input(
 
         // Cursor (block character at position)
         if cursor_pos < chars.len() {
-            spans.push(Span::styled(
-                chars[cursor_pos].to_string(),
-                Style::default().bg(Color::White).fg(Color::Black),
-            ));
+            spans.push(Span::styled(chars[cursor_pos].to_string(), theme.cursor()));
 
             // Characters after cursor
             if cursor_pos + 1 < chars.len() {
@@ -77,12 +77,18 @@ pub fn render_args_input(
                     chars[cursor_pos + 1..].iter().collect::<String>(),
                 ));
             }
-        } else {
-            // Cursor at end
+        } else if let Some(suggestion) = suggestion.filter(|s| !s.is_empty()) {
+            // Cursor at end, over the ghost text of the history match (Tab/→ takes it)
+            let mut ghost = suggestion.chars();
+            let first = ghost.next().unwrap_or_default();
+            spans.push(Span::styled(first.to_string(), theme.cursor()));
             spans.push(Span::styled(
-                "█",
-                Style::default().bg(Color::White).fg(Color::Black),
+                ghost.collect::<String>(),
+                Style::default().fg(theme.muted),
             ));
+        } else {
+            // Cursor at end
+            spans.push(Span::styled(theme.symbols.text_cursor, theme.cursor()));
         }
 
         spans
@@ -97,37 +103,54 @@ pub fn render_args_input(
     .block(Block::default().borders(Borders::ALL));
     frame.render_widget(input_widget, chunks[0]);
 
-    // Render examples
-    let examples = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Examples: ", Style::default().fg(Color::DarkGray)),
-        Span::styled("--port 3000", Style::default().fg(Color::Green)),
+    // Render examples, and below them flags the script's tool doesn't know
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Examples: ", Style::default().fg(theme.muted)),
+        Span::styled("--port 3000", Style::default().fg(theme.success)),
         Span::raw("  "),
-        Span::styled("--watch", Style::default().fg(Color::Green)),
+        Span::styled("--watch", Style::default().fg(theme.success)),
         Span::raw("  "),
-        Span::styled("--env production", Style::default().fg(Color::Green)),
-    ])])
-    .style(Style::default());
+        Span::styled("--env production", Style::default().fg(theme.success)),
+    ])];
+    if let Some(warning) = warning {
+        lines.push(Line::from(Span::styled(
+            format!("{} {}", theme.symbols.warning, warning),
+            Style::default().fg(theme.highlight),
+        )));
+    }
+    let examples = Paragraph::new(lines).style(Style::default());
     frame.render_widget(examples, chunks[1]);
 
-    // Render history list (show up to 5 most recent)
+    // Render history list (show up to 5 most recent), the script's own first
     if !history.is_empty() {
+        let title = if own_history > 0 {
+            "Recent"
+        } else {
+            "Other scripts"
+        };
         let mut history_items = vec![ListItem::new(Line::from(Span::styled(
-            "Recent (↑↓):",
-            Style::default().fg(Color::Cyan),
+            format!("{} ({}):", title, theme.symbols.up_down),
+            Style::default().fg(theme.accent),
         )))];
 
         for (idx, entry) in history.iter().take(5).enumerate() {
+            if idx > 0 && idx == own_history {
+                history_items.push(ListItem::new(Line::from(Span::styled(
+                    "Other scripts:",
+                    Style::default().fg(theme.accent),
+                ))));
+            }
             let is_selected = history_index == Some(idx);
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.highlight)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
 
             let line_text = if is_selected {
-                format!("❯ {}", entry)
+                format!("{} {}", theme.symbols.pointer, entry)
             } else {
                 format!("  {}", entry)
             };
@@ -140,7 +163,11 @@ pub fn render_args_input(
     }
 
     // Status bar
-    let status = Paragraph::new("←→: Move  ↑↓: History  Enter: Next  Esc: Cancel")
-        .style(Style::default().fg(Color::DarkGray));
+    let status = Paragraph::new(
+        theme
+            .symbols
+            .text("←→: Move  ↑↓: History  Tab: Complete  Enter: Next  Esc: Cancel"),
+    )
+    .style(Style::default().fg(theme.muted));
     frame.render_widget(status, chunks[3]);
 }
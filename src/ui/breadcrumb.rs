@@ -0,0 +1,54 @@
+use crate::ui::theme::Theme;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+/// Where the list below is, e.g. `Packages ▸ @mono/web`, so a package's
+/// scripts can't be mistaken for the root's.
+pub fn render_breadcrumb(frame: &mut Frame, area: Rect, theme: &Theme, steps: &[String]) {
+    frame.render_widget(
+        Paragraph::new(Line::from(breadcrumb_spans(steps, theme))),
+        area,
+    );
+}
+
+/// The steps joined by the breadcrumb symbol, the last one (where you are) bold.
+fn breadcrumb_spans<'a>(steps: &'a [String], theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    for (i, step) in steps.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                format!(" {} ", theme.symbols.breadcrumb),
+                Style::default().fg(theme.muted),
+            ));
+        }
+        let style = if i + 1 == steps.len() {
+            Style::default().fg(theme.accent).bold()
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        spans.push(Span::styled(step.as_str(), style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::symbols::Symbols;
+
+    #[test]
+    fn test_breadcrumb_joins_the_steps() {
+        let steps = vec!["Packages".to_string(), "@mono/web".to_string()];
+        let text = |theme: &Theme| -> String {
+            breadcrumb_spans(&steps, theme)
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        assert_eq!(text(&Theme::DARK), "Packages ▸ @mono/web");
+
+        let mut ascii = Theme::DARK;
+        ascii.symbols = Symbols::ASCII;
+        assert_eq!(text(&ascii), "Packages > @mono/web");
+    }
+}
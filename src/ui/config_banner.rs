@@ -0,0 +1,42 @@
+use crate::store::config::ConfigIssue;
+use crate::ui::theme::Theme;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+/// One-line notice of the first config problem, pointing to `nr doctor` for
+/// the full list.
+pub fn render_config_banner(frame: &mut Frame, area: Rect, theme: &Theme, issues: &[ConfigIssue]) {
+    let Some(first) = issues.first() else {
+        return;
+    };
+    let file = first
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let location = if first.key.is_empty() {
+        file
+    } else {
+        format!("{} {}", file, first.key)
+    };
+    let more = match issues.len() {
+        1 => String::new(),
+        n => format!(" (+{} more)", n - 1),
+    };
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!(" {} ", theme.symbols.warning),
+            Style::default().fg(theme.error).bold(),
+        ),
+        Span::styled(
+            format!("{}: {}{}", location, first.message, more),
+            Style::default().fg(theme.error),
+        ),
+        Span::styled(
+            "  ignored, run `nr doctor` for details",
+            Style::default().fg(theme.muted),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
@@ -0,0 +1,77 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Asks to type `script_name`, one of the `dangerous_scripts`, before it runs.
+pub fn render_danger_prompt(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    script_name: &str,
+    input: &str,
+) {
+    // Calculate modal size (centered, 60% width, fixed height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = 8.min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(format!(" Run {}? ", script_name))
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Warning
+        Constraint::Length(3), // Input field
+        Constraint::Min(0),
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::raw("Type "),
+            Span::styled(
+                script_name,
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" to proceed"),
+        ])),
+        chunks[0],
+    );
+
+    let typed = if input == script_name {
+        Style::default().fg(theme.success)
+    } else {
+        Style::default()
+    };
+    let input_widget = Paragraph::new(Line::from(vec![
+        Span::styled(input, typed),
+        Span::styled(theme.symbols.text_cursor, theme.cursor()),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(input_widget, chunks[1]);
+
+    let status = Paragraph::new("Enter: Run  Esc: Cancel").style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[3]);
+}
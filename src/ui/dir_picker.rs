@@ -0,0 +1,93 @@
+use crate::flow::DirPick;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+pub fn render_dir_picker(frame: &mut Frame, area: Rect, theme: &Theme, pick: &DirPick) {
+    // Centered, 60% width, 70% height
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Working directory in {} ", pick.root.display()))
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Query
+        Constraint::Min(1),    // Directories
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", theme.symbols.pointer),
+                Style::default().fg(theme.accent),
+            ),
+            Span::raw(pick.query.as_str()),
+            Span::styled(theme.symbols.text_cursor, theme.cursor()),
+        ])),
+        chunks[0],
+    );
+
+    // Keep the highlighted row in view
+    let height = chunks[1].height as usize;
+    let offset = (pick.selected + 1).saturating_sub(height);
+    let items: Vec<ListItem> = if pick.matches.is_empty() {
+        vec![ListItem::new("No matching directories").style(Style::default().fg(theme.muted))]
+    } else {
+        pick.matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(row, &index)| {
+                let is_selected = row == pick.selected;
+                let cursor = if is_selected {
+                    theme.symbols.pointer
+                } else {
+                    " "
+                };
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!("{} {}", cursor, pick.label(index))).style(style)
+            })
+            .collect()
+    };
+    frame.render_widget(List::new(items), chunks[1]);
+
+    frame.render_widget(
+        Paragraph::new(
+            theme
+                .symbols
+                .text("Type: Filter  ↑↓: Navigate  Enter: Run here  Esc: Back"),
+        )
+        .style(Style::default().fg(theme.muted)),
+        chunks[2],
+    );
+}
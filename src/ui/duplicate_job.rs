@@ -0,0 +1,75 @@
+use crate::core::jobs::Job;
+use crate::core::runner::format_duration;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::path::Path;
+use std::time::Instant;
+
+/// Asks what to do about starting `job`'s script again while `job` still runs
+/// in `dir` (relative to the project root).
+pub fn render_duplicate_job(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    job: &Job,
+    dir: &Path,
+    now: Instant,
+) {
+    // Calculate modal size (centered, 60% width, fixed height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = 6.min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} is already running ", job.spec.script_name))
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Running job
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let location = if dir.as_os_str().is_empty() {
+        String::new()
+    } else {
+        format!(" in {}", dir.display())
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", theme.symbols.running),
+                Style::default().fg(theme.accent),
+            ),
+            Span::raw(format!(
+                "Started {} ago{}",
+                format_duration(job.elapsed(now)),
+                location
+            )),
+        ])),
+        chunks[0],
+    );
+
+    let status = Paragraph::new("Enter: Show it  s: Start another  Esc: Cancel")
+        .style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[1]);
+}
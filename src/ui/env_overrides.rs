@@ -0,0 +1,123 @@
+use crate::core::secrets::SecretEnv;
+use crate::flow::EnvOverrides;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+/// The variables set on top of the env files for this run, one `KEY=value`
+/// row each, and a last row for adding one. Values of `secrets` are masked.
+pub fn render_env_overrides(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    overrides: &EnvOverrides,
+    secrets: &SecretEnv,
+) {
+    // Centered, 60% width, tall enough for every row
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (overrides.vars.len() as u16 + 5).min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Env overrides ")
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Variables
+        Constraint::Length(1), // Hint
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let rows = overrides
+        .vars
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, secrets.display(key, value)))
+        .chain(std::iter::once("+ Add variable".to_string()));
+    let items: Vec<ListItem> = rows
+        .enumerate()
+        .map(|(idx, row)| {
+            let is_selected = idx == overrides.selected;
+            let cursor = if is_selected {
+                theme.symbols.pointer
+            } else {
+                " "
+            };
+            match overrides.input {
+                Some(ref input) if is_selected => ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", cursor)),
+                    Span::raw(masked_input(input, secrets)),
+                    Span::styled(theme.symbols.text_cursor, theme.cursor()),
+                ])),
+                _ => {
+                    let style = if is_selected {
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD)
+                    } else if idx == overrides.vars.len() {
+                        Style::default().fg(theme.muted)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(format!("{} {}", cursor, row)).style(style)
+                }
+            }
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new("Set on top of the env files, for this script's runs")
+            .style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+
+    let status = if overrides.input.is_some() {
+        "Type KEY=value  Enter: Set  Esc: Cancel"
+    } else {
+        "↑↓: Navigate  Enter: Edit  d: Delete  Esc: Back"
+    };
+    frame.render_widget(
+        Paragraph::new(theme.symbols.text(status)).style(Style::default().fg(theme.muted)),
+        chunks[2],
+    );
+}
+
+/// The `KEY=value` being typed, its value masked once the key is a secret's.
+fn masked_input(input: &str, secrets: &SecretEnv) -> String {
+    match input.split_once('=') {
+        Some((key, value)) => format!("{}={}", key, secrets.display(key.trim(), value)),
+        None => input.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_input_hides_secret_values_as_typed() {
+        let secrets = SecretEnv::default();
+        assert_eq!(masked_input("DEBUG=app:*", &secrets), "DEBUG=app:*");
+        assert_eq!(masked_input("API_TOKEN=abc", &secrets), "API_TOKEN=****");
+        assert_eq!(masked_input("API_TOK", &secrets), "API_TOK");
+    }
+}
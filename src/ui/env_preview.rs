@@ -0,0 +1,148 @@
+use crate::core::secrets::SecretEnv;
+use crate::flow::{EnvPreview, PreviewVar};
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// The merged environment of the checked env files: each variable with the
+/// file its value comes from, and the files that file overrides. Values of
+/// `secrets` are masked.
+pub fn render_env_preview(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    preview: &EnvPreview,
+    secrets: &SecretEnv,
+) {
+    // Centered, 80% width, 70% height
+    let modal_width = (area.width as f32 * 0.8) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    let overridden = preview
+        .vars
+        .iter()
+        .filter(|var| !var.overridden.is_empty())
+        .count();
+    let title = match overridden {
+        0 => format!(" Environment: {} variables ", preview.vars.len()),
+        n => format!(
+            " Environment: {} variables, {} overridden ",
+            preview.vars.len(),
+            n
+        ),
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(
+        modal_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        }),
+    );
+
+    let lines: Vec<Line> = if preview.vars.is_empty() {
+        vec![Line::styled(
+            "No variables: check env files with Space",
+            Style::default().fg(theme.muted),
+        )]
+    } else {
+        preview
+            .vars
+            .iter()
+            .skip(preview.scroll)
+            .take(chunks[0].height as usize)
+            .map(|var| var_line(var, secrets, theme))
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(theme.symbols.text("↑↓: Scroll  Esc: Back"))
+            .style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+}
+
+/// `PORT=3000  .env.local  overrides .env`
+fn var_line<'a>(var: &'a PreviewVar, secrets: &SecretEnv, theme: &Theme) -> Line<'a> {
+    let mut spans = vec![
+        Span::styled(
+            var.key.as_str(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("="),
+        Span::raw(secrets.display(&var.key, &var.value)),
+        Span::styled(
+            format!("  {}", var.source),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+    if !var.overridden.is_empty() {
+        spans.push(Span::styled(
+            format!("  overrides {}", var.overridden.join(", ")),
+            Style::default().fg(theme.highlight),
+        ));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_var_line_marks_overridden_files() {
+        let theme = Theme::default();
+        let secrets = SecretEnv::default();
+        let var = PreviewVar {
+            key: "PORT".to_string(),
+            value: "3000".to_string(),
+            source: ".env.local".to_string(),
+            overridden: vec![".env (root)".to_string(), ".env".to_string()],
+        };
+        assert_eq!(
+            var_line(&var, &secrets, &theme).to_string(),
+            "PORT=3000  .env.local  overrides .env (root), .env"
+        );
+
+        let var = PreviewVar {
+            overridden: Vec::new(),
+            ..var
+        };
+        assert_eq!(
+            var_line(&var, &secrets, &theme).to_string(),
+            "PORT=3000  .env.local"
+        );
+
+        let var = PreviewVar {
+            key: "GITHUB_TOKEN".to_string(),
+            value: "ghp_1234abcd".to_string(),
+            ..var
+        };
+        assert_eq!(
+            var_line(&var, &secrets, &theme).to_string(),
+            "GITHUB_TOKEN=g****d  .env.local"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Asks for the value of `missing[0]`, a `required_env` key the run of
+/// `script_name` would otherwise start without.
+pub fn render_env_prompt(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    script_name: &str,
+    missing: &[String],
+    input: &str,
+) {
+    let Some(key) = missing.first() else {
+        return;
+    };
+
+    // Calculate modal size (centered, 60% width, fixed height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = 8.min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} needs env ", script_name))
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Input field
+        Constraint::Min(1),    // Hint
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let input_widget = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{}=", key), Style::default().fg(theme.accent)),
+        Span::raw(input),
+        Span::styled(theme.symbols.text_cursor, theme.cursor()),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(input_widget, chunks[0]);
+
+    let hint = if missing.len() > 1 {
+        format!(
+            "Not set by env files or inherited; then {}",
+            missing[1..].join(", ")
+        )
+    } else {
+        "Not set by env files or inherited".to_string()
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+
+    let status =
+        Paragraph::new("Enter: Next  Esc: Cancel run").style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[2]);
+}
@@ -1,21 +1,26 @@
-use crate::core::env_files::{EnvFileList, EnvScope};
+use crate::core::env_files::{EnvFile, EnvFileList, EnvScope};
+use crate::flow::NewEnvFile;
+use crate::ui::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 use std::collections::HashSet;
 use std::path::PathBuf;
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_env_selector(
     frame: &mut Frame,
     area: Rect,
+    theme: &Theme,
     env_list: &EnvFileList,
     selected_index: usize,
     _scroll_offset: usize,
     selected_files: &HashSet<PathBuf>,
+    new_file: Option<&NewEnvFile>,
 ) {
     // Calculate modal size (centered, 60% width, 70% height)
     let modal_width = (area.width as f32 * 0.6) as u16;
@@ -37,7 +42,7 @@ pub fn render_env_selector(
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Environment Files ")
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     frame.render_widget(block, modal_area);
 
     // Split modal into content + status bar
@@ -50,116 +55,196 @@ pub fn render_env_selector(
         vertical: 1,
     }));
 
-    // Build list of all env files with section headers
+    // Env files under section headers, grouped by directory
     let mut items = Vec::new();
-    let mut flat_indices = Vec::new(); // Map display index -> (scope, file_index)
-
-    // Package section
-    if !env_list.package_files.is_empty() {
-        let scope_display = if let Some(first) = env_list.package_files.first() {
-            match &first.scope {
-                EnvScope::Package(path) => format!("Package: {}", path.display()),
-                _ => "Package:".to_string(),
+    let mut display_idx = 0;
+    for row in selector_rows(env_list) {
+        match row {
+            SelectorRow::Rule => {
+                items.push(ListItem::new(Line::from(theme.symbols.rule.repeat(33))));
             }
-        } else {
-            "Package:".to_string()
-        };
-
-        items.push(
-            ListItem::new(Line::from(Span::styled(
-                scope_display,
+            SelectorRow::Section(title) => items.push(ListItem::new(Line::from(Span::styled(
+                title,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
-            )))
-            .style(Style::default()),
-        );
-
-        for (idx, _env_file) in env_list.package_files.iter().enumerate() {
-            flat_indices.push(("package", idx));
+            )))),
+            SelectorRow::Directory(dir) => items.push(
+                ListItem::new(Line::from(format!("  {}/", dir)))
+                    .style(Style::default().fg(theme.muted)),
+            ),
+            SelectorRow::File(env_file) => {
+                let is_selected = display_idx == selected_index;
+                let is_checked = selected_files.contains(&env_file.path);
+                display_idx += 1;
+
+                let checkbox = if is_checked { "[x]" } else { "[ ]" };
+                let cursor = if is_selected {
+                    theme.symbols.pointer
+                } else {
+                    " "
+                };
+
+                // Files of subdirectories sit under their directory's row;
+                // the others show their parent directory for context
+                let mut line_text = match env_file.display_name.rsplit_once('/') {
+                    Some((_, name)) => format!("{} {}   {}", cursor, checkbox, name),
+                    None => {
+                        let path_hint = env_file
+                            .path
+                            .parent()
+                            .and_then(|parent| parent.file_name())
+                            .map(|name| format!(" ({})", name.to_string_lossy()))
+                            .unwrap_or_default();
+                        format!(
+                            "{} {} {}{}",
+                            cursor, checkbox, env_file.display_name, path_hint
+                        )
+                    }
+                };
+                if env_file.encrypted {
+                    line_text.push_str("  [encrypted]");
+                }
+
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else if is_checked {
+                    Style::default().fg(theme.success)
+                } else {
+                    Style::default()
+                };
+
+                items.push(ListItem::new(Line::from(line_text)).style(style));
+            }
         }
     }
 
-    // Root section
-    if !env_list.root_files.is_empty() {
-        if !items.is_empty() {
-            items.push(ListItem::new(Line::from(
-                "─────────────────────────────────",
-            )));
-        }
+    let list = List::new(items);
+    frame.render_widget(list, chunks[0]);
 
-        let scope_display = if let Some(first) = env_list.root_files.first() {
-            match &first.scope {
-                EnvScope::Root(path) => format!("Root: {}", path.display()),
-                _ => "Root:".to_string(),
+    // Status bar, or the name prompt of a new file
+    let status = match new_file {
+        Some(new_file) => {
+            let mut spans = vec![
+                Span::styled("New file: ", Style::default().fg(theme.accent)),
+                Span::raw(new_file.name.as_str()),
+                Span::styled(theme.symbols.text_cursor, theme.cursor()),
+            ];
+            if new_file.example.is_some() {
+                let checkbox = if new_file.from_example { "[x]" } else { "[ ]" };
+                spans.push(Span::styled(
+                    format!("  Tab: {} from .env.example", checkbox),
+                    Style::default().fg(theme.muted),
+                ));
             }
-        } else {
-            "Root:".to_string()
-        };
+            match &new_file.error {
+                Some(error) => spans.push(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(theme.error),
+                )),
+                None => spans.push(Span::styled(
+                    "  Enter: Create  Esc: Cancel",
+                    Style::default().fg(theme.muted),
+                )),
+            }
+            Paragraph::new(Line::from(spans))
+        }
+        None => Paragraph::new(theme.symbols.text(
+            "↑↓: Navigate  Space: Toggle  n: New  e: Overrides  p: Preview  Enter: Next  Esc: Cancel",
+        ))
+        .style(Style::default().fg(theme.muted)),
+    };
+    frame.render_widget(status, chunks[1]);
+}
 
-        items.push(
-            ListItem::new(Line::from(Span::styled(
-                scope_display,
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )))
-            .style(Style::default()),
-        );
+/// A row of the selector: files in [`EnvFileList::all_files`] order, so the
+/// n-th `File` row is the n-th file.
+#[derive(Debug, PartialEq)]
+enum SelectorRow<'a> {
+    Rule,
+    Section(String),
+    /// A subdirectory, relative to the section's directory, heading its files.
+    Directory(&'a str),
+    File(&'a EnvFile),
+}
 
-        for (idx, _env_file) in env_list.root_files.iter().enumerate() {
-            flat_indices.push(("root", idx));
+/// The package section, then the root section, each with its files grouped by directory.
+fn selector_rows(env_list: &EnvFileList) -> Vec<SelectorRow<'_>> {
+    let mut rows = Vec::new();
+    for files in [&env_list.package_files, &env_list.root_files] {
+        let Some(first) = files.first() else {
+            continue;
+        };
+        if !rows.is_empty() {
+            rows.push(SelectorRow::Rule);
+        }
+        rows.push(SelectorRow::Section(match &first.scope {
+            EnvScope::Package(path) => format!("Package: {}", path.display()),
+            EnvScope::Root(path) => format!("Root: {}", path.display()),
+        }));
+
+        let mut current_dir = None;
+        for file in files.iter() {
+            let dir = file.display_name.rsplit_once('/').map(|(dir, _)| dir);
+            if dir != current_dir {
+                if let Some(dir) = dir {
+                    rows.push(SelectorRow::Directory(dir));
+                }
+                current_dir = dir;
+            }
+            rows.push(SelectorRow::File(file));
         }
     }
+    rows
+}
 
-    // Render file items
-    for (display_idx, (scope, file_idx)) in flat_indices.iter().enumerate() {
-        let env_file = if *scope == "package" {
-            &env_list.package_files[*file_idx]
-        } else {
-            &env_list.root_files[*file_idx]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector_rows_group_files_by_directory() {
+        let file = |name: &str, scope: EnvScope| EnvFile {
+            path: PathBuf::from("/repo").join(name),
+            display_name: name.to_string(),
+            scope,
+            encrypted: false,
         };
-
-        let is_selected = display_idx == selected_index;
-        let is_checked = selected_files.contains(&env_file.path);
-
-        let checkbox = if is_checked { "[x]" } else { "[ ]" };
-        let cursor = if is_selected { "❯ " } else { "  " };
-
-        // Show parent directory path for context
-        let path_hint = if let Some(parent) = env_file.path.parent() {
-            if let Some(parent_name) = parent.file_name() {
-                format!(" ({})", parent_name.to_string_lossy())
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
+        let package = EnvScope::Package(PathBuf::from("/repo/apps/web"));
+        let root = EnvScope::Root(PathBuf::from("/repo"));
+        let list = EnvFileList {
+            package_files: vec![
+                file(".env", package.clone()),
+                file("config/env/.env.staging", package.clone()),
+                file("config/env/.env.test", package),
+            ],
+            root_files: vec![file(".env", root)],
         };
 
-        let line_text = format!(
-            "{}{} {}{}",
-            cursor, checkbox, env_file.display_name, path_hint
+        let rows = selector_rows(&list);
+        let labels: Vec<String> = rows
+            .iter()
+            .map(|row| match row {
+                SelectorRow::Rule => "---".to_string(),
+                SelectorRow::Section(title) => title.clone(),
+                SelectorRow::Directory(dir) => format!("{}/", dir),
+                SelectorRow::File(file) => file.display_name.clone(),
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Package: /repo/apps/web",
+                ".env",
+                "config/env/",
+                "config/env/.env.staging",
+                "config/env/.env.test",
+                "---",
+                "Root: /repo",
+                ".env",
+            ]
         );
-
-        let style = if is_selected {
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else if is_checked {
-            Style::default().fg(Color::Green)
-        } else {
-            Style::default()
-        };
-
-        items.push(ListItem::new(Line::from(line_text)).style(style));
     }
-
-    let list = List::new(items);
-    frame.render_widget(list, chunks[0]);
-
-    // Status bar
-    let status = Paragraph::new("↑↓: Navigate  Space: Toggle  Enter: Next  Esc: Cancel")
-        .style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(status, chunks[1]);
 }
@@ -1,21 +1,31 @@
-use crate::core::package_manager::PackageManager;
+use crate::core::package_manager::shell_join;
+use crate::ui::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 use std::path::Path;
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_execution_confirm(
     frame: &mut Frame,
     area: Rect,
-    pm: PackageManager,
-    script_name: &str,
+    theme: &Theme,
+    command: &[String],
+    args: &[String],
+    unclosed_quote: bool,
     env_files: &[String],
-    args: &str,
+    missing_keys: &[(String, Vec<String>)],
     cwd: &Path,
+    package_manager: &str,
+    before: &[String],
+    post_run: &[String],
+    hooks_note: Option<&str>,
+    watch: Option<&str>,
+    preset_name: Option<&str>,
 ) {
     // Calculate modal size (centered, 70% width, 60% height)
     let modal_width = (area.width as f32 * 0.7) as u16;
@@ -37,7 +47,7 @@ pub fn render_execution_confirm(
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Ready to Execute ")
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     frame.render_widget(block, modal_area);
 
     // Split modal into content + status bar
@@ -54,51 +64,172 @@ pub fn render_execution_confirm(
     let mut content_items = Vec::new();
 
     // Command preview
-    let cmd_args = pm.run_args(script_name);
-    let cmd_text = if args.is_empty() {
-        format!("$ {} {}", pm.command_name(), cmd_args.join(" "))
-    } else {
-        format!("$ {} {} {}", pm.command_name(), cmd_args.join(" "), args)
-    };
+    let cmd_text = format!("$ {}", shell_join(command));
 
     content_items.push(ListItem::new(Line::from(Span::styled(
         cmd_text,
-        Style::default().fg(Color::Green).bold(),
+        Style::default().fg(theme.success).bold(),
     ))));
 
     content_items.push(ListItem::new(Line::from("")));
 
+    // Arguments as the script will receive them, one box per argument
+    if !args.is_empty() {
+        content_items.push(ListItem::new(Line::from(args_spans(args, theme))));
+        if unclosed_quote {
+            content_items.push(ListItem::new(Line::from(Span::styled(
+                "      Unclosed quote: arguments are split on spaces",
+                Style::default().fg(theme.highlight),
+            ))));
+        }
+        content_items.push(ListItem::new(Line::from("")));
+    }
+
     // Environment files
     if !env_files.is_empty() {
         content_items.push(ListItem::new(Line::from(Span::styled(
             "Env:",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         ))));
 
         for env_file in env_files {
             content_items.push(
-                ListItem::new(Line::from(format!("  • {}", env_file)))
-                    .style(Style::default().fg(Color::DarkGray)),
+                ListItem::new(Line::from(format!(
+                    "  {} {}",
+                    theme.symbols.bullet, env_file
+                )))
+                .style(Style::default().fg(theme.muted)),
             );
         }
 
+        // Keys the project's example env file lists but nothing sets
+        for (template, keys) in missing_keys {
+            content_items.push(ListItem::new(Line::from(Span::styled(
+                format!(
+                    "  {} Missing from {}: {}",
+                    theme.symbols.warning,
+                    template,
+                    keys.join(", ")
+                ),
+                Style::default().fg(theme.highlight),
+            ))));
+        }
+
         content_items.push(ListItem::new(Line::from("")));
     }
 
     // Working directory
     content_items.push(
         ListItem::new(Line::from(vec![
-            Span::styled("CWD: ", Style::default().fg(Color::Cyan)),
+            Span::styled("CWD: ", Style::default().fg(theme.accent)),
             Span::raw(cwd.display().to_string()),
         ]))
-        .style(Style::default().fg(Color::DarkGray)),
+        .style(Style::default().fg(theme.muted)),
+    );
+    content_items.push(
+        ListItem::new(Line::from(vec![
+            Span::styled("PM:  ", Style::default().fg(theme.accent)),
+            Span::raw(package_manager.to_string()),
+        ]))
+        .style(Style::default().fg(theme.muted)),
     );
 
+    // Hooks run before the script
+    if !before.is_empty() {
+        content_items.push(ListItem::new(Line::from("")));
+        content_items.push(ListItem::new(Line::from(Span::styled(
+            "Before:",
+            Style::default().fg(theme.accent),
+        ))));
+
+        for command in before {
+            content_items.push(
+                ListItem::new(Line::from(format!(
+                    "  {} {}",
+                    theme.symbols.bullet, command
+                )))
+                .style(Style::default().fg(theme.muted)),
+            );
+        }
+    }
+
+    // Post-run hooks
+    if !post_run.is_empty() {
+        content_items.push(ListItem::new(Line::from("")));
+        content_items.push(ListItem::new(Line::from(Span::styled(
+            "After:",
+            Style::default().fg(theme.accent),
+        ))));
+
+        for hook in post_run {
+            content_items.push(
+                ListItem::new(Line::from(format!("  {} {}", theme.symbols.bullet, hook)))
+                    .style(Style::default().fg(theme.muted)),
+            );
+        }
+    }
+
+    // Run paths that skip some of the hooks
+    if let Some(note) = hooks_note {
+        content_items.push(
+            ListItem::new(Line::from(format!("  {}", note)))
+                .style(Style::default().fg(theme.highlight)),
+        );
+    }
+
+    // Watch mode
+    if let Some(globs) = watch {
+        content_items.push(ListItem::new(Line::from("")));
+        content_items.push(ListItem::new(Line::from(vec![
+            Span::styled("Watch: ", Style::default().fg(theme.accent)),
+            Span::raw(format!("runs again when {} change", globs)),
+        ])));
+    }
+
     let content_list = List::new(content_items);
     frame.render_widget(content_list, chunks[0]);
 
-    // Status bar
-    let status =
-        Paragraph::new("Enter: Execute  Esc: Cancel").style(Style::default().fg(Color::DarkGray));
+    // Status bar, or the name of the preset being saved
+    let status = match preset_name {
+        Some(name) => Paragraph::new(Line::from(vec![
+            Span::styled("Save preset as: ", Style::default().fg(theme.accent)),
+            Span::raw(name),
+            Span::styled(theme.symbols.text_cursor, theme.cursor()),
+            Span::styled("  Enter: Save  Esc: Cancel", Style::default().fg(theme.muted)),
+        ])),
+        None => Paragraph::new(
+            "Enter: Execute  ^L: Watch  ^D: CWD  ^B: Background  ^X: Matrix  ^A: Queue  ^T: Schedule  ^S: Save preset  ^Y: Copy  ^P: Print  ^E: Print env  ^W: Save env  Esc: Cancel",
+        )
+        .style(Style::default().fg(theme.muted)),
+    };
     frame.render_widget(status, chunks[1]);
 }
+
+/// `Args: [--name] [my app]`, each argument bracketed so spaces inside one show.
+fn args_spans<'a>(args: &'a [String], theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = vec![Span::styled("Args: ", Style::default().fg(theme.accent))];
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled("[", Style::default().fg(theme.muted)));
+        spans.push(Span::raw(arg.as_str()));
+        spans.push(Span::styled("]", Style::default().fg(theme.muted)));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_are_bracketed_one_by_one() {
+        let args = vec!["--name".to_string(), "my app".to_string()];
+        let text: String = args_spans(&args, &Theme::DARK)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "Args: [--name] [my app]");
+    }
+}
@@ -1,35 +1,147 @@
+use crate::ui::theme::Theme;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
+/// Header shape used when no `header_format` is configured.
+pub const DEFAULT_HEADER_FORMAT: &str = "{project}  {path}  {pm}";
+
+/// Values the header's `{placeholders}` stand for.
+pub struct HeaderInfo<'a> {
+    pub project_name: &'a str,
+    pub project_path: &'a str,
+    pub package_manager: &'a str,
+    /// Workspace packages (0 outside monorepos).
+    pub packages: usize,
+    /// Scripts of the nearest `package.json`.
+    pub scripts: usize,
+    /// Background and pane jobs still running.
+    pub running_jobs: usize,
+    /// Favorites changed since they were last written to disk.
+    pub unsaved: bool,
+}
+
 pub fn render_header_bar(
     frame: &mut Frame,
     area: Rect,
-    project_name: &str,
-    project_path: &str,
-    package_manager: &str,
+    theme: &Theme,
+    format: &str,
+    info: &HeaderInfo,
 ) {
-    let display_path = shorten_path(project_path);
-
-    let line = Line::from(vec![
-        Span::styled(project_name, Style::default().fg(Color::Cyan).bold()),
-        Span::styled("  ", Style::default()),
-        Span::styled(display_path, Style::default().dim()),
-        Span::styled("  ", Style::default()),
-        Span::styled(package_manager, Style::default().fg(Color::Green)),
-    ]);
     frame.render_widget(
-        Paragraph::new(line).style(Style::default().bg(Color::DarkGray)),
+        Paragraph::new(Line::from(header_spans(format, info, theme)))
+            .style(Style::default().bg(theme.selection)),
         area,
     );
 }
 
-fn shorten_path(path: &str) -> String {
-    if let Some(home) = dirs::home_dir() {
-        if let Some(home_str) = home.to_str() {
-            if let Some(rest) = path.strip_prefix(home_str) {
-                return format!("~{rest}");
+/// Expands `format`: `{project}`, `{path}`, `{pm}`, `{packages}`, `{scripts}`,
+/// `{jobs}` and `{saved}` become styled values; anything else is kept as is.
+fn header_spans(format: &str, info: &HeaderInfo, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start + 1..start + len];
+        let value = match placeholder {
+            "project" => Some(Span::styled(
+                info.project_name.to_string(),
+                Style::default().fg(theme.accent).bold(),
+            )),
+            "path" => Some(Span::styled(
+                shorten_path(info.project_path),
+                Style::default().dim(),
+            )),
+            "pm" => Some(Span::styled(
+                info.package_manager.to_string(),
+                Style::default().fg(theme.success),
+            )),
+            "packages" => Some(Span::raw(info.packages.to_string())),
+            "scripts" => Some(Span::raw(info.scripts.to_string())),
+            "jobs" => Some(Span::raw(info.running_jobs.to_string())),
+            "saved" if info.unsaved => Some(Span::styled(
+                "unsaved",
+                Style::default().fg(theme.highlight),
+            )),
+            "saved" => Some(Span::styled("saved", Style::default().dim())),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                if start > 0 {
+                    spans.push(Span::raw(rest[..start].to_string()));
+                }
+                spans.push(value);
             }
+            None => spans.push(Span::raw(rest[..start + len + 1].to_string())),
         }
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        spans.push(Span::raw(rest.to_string()));
+    }
+    spans
+}
+
+fn shorten_path(path: &str) -> String {
+    match dirs::home_dir() {
+        Some(home) => shorten_with_home(path, &home),
+        None => path.to_string(),
+    }
+}
+
+/// `path` with a leading `home` directory shown as `~`, compared by path
+/// components so `C:\Users\me` and `/home/me` both work and `/home/meg`
+/// isn't taken for `/home/me`.
+fn shorten_with_home(path: &str, home: &std::path::Path) -> String {
+    match std::path::Path::new(path).strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~{}{}", std::path::MAIN_SEPARATOR, rest.display()),
+        Err(_) => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(format: &str, info: &HeaderInfo) -> String {
+        header_spans(format, info, &Theme::DARK)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_shorten_with_home_compares_whole_components() {
+        let home = std::path::Path::new("/home/me");
+        assert_eq!(
+            shorten_with_home("/home/me/shop", home),
+            format!("~{}shop", std::path::MAIN_SEPARATOR)
+        );
+        assert_eq!(shorten_with_home("/home/me", home), "~");
+        assert_eq!(shorten_with_home("/home/meg/shop", home), "/home/meg/shop");
+    }
+
+    #[test]
+    fn test_header_format_expands_known_placeholders() {
+        let info = HeaderInfo {
+            project_name: "shop",
+            project_path: "/srv/shop",
+            package_manager: "pnpm",
+            packages: 12,
+            scripts: 7,
+            running_jobs: 2,
+            unsaved: true,
+        };
+        assert_eq!(text(DEFAULT_HEADER_FORMAT, &info), "shop  /srv/shop  pnpm");
+        assert_eq!(
+            text(
+                "{project} · {packages} pkgs · {jobs} running · {saved} {nope} {",
+                &info
+            ),
+            "shop · 12 pkgs · 2 running · unsaved {nope} {"
+        );
     }
-    path.to_string()
 }
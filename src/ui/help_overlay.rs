@@ -0,0 +1,293 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::store::keymap::{KeyAction, Keymap};
+use crate::ui::theme::Theme;
+
+/// Keybindings of one mode, as shown in the help overlay.
+pub struct HelpSection {
+    pub title: &'static str,
+    pub keys: &'static [(&'static str, &'static str)],
+}
+
+pub const NORMAL: usize = 0;
+pub const CONFIGURE_ENV: usize = 1;
+pub const CONFIGURE_ARGS: usize = 2;
+pub const CONFIRM: usize = 3;
+pub const QUEUE: usize = 4;
+pub const JOBS: usize = 5;
+pub const OUTPUT: usize = 6;
+pub const QUICK_DIAL: usize = 7;
+pub const SETTINGS: usize = 8;
+pub const SCRIPT_GROUPS: usize = 9;
+pub const PRESETS: usize = 10;
+
+pub const SECTIONS: &[HelpSection] = &[
+    // The remappable keys come from the keymap (see `help_lines`)
+    HelpSection {
+        title: "Script list",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            ("← →", "Switch tabs"),
+            (
+                "Alt+Enter",
+                "Run in the output pane (or the terminal with run_in_pane)",
+            ),
+            ("Click", "Select (double-click runs)"),
+            ("Type", "Fuzzy search"),
+        ],
+    },
+    HelpSection {
+        title: "Env files",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            ("Space", "Toggle the file"),
+            ("n", "Create an env file"),
+            ("e", "Set variables on top of the env files"),
+            ("p", "Preview the merged environment"),
+            ("Enter", "Continue to arguments"),
+            ("Esc", "Cancel"),
+        ],
+    },
+    HelpSection {
+        title: "Arguments",
+        keys: &[
+            ("← → Home End", "Move the cursor"),
+            ("↑ ↓", "Browse previous arguments"),
+            ("Tab →", "Complete the suggested arguments"),
+            ("Enter", "Continue to confirmation"),
+            ("Esc", "Back to env files"),
+        ],
+    },
+    HelpSection {
+        title: "Confirmation",
+        keys: &[
+            ("Enter", "Run"),
+            ("Ctrl+L", "Watch mode: run again when watched files change"),
+            ("Ctrl+D", "Choose the working directory"),
+            ("Ctrl+B", "Run in the background"),
+            ("Ctrl+X", "Run once per env file (matrix)"),
+            ("Ctrl+A", "Add to the execution queue"),
+            ("Ctrl+T", "Schedule to run later"),
+            ("Ctrl+Y", "Copy the command"),
+            ("Ctrl+S", "Save env files and arguments as a preset"),
+            ("Ctrl+P", "Print the command and exit"),
+            ("Ctrl+E", "Print the env as exports and exit"),
+            ("Ctrl+W", "Save the env to .nr-env.sh"),
+            ("Esc", "Back to arguments"),
+        ],
+    },
+    HelpSection {
+        title: "Queue panel",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            ("Shift+↑ ↓  K J", "Reorder"),
+            ("d  Delete", "Remove, or cancel a scheduled run"),
+            ("p", "Pause after the next item"),
+            ("Enter", "Start the queue"),
+            ("c", "Run everything queued at once"),
+            ("Esc", "Close"),
+        ],
+    },
+    HelpSection {
+        title: "Jobs tab",
+        keys: &[
+            ("Enter", "Attach to the output"),
+            ("x", "Stop (again to escalate)"),
+            ("r", "Rerun"),
+            ("d  Delete", "Remove a finished job"),
+            ("Esc", "Back to scripts"),
+        ],
+    },
+    HelpSection {
+        title: "Output pane",
+        keys: &[
+            ("↑ ↓ PgUp PgDn", "Scroll"),
+            ("End", "Follow new output"),
+            ("r", "Restart"),
+            ("n", "Run the suggested next script (once exited)"),
+            ("Ctrl+C", "Stop the script"),
+            ("Esc", "Back (the script keeps running)"),
+        ],
+    },
+    HelpSection {
+        title: "Quick dial",
+        keys: &[
+            ("1-9", "Run that package's script"),
+            ("↑ ↓", "Navigate"),
+            ("Enter", "Run the selected script"),
+            ("Esc", "Close"),
+        ],
+    },
+    HelpSection {
+        title: "Settings",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            ("Enter", "Clear the selected data (asks first)"),
+            ("y / n", "Confirm / keep it"),
+            ("Esc", "Close"),
+        ],
+    },
+    HelpSection {
+        title: "Scripts by name",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            ("Enter →", "Choose packages for the script"),
+            ("Space", "Toggle the package"),
+            ("a", "Toggle all packages"),
+            ("Enter", "Start in the checked packages (Jobs tab)"),
+            ("Esc ←", "Back / close"),
+        ],
+    },
+    HelpSection {
+        title: "Presets",
+        keys: &[
+            ("↑ ↓", "Navigate"),
+            (
+                "Enter",
+                "Continue with the preset (or the last used settings)",
+            ),
+            ("d  Delete", "Delete the preset"),
+            ("Esc", "Cancel"),
+        ],
+    },
+];
+
+/// Help text with the section for the current mode (`first`) on top. The
+/// script list section also lists every `keymap` action with its keys.
+pub fn help_lines(first: usize, keymap: &Keymap, theme: &Theme) -> Vec<Line<'static>> {
+    let rows: Vec<Vec<(String, &'static str)>> = SECTIONS
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let mut rows: Vec<(String, &'static str)> = section
+                .keys
+                .iter()
+                .map(|&(key, action)| (theme.symbols.text(key).into_owned(), action))
+                .collect();
+            if i == NORMAL {
+                for action in KeyAction::ALL {
+                    let keys = keymap.keys_for(action);
+                    if !keys.is_empty() {
+                        rows.push((keys.join(" "), action.description()));
+                    }
+                }
+            }
+            rows
+        })
+        .collect();
+
+    let key_width = rows
+        .iter()
+        .flatten()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let order = std::iter::once(first).chain((0..SECTIONS.len()).filter(|&i| i != first));
+    let mut lines = Vec::new();
+    for (n, i) in order.enumerate() {
+        let section = &SECTIONS[i];
+        if n > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            section.title,
+            Style::default().fg(theme.accent).bold(),
+        ));
+        for (key, action) in &rows[i] {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}", key, width = key_width),
+                    Style::default().bold(),
+                ),
+                Span::raw(*action),
+            ]));
+        }
+    }
+    lines
+}
+
+pub fn render_help_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    first: usize,
+    scroll: usize,
+    keymap: &Keymap,
+) {
+    // Centered, 70% width, 80% height
+    let modal_width = (area.width as f32 * 0.7) as u16;
+    let modal_height = (area.height as f32 * 0.8) as u16;
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Keybindings ")
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(
+        modal_area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        }),
+    );
+
+    let lines = help_lines(first, keymap, theme);
+    let max_scroll = lines.len().saturating_sub(chunks[0].height as usize);
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll.min(max_scroll) as u16, 0)),
+        chunks[0],
+    );
+    frame.render_widget(
+        Paragraph::new(theme.symbols.text("↑↓: Scroll  Esc/?: Close"))
+            .style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_lines_start_with_current_mode() {
+        let lines = help_lines(CONFIRM, &Keymap::default(), &Theme::default());
+
+        assert_eq!(lines[0].to_string(), "Confirmation");
+        // Every section is listed once, with a blank line between sections
+        let total_keys: usize =
+            SECTIONS.iter().map(|s| s.keys.len()).sum::<usize>() + KeyAction::ALL.len();
+        assert_eq!(lines.len(), total_keys + SECTIONS.len() * 2 - 1);
+    }
+
+    #[test]
+    fn test_help_lists_remapped_keys() {
+        let keymap = Keymap::new(&serde_json::from_str(r#"{"enqueue": "ctrl+e"}"#).unwrap());
+
+        let text: Vec<String> = help_lines(NORMAL, &keymap, &Theme::default())
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+
+        assert!(
+            text.iter()
+                .any(|l| l.contains("Ctrl+E") && l.contains("Add to the execution queue"))
+        );
+    }
+}
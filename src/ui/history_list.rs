@@ -0,0 +1,163 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::core::runner::format_duration;
+use crate::store::history::HistoryEntry;
+use crate::ui::theme::Theme;
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_history_list(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    history: &[HistoryEntry],
+    filtered_indices: &[usize],
+    selected_index: usize,
+    scroll_offset: usize,
+    root: &Path,
+    now_ms: u64,
+) {
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(area);
+    let visible_height = chunks[0].height as usize;
+
+    let key_width = filtered_indices
+        .iter()
+        .map(|&i| history[i].key.len())
+        .max()
+        .unwrap_or(12)
+        .max(12)
+        + 2;
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (display_i, &entry_i) in filtered_indices
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_height)
+    {
+        let entry = &history[entry_i];
+        let is_selected = display_i == selected_index;
+        let bg = if is_selected {
+            theme.selection
+        } else {
+            Color::Reset
+        };
+
+        let mut spans = vec![
+            if is_selected {
+                Span::styled(
+                    theme.symbols.selected,
+                    Style::default().fg(theme.accent).bg(bg),
+                )
+            } else {
+                Span::raw(" ")
+            },
+            match entry.exit_code {
+                Some(0) => Span::styled(
+                    format!("{} ", theme.symbols.success),
+                    Style::default().fg(theme.success).bg(bg),
+                ),
+                Some(_) => Span::styled(
+                    format!("{} ", theme.symbols.failure),
+                    Style::default().fg(theme.error).bg(bg),
+                ),
+                None => Span::styled("  ", Style::default().bg(bg)),
+            },
+            Span::styled(
+                format!("{:<width$}", entry.key, width = key_width),
+                Style::default().bold().bg(bg),
+            ),
+            Span::styled(
+                format!("{:>10}  ", format_age(entry.last_run, now_ms)),
+                Style::default().fg(theme.secondary).bg(bg),
+            ),
+            Span::styled(
+                format!(
+                    "{:>8}  ",
+                    entry
+                        .duration_ms
+                        .map(|ms| format_duration(Duration::from_millis(ms)))
+                        .unwrap_or_default()
+                ),
+                Style::default().fg(theme.muted).bg(bg),
+            ),
+        ];
+        if let Some(code) = entry.exit_code.filter(|&code| code != 0) {
+            spans.push(Span::styled(
+                format!("exit {} ", code),
+                Style::default().fg(theme.error).bg(bg),
+            ));
+        }
+        if entry.count > 1 {
+            spans.push(Span::styled(
+                format!("{}{} ", entry.count, theme.symbols.text("×")),
+                Style::default().fg(theme.muted).bg(bg),
+            ));
+        }
+        if !entry.args.is_empty() {
+            spans.push(Span::styled(
+                format!("{} ", entry.args),
+                Style::default().fg(theme.highlight).bg(bg),
+            ));
+        }
+        if !entry.env_files.is_empty() {
+            let env_names: Vec<String> = entry
+                .env_files
+                .iter()
+                .map(|p| p.strip_prefix(root).unwrap_or(p).display().to_string())
+                .collect();
+            spans.push(Span::styled(
+                format!("[{}]", env_names.join(", ")),
+                Style::default().fg(theme.accent).bg(bg),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if filtered_indices.is_empty() {
+        lines.push(Line::styled(
+            "  No matching runs",
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(Text::from(lines)), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {} run again  Tab edit & run  type to search  {} back",
+            theme.symbols.enter, theme.symbols.escape
+        ))
+        .style(Style::default().dim()),
+        chunks[1],
+    );
+}
+
+/// Coarse age of a timestamp, e.g. `5m ago` or `3d ago`.
+fn format_age(then_ms: u64, now_ms: u64) -> String {
+    let secs = now_ms.saturating_sub(then_ms) / 1000;
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        86_400..2_592_000 => format!("{}d ago", secs / 86_400),
+        _ => format!("{}mo ago", secs / 2_592_000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        let now = 100_000_000_000;
+        assert_eq!(format_age(now - 5_000, now), "just now");
+        assert_eq!(format_age(now - 5 * 60_000, now), "5m ago");
+        assert_eq!(format_age(now - 3 * 3_600_000, now), "3h ago");
+        assert_eq!(format_age(now - 40 * 86_400_000, now), "1mo ago");
+        // Clock skew never underflows
+        assert_eq!(format_age(now + 1_000, now), "just now");
+    }
+}
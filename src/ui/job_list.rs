@@ -0,0 +1,93 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::time::Instant;
+
+use crate::core::jobs::JobRegistry;
+use crate::ui::theme::Theme;
+
+pub fn render_job_list(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    jobs: &JobRegistry,
+    selected_index: usize,
+    now: Instant,
+) {
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(area);
+    let visible_height = chunks[0].height as usize;
+
+    let name_width = jobs
+        .iter()
+        .map(|job| job.spec.script_name.len())
+        .max()
+        .unwrap_or(12)
+        .max(12)
+        + 2;
+
+    // Keep the selection in view without tracking a separate scroll offset
+    let skip = (selected_index + 1).saturating_sub(visible_height);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, job) in jobs.iter().enumerate().skip(skip).take(visible_height) {
+        let is_selected = i == selected_index;
+        let bg = if is_selected {
+            theme.selection
+        } else {
+            Color::Reset
+        };
+
+        let (status, status_style) = match job.finished {
+            None => (
+                format!("{} running", theme.symbols.running),
+                Style::default().fg(theme.highlight),
+            ),
+            Some((0, _)) => (
+                format!("{} exited 0", theme.symbols.success),
+                Style::default().fg(theme.success),
+            ),
+            Some((code, _)) => (
+                format!("{} exited {}", theme.symbols.failure, code),
+                Style::default().fg(theme.error),
+            ),
+        };
+        let label = if job.spec.args.is_empty() {
+            job.spec.script_name.clone()
+        } else {
+            format!("{} {}", job.spec.script_name, job.spec.args)
+        };
+
+        lines.push(Line::from(vec![
+            if is_selected {
+                Span::styled(
+                    theme.symbols.selected,
+                    Style::default().fg(theme.accent).bg(bg),
+                )
+            } else {
+                Span::raw(" ")
+            },
+            Span::styled(
+                format!("{:<width$}", label, width = name_width),
+                Style::default().bold().bg(bg),
+            ),
+            Span::styled(format!("{:<12}", status), status_style.bg(bg)),
+            Span::styled(
+                format!("{:>8.1}s  ", job.elapsed(now).as_secs_f64()),
+                Style::default().fg(theme.secondary).bg(bg),
+            ),
+            Span::styled(
+                job.spec.cwd.display().to_string(),
+                Style::default().fg(theme.muted).bg(bg),
+            ),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(Text::from(lines)), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {} attach  x stop  r rerun  d remove  {} back",
+            theme.symbols.enter, theme.symbols.escape
+        ))
+        .style(Style::default().dim()),
+        chunks[1],
+    );
+}
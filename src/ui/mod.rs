@@ -1,9 +1,30 @@
 pub mod args_input;
+pub mod breadcrumb;
+pub mod config_banner;
+pub mod danger_prompt;
+pub mod dir_picker;
+pub mod duplicate_job;
+pub mod env_overrides;
+pub mod env_preview;
+pub mod env_prompt;
 pub mod env_selector;
 pub mod execution_confirm;
 pub mod header_bar;
+pub mod help_overlay;
+pub mod history_list;
+pub mod job_list;
+pub mod output_view;
 pub mod package_list;
+pub mod preset_picker;
+pub mod queue_panel;
+pub mod quick_dial;
+pub mod schedule_input;
+pub mod script_groups;
 pub mod script_list;
 pub mod search_input;
+pub mod settings;
 pub mod status_bar;
+pub mod symbols;
 pub mod tabs;
+pub mod tag_input;
+pub mod theme;
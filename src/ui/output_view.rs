@@ -0,0 +1,127 @@
+use crate::app::OutputView;
+use crate::core::jobs::Job;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::time::Instant;
+
+/// Rows taken by the border, header line and status bar around the output lines.
+pub const OUTPUT_CHROME_HEIGHT: u16 = 4;
+
+/// `suggested` is the script usually run next (`n` runs it once the job has exited).
+#[allow(clippy::too_many_arguments)]
+pub fn render_output_view(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    job: &Job,
+    view: &OutputView,
+    message: Option<&str>,
+    suggested: Option<&str>,
+    now: Instant,
+) {
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", job.spec.script_name));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Status header
+        Constraint::Min(1),    // Output
+        Constraint::Length(1), // Key hints
+    ])
+    .split(inner);
+
+    // Header: running time or exit code + duration
+    let header = match job.finished {
+        None => Line::from(vec![
+            Span::styled(
+                format!("{} running ", theme.symbols.running),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(
+                format!(
+                    "{:.1}s  {}",
+                    job.elapsed(now).as_secs_f64(),
+                    job.spec.cwd.display()
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Some((0, duration)) => Line::from(vec![
+            Span::styled(
+                format!("{} exited 0 ", theme.symbols.success),
+                Style::default().fg(theme.success).bold(),
+            ),
+            Span::styled(
+                format!("in {:.1}s", duration.as_secs_f64()),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Some((code, duration)) => Line::from(vec![
+            Span::styled(
+                format!("{} exited {} ", theme.symbols.failure, code),
+                Style::default().fg(theme.error).bold(),
+            ),
+            Span::styled(
+                format!("in {:.1}s", duration.as_secs_f64()),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    // Output lines (pinned to the bottom while following)
+    let height = chunks[1].height as usize;
+    let start = if view.follow {
+        job.lines.len().saturating_sub(height)
+    } else {
+        view.scroll.min(job.lines.len())
+    };
+    let lines: Vec<Line> = job.lines[start..]
+        .iter()
+        .take(height)
+        .map(|line| {
+            let style = if line.is_stderr {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(line.text.as_str(), style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    // Key hints, replaced by a status message when there is one
+    let hints = match message {
+        Some(message) => Paragraph::new(message).style(Style::default().fg(theme.highlight)),
+        None => {
+            let hints = if job.finished.is_some() {
+                "↑↓ PgUp PgDn: Scroll  r: Restart  Esc/Enter: Back to scripts"
+            } else {
+                "↑↓ PgUp PgDn: Scroll  End: Follow  r: Restart  Ctrl+C: Stop  Esc: Background"
+            };
+            let mut spans = Vec::new();
+            if let Some(key) = suggested {
+                spans.push(Span::styled(
+                    format!("n: Next {}  ", key),
+                    Style::default().fg(theme.accent).bold(),
+                ));
+            }
+            spans.push(Span::styled(
+                theme.symbols.text(hints),
+                Style::default().fg(theme.muted),
+            ));
+            Paragraph::new(Line::from(spans))
+        }
+    };
+    frame.render_widget(hints, chunks[2]);
+}
@@ -1,17 +1,31 @@
 use ratatui::prelude::*;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use std::collections::HashSet;
 
 use crate::core::workspaces::WorkspacePackage;
+use crate::fuzzy::match_indices;
+use crate::store::favorites::package_key;
+use crate::ui::script_list::{highlight_matches, wrapped_rows};
+use crate::ui::theme::Theme;
 
+/// `name_query` and `path_query` highlight the matched characters of the
+/// names and paths (see `App::package_queries`).
+#[allow(clippy::too_many_arguments)]
 pub fn render_package_list(
     frame: &mut Frame,
     area: Rect,
+    theme: &Theme,
     packages: &[WorkspacePackage],
     filtered_indices: &[usize],
     selected_index: usize,
     scroll_offset: usize,
+    favorites: &HashSet<String>,
+    name_query: &str,
+    path_query: &str,
+    scanning: Option<&str>,
 ) {
-    let visible_height = area.height as usize;
+    // While the first scan runs, the last row shows its progress
+    let visible_height = (area.height as usize).saturating_sub(scanning.is_some() as usize);
 
     // Calculate dynamic name column width from filtered packages
     let name_width = filtered_indices
@@ -21,6 +35,12 @@ pub fn render_package_list(
         .unwrap_or(20)
         .max(12)
         + 2;
+    let path_width = filtered_indices
+        .iter()
+        .map(|&i| packages[i].relative_path.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
 
     let mut lines: Vec<Line> = Vec::new();
 
@@ -33,31 +53,184 @@ pub fn render_package_list(
         let pkg = &packages[pkg_i];
         let is_selected = display_i == selected_index;
 
-        let line = if is_selected {
-            Line::from(vec![
-                Span::styled("▎", Style::default().fg(Color::Cyan).bg(Color::DarkGray)),
-                Span::styled(
-                    format!("{:<width$}", &pkg.name, width = name_width),
-                    Style::default().bold().bg(Color::DarkGray),
-                ),
+        let (cursor, name_style, path_style) = if is_selected {
+            (
                 Span::styled(
-                    &pkg.relative_path,
-                    Style::default().fg(Color::Gray).bg(Color::DarkGray),
+                    theme.symbols.selected,
+                    Style::default().fg(theme.accent).bg(theme.selection),
                 ),
-            ])
+                Style::default().bold().bg(theme.selection),
+                Style::default().fg(theme.secondary).bg(theme.selection),
+            )
         } else {
-            Line::from(vec![
+            (
                 Span::raw(" "),
-                Span::styled(
-                    format!("{:<width$}", &pkg.name, width = name_width),
-                    Style::default(),
-                ),
-                Span::styled(&pkg.relative_path, Style::default().fg(Color::DarkGray)),
-            ])
+                Style::default(),
+                Style::default().fg(theme.muted),
+            )
         };
+        let star = if favorites.contains(&package_key(&pkg.name)) {
+            theme.symbols.favorite
+        } else {
+            " "
+        };
+        let mut spans = vec![
+            cursor,
+            Span::styled(format!("{} ", star), name_style.fg(theme.highlight)),
+        ];
+        spans.extend(highlight_matches(
+            &pkg.name,
+            &match_indices(&pkg.name, name_query),
+            name_style,
+            name_style.fg(theme.highlight).bold(),
+        ));
+        spans.push(Span::styled(
+            " ".repeat(name_width.saturating_sub(pkg.name.chars().count())),
+            name_style,
+        ));
+        spans.extend(highlight_matches(
+            &pkg.relative_path,
+            &match_indices(&pkg.relative_path, path_query),
+            path_style,
+            path_style.fg(theme.highlight).bold(),
+        ));
+        spans.push(Span::styled(
+            " ".repeat(path_width.saturating_sub(pkg.relative_path.chars().count())),
+            path_style,
+        ));
+        spans.push(Span::styled(package_details(pkg), path_style.dim()));
+        let line = Line::from(spans);
         lines.push(line);
     }
 
+    if let Some(spinner) = scanning {
+        lines.push(Line::styled(
+            format!(
+                "{} Scanning workspaces... {} found",
+                spinner,
+                packages.len()
+            ),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
     let paragraph = Paragraph::new(Text::from(lines));
     frame.render_widget(paragraph, area);
 }
+
+/// `v1.2.3 · 14 scripts · private`, leaving out what the package doesn't set.
+fn package_details(pkg: &WorkspacePackage) -> String {
+    let count = pkg.scripts.len();
+    let mut details = vec![format!(
+        "{} script{}",
+        count,
+        if count == 1 { "" } else { "s" }
+    )];
+    if let Some(ref version) = pkg.version {
+        details.insert(0, format!("v{}", version.trim_start_matches('v')));
+    }
+    if pkg.private {
+        details.push("private".to_string());
+    }
+    details.join(" · ")
+}
+
+/// `3 scripts: dev, build, test`, or `no scripts`.
+fn scripts_summary(pkg: &WorkspacePackage) -> String {
+    match pkg.scripts.len() {
+        0 => "no scripts".to_string(),
+        n => format!(
+            "{} script{}: {}",
+            n,
+            if n == 1 { "" } else { "s" },
+            pkg.scripts
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Rows the preview of `pkg` needs at `width`, borders included.
+pub fn package_preview_height(pkg: &WorkspacePackage, width: u16) -> u16 {
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+    let description_rows = pkg
+        .description
+        .as_ref()
+        .map_or(0, |d| wrapped_rows(d, inner_width));
+    (description_rows + wrapped_rows(&scripts_summary(pkg), inner_width) + 2) as u16
+}
+
+/// Bordered panel with the highlighted package's description (from
+/// `package.json` or its README) and the scripts it declares.
+pub fn render_package_preview(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    pkg: &WorkspacePackage,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted))
+        .title(format!(" {} ", pkg.name));
+
+    let mut lines = Vec::new();
+    if let Some(ref description) = pkg.description {
+        lines.push(Line::styled(
+            description.as_str(),
+            Style::default().fg(theme.muted).dim().italic(),
+        ));
+    }
+    lines.push(Line::styled(
+        scripts_summary(pkg),
+        Style::default().fg(theme.secondary),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    #[test]
+    fn test_package_preview_height_counts_description_rows() {
+        let mut pkg = WorkspacePackage {
+            name: "@mono/web".to_string(),
+            relative_path: "apps/web".to_string(),
+            scripts: IndexMap::from([("dev".to_string(), "vite".to_string())]),
+            description: None,
+            version: None,
+            private: false,
+        };
+        assert_eq!(scripts_summary(&pkg), "1 script: dev");
+        assert_eq!(package_preview_height(&pkg, 40), 3);
+
+        pkg.description = Some("Customer dashboard with billing pages".to_string());
+        assert_eq!(package_preview_height(&pkg, 22), 5);
+    }
+
+    #[test]
+    fn test_package_details_show_version_count_and_private() {
+        let mut pkg = WorkspacePackage {
+            name: "@mono/web".to_string(),
+            relative_path: "apps/web".to_string(),
+            scripts: IndexMap::from([("dev".to_string(), "vite".to_string())]),
+            description: None,
+            version: None,
+            private: false,
+        };
+        assert_eq!(package_details(&pkg), "1 script");
+
+        pkg.version = Some("1.2.3".to_string());
+        pkg.private = true;
+        pkg.scripts
+            .insert("build".to_string(), "vite build".to_string());
+        assert_eq!(package_details(&pkg), "v1.2.3 · 2 scripts · private");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::store::script_configs::Preset;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+/// The configure flow's first step for scripts with presets: the last used
+/// settings, then each preset with its env files and arguments. Presets past
+/// the first `saved` come from the config and are marked as shared.
+pub fn render_preset_picker(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    presets: &[Preset],
+    saved: usize,
+    selected_index: usize,
+) {
+    // Centered, 60% width, tall enough for every row
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (presets.len() as u16 + 4).min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Presets ")
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(
+        modal_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        }),
+    );
+
+    let name_width = presets
+        .iter()
+        .map(|preset| preset.name.chars().count())
+        .chain(std::iter::once(LAST_USED.len()))
+        .max()
+        .unwrap_or(0);
+    let rows = std::iter::once((LAST_USED, String::new())).chain(presets.iter().enumerate().map(
+        |(idx, preset)| {
+            let mut details = preset_details(preset);
+            if idx >= saved {
+                details.push_str("  [shared]");
+            }
+            (preset.name.as_str(), details)
+        },
+    ));
+    let items: Vec<ListItem> = rows
+        .enumerate()
+        .map(|(idx, (name, details))| {
+            let is_selected = idx == selected_index;
+            let cursor = if is_selected {
+                theme.symbols.pointer
+            } else {
+                " "
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", cursor)),
+                Span::raw(format!("{:<width$}  ", name, width = name_width)),
+                Span::styled(details, Style::default().fg(theme.muted)),
+            ]))
+            .style(style)
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(
+            theme
+                .symbols
+                .text("↑↓: Navigate  Enter: Continue  d: Delete  Esc: Cancel"),
+        )
+        .style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+}
+
+/// Label of the first row, which keeps the script's last used settings.
+const LAST_USED: &str = "Last used";
+
+/// `.env.test · --watch`, or `no env files · no arguments`.
+fn preset_details(preset: &Preset) -> String {
+    let env = if preset.env_files.is_empty() {
+        "no env files".to_string()
+    } else {
+        preset.env_files.join(", ")
+    };
+    let args = if preset.args.is_empty() {
+        "no arguments"
+    } else {
+        preset.args.as_str()
+    };
+    format!("{} · {}", env, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_details_list_env_files_and_args() {
+        let preset = Preset {
+            name: "coverage CI".to_string(),
+            env_files: vec![".env".to_string(), ".env.ci".to_string()],
+            args: "--coverage".to_string(),
+        };
+        assert_eq!(preset_details(&preset), ".env, .env.ci · --coverage");
+        assert_eq!(
+            preset_details(&Preset {
+                env_files: Vec::new(),
+                args: String::new(),
+                ..preset
+            }),
+            "no env files · no arguments"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::core::queue::ExecutionQueue;
+use crate::core::schedule::{ScheduledRun, format_countdown};
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+use std::time::Instant;
+
+pub fn render_queue_panel(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    queue: &ExecutionQueue,
+    scheduled: &[ScheduledRun],
+    selected_index: usize,
+    now: Instant,
+) {
+    // Calculate modal size (centered, 60% width, 60% height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (area.height as f32 * 0.6) as u16;
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: area.x + modal_x,
+        y: area.y + modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    // Clear the background area
+    frame.render_widget(Clear, modal_area);
+
+    let title = if queue.paused {
+        format!(
+            " Queue ({}) {} paused ",
+            queue.len(),
+            theme.symbols.text("—")
+        )
+    } else {
+        format!(" Queue ({}) ", queue.len())
+    };
+
+    // Render modal block with opaque background
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Style::default().bg(theme.background));
+    frame.render_widget(block, modal_area);
+
+    // Split modal into content + status bar
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Content
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let mut items: Vec<ListItem> = if queue.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            theme
+                .symbols
+                .text("Queue is empty — press Ctrl+A on a script to add it"),
+            Style::default().fg(theme.muted),
+        )))]
+    } else {
+        queue
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, run)| {
+                let is_selected = idx == selected_index;
+                let cursor = if is_selected {
+                    theme.symbols.pointer
+                } else {
+                    " "
+                };
+
+                let mut spans = vec![
+                    Span::raw(format!("{} {}. ", cursor, idx + 1)),
+                    Span::raw(run.key.clone()),
+                ];
+                if !run.args.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}", run.args),
+                        Style::default().fg(theme.success),
+                    ));
+                }
+                if !run.env_files.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  [{} env]", run.env_files.len()),
+                        Style::default().fg(theme.muted),
+                    ));
+                }
+
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect()
+    };
+
+    // Scheduled runs follow the queue; selection indices continue past the queue
+    if !scheduled.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "Scheduled",
+            Style::default().fg(theme.accent),
+        ))));
+        for (offset, item) in scheduled.iter().enumerate() {
+            let is_selected = queue.len() + offset == selected_index;
+            let cursor = if is_selected {
+                theme.symbols.pointer
+            } else {
+                " "
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            items.push(
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} {} ", cursor, theme.symbols.timer)),
+                    Span::raw(item.run.key.clone()),
+                    Span::styled(
+                        format!(
+                            "  in {}",
+                            format_countdown(item.due.saturating_duration_since(now))
+                        ),
+                        Style::default().fg(theme.accent),
+                    ),
+                ]))
+                .style(style),
+            );
+        }
+    }
+
+    frame.render_widget(List::new(items), chunks[0]);
+
+    // Status bar
+    let status = Paragraph::new(theme.symbols.text(
+        "↑↓: Navigate  Shift+↑↓: Reorder  Del: Remove/Cancel  p: Pause  Enter: Start  c: Parallel  Esc: Close",
+    ))
+    .style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[1]);
+}
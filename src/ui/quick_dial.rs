@@ -0,0 +1,101 @@
+use crate::core::queue::QueuedRun;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+pub fn render_quick_dial(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    runs: &[QueuedRun],
+    selected_index: usize,
+) {
+    // Centered, 60% width, tall enough for every entry
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (runs.len() as u16 + 3).min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Quick dial ")
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(
+        modal_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        }),
+    );
+
+    let package_width = runs
+        .iter()
+        .map(|run| package_of(run).chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = runs
+        .iter()
+        .enumerate()
+        .map(|(idx, run)| {
+            let is_selected = idx == selected_index;
+            let cursor = if is_selected {
+                theme.symbols.pointer
+            } else {
+                " "
+            };
+            // Only the first nine entries have a number key
+            let number = if idx < 9 {
+                format!("{} ", idx + 1)
+            } else {
+                "  ".to_string()
+            };
+
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", cursor)),
+                Span::styled(number, Style::default().fg(theme.accent)),
+                Span::styled(
+                    format!("{:<width$}  ", package_of(run), width = package_width),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw(run.script_name.clone()),
+            ]))
+            .style(style)
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(
+            theme
+                .symbols
+                .text("1-9/Enter: Run  ↑↓: Navigate  Esc: Close"),
+        )
+        .style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+}
+
+/// `web` for `web:dev` (package names can't contain `:`, script names can).
+fn package_of(run: &QueuedRun) -> &str {
+    run.key.split_once(':').map_or(&run.key, |(scope, _)| scope)
+}
@@ -0,0 +1,77 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_schedule_input(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    script_key: &str,
+    input: &str,
+    error: Option<&str>,
+) {
+    // Calculate modal size (centered, 60% width, fixed height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = 9.min(area.height);
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: area.x + modal_x,
+        y: area.y + modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    // Clear the background area
+    frame.render_widget(Clear, modal_area);
+
+    // Render modal block with opaque background
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Schedule {} ", script_key))
+        .style(Style::default().bg(theme.background));
+    frame.render_widget(block, modal_area);
+
+    // Split modal into: input field + examples/error + status bar
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Input field
+        Constraint::Min(1),    // Examples or error
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let input_widget = Paragraph::new(Line::from(vec![
+        Span::raw("Start: "),
+        Span::raw(input),
+        Span::styled(theme.symbols.text_cursor, theme.cursor()),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(input_widget, chunks[0]);
+
+    let hint = match error {
+        Some(message) => Line::from(Span::styled(message, Style::default().fg(theme.error))),
+        None => Line::from(vec![
+            Span::styled("Examples: ", Style::default().fg(theme.muted)),
+            Span::styled("10m", Style::default().fg(theme.success)),
+            Span::raw("  "),
+            Span::styled("1h30m", Style::default().fg(theme.success)),
+            Span::raw("  "),
+            Span::styled("14:30", Style::default().fg(theme.success)),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(hint), chunks[1]);
+
+    // Status bar
+    let status =
+        Paragraph::new("Enter: Schedule  Esc: Cancel").style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[2]);
+}
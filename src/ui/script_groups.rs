@@ -0,0 +1,163 @@
+use crate::core::script_groups::ScriptGroup;
+use crate::core::workspaces::WorkspacePackage;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Script names shared by several packages or, once one is opened
+/// (`checked` is `Some`), that name's packages with checkboxes.
+#[allow(clippy::too_many_arguments)]
+pub fn render_script_groups(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    groups: &[ScriptGroup],
+    packages: &[WorkspacePackage],
+    selected_index: usize,
+    checked: Option<&[bool]>,
+    pkg_selected_index: usize,
+) {
+    // Centered, 60% width, 70% height
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    let group = groups.get(selected_index);
+    let title = match (checked, group) {
+        (Some(_), Some(group)) => format!(" {} in packages ", group.name),
+        _ => " Scripts by name ".to_string(),
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(
+        modal_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        }),
+    );
+
+    let row_style = |is_selected: bool| {
+        if is_selected {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let cursor = |is_selected: bool| {
+        if is_selected {
+            theme.symbols.pointer
+        } else {
+            " "
+        }
+    };
+
+    let (items, selected, hints): (Vec<ListItem>, usize, &str) = match (checked, group) {
+        (Some(checked), Some(group)) => {
+            let name_width = group
+                .packages
+                .iter()
+                .map(|&i| packages[i].name.chars().count())
+                .max()
+                .unwrap_or(0);
+            let items = group
+                .packages
+                .iter()
+                .zip(checked)
+                .enumerate()
+                .map(|(idx, (&pkg_i, &on))| {
+                    let pkg = &packages[pkg_i];
+                    let is_selected = idx == pkg_selected_index;
+                    let style = if !is_selected && on {
+                        Style::default().fg(theme.success)
+                    } else {
+                        row_style(is_selected)
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!(
+                            "{} {} ",
+                            cursor(is_selected),
+                            if on { "[x]" } else { "[ ]" }
+                        )),
+                        Span::raw(format!("{:<width$}  ", pkg.name, width = name_width)),
+                        Span::styled(
+                            pkg.scripts
+                                .get(&group.name)
+                                .map(String::as_str)
+                                .unwrap_or_default(),
+                            Style::default().fg(theme.muted),
+                        ),
+                    ]))
+                    .style(style)
+                })
+                .collect();
+            (
+                items,
+                pkg_selected_index,
+                "Space: Toggle  a: All  Enter: Start  Esc: Back",
+            )
+        }
+        _ => {
+            let name_width = groups
+                .iter()
+                .map(|group| group.name.chars().count())
+                .max()
+                .unwrap_or(0);
+            let items = groups
+                .iter()
+                .enumerate()
+                .map(|(idx, group)| {
+                    let is_selected = idx == selected_index;
+                    let names: Vec<&str> = group
+                        .packages
+                        .iter()
+                        .map(|&i| packages[i].name.as_str())
+                        .collect();
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{} ", cursor(is_selected))),
+                        Span::raw(format!("{:<width$}  ", group.name, width = name_width)),
+                        Span::styled(
+                            format!("{} packages  ", group.packages.len()),
+                            Style::default().fg(theme.accent),
+                        ),
+                        Span::styled(names.join(", "), Style::default().fg(theme.muted)),
+                    ]))
+                    .style(row_style(is_selected))
+                })
+                .collect();
+            (
+                items,
+                selected_index,
+                "↑↓: Navigate  Enter: Choose packages  Esc: Close",
+            )
+        }
+    };
+
+    // Scroll to keep the selected row visible in long lists
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(List::new(items), chunks[0], &mut state);
+
+    frame.render_widget(
+        Paragraph::new(theme.symbols.text(hints)).style(Style::default().fg(theme.muted)),
+        chunks[1],
+    );
+}
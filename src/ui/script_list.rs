@@ -1,24 +1,40 @@
 use ratatui::prelude::*;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
-use crate::sort::SortableScript;
+use crate::core::runner::format_duration;
+use crate::core::scripts::pre_post_names;
+use crate::core::tasks::TaskRunner;
+use crate::fuzzy::match_indices;
+use crate::grouping::ListRow;
+use crate::sort::{ScriptQuery, SortableScript};
+use crate::store::bench::BenchRecord;
+use crate::ui::theme::Theme;
 use std::collections::HashSet;
+use std::time::Duration;
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_script_list(
     frame: &mut Frame,
     area: Rect,
+    theme: &Theme,
     scripts: &[SortableScript],
-    filtered_indices: &[usize],
+    rows: &[ListRow],
     selected_index: usize,
     scroll_offset: usize,
     favorites: &HashSet<String>,
+    query: &ScriptQuery,
 ) {
     let visible_height = area.height as usize;
 
-    // Calculate dynamic name column width from filtered scripts
-    let name_width = filtered_indices
+    // Calculate dynamic name column width from filtered scripts (grouped ones are indented)
+    let name_width = rows
         .iter()
-        .map(|&i| scripts[i].name.len())
+        .filter_map(|row| match *row {
+            ListRow::Script { index, grouped } => {
+                Some(scripts[index].label().len() + if grouped { 2 } else { 0 })
+            }
+            ListRow::Group { .. } | ListRow::Section { .. } => None,
+        })
         .max()
         .unwrap_or(20)
         .max(12) // minimum 12 chars
@@ -26,56 +42,468 @@ pub fn render_script_list(
 
     let mut lines: Vec<Line> = Vec::new();
 
-    for (display_i, &script_i) in filtered_indices
+    for (display_i, row) in rows
         .iter()
         .enumerate()
         .skip(scroll_offset)
         .take(visible_height)
     {
-        let script = &scripts[script_i];
         let is_selected = display_i == selected_index;
+        let (script_i, grouped) = match *row {
+            ListRow::Script { index, grouped } => (index, grouped),
+            ListRow::Group {
+                ref prefix,
+                len,
+                expanded,
+            } => {
+                lines.push(group_header(theme, prefix, len, expanded, is_selected));
+                continue;
+            }
+            ListRow::Section { runner, len } => {
+                lines.push(section_header(theme, runner, len, is_selected));
+                continue;
+            }
+        };
+        let script = &scripts[script_i];
         let is_favorite = favorites.contains(&script.key);
 
-        let star = if is_favorite { "★ " } else { "  " };
-        let cursor = if is_selected { "▎" } else { " " };
+        let star = if is_favorite {
+            theme.symbols.favorite
+        } else {
+            " "
+        };
+        let cursor = if is_selected {
+            theme.symbols.selected
+        } else {
+            " "
+        };
 
-        let line = Line::from(vec![
+        let mut spans = vec![
             Span::styled(
                 cursor,
                 if is_selected {
-                    Style::default().fg(Color::Cyan).bg(Color::DarkGray)
+                    Style::default().fg(theme.accent).bg(theme.selection)
                 } else {
                     Style::default()
                 },
             ),
             Span::styled(
-                star,
+                format!("{} ", star),
                 if is_selected {
-                    Style::default().fg(Color::Yellow).bg(Color::DarkGray)
+                    Style::default().fg(theme.highlight).bg(theme.selection)
                 } else {
-                    Style::default().fg(Color::Yellow)
+                    Style::default().fg(theme.highlight)
                 },
             ),
-            Span::styled(
-                format!("{:<width$}", &script.name, width = name_width),
+        ];
+        let name_style = if is_selected {
+            Style::default().bold().bg(theme.selection)
+        } else {
+            Style::default()
+        };
+        if grouped {
+            spans.push(Span::styled("  ", name_style));
+        }
+        let name_matches = match_indices(script.label(), &query.text);
+        // Scripts listed for their command (see `sort_scripts`) highlight it instead
+        let command_matches = if !query.command.is_empty() {
+            match_indices(&script.command, &query.command)
+        } else if name_matches.is_empty() {
+            match_indices(&script.command, &query.text)
+        } else {
+            Vec::new()
+        };
+        spans.extend(highlight_matches(
+            script.label(),
+            &name_matches,
+            name_style,
+            name_style.fg(theme.highlight).bold(),
+        ));
+        spans.push(Span::styled(
+            " ".repeat(
+                name_width
+                    .saturating_sub(script.label().chars().count())
+                    .saturating_sub(if grouped { 2 } else { 0 }),
+            ),
+            name_style,
+        ));
+
+        // Edited (e.g. by a teammate) since it was last run from nr
+        if script.changed {
+            spans.push(Span::styled(
+                "changed ",
                 if is_selected {
-                    Style::default().bold().bg(Color::DarkGray)
+                    Style::default().fg(theme.highlight).bg(theme.selection)
                 } else {
-                    Style::default()
+                    Style::default().fg(theme.highlight)
                 },
-            ),
-            Span::styled(
-                &script.command,
+            ));
+        }
+
+        // Health warning: the command runs sibling scripts that don't exist
+        if !script.missing_refs.is_empty() {
+            let warning = format!(
+                "{} missing {} ",
+                theme.symbols.warning,
+                script.missing_refs.join(", ")
+            );
+            spans.push(Span::styled(
+                warning,
                 if is_selected {
-                    Style::default().fg(Color::Gray).bg(Color::DarkGray)
+                    Style::default().fg(theme.error).bg(theme.selection)
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(theme.error)
                 },
-            ),
-        ]);
-        lines.push(line);
+            ));
+        }
+
+        for tag in &script.tags {
+            spans.push(Span::styled(
+                format!("#{} ", tag),
+                if is_selected {
+                    Style::default().fg(theme.tag).bg(theme.selection)
+                } else {
+                    Style::default().fg(theme.tag)
+                },
+            ));
+        }
+
+        let command_style = if is_selected {
+            Style::default().fg(theme.secondary).bg(theme.selection)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        spans.extend(highlight_matches(
+            &script.command,
+            &command_matches,
+            command_style,
+            command_style.fg(theme.highlight).bold(),
+        ));
+        // How long it took last time, to spot slow scripts before running them
+        if let Some(duration) = script.last_duration {
+            spans.push(Span::styled(
+                format!("  {}", format_duration(duration)),
+                command_style.dim(),
+            ));
+        }
+        if let Some(ref description) = script.description {
+            spans.push(Span::styled(
+                format!("  {}", description),
+                command_style.dim().italic(),
+            ));
+        }
+        lines.push(Line::from(spans));
     }
 
     let paragraph = Paragraph::new(Text::from(lines));
     frame.render_widget(paragraph, area);
 }
+
+/// Header row of a script group: `▸ test  3 scripts`.
+fn group_header(
+    theme: &Theme,
+    prefix: &str,
+    len: usize,
+    expanded: bool,
+    is_selected: bool,
+) -> Line<'static> {
+    let bg = |style: Style| {
+        if is_selected {
+            style.bg(theme.selection)
+        } else {
+            style
+        }
+    };
+    let marker = if expanded {
+        theme.symbols.expanded
+    } else {
+        theme.symbols.collapsed
+    };
+    let cursor = if is_selected {
+        theme.symbols.selected
+    } else {
+        " "
+    };
+    Line::from(vec![
+        Span::styled(cursor, bg(Style::default().fg(theme.accent))),
+        Span::styled(
+            format!("{} {}", marker, prefix),
+            bg(Style::default().fg(theme.accent).bold()),
+        ),
+        Span::styled(
+            format!("  {} scripts", len),
+            bg(Style::default().fg(theme.muted)),
+        ),
+    ])
+}
+
+/// Heading of a task runner's tasks, e.g. `justfile  4 tasks`.
+fn section_header(
+    theme: &Theme,
+    runner: TaskRunner,
+    len: usize,
+    is_selected: bool,
+) -> Line<'static> {
+    let style = if is_selected {
+        Style::default().bg(theme.selection)
+    } else {
+        Style::default()
+    };
+    let cursor = if is_selected {
+        theme.symbols.selected
+    } else {
+        " "
+    };
+    Line::from(vec![
+        Span::styled(cursor, style.fg(theme.accent)),
+        Span::styled(runner.label(), style.fg(theme.muted).bold()),
+        Span::styled(
+            format!("  {} task{}", len, if len == 1 { "" } else { "s" }),
+            style.fg(theme.muted),
+        ),
+    ])
+}
+
+/// `text` as spans, with the chars at `matched` (see `fuzzy::match_indices`)
+/// drawn in `highlight` and the rest in `base`.
+pub fn highlight_matches(
+    text: &str,
+    matched: &[usize],
+    base: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let is_matched = matched.binary_search(&i).is_ok();
+        if is_matched != run_matched && !run.is_empty() {
+            let style = if run_matched { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { highlight } else { base };
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
+
+/// Which list entry (an index into `filtered_indices`) is drawn at terminal
+/// cell (`column`, `row`), for mouse clicks on a list rendered in `area`.
+pub fn row_at(
+    area: Rect,
+    scroll_offset: usize,
+    len: usize,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    if !area.contains(Position::new(column, row)) {
+        return None;
+    }
+    let index = scroll_offset + (row - area.y) as usize;
+    (index < len).then_some(index)
+}
+
+/// Scripts run implicitly before and after `script`, if they exist among `scripts`.
+fn pre_post<'a>(
+    script: &SortableScript,
+    scripts: &'a [SortableScript],
+) -> [(&'static str, Option<&'a SortableScript>); 2] {
+    let (pre, post) = pre_post_names(&script.name);
+    let find = |name: &str| {
+        scripts
+            .iter()
+            .find(|s| s.name == name && s.package == script.package)
+    };
+    [("before", find(&pre)), ("after", find(&post))]
+}
+
+/// Rows the preview of `script` needs at `width`, borders included.
+pub fn preview_height(script: &SortableScript, scripts: &[SortableScript], width: u16) -> u16 {
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+
+    let command_rows = wrapped_rows(&script.command, inner_width);
+    let description_rows = script
+        .description
+        .as_ref()
+        .map_or(0, |d| wrapped_rows(d, inner_width));
+    let bench_rows = bench_summary(&script.bench).map_or(0, |b| wrapped_rows(&b, inner_width));
+    let hook_rows: usize = pre_post(script, scripts)
+        .iter()
+        .filter_map(|(label, hook)| hook.map(|h| (label, h)))
+        .map(|(label, hook)| {
+            let text = format!("↳ {}: {}  {}", label, hook.name, hook.command);
+            wrapped_rows(&text, inner_width)
+        })
+        .sum();
+    (command_rows + description_rows + bench_rows + hook_rows + 2) as u16
+}
+
+/// `nr --bench` timings as `bench (cold/warm): bun 400ms/300ms, npm 1.6s/1.2s`,
+/// fastest first.
+fn bench_summary(records: &[BenchRecord]) -> Option<String> {
+    if records.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<&BenchRecord> = records.iter().collect();
+    sorted.sort_by_key(|r| r.warm_ms.unwrap_or(u64::MAX));
+    let timings: Vec<String> = sorted
+        .iter()
+        .map(|r| match r.warm_ms {
+            Some(warm) => format!(
+                "{} {}/{}",
+                r.pm,
+                format_duration(Duration::from_millis(r.cold_ms)),
+                format_duration(Duration::from_millis(warm))
+            ),
+            None => format!("{} failed ({})", r.pm, r.exit_code),
+        })
+        .collect();
+    Some(format!("bench (cold/warm): {}", timings.join(", ")))
+}
+
+/// Rows `text` takes when word-wrapped to `width` columns.
+pub fn wrapped_rows(text: &str, width: usize) -> usize {
+    let mut rows = 1;
+    let mut col = 0;
+    for word in text.split(' ') {
+        let len = word.chars().count();
+        let needed = if col == 0 { len } else { col + 1 + len };
+        if needed <= width {
+            col = needed;
+        } else if len <= width {
+            rows += 1;
+            col = len;
+        } else {
+            // Words longer than a row are broken across rows
+            if col > 0 {
+                rows += 1;
+            }
+            rows += (len - 1) / width;
+            col = (len - 1) % width + 1;
+        }
+    }
+    rows
+}
+
+/// Bordered panel with the complete (wrapped) command of the highlighted script,
+/// its description, and the `pre<name>` / `post<name>` scripts that run with it.
+pub fn render_script_preview(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    script: &SortableScript,
+    scripts: &[SortableScript],
+    runs_pre_post: bool,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted))
+        .title(format!(" {} ", script.label()));
+
+    let mut lines = vec![Line::from(script.command.as_str())];
+    if let Some(ref description) = script.description {
+        lines.push(Line::styled(
+            description.as_str(),
+            Style::default().fg(theme.muted).dim().italic(),
+        ));
+    }
+    if let Some(bench) = bench_summary(&script.bench) {
+        lines.push(Line::styled(bench, Style::default().fg(theme.secondary)));
+    }
+    for (label, hook) in pre_post(script, scripts) {
+        let Some(hook) = hook else {
+            continue;
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("{} {}: ", theme.symbols.hook, label),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(format!("{}  ", hook.name), Style::default().bold()),
+            Span::styled(hook.command.as_str(), Style::default().fg(theme.secondary)),
+        ];
+        if !runs_pre_post {
+            spans.push(Span::styled(
+                "  (not run by this package manager)",
+                Style::default().fg(theme.muted),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_matches_splits_runs() {
+        let base = Style::default();
+        let highlight = Style::default().bold();
+
+        let spans = highlight_matches("test:unit", &[0, 5, 6], base, highlight);
+
+        let runs: Vec<(&str, bool)> = spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style == highlight))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![("t", true), ("est:", false), ("un", true), ("it", false)]
+        );
+    }
+
+    #[test]
+    fn test_preview_height_counts_description_rows() {
+        let mut script = SortableScript {
+            name: "build".to_string(),
+            command: "tsc -p .".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(preview_height(&script, &[], 22), 3);
+
+        script.description = Some("Compile the app for production".to_string());
+        assert_eq!(preview_height(&script, &[], 22), 5);
+    }
+
+    #[test]
+    fn test_bench_summary_lists_fastest_first() {
+        let record = |pm: &str, cold_ms, warm_ms| BenchRecord {
+            pm: pm.to_string(),
+            cold_ms,
+            warm_ms,
+            exit_code: if warm_ms.is_some() { 0 } else { 1 },
+        };
+        assert_eq!(bench_summary(&[]), None);
+        assert_eq!(
+            bench_summary(&[
+                record("npm", 1600, Some(1200)),
+                record("yarn", 200, None),
+                record("bun", 400, Some(300)),
+            ])
+            .unwrap(),
+            "bench (cold/warm): bun 400ms/300ms, npm 1.6s/1.2s, yarn failed (1)"
+        );
+    }
+
+    #[test]
+    fn test_row_at_maps_rows_to_scrolled_indices() {
+        let area = Rect::new(0, 4, 40, 5);
+
+        assert_eq!(row_at(area, 0, 10, 3, 4), Some(0));
+        assert_eq!(row_at(area, 3, 10, 3, 6), Some(5));
+        // Outside the list, or below the last entry
+        assert_eq!(row_at(area, 0, 10, 3, 3), None);
+        assert_eq!(row_at(area, 0, 10, 40, 4), None);
+        assert_eq!(row_at(area, 0, 2, 3, 7), None);
+    }
+}
@@ -1,8 +1,9 @@
+use crate::ui::theme::Theme;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
-pub fn render_search_input(frame: &mut Frame, area: Rect, query: &str) {
-    let display = format!("> {query}\u{2588}");
-    let paragraph = Paragraph::new(display).style(Style::default().fg(Color::Cyan));
+pub fn render_search_input(frame: &mut Frame, area: Rect, theme: &Theme, query: &str) {
+    let display = format!("> {query}{}", theme.symbols.text_cursor);
+    let paragraph = Paragraph::new(display).style(Style::default().fg(theme.accent));
     frame.render_widget(paragraph, area);
 }
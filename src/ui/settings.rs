@@ -0,0 +1,104 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+/// Settings modal: each piece of project data that can be cleared, with a
+/// preview of what clearing it removes, and the pending y/n question.
+pub fn render_settings(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    rows: &[(&str, String)],
+    selected_index: usize,
+    confirm: Option<&str>,
+) {
+    // Centered, 60% width, tall enough for every row plus the heading and hint
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (rows.len() as u16 + 5).min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Settings ")
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    frame.render_widget(
+        Paragraph::new("Clear this project's data").style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        chunks[0],
+    );
+
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .map(|(idx, (label, preview))| {
+            let is_selected = idx == selected_index;
+            let cursor = if is_selected {
+                theme.symbols.pointer
+            } else {
+                " "
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", cursor)),
+                Span::raw(format!("{:<width$}  ", label, width = label_width)),
+                Span::styled(preview.clone(), Style::default().fg(theme.muted)),
+            ]))
+            .style(style)
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[1]);
+
+    let footer = match confirm {
+        Some(question) => Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} {} ", theme.symbols.warning, question),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("y/n", Style::default().fg(theme.highlight)),
+        ])),
+        None => Paragraph::new(theme.symbols.text("Enter: Clear  ↑↓: Navigate  Esc: Close"))
+            .style(Style::default().fg(theme.muted)),
+    };
+    frame.render_widget(footer, chunks[2]);
+}
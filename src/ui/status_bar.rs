@@ -1,18 +1,79 @@
+use crate::ui::theme::Theme;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
-pub fn render_status_bar(frame: &mut Frame, area: Rect) {
-    let hints = Line::from(vec![
-        Span::styled(" ↑↓ ", Style::default().bold()),
+#[allow(clippy::too_many_arguments)]
+pub fn render_status_bar(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    queued: usize,
+    next_scheduled: Option<&str>,
+    suggested: Option<&str>,
+    watching: Option<&str>,
+    update: Option<&str>,
+    message: Option<&str>,
+) {
+    if let Some(message) = message {
+        frame.render_widget(
+            Paragraph::new(format!(" {}", message)).style(Style::default().fg(theme.highlight)),
+            area,
+        );
+        return;
+    }
+
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", theme.symbols.up_down),
+            Style::default().bold(),
+        ),
         Span::raw("navigate  "),
-        Span::styled("⏎ ", Style::default().bold()),
+        Span::styled(format!("{} ", theme.symbols.enter), Style::default().bold()),
         Span::raw("run  "),
-        Span::styled("⇥ ", Style::default().bold()),
+        Span::styled(format!("{} ", theme.symbols.tab), Style::default().bold()),
         Span::raw("config  "),
-        Span::styled("␣ ", Style::default().bold()),
+        Span::styled(format!("{} ", theme.symbols.space), Style::default().bold()),
         Span::raw("fav  "),
-        Span::styled("⎋ ", Style::default().bold()),
+        Span::styled("^A ", Style::default().bold()),
+        Span::raw("queue  "),
+        Span::styled("? ", Style::default().bold()),
+        Span::raw("help  "),
+        Span::styled(
+            format!("{} ", theme.symbols.escape),
+            Style::default().bold(),
+        ),
         Span::raw("quit"),
-    ]);
-    frame.render_widget(Paragraph::new(hints).style(Style::default().dim()), area);
+    ];
+    if queued > 0 {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled("^Q ", Style::default().bold()));
+        spans.push(Span::raw(format!("{} queued", queued)));
+    }
+    if let Some(key) = suggested {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled("^N ", Style::default().bold()));
+        spans.push(Span::raw(format!("next: {}", key)));
+    }
+    if let Some(label) = next_scheduled {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{} ", theme.symbols.timer),
+            Style::default().bold(),
+        ));
+        spans.push(Span::raw(label.to_string()));
+    }
+    if let Some(label) = watching {
+        spans.push(Span::raw("  "));
+        spans.push(Span::raw(label.to_string()));
+    }
+    if let Some(version) = update {
+        spans.push(Span::raw(format!(
+            "  v{} available: nr self-update",
+            version
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().dim()),
+        area,
+    );
 }
@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+/// Glyphs the `ui` modules draw. `ASCII` stands in for terminals and fonts
+/// that render the Unicode ones as boxes (`--ascii` or `"ascii": true`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Symbols {
+    pub favorite: &'static str,
+    /// Marks the selected row in modals.
+    pub pointer: &'static str,
+    /// Bar in front of the selected row of a list.
+    pub selected: &'static str,
+    /// Cursor block in text inputs.
+    pub text_cursor: &'static str,
+    pub enter: &'static str,
+    pub tab: &'static str,
+    pub space: &'static str,
+    pub escape: &'static str,
+    pub up_down: &'static str,
+    pub timer: &'static str,
+    pub warning: &'static str,
+    pub success: &'static str,
+    pub failure: &'static str,
+    pub running: &'static str,
+    /// Prefix of the pre/post scripts in the preview.
+    pub hook: &'static str,
+    pub bullet: &'static str,
+    /// Markers of collapsed and expanded script groups.
+    pub collapsed: &'static str,
+    pub expanded: &'static str,
+    /// Repeated to draw horizontal separators.
+    pub rule: &'static str,
+    /// Between the steps of a breadcrumb (`Packages ▸ web`).
+    pub breadcrumb: &'static str,
+    /// Frames of the busy indicator, e.g. while workspaces are scanned.
+    pub spinner: &'static [&'static str],
+    ascii: bool,
+}
+
+impl Symbols {
+    pub const UNICODE: Symbols = Symbols {
+        favorite: "★",
+        pointer: "❯",
+        selected: "▎",
+        text_cursor: "█",
+        enter: "⏎",
+        tab: "⇥",
+        space: "␣",
+        escape: "⎋",
+        up_down: "↑↓",
+        timer: "⏱",
+        warning: "⚠",
+        success: "✔",
+        failure: "✘",
+        running: "●",
+        hook: "↳",
+        bullet: "•",
+        collapsed: "▸",
+        expanded: "▾",
+        rule: "─",
+        breadcrumb: "▸",
+        spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+        ascii: false,
+    };
+
+    pub const ASCII: Symbols = Symbols {
+        favorite: "*",
+        pointer: ">",
+        selected: ">",
+        text_cursor: "_",
+        enter: "enter",
+        tab: "tab",
+        space: "space",
+        escape: "esc",
+        up_down: "up/down",
+        timer: "@",
+        warning: "!",
+        success: "+",
+        failure: "x",
+        running: "~",
+        hook: "->",
+        bullet: "-",
+        collapsed: "+",
+        expanded: "-",
+        rule: "-",
+        breadcrumb: ">",
+        spinner: &["|", "/", "-", "\\"],
+        ascii: true,
+    };
+
+    /// The [`spinner`](Self::spinner) frame to show `elapsed` after it started.
+    pub fn spinner_frame(&self, elapsed: std::time::Duration) -> &'static str {
+        self.spinner[(elapsed.as_millis() / 100) as usize % self.spinner.len()]
+    }
+
+    /// `text` with arrows and other Unicode punctuation spelled out in ASCII
+    /// mode, for fixed strings such as key hints (`↑↓: Navigate`).
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.ascii || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        const REPLACEMENTS: [(&str, &str); 10] = [
+            ("↑↓", "Up/Down"),
+            ("←→", "Left/Right"),
+            ("↑", "Up"),
+            ("↓", "Down"),
+            ("←", "Left"),
+            ("→", "Right"),
+            ("—", "-"),
+            ("×", "x"),
+            ("…", "..."),
+            ("•", "-"),
+        ];
+        let mut text = text.to_string();
+        for (from, to) in REPLACEMENTS {
+            text = text.replace(from, to);
+        }
+        Cow::Owned(text)
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Symbols::UNICODE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_spells_out_arrows_in_ascii_mode() {
+        let hint = "↑↓: Navigate  ← →: Move  Esc: Close";
+
+        assert_eq!(Symbols::UNICODE.text(hint), hint);
+        assert_eq!(
+            Symbols::ASCII.text(hint),
+            "Up/Down: Navigate  Left Right: Move  Esc: Close"
+        );
+    }
+}
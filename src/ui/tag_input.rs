@@ -0,0 +1,67 @@
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_tag_input(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    script_key: &str,
+    input: &str,
+) {
+    // Calculate modal size (centered, 60% width, fixed height)
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = 8.min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+        y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Tags for {} ", script_key))
+            .style(Style::default().bg(theme.background)),
+        modal_area,
+    );
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Input field
+        Constraint::Min(1),    // Hint
+        Constraint::Length(1), // Status bar
+    ])
+    .split(modal_area.inner(ratatui::layout::Margin {
+        horizontal: 1,
+        vertical: 1,
+    }));
+
+    let input_widget = Paragraph::new(Line::from(vec![
+        Span::raw("Tags: "),
+        Span::raw(input),
+        Span::styled(theme.symbols.text_cursor, theme.cursor()),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(input_widget, chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                "Separate with spaces; filter with ",
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("#tag", Style::default().fg(theme.tag)),
+        ])),
+        chunks[1],
+    );
+
+    let status = Paragraph::new("Enter: Save  Esc: Cancel").style(Style::default().fg(theme.muted));
+    frame.render_widget(status, chunks[2]);
+}
@@ -0,0 +1,218 @@
+use crate::ui::symbols::Symbols;
+use ratatui::style::{Color, Style};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+/// Colors every `ui` module draws with, picked by the `theme` config setting,
+/// and the glyphs it draws (see `Symbols`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Titles, headings, the cursor bar and the active tab.
+    pub accent: Color,
+    /// Favorites, selected modal rows and warnings.
+    pub highlight: Color,
+    /// Arguments, examples and successful runs.
+    pub success: Color,
+    /// Errors, failed runs and stderr.
+    pub error: Color,
+    /// `#tag` labels.
+    pub tag: Color,
+    /// Hints, commands and other secondary text.
+    pub muted: Color,
+    /// Secondary text that needs more contrast (e.g. on the selected row).
+    pub secondary: Color,
+    /// Background of the selected row and the header bar.
+    pub selection: Color,
+    /// Modal background (and text on accent-colored backgrounds).
+    pub background: Color,
+    /// Regular text, used for the text cursor.
+    pub text: Color,
+    pub symbols: Symbols,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        accent: Color::Cyan,
+        highlight: Color::Yellow,
+        success: Color::Green,
+        error: Color::Red,
+        tag: Color::Magenta,
+        muted: Color::DarkGray,
+        secondary: Color::Gray,
+        selection: Color::DarkGray,
+        background: Color::Black,
+        text: Color::White,
+        symbols: Symbols::UNICODE,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        accent: Color::Blue,
+        highlight: Color::Indexed(130),
+        success: Color::Indexed(28),
+        error: Color::Indexed(160),
+        tag: Color::Indexed(90),
+        muted: Color::Indexed(244),
+        secondary: Color::Indexed(240),
+        selection: Color::Indexed(253),
+        background: Color::White,
+        text: Color::Black,
+        symbols: Symbols::UNICODE,
+    };
+
+    pub const SOLARIZED: Theme = Theme {
+        accent: Color::Rgb(0x26, 0x8b, 0xd2),
+        highlight: Color::Rgb(0xb5, 0x89, 0x00),
+        success: Color::Rgb(0x85, 0x99, 0x00),
+        error: Color::Rgb(0xdc, 0x32, 0x2f),
+        tag: Color::Rgb(0xd3, 0x36, 0x82),
+        muted: Color::Rgb(0x58, 0x6e, 0x75),
+        secondary: Color::Rgb(0x93, 0xa1, 0xa1),
+        selection: Color::Rgb(0x07, 0x36, 0x42),
+        background: Color::Rgb(0x00, 0x2b, 0x36),
+        text: Color::Rgb(0x83, 0x94, 0x96),
+        symbols: Symbols::UNICODE,
+    };
+
+    /// The block drawn as the text cursor in input fields.
+    pub fn cursor(&self) -> Style {
+        Style::default().bg(self.text).fg(self.background)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DARK
+    }
+}
+
+impl From<&ThemeConfig> for Theme {
+    fn from(config: &ThemeConfig) -> Self {
+        let mut theme = match config.preset {
+            ThemePreset::Dark => Theme::DARK,
+            ThemePreset::Light => Theme::LIGHT,
+            ThemePreset::Solarized => Theme::SOLARIZED,
+        };
+        let overrides = [
+            (&mut theme.accent, config.accent),
+            (&mut theme.highlight, config.highlight),
+            (&mut theme.success, config.success),
+            (&mut theme.error, config.error),
+            (&mut theme.tag, config.tag),
+            (&mut theme.muted, config.muted),
+            (&mut theme.secondary, config.secondary),
+            (&mut theme.selection, config.selection),
+            (&mut theme.background, config.background),
+            (&mut theme.text, config.text),
+        ];
+        for (color, custom) in overrides {
+            if let Some(ThemeColor(custom)) = custom {
+                *color = custom;
+            }
+        }
+        theme
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+}
+
+/// The `theme` config setting: a preset name (`"light"`), or a preset with
+/// some colors replaced (`{ "preset": "light", "accent": "#005f87" }`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    pub accent: Option<ThemeColor>,
+    pub highlight: Option<ThemeColor>,
+    pub success: Option<ThemeColor>,
+    pub error: Option<ThemeColor>,
+    pub tag: Option<ThemeColor>,
+    pub muted: Option<ThemeColor>,
+    pub secondary: Option<ThemeColor>,
+    pub selection: Option<ThemeColor>,
+    pub background: Option<ThemeColor>,
+    pub text: Option<ThemeColor>,
+}
+
+/// Reads the `theme` setting in either form (see `ThemeConfig`).
+pub fn deserialize_theme<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ThemeConfig, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let config = match value {
+        serde_json::Value::String(_) => ThemePreset::deserialize(value).map(|preset| ThemeConfig {
+            preset,
+            ..ThemeConfig::default()
+        }),
+        other => ThemeConfig::deserialize(other),
+    };
+    config.map_err(serde::de::Error::custom)
+}
+
+/// A color written as a name (`blue`, `lightred`), an ANSI index (`130`) or `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeColor(pub Color);
+
+impl Serialize for ThemeColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Color::from_str(&name)
+            .map(ThemeColor)
+            .map_err(|_| serde::de::Error::custom(format!("unknown color '{}'", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::config::Config;
+
+    fn theme_of(json: &str) -> Result<Theme, serde_json::Error> {
+        serde_json::from_str::<Config>(json).map(|config| Theme::from(&config.theme))
+    }
+
+    #[test]
+    fn test_default_theme_is_dark() {
+        assert_eq!(theme_of("{}").unwrap(), Theme::DARK);
+    }
+
+    #[test]
+    fn test_theme_preset_by_name() {
+        assert_eq!(theme_of(r#"{"theme": "light"}"#).unwrap(), Theme::LIGHT);
+        assert_eq!(
+            theme_of(r#"{"theme": "solarized"}"#).unwrap(),
+            Theme::SOLARIZED
+        );
+    }
+
+    #[test]
+    fn test_custom_colors_replace_preset_colors() {
+        let theme =
+            theme_of(r##"{"theme": {"preset": "light", "accent": "#005f87", "tag": "blue"}}"##)
+                .unwrap();
+
+        assert_eq!(theme.accent, Color::Rgb(0x00, 0x5f, 0x87));
+        assert_eq!(theme.tag, Color::Blue);
+        assert_eq!(theme.error, Theme::LIGHT.error);
+    }
+
+    #[test]
+    fn test_invalid_theme_is_a_config_error() {
+        let err = theme_of(r#"{"theme": {"accent": "not-a-color"}}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown color 'not-a-color'"));
+
+        assert!(theme_of(r#"{"theme": "neon"}"#).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Integration tests for the non-interactive command line (`nr <script>`).
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn write_package_json(dir: &Path) {
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name": "cli-test", "scripts": {"build": "tsc", "test": "vitest"}}"#,
+    )
+    .unwrap();
+}
+
+fn nr(project: &Path, config_home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nr"))
+        .args(args)
+        .current_dir(project)
+        .env("XDG_CONFIG_HOME", config_home)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_unknown_script_exits_with_suggestion() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    let output = nr(project.path(), config_home.path(), &["buld"]);
+
+    assert_eq!(output.status.code(), Some(127));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No script named 'buld'"));
+    assert!(stderr.contains("Did you mean: build"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_direct_run_passes_exit_code_and_records_recent() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    // Swap the package manager for `sh` so the test doesn't need npm installed
+    fs::write(project.path().join("run.sh"), "echo \"$0 $@\"; exit 3\n").unwrap();
+    fs::create_dir_all(config_home.path().join("nr")).unwrap();
+    fs::write(
+        config_home.path().join("nr/config.json"),
+        r#"{"run_templates": {"npm": "sh run.sh {script} {args}"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(project.path(), config_home.path(), &["build", "--watch"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "run.sh build --watch"
+    );
+
+    let projects = config_home.path().join("nr/projects");
+    let project_dir = fs::read_dir(&projects).unwrap().next().unwrap().unwrap();
+    let recents = fs::read_to_string(project_dir.path().join("recents.json")).unwrap();
+    assert!(recents.contains("root:build"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_flags_after_script_name_reach_the_script() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    fs::write(project.path().join("run.sh"), "echo \"$0 $@\"\n").unwrap();
+    fs::create_dir_all(config_home.path().join("nr")).unwrap();
+    fs::write(
+        config_home.path().join("nr/config.json"),
+        r#"{"run_templates": {"npm": "sh run.sh {script} {args}"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(
+        project.path(),
+        config_home.path(),
+        &["test", "--json", "--help"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "run.sh test --json --help"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_direct_run_keeps_quoted_args_whole() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    fs::write(project.path().join("run.sh"), "printf '[%s]' \"$@\"\n").unwrap();
+    fs::create_dir_all(config_home.path().join("nr")).unwrap();
+    fs::write(
+        config_home.path().join("nr/config.json"),
+        r#"{"run_templates": {"npm": "sh run.sh {script} {args}"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(
+        project.path(),
+        config_home.path(),
+        &["build", "--name", "my app", "it's"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[build][--name][my app][it's]"
+    );
+}
+
+#[test]
+fn test_exit_codes_say_why_nr_stopped() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+
+    // No package.json anywhere up the tree
+    let output = nr(project.path(), config_home.path(), &["build"]);
+    assert_eq!(output.status.code(), Some(125));
+
+    write_package_json(project.path());
+    let output = nr(project.path(), config_home.path(), &["--last"]);
+    assert_eq!(output.status.code(), Some(127));
+
+    // A required env key can't be asked for without a terminal
+    fs::create_dir_all(config_home.path().join("nr")).unwrap();
+    fs::write(
+        config_home.path().join("nr/config.json"),
+        r#"{"required_env": {"build": ["NR_TEST_UNSET_TOKEN"]}}"#,
+    )
+    .unwrap();
+    let output = nr(project.path(), config_home.path(), &["build"]);
+    assert_eq!(output.status.code(), Some(130));
+}
+
+#[test]
+fn test_root_flag_skips_nested_package_json() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+    let tooling = project.path().join("tools/eslint");
+    fs::create_dir_all(&tooling).unwrap();
+    fs::write(
+        tooling.join("package.json"),
+        r#"{"scripts": {"lint": "eslint"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(&tooling, config_home.path(), &["--root", "../..", "buld"]);
+
+    assert_eq!(output.status.code(), Some(127));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Did you mean: build"));
+}
+
+#[test]
+fn test_root_marker_skips_nested_package_json() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+    fs::write(project.path().join(".nr-root"), "").unwrap();
+    let tooling = project.path().join("tools/eslint");
+    fs::create_dir_all(&tooling).unwrap();
+    fs::write(
+        tooling.join("package.json"),
+        r#"{"scripts": {"lint": "eslint"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(&tooling, config_home.path(), &["buld"]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Did you mean: build"));
+}
+
+#[test]
+fn test_list_and_json_print_scripts_without_tui() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    fs::write(
+        project.path().join("package.json"),
+        r#"{"name": "mono", "workspaces": ["packages/*"], "scripts": {"build": "turbo build"}}"#,
+    )
+    .unwrap();
+    let web = project.path().join("packages/web");
+    fs::create_dir_all(&web).unwrap();
+    fs::write(
+        web.join("package.json"),
+        r#"{"name": "@mono/web", "scripts": {"dev": "vite", "test": "vitest"}}"#,
+    )
+    .unwrap();
+
+    let output = nr(project.path(), config_home.path(), &["--list"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "build\n@mono/web:dev\n@mono/web:test\n"
+    );
+
+    let output = nr(project.path(), config_home.path(), &["--json"]);
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["scripts"]["build"], "turbo build");
+    assert_eq!(value["workspaces"][0]["path"], "packages/web");
+    assert_eq!(value["workspaces"][0]["scripts"]["test"], "vitest");
+}
+
+#[test]
+fn test_without_tty_prints_script_list() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    // `output()` pipes stdout and closes stdin, so there is no terminal for the TUI
+    let output = nr(project.path(), config_home.path(), &[]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "build\ntest\n");
+}
+
+#[test]
+fn test_picker_without_terminal_fails_cleanly() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    let output = nr(project.path(), config_home.path(), &["--picker"]);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--picker needs a terminal"));
+}
+
+#[test]
+fn test_popup_outside_tmux_fails() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    write_package_json(project.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_nr"))
+        .arg("popup")
+        .current_dir(project.path())
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .env_remove("TMUX")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("needs to run inside tmux"));
+}
@@ -2,6 +2,8 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use indexmap::IndexMap;
 use nr::app::{Action, App, AppMode};
 use nr::core::package_manager::PackageManager;
+use nr::flow::ConfigureFlow;
+use nr::store::project_id::project_id;
 use std::collections::HashSet;
 use std::fs;
 use tempfile::TempDir;
@@ -25,6 +27,11 @@ fn create_test_app(project_dir: &std::path::Path) -> App {
     )
 }
 
+/// The open configure flow's state
+fn flow(app: &App) -> &ConfigureFlow {
+    app.flow.as_ref().expect("configure flow is open")
+}
+
 #[test]
 fn test_configure_flow_starts_with_tab_key() {
     let temp_dir = TempDir::new().unwrap();
@@ -47,7 +54,7 @@ fn test_configure_flow_starts_with_tab_key() {
     assert_eq!(app.mode, AppMode::ConfigureEnv);
 
     // Should have scanned env files
-    assert!(app.env_files_list.is_some());
+    assert!(app.flow.is_some());
 }
 
 #[test]
@@ -69,15 +76,12 @@ fn test_configure_flow_restores_global_env() {
     app.handle_key(key);
 
     // Should have pre-selected the global env files
-    assert_eq!(app.env_selected_files.len(), 2);
+    assert_eq!(flow(&app).env.checked.len(), 2);
 
     // Verify correct files are selected
-    let selected_names: HashSet<String> = app
-        .env_files_list
-        .as_ref()
-        .unwrap()
-        .all_files()
-        .filter(|f| app.env_selected_files.contains(&f.path))
+    let selected_names: HashSet<String> = flow(&app)
+        .env
+        .checked_files()
         .map(|f| f.display_name.clone())
         .collect();
 
@@ -110,9 +114,9 @@ fn test_configure_flow_restores_script_specific_args() {
     app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
     app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)); // To args mode
 
-    // Args should be restored in args_input after entering args mode
-    // (when mode switches to ConfigureArgs, args from execution_config should be in args_input)
-    assert_eq!(app.args_input, "-w");
+    // Args should be restored in the args input after entering args mode
+    // (when mode switches to ConfigureArgs, the saved args should be in the input)
+    assert_eq!(flow(&app).args.input, "-w");
 }
 
 #[test]
@@ -259,12 +263,12 @@ fn test_configure_flow_env_selection_toggle() {
     // Start configure flow
     app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
 
-    let initial_count = app.env_selected_files.len();
+    let initial_count = flow(&app).env.checked.len();
 
     // Toggle selection with Space
     app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
 
-    let after_toggle = app.env_selected_files.len();
+    let after_toggle = flow(&app).env.checked.len();
 
     // Count should change
     assert_ne!(initial_count, after_toggle);
@@ -289,14 +293,14 @@ fn test_configure_flow_args_input_editing() {
     app.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE));
     app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
 
-    assert_eq!(app.args_input, "test");
+    assert_eq!(flow(&app).args.input, "test");
 
     // Backspace should delete
     app.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
-    assert_eq!(app.args_input, "tes");
+    assert_eq!(flow(&app).args.input, "tes");
 
     // Cursor should be at end
-    assert_eq!(app.args_cursor_pos, 3);
+    assert_eq!(flow(&app).args.cursor, 3);
 }
 
 #[test]
@@ -317,19 +321,19 @@ fn test_configure_flow_args_cursor_movement() {
         app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
     }
 
-    assert_eq!(app.args_cursor_pos, 4);
+    assert_eq!(flow(&app).args.cursor, 4);
 
     // Move cursor left
     app.handle_key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
-    assert_eq!(app.args_cursor_pos, 3);
+    assert_eq!(flow(&app).args.cursor, 3);
 
     // Move cursor to start
     app.handle_key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
-    assert_eq!(app.args_cursor_pos, 0);
+    assert_eq!(flow(&app).args.cursor, 0);
 
     // Move cursor to end
     app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
-    assert_eq!(app.args_cursor_pos, 4);
+    assert_eq!(flow(&app).args.cursor, 4);
 }
 
 #[test]
@@ -357,7 +361,7 @@ fn test_multiple_scripts_share_global_env_in_app() {
     app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
 
     // Global env should be pre-selected
-    let pre_selected_count = app.env_selected_files.len();
+    let pre_selected_count = flow(&app).env.checked.len();
     assert_eq!(global_env_after_first.len(), pre_selected_count);
 }
 
@@ -402,3 +406,266 @@ fn test_configure_flow_with_monorepo() {
     // Should be able to start configure flow
     assert_eq!(app.mode, AppMode::Normal);
 }
+
+#[test]
+fn test_args_history_lists_the_scripts_own_args_first() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut app = create_test_app(temp_dir.path());
+    let key = |script: &str| format!("{}:root:{}", project_id(temp_dir.path()), script);
+    app.args_history
+        .add_entry(&key("build"), "--minify".to_string());
+    app.args_history
+        .add_entry(&key("test"), "--watch".to_string());
+    app.args_history
+        .add_entry(&key("build"), "--sourcemap".to_string());
+
+    for c in "test".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::ConfigureArgs);
+
+    // `test`'s own args, then the other scripts' from most recent
+    let mut browsed = Vec::new();
+    for _ in 0..3 {
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        browsed.push(flow(&app).args.input.clone());
+    }
+    assert_eq!(browsed, vec!["--watch", "--sourcemap", "--minify"]);
+}
+
+#[test]
+fn test_presets_are_saved_from_confirmation_and_picked_first() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env"), "VAR=test").unwrap();
+    fs::write(project_dir.join(".env.ci"), "CI=1").unwrap();
+    let mut app = create_test_app(project_dir);
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let type_text = |app: &mut App, text: &str| {
+        for c in text.chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+    };
+
+    // Configure .env.ci with --coverage and save it as "coverage CI"
+    app.handle_key(key(KeyCode::Tab));
+    assert_eq!(app.mode, AppMode::ConfigureEnv);
+    let ci = flow(&app)
+        .env
+        .files
+        .all_files()
+        .position(|f| f.display_name == ".env.ci")
+        .unwrap();
+    for _ in 0..ci {
+        app.handle_key(key(KeyCode::Down));
+    }
+    app.handle_key(key(KeyCode::Char(' ')));
+    app.handle_key(key(KeyCode::Enter));
+    type_text(&mut app, "--coverage");
+    app.handle_key(key(KeyCode::Enter));
+    app.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    type_text(&mut app, "coverage CI");
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.mode, AppMode::ConfirmExecution);
+    app.handle_key(key(KeyCode::Esc));
+    app.handle_key(key(KeyCode::Esc));
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.mode, AppMode::Normal);
+
+    // The next Tab starts with the presets; picking one applies it
+    app.handle_key(key(KeyCode::Tab));
+    assert_eq!(app.mode, AppMode::PickPreset);
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.mode, AppMode::ConfigureEnv);
+    let flow = flow(&app);
+    assert_eq!(flow.confirm.args, "--coverage");
+    let checked: Vec<&str> = flow
+        .env
+        .checked_files()
+        .map(|f| f.display_name.as_str())
+        .collect();
+    assert_eq!(checked, vec![".env.ci"]);
+}
+
+#[test]
+fn test_quick_run_reuses_the_last_env_files_and_args() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env"), "VAR=test").unwrap();
+    fs::write(project_dir.join(".env.ci"), "CI=1").unwrap();
+    let mut app = create_test_app(project_dir);
+    app.global_env_config.last_env_files = vec![".env.ci".to_string()];
+    app.script_configs.insert(
+        format!("{}:root:build", project_id(project_dir)),
+        nr::store::script_configs::ScriptConfig {
+            args: "--coverage".to_string(),
+            last_used: std::time::SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
+        },
+    );
+
+    let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT));
+
+    assert!(app.flow.is_none());
+    let Action::RunScript {
+        script_name,
+        env_files,
+        args,
+        ..
+    } = action
+    else {
+        panic!("expected RunScript");
+    };
+    assert_eq!(script_name, "build");
+    assert_eq!(args, "--coverage");
+    assert_eq!(env_files, vec![project_dir.join(".env.ci")]);
+}
+
+#[test]
+fn test_env_preview_shows_the_checked_files_merged() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env"), "PORT=3000\nLOG=info").unwrap();
+    fs::write(project_dir.join(".env.local"), "PORT=4000").unwrap();
+    let mut app = create_test_app(project_dir);
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    app.handle_key(key(KeyCode::Tab));
+    app.handle_key(key(KeyCode::Char(' ')));
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char(' ')));
+    app.handle_key(key(KeyCode::Char('p')));
+    assert_eq!(app.mode, AppMode::PreviewEnv);
+    let preview = flow(&app).env_preview.as_ref().unwrap();
+    let port = preview.vars.iter().find(|v| v.key == "PORT").unwrap();
+    assert_eq!(port.value, "4000");
+    assert_eq!(port.source, ".env.local");
+    assert_eq!(port.overridden, vec![".env".to_string()]);
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.mode, AppMode::ConfigureEnv);
+    assert!(flow(&app).env_preview.is_none());
+}
+
+#[test]
+fn test_env_overrides_apply_to_the_run_and_are_kept_per_script() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env"), "DEBUG=0").unwrap();
+    let mut app = create_test_app(project_dir);
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    app.handle_key(key(KeyCode::Tab));
+    app.handle_key(key(KeyCode::Char('e')));
+    assert_eq!(app.mode, AppMode::EditEnv);
+    app.handle_key(key(KeyCode::Enter));
+    for c in "DEBUG=app:*".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.mode, AppMode::ConfigureEnv);
+
+    app.handle_key(key(KeyCode::Enter));
+    app.handle_key(key(KeyCode::Enter));
+    let action = app.handle_key(key(KeyCode::Enter));
+    let Action::RunScript { env, .. } = action else {
+        panic!("expected RunScript");
+    };
+    assert_eq!(env, vec![("DEBUG".to_string(), "app:*".to_string())]);
+
+    // The next configure flow of the script starts with them
+    app.handle_key(key(KeyCode::Tab));
+    assert_eq!(
+        flow(&app).overrides.vars,
+        vec![("DEBUG".to_string(), "app:*".to_string())]
+    );
+}
+
+#[test]
+fn test_new_env_file_is_created_from_the_example_and_checked() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env.example"), "API_URL=\n").unwrap();
+    let mut app = create_test_app(project_dir);
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    app.handle_key(key(KeyCode::Tab));
+    app.handle_key(key(KeyCode::Char('n')));
+    let new_file = flow(&app).env.new_file.as_ref().unwrap();
+    assert_eq!(new_file.name, ".env.");
+    assert!(new_file.from_example);
+
+    // An existing name is refused and the prompt stays open
+    for c in "example".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    let new_file = flow(&app).env.new_file.as_ref().unwrap();
+    assert_eq!(
+        new_file.error.as_deref(),
+        Some(".env.example already exists")
+    );
+
+    for _ in 0.."example".len() {
+        app.handle_key(key(KeyCode::Backspace));
+    }
+    for c in "local".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+
+    let created = project_dir.join(".env.local");
+    assert_eq!(fs::read_to_string(&created).unwrap(), "API_URL=\n");
+    let env = &flow(&app).env;
+    assert!(env.new_file.is_none());
+    assert!(env.checked.contains(&created));
+    let selected = env.files.all_files().nth(env.selected).unwrap();
+    assert_eq!(selected.path, created);
+    assert_eq!(app.mode, AppMode::ConfigureEnv);
+}
+
+#[test]
+fn test_checked_in_env_files_and_presets_apply_to_their_script() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join(".env"), "VAR=test").unwrap();
+    fs::write(project_dir.join(".env.ci"), "CI=1").unwrap();
+    let config: nr::store::config::Config = serde_json::from_str(
+        r#"{
+            "env_files": {"build": [".env.ci"]},
+            "presets": {"build": [{"name": "CI", "args": "--ci"}]}
+        }"#,
+    )
+    .unwrap();
+    let mut app = create_test_app(project_dir).with_config(config);
+    app.global_env_config.last_env_files = vec![".env".to_string()];
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    // The shared preset is offered but can't be deleted from nr
+    app.handle_key(key(KeyCode::Tab));
+    assert_eq!(app.mode, AppMode::PickPreset);
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char('d')));
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Shared presets are edited in nr.config.json")
+    );
+    let pick = flow(&app).preset_pick.as_ref().unwrap();
+    assert_eq!(pick.presets.len(), 1);
+    assert_eq!(pick.saved, 0);
+
+    // The last used row starts from the script's env files, not the shared last used ones
+    app.handle_key(key(KeyCode::Up));
+    app.handle_key(key(KeyCode::Enter));
+    let checked: Vec<&str> = flow(&app)
+        .env
+        .checked_files()
+        .map(|f| f.display_name.as_str())
+        .collect();
+    assert_eq!(checked, vec![".env.ci"]);
+}
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use nr::core::env_files::scan_env_files;
 use nr::store::global_env::{GlobalEnvConfig, load_global_env_config, save_global_env_config};
 use nr::store::script_configs::{ScriptConfig, load_script_configs, save_script_configs};
@@ -13,8 +14,9 @@ fn test_global_env_persists_across_sessions() {
     let config_dir = temp_dir.path();
 
     // First session: save global env
-    let mut config = GlobalEnvConfig::default();
-    config.last_env_files = vec![".env".to_string(), ".env.local".to_string()];
+    let config = GlobalEnvConfig {
+        last_env_files: vec![".env".to_string(), ".env.local".to_string()],
+    };
     save_global_env_config(config_dir, &config).unwrap();
 
     // Second session: load global env
@@ -36,6 +38,8 @@ fn test_script_args_persist_independently() {
         ScriptConfig {
             args: "--watch".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
 
@@ -45,6 +49,8 @@ fn test_script_args_persist_independently() {
         ScriptConfig {
             args: "--production".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
 
@@ -72,13 +78,16 @@ fn test_global_env_independent_of_script_args() {
         ScriptConfig {
             args: "--watch".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
     save_script_configs(config_dir, &script_configs).unwrap();
 
     // Save global env
-    let mut global_env = GlobalEnvConfig::default();
-    global_env.last_env_files = vec![".env".to_string()];
+    let mut global_env = GlobalEnvConfig {
+        last_env_files: vec![".env".to_string()],
+    };
     save_global_env_config(config_dir, &global_env).unwrap();
 
     // Update global env
@@ -175,8 +184,9 @@ fn test_multiple_scripts_share_global_env() {
     let config_dir = temp_dir.path();
 
     // Save global env (shared)
-    let mut global_env = GlobalEnvConfig::default();
-    global_env.last_env_files = vec![".env".to_string(), ".env.local".to_string()];
+    let global_env = GlobalEnvConfig {
+        last_env_files: vec![".env".to_string(), ".env.local".to_string()],
+    };
     save_global_env_config(config_dir, &global_env).unwrap();
 
     // Save different args for different scripts
@@ -186,6 +196,8 @@ fn test_multiple_scripts_share_global_env() {
         ScriptConfig {
             args: "--watch".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
     script_configs.insert(
@@ -193,6 +205,8 @@ fn test_multiple_scripts_share_global_env() {
         ScriptConfig {
             args: "--production".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
     script_configs.insert(
@@ -200,6 +214,8 @@ fn test_multiple_scripts_share_global_env() {
         ScriptConfig {
             args: "--hot".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
     save_script_configs(config_dir, &script_configs).unwrap();
@@ -228,8 +244,9 @@ fn test_global_env_updates_affect_all_scripts() {
     let config_dir = temp_dir.path();
 
     // Initial global env
-    let mut global_env = GlobalEnvConfig::default();
-    global_env.last_env_files = vec![".env".to_string()];
+    let mut global_env = GlobalEnvConfig {
+        last_env_files: vec![".env".to_string()],
+    };
     save_global_env_config(config_dir, &global_env).unwrap();
 
     // Update global env (simulating execution from any script)
@@ -255,6 +272,8 @@ fn test_backward_compatibility_script_configs() {
         ScriptConfig {
             args: "--watch".to_string(),
             last_used: SystemTime::now(),
+            presets: Vec::new(),
+            env: IndexMap::new(),
         },
     );
     save_script_configs(config_dir, &configs).unwrap();
@@ -0,0 +1,119 @@
+//! Scenario tests that replay keystrokes through `App::feed_events` and
+//! check the resulting `App::snapshot`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use indexmap::IndexMap;
+use nr::app::{Action, App, AppMode};
+use nr::core::package_manager::PackageManager;
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_test_app(project_dir: &std::path::Path) -> App {
+    let mut scripts = IndexMap::new();
+    scripts.insert("build".to_string(), "tsc".to_string());
+    scripts.insert("dev".to_string(), "vite".to_string());
+    scripts.insert("test".to_string(), "vitest".to_string());
+
+    App::new(
+        scripts,
+        vec![],
+        project_dir.to_path_buf(),
+        None,
+        project_dir,
+        "test-project".to_string(),
+        project_dir.display().to_string(),
+        "npm".to_string(),
+        PackageManager::Npm,
+    )
+}
+
+/// One key press per character.
+fn typed(text: &str) -> Vec<KeyEvent> {
+    text.chars().map(|c| key(KeyCode::Char(c))).collect()
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn ctrl(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+#[test]
+fn test_search_configure_and_queue_scenario() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".env"), "A=1").unwrap();
+    let mut app = create_test_app(temp_dir.path());
+
+    let mut keys = typed("dev");
+    keys.extend([
+        key(KeyCode::Tab),
+        key(KeyCode::Char(' ')),
+        key(KeyCode::Enter),
+    ]);
+    keys.extend(typed("--host"));
+    keys.extend([key(KeyCode::Left), key(KeyCode::Enter)]);
+    assert!(matches!(app.feed_events(&keys), Action::Continue));
+
+    let snapshot = serde_json::to_value(app.snapshot()).unwrap();
+    assert_eq!(
+        snapshot,
+        json!({
+            "tab": "Scripts",
+            "mode": "ConfirmExecution",
+            "show_help": false,
+            "query": "dev",
+            "rows": ["dev"],
+            "selected": 0,
+            "breadcrumb": [],
+            "status": null,
+            "queue": [],
+            "flow": {
+                "env_files": [".env"],
+                "env_selected": 0,
+                "args": "--host",
+                "args_cursor": 5,
+                "confirm_args": "--host",
+            },
+        })
+    );
+
+    // Ctrl+A queues the configured run and leaves the flow
+    assert!(matches!(app.feed_events(&[ctrl('a')]), Action::Continue));
+    let snapshot = app.snapshot();
+    assert_eq!(snapshot.mode, AppMode::Normal);
+    assert_eq!(snapshot.queue, vec!["root:dev"]);
+    assert!(snapshot.flow.is_none());
+}
+
+#[test]
+fn test_feed_events_stops_at_the_action_that_leaves_the_tui() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut app = create_test_app(temp_dir.path());
+
+    let mut keys = typed("test");
+    keys.push(key(KeyCode::Enter));
+    // Never reached: the event loop exits to run the script first
+    keys.extend(typed("xyz"));
+
+    match app.feed_events(&keys) {
+        Action::RunScript { script_name, .. } => assert_eq!(script_name, "test"),
+        _ => panic!("expected RunScript"),
+    }
+    assert_eq!(app.snapshot().query, "test");
+}
+
+#[test]
+fn test_feed_events_skips_key_releases() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut app = create_test_app(temp_dir.path());
+
+    let mut release = key(KeyCode::Char('b'));
+    release.kind = KeyEventKind::Release;
+    app.feed_events(&[key(KeyCode::Char('b')), release]);
+
+    assert_eq!(app.snapshot().query, "b");
+    assert_eq!(app.snapshot().rows, vec!["build"]);
+}